@@ -12,12 +12,64 @@ pub struct PrefixConfig {
     pub name: String,
     pub creation_date: DateTime<Utc>,
     pub last_modified: DateTime<Utc>,
+    /// When an executable or winecfg was last started in this prefix.
+    /// Config edits leave it alone, unlike `last_modified`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_used: Option<DateTime<Utc>>,
     pub wine_version: Option<String>,
     pub architecture: String,
     pub description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub graphics: Option<GraphicsConfig>,
+    /// Prefix-wide environment (e.g. `WINEESYNC=1`) applied to every Wine
+    /// process started for this prefix. An executable's own `env_vars`
+    /// override entries with the same name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env_vars: HashMap<String, String>,
+    /// Command every launch in this prefix is run through, e.g.
+    /// `gamemoderun` or `mangohud --dlsym`. An executable's own
+    /// `launch_wrapper` takes precedence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub launch_wrapper: Option<String>,
+    /// Stop the wineserver once the last application launched from
+    /// Tequila exits, instead of letting it linger and hold the prefix.
+    #[serde(default)]
+    pub stop_wineserver_on_exit: bool,
     pub registered_executables: Vec<RegisteredExecutable>,
+    /// Append-only log of what was done to the prefix, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<NoteEntry>,
+}
+
+/// File extensions of batch scripts that can be registered and launched
+/// alongside `.exe` files.
+pub const SCRIPT_EXTENSIONS: &[&str] = &["bat", "cmd"];
+
+/// One entry in a prefix's notes log.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NoteEntry {
+    pub timestamp: DateTime<Utc>,
+    pub text: String,
+    /// Recorded by Tequila for one of its own actions rather than written by
+    /// the user.
+    #[serde(default)]
+    pub automatic: bool,
+}
+
+/// A named set of launch options for a registered executable, picked from
+/// the Run button's menu instead of the plain launch.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct LaunchProfile {
+    pub name: String,
+    /// Command-line arguments passed after the executable.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Layered over the executable's own `env_vars`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env_vars: HashMap<String, String>,
+    /// Replaces the executable's `cwd` when set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -47,6 +99,39 @@ pub struct RegisteredExecutable {
     #[serde(default)]
     pub env_vars: HashMap<String, String>,
     pub cwd: Option<PathBuf>,
+    /// User-assigned group shown in the apps list; `None` means "Other".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Pinned to the top of the apps list.
+    #[serde(default)]
+    pub favorite: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_launched: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub launch_count: u64,
+    /// PE machine type from the executable header (`"x86"` or `"x64"`);
+    /// `None` until the file has been inspected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub architecture: Option<String>,
+    /// Metadata extraction has been tried on the file, whether or not it
+    /// found anything, so files that can't be parsed aren't read again.
+    #[serde(default)]
+    pub metadata_checked: bool,
+    /// Launch with the 32-bit Wine loader, even in a win64 prefix.
+    #[serde(default)]
+    pub force_32bit: bool,
+    /// Registered on purpose from outside the prefix's `drive_c`, e.g. a
+    /// portable game kept on the host.
+    #[serde(default)]
+    pub allow_external: bool,
+    /// Alternative ways to launch the executable; the plain launch uses
+    /// none of them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub launch_profiles: Vec<LaunchProfile>,
+    /// Command the launch is run through instead of the prefix's
+    /// `launch_wrapper`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub launch_wrapper: Option<String>,
 }
 
 impl PrefixConfig {
@@ -57,11 +142,16 @@ impl PrefixConfig {
             name,
             creation_date: now,
             last_modified: now,
+            last_used: None,
             wine_version: None,
             architecture,
             description: None,
             graphics: None,
+            env_vars: HashMap::new(),
+            launch_wrapper: None,
+            stop_wineserver_on_exit: false,
             registered_executables: Vec::new(),
+            notes: Vec::new(),
         }
     }
 
@@ -86,9 +176,66 @@ impl PrefixConfig {
         self.last_modified = Utc::now();
     }
 
-    pub fn add_executable(&mut self, executable: RegisteredExecutable) {
+    /// Whether `other` holds the same settings, `last_modified` aside.
+    pub fn same_content(&self, other: &Self) -> bool {
+        let mut this = self.clone();
+        this.last_modified = other.last_modified;
+        this == *other
+    }
+
+    /// Stamp `last_used` with the current time.
+    pub fn mark_used(&mut self) {
+        self.last_used = Some(Utc::now());
+    }
+
+    /// Stamp `last_used` in the config saved at `prefix_path`, leaving any
+    /// unsaved copy of the config alone.
+    pub fn mark_used_on_disk(prefix_path: &Path) -> Result<()> {
+        let mut config = Self::load_from_file(prefix_path)?.ok_or_else(|| {
+            PrefixError::NotFound(format!("No config in {}", prefix_path.display()))
+        })?;
+        config.mark_used();
+        config.save_to_file(prefix_path)
+    }
+
+    /// Append a note stamped with the current time.
+    pub fn add_note(&mut self, text: impl Into<String>, automatic: bool) {
+        self.notes.push(NoteEntry {
+            timestamp: Utc::now(),
+            text: text.into(),
+            automatic,
+        });
+        self.update_last_modified();
+    }
+
+    /// Add the entries of `notes` this config doesn't have yet, keeping the
+    /// log in time order. Notes are never removed, so this is how a config
+    /// loaded earlier picks up entries written to disk since.
+    pub fn merge_notes(&mut self, notes: &[NoteEntry]) {
+        let before = self.notes.len();
+        for note in notes {
+            if !self.notes.contains(note) {
+                self.notes.push(note.clone());
+            }
+        }
+        if self.notes.len() != before {
+            self.notes.sort_by_key(|note| note.timestamp);
+        }
+    }
+
+    /// Register `executable` unless one with the same path is already
+    /// registered. Returns `true` if it was added.
+    pub fn add_executable(&mut self, executable: RegisteredExecutable) -> bool {
+        if self
+            .registered_executables
+            .iter()
+            .any(|exe| exe.executable_path == executable.executable_path)
+        {
+            return false;
+        }
         self.registered_executables.push(executable);
         self.update_last_modified();
+        true
     }
 
     pub fn remove_executable(&mut self, index: usize) {
@@ -98,6 +245,70 @@ impl PrefixConfig {
         }
     }
 
+    /// Remove every registered executable whose path is in `paths`.
+    /// Returns the number of entries removed.
+    pub fn remove_executables_by_path(&mut self, paths: &[PathBuf]) -> usize {
+        let before = self.registered_executables.len();
+        self.registered_executables
+            .retain(|exe| !paths.contains(&exe.executable_path));
+        let removed = before - self.registered_executables.len();
+        if removed > 0 {
+            self.update_last_modified();
+        }
+        removed
+    }
+
+    /// Remove registered executables whose path no longer exists on disk.
+    /// Returns the removed entries.
+    pub fn remove_missing_executables(&mut self) -> Vec<RegisteredExecutable> {
+        let (kept, missing): (Vec<_>, Vec<_>) = self
+            .registered_executables
+            .drain(..)
+            .partition(|exe| exe.executable_path.exists());
+        self.registered_executables = kept;
+        if !missing.is_empty() {
+            self.update_last_modified();
+        }
+        missing
+    }
+
+    /// Bump the launch count and timestamp of the executable at `exe_path`,
+    /// and the prefix's `last_used` with it. Returns `false` if it is not registered.
+    pub fn record_launch(&mut self, exe_path: &Path) -> bool {
+        let Some(exe) = self
+            .registered_executables
+            .iter_mut()
+            .find(|e| e.executable_path == exe_path)
+        else {
+            return false;
+        };
+        exe.last_launched = Some(Utc::now());
+        exe.launch_count += 1;
+        self.last_used = exe.last_launched;
+        true
+    }
+
+    /// Move the executable at `from` into the position held by `to`, shifting
+    /// the entries in between. Returns `false` if either path is not
+    /// registered or both are the same.
+    pub fn move_executable(&mut self, from: &Path, to: &Path) -> bool {
+        let position = |path: &Path| {
+            self.registered_executables
+                .iter()
+                .position(|e| e.executable_path == path)
+        };
+        let (Some(src), Some(dst)) = (position(from), position(to)) else {
+            return false;
+        };
+        if src == dst {
+            return false;
+        }
+        let exe = self.registered_executables.remove(src);
+        self.registered_executables.insert(dst, exe);
+        self.update_last_modified();
+        true
+    }
+
     pub fn get_executable_count(&self) -> usize {
         self.registered_executables.len()
     }
@@ -112,6 +323,41 @@ impl PrefixConfig {
         self.registered_executables.iter()
     }
 
+    /// Describe this prefix as a shell snippet that recreates it with stock
+    /// Wine and winetricks, for sharing in bug reports. Only what the config
+    /// records is covered; graphics backends winetricks can't install are
+    /// noted as comments.
+    pub fn to_winetricks_snippet(&self, prefix_path: &Path) -> String {
+        let quote = |s: &str| format!("'{}'", s.replace('\'', "'\\''"));
+        let mut out = format!("# {}\n", self.name);
+        if let Some(runtime) = &self.wine_version {
+            out.push_str(&format!("# Wine runtime: {}\n", runtime));
+        }
+        out.push_str(&format!(
+            "export WINEPREFIX={} WINEARCH={}\n",
+            quote(&prefix_path.to_string_lossy()),
+            self.architecture
+        ));
+        let mut env: Vec<_> = self.env_vars.iter().collect();
+        env.sort();
+        for (key, value) in env {
+            out.push_str(&format!("export {}={}\n", key, quote(value)));
+        }
+        out.push_str("wineboot --init\n");
+
+        if let Some(gfx) = &self.graphics {
+            if gfx.backend == "dxvk-vkd3d" {
+                out.push_str("winetricks dxvk vkd3d\n");
+            } else {
+                out.push_str(&format!(
+                    "# graphics: {} {} (not available through winetricks)\n",
+                    gfx.backend, gfx.version
+                ));
+            }
+        }
+        out
+    }
+
     pub fn validate(&self) -> Result<()> {
         if self.name.is_empty() {
             return Err(PrefixError::Validation(
@@ -205,9 +451,8 @@ impl ConfigOperations for PrefixConfig {
 }
 
 impl ExecutableManager for PrefixConfig {
-    fn add_executable(&mut self, executable: RegisteredExecutable) {
-        self.registered_executables.push(executable);
-        self.update_last_modified();
+    fn add_executable(&mut self, executable: RegisteredExecutable) -> bool {
+        PrefixConfig::add_executable(self, executable)
     }
 
     fn remove_executable(&mut self, index: usize) {
@@ -270,6 +515,16 @@ impl RegisteredExecutable {
             imported_modules: Vec::new(),
             env_vars: HashMap::new(),
             cwd: None,
+            category: None,
+            favorite: false,
+            last_launched: None,
+            launch_count: 0,
+            architecture: None,
+            metadata_checked: false,
+            force_32bit: false,
+            allow_external: false,
+            launch_profiles: Vec::new(),
+            launch_wrapper: None,
         }
     }
 
@@ -313,6 +568,66 @@ impl RegisteredExecutable {
         self
     }
 
+    pub fn with_category<S: Into<String>>(mut self, category: S) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    pub fn with_architecture<S: Into<String>>(mut self, architecture: S) -> Self {
+        self.set_architecture(architecture);
+        self
+    }
+
+    /// Record the detected PE architecture. The first time it becomes known,
+    /// 32-bit executables default to launching with 32-bit Wine.
+    pub fn set_architecture<S: Into<String>>(&mut self, architecture: S) {
+        let architecture = architecture.into();
+        if self.architecture.is_none() && architecture == "x86" {
+            self.force_32bit = true;
+        }
+        self.architecture = Some(architecture);
+    }
+
+    pub fn is_64bit(&self) -> bool {
+        self.architecture.as_deref() == Some("x64")
+    }
+
+    /// Whether this is a `.bat`/`.cmd` batch script rather than a PE image.
+    /// Scripts are run through Wine's `cmd /c`.
+    pub fn is_script(&self) -> bool {
+        self.executable_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| SCRIPT_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+    }
+
+    /// Whether `executable_path` lies under the prefix's `drive_c`. Both
+    /// paths are canonicalized when they exist, so `..` components and
+    /// symlinks can't lead out of it.
+    pub fn is_inside_prefix(&self, prefix_path: &Path) -> bool {
+        let drive_c = prefix_path.join("drive_c");
+        let drive_c = drive_c.canonicalize().unwrap_or(drive_c);
+        let path = self
+            .executable_path
+            .canonicalize()
+            .unwrap_or_else(|_| self.executable_path.clone());
+        path.starts_with(&drive_c)
+            && !path
+                .components()
+                .any(|c| c == std::path::Component::ParentDir)
+    }
+
+    /// The launch profile called `name`, if any.
+    pub fn launch_profile(&self, name: &str) -> Option<&LaunchProfile> {
+        self.launch_profiles.iter().find(|p| p.name == name)
+    }
+
+    /// Whether this executable may be registered in the prefix at
+    /// `prefix_path`: it lives inside it or was explicitly allowed not to.
+    pub fn is_allowed_in_prefix(&self, prefix_path: &Path) -> bool {
+        self.allow_external || self.is_inside_prefix(prefix_path)
+    }
+
     /// Resolve the configured `icon_path` to a usable on-disk path.
     ///
     /// Behaviour:
@@ -352,6 +667,7 @@ pub struct RegisteredExecutableBuilder {
     imported_modules: Vec<String>,
     env_vars: HashMap<String, String>,
     cwd: Option<PathBuf>,
+    category: Option<String>,
 }
 
 impl RegisteredExecutableBuilder {
@@ -369,6 +685,7 @@ impl RegisteredExecutableBuilder {
             imported_modules: Vec::new(),
             env_vars: HashMap::new(),
             cwd: None,
+            category: None,
         }
     }
 
@@ -432,6 +749,11 @@ impl RegisteredExecutableBuilder {
         self
     }
 
+    pub fn category<S: Into<String>>(mut self, category: S) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
     pub fn build(self) -> std::result::Result<RegisteredExecutable, PrefixError> {
         Ok(RegisteredExecutable {
             name: self
@@ -450,6 +772,16 @@ impl RegisteredExecutableBuilder {
             imported_modules: self.imported_modules,
             env_vars: self.env_vars,
             cwd: self.cwd,
+            category: self.category,
+            favorite: false,
+            last_launched: None,
+            launch_count: 0,
+            architecture: None,
+            metadata_checked: false,
+            force_32bit: false,
+            allow_external: false,
+            launch_profiles: Vec::new(),
+            launch_wrapper: None,
         })
     }
 }
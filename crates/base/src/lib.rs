@@ -3,7 +3,9 @@ pub mod error;
 pub mod graphics;
 pub mod traits;
 
-pub use config::{PrefixConfig, RegisteredExecutable, RegisteredExecutableBuilder};
+pub use config::{
+    LaunchProfile, NoteEntry, PrefixConfig, RegisteredExecutable, RegisteredExecutableBuilder,
+};
 pub use error::{PrefixError, Result};
 pub use graphics::{GraphicsBackend, GraphicsConfig};
 pub use traits::{ConfigOperations, ExecutableManager, PrefixInfo, Scanner, WinePrefix};
@@ -40,7 +40,7 @@ pub trait PrefixManager {
 }
 
 pub trait ExecutableManager {
-    fn add_executable(&mut self, executable: RegisteredExecutable);
+    fn add_executable(&mut self, executable: RegisteredExecutable) -> bool;
     fn remove_executable(&mut self, index: usize);
     fn executable_count(&self) -> usize;
     fn find_executable_by_name(&self, name: &str) -> Option<&RegisteredExecutable>;
@@ -60,6 +60,12 @@ impl WinePrefix {
         &self.path
     }
 
+    /// Where the prefix really lives when it was linked into the wine
+    /// directory from elsewhere, `None` for prefixes Tequila manages.
+    pub fn external_path(&self) -> Option<PathBuf> {
+        std::fs::read_link(&self.path).ok()
+    }
+
     /// The prefix configuration.
     pub fn config(&self) -> &PrefixConfig {
         &self.config
@@ -81,7 +87,7 @@ impl WinePrefix {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct PrefixInfo {
     pub name: String,
     pub path: PathBuf,
@@ -91,4 +97,5 @@ pub struct PrefixInfo {
     pub architecture: String,
     pub creation_date: DateTime<Utc>,
     pub last_modified: DateTime<Utc>,
+    pub wineserver_running: bool,
 }
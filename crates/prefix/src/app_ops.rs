@@ -1,7 +1,60 @@
 use crate::Manager;
-use base::config::PrefixConfig;
-use base::error::Result;
-use std::path::Path;
+use crate::desktop;
+use crate::prefix::{prefix_label, resolve_or_extract_icon};
+use base::RegisteredExecutable;
+use base::config::{NoteEntry, PrefixConfig};
+use base::error::{PrefixError, Result};
+use std::path::{Path, PathBuf};
+
+/// An application registered in another prefix, as it would be registered
+/// in the prefix being compared against; see
+/// [`Manager::compare_registered_apps`].
+#[derive(Debug, Clone)]
+pub struct AppTransfer {
+    /// The source entry with its paths moved into the target prefix and its
+    /// launch history cleared.
+    pub executable: RegisteredExecutable,
+    /// Whether the executable exists in the target prefix. Entries that
+    /// don't were installed only in the source and can't be copied.
+    pub available: bool,
+}
+
+/// Move `path` from under `source` to the same place under `target`. Paths
+/// outside `source` are shared by both prefixes and kept as they are.
+fn rebase_path(path: &Path, source: &Path, target: &Path) -> PathBuf {
+    match path.strip_prefix(source) {
+        Ok(relative) => target.join(relative),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// `exe` as registered in `source`, rewritten for `target`.
+fn rebase_executable(
+    exe: &RegisteredExecutable,
+    source: &Path,
+    target: &Path,
+) -> RegisteredExecutable {
+    let mut rebased = exe.clone();
+    rebased.executable_path = rebase_path(&exe.executable_path, source, target);
+    rebased.cwd = exe
+        .cwd
+        .as_deref()
+        .map(|cwd| rebase_path(cwd, source, target));
+    // Relative icon paths are already resolved against the prefix root
+    rebased.icon_path = exe
+        .icon_path
+        .as_deref()
+        .map(|icon| rebase_path(icon, source, target));
+    for profile in &mut rebased.launch_profiles {
+        profile.cwd = profile
+            .cwd
+            .as_deref()
+            .map(|cwd| rebase_path(cwd, source, target));
+    }
+    rebased.last_launched = None;
+    rebased.launch_count = 0;
+    rebased
+}
 
 impl Manager {
     /// Open a prefix and scan it for installed applications.
@@ -13,12 +66,234 @@ impl Manager {
         prefix.scan_applications()
     }
 
-    /// Update and persist a prefix's configuration.
+    /// Update and persist a prefix's configuration. Nothing is written when
+    /// `config` matches what is saved, so `last_modified` only moves on a
+    /// real change.
     pub fn update_config(&self, prefix_path: &Path, config: &PrefixConfig) -> Result<()> {
         config.validate()?;
         let mut updated_config = config.clone();
+        // Notes may have been appended on disk since `config` was loaded
+        // (a graphics install, say); keep them. Likewise a newer launch.
+        if let Ok(Some(on_disk)) = PrefixConfig::load_from_file(prefix_path) {
+            updated_config.merge_notes(&on_disk.notes);
+            updated_config.last_used = updated_config.last_used.max(on_disk.last_used);
+            if updated_config.same_content(&on_disk) {
+                return Ok(());
+            }
+        }
         updated_config.update_last_modified();
         updated_config.save_to_file(prefix_path)?;
         Ok(())
     }
+
+    /// Append a note to the prefix's log and persist it right away,
+    /// independently of any unsaved edits to the config. Returns the entry
+    /// written, so callers holding a config can merge it in.
+    pub fn add_prefix_note(
+        &self,
+        prefix_path: &Path,
+        text: &str,
+        automatic: bool,
+    ) -> Result<NoteEntry> {
+        let mut config = PrefixConfig::load_from_file(prefix_path)?.ok_or_else(|| {
+            PrefixError::NotFound(format!("No config in {}", prefix_path.display()))
+        })?;
+        config.add_note(text, automatic);
+        config.save_to_file(prefix_path)?;
+        Ok(config.notes.last().cloned().expect("note was just added"))
+    }
+
+    /// Add `exe` to the system application menu: a `.desktop` entry on
+    /// Linux, an app bundle on macOS. The entry runs `tequila run` against
+    /// this prefix and uses the exe's extracted icon. An existing entry is
+    /// rewritten, so this also picks up a new name or icon.
+    pub fn export_desktop_entry(
+        &self,
+        prefix_path: &Path,
+        exe: &RegisteredExecutable,
+    ) -> Result<PathBuf> {
+        let prefix_name = PrefixConfig::load_from_file(prefix_path)?
+            .map(|config| config.name)
+            .unwrap_or_else(|| prefix_label(prefix_path));
+        let icon = resolve_or_extract_icon(exe, prefix_path, self.scanner().icon_cache());
+        desktop::create_launcher(
+            prefix_path,
+            &prefix_name,
+            &exe.name,
+            &exe.executable_path,
+            icon.as_deref(),
+        )
+    }
+
+    /// Remove the menu entry written by [`Manager::export_desktop_entry`].
+    pub fn remove_desktop_entry(
+        &self,
+        prefix_path: &Path,
+        exe: &RegisteredExecutable,
+    ) -> Result<()> {
+        desktop::remove_launcher(prefix_path, &exe.executable_path)
+    }
+
+    /// List the applications registered in `source` but not in `target`,
+    /// rebased onto `target` so they can be registered there as-is.
+    ///
+    /// Entries are matched by executable path after rebasing, so the same
+    /// install in a cloned prefix counts as registered in both.
+    pub fn compare_registered_apps(
+        &self,
+        source: &Path,
+        target: &Path,
+    ) -> Result<Vec<AppTransfer>> {
+        let load = |path: &Path| {
+            PrefixConfig::load_from_file(path)?
+                .ok_or_else(|| PrefixError::NotFound(format!("No config in {}", path.display())))
+        };
+        let source_config = load(source)?;
+        let target_config = load(target)?;
+        Ok(source_config
+            .registered_executables
+            .iter()
+            .map(|exe| rebase_executable(exe, source, target))
+            .filter(|exe| {
+                target_config
+                    .registered_executables
+                    .iter()
+                    .all(|registered| registered.executable_path != exe.executable_path)
+            })
+            .map(|executable| AppTransfer {
+                available: executable.executable_path.exists(),
+                executable,
+            })
+            .collect())
+    }
+
+    /// Whether `exe` currently has a menu entry.
+    pub fn has_desktop_entry(&self, prefix_path: &Path, exe: &RegisteredExecutable) -> bool {
+        desktop::launcher_exists(prefix_path, &exe.executable_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::Fixture;
+
+    #[test]
+    fn test_update_config_keeps_notes_added_on_disk() {
+        let fixture = Fixture::new();
+        let path = fixture.add_prefix("sample");
+        let manager = fixture.manager();
+        PrefixConfig::new("Sample".into(), "win64".into())
+            .save_to_file(&path)
+            .unwrap();
+
+        // A config loaded before the note was written, then saved
+        let mut stale = PrefixConfig::load_from_file(&path).unwrap().unwrap();
+        let note = manager
+            .add_prefix_note(&path, "Installed DXVK", true)
+            .unwrap();
+        stale.description = Some("edited".into());
+        manager.update_config(&path, &stale).unwrap();
+
+        let saved = PrefixConfig::load_from_file(&path).unwrap().unwrap();
+        assert_eq!(saved.description.as_deref(), Some("edited"));
+        assert_eq!(saved.notes, vec![note]);
+    }
+
+    #[test]
+    fn test_update_config_leaves_unchanged_config_alone() {
+        let fixture = Fixture::new();
+        let path = fixture.add_prefix("sample");
+        let manager = fixture.manager();
+        PrefixConfig::new("Sample".into(), "win64".into())
+            .save_to_file(&path)
+            .unwrap();
+        let loaded = PrefixConfig::load_from_file(&path).unwrap().unwrap();
+
+        manager.update_config(&path, &loaded).unwrap();
+        let saved = PrefixConfig::load_from_file(&path).unwrap().unwrap();
+        assert_eq!(saved.last_modified, loaded.last_modified);
+
+        let mut edited = loaded.clone();
+        edited.description = Some("edited".into());
+        manager.update_config(&path, &edited).unwrap();
+        let saved = PrefixConfig::load_from_file(&path).unwrap().unwrap();
+        assert_eq!(saved.description.as_deref(), Some("edited"));
+        assert!(saved.last_modified > loaded.last_modified);
+    }
+
+    #[test]
+    fn test_compare_registered_apps_rebases_onto_target() {
+        let fixture = Fixture::new();
+        let source = fixture.add_prefix("source");
+        let target = fixture.add_prefix("target");
+        let manager = fixture.manager();
+
+        // Installed in both prefixes, only in the source, and registered
+        // in both already
+        let shared_dir = "drive_c/Program Files/Sample App";
+        let only_dir = source.join("drive_c/Program Files/Only Here");
+        std::fs::create_dir_all(&only_dir).unwrap();
+        std::fs::write(only_dir.join("only.exe"), b"MZ").unwrap();
+        std::fs::write(source.join(shared_dir).join("tool.exe"), b"MZ").unwrap();
+        std::fs::write(target.join(shared_dir).join("tool.exe"), b"MZ").unwrap();
+
+        let mut sample =
+            RegisteredExecutable::new("Sample".into(), source.join(shared_dir).join("sample.exe"));
+        sample.cwd = Some(source.join(shared_dir));
+        sample.launch_count = 3;
+        let only = RegisteredExecutable::new("Only".into(), only_dir.join("only.exe"));
+        let tool =
+            RegisteredExecutable::new("Tool".into(), source.join(shared_dir).join("tool.exe"));
+        let mut source_config = PrefixConfig::new("Source".into(), "win64".into());
+        source_config.registered_executables = vec![sample, only, tool];
+        source_config.save_to_file(&source).unwrap();
+        let mut target_config = PrefixConfig::new("Target".into(), "win64".into());
+        target_config.registered_executables = vec![RegisteredExecutable::new(
+            "Tool".into(),
+            target.join(shared_dir).join("tool.exe"),
+        )];
+        target_config.save_to_file(&target).unwrap();
+
+        let transfers = manager.compare_registered_apps(&source, &target).unwrap();
+        let summary: Vec<(&str, bool)> = transfers
+            .iter()
+            .map(|t| (t.executable.name.as_str(), t.available))
+            .collect();
+        assert_eq!(summary, [("Sample", true), ("Only", false)]);
+
+        let sample = &transfers[0].executable;
+        assert_eq!(
+            sample.executable_path,
+            target.join(shared_dir).join("sample.exe")
+        );
+        assert_eq!(
+            sample.cwd.as_deref(),
+            Some(target.join(shared_dir).as_path())
+        );
+        assert_eq!(sample.launch_count, 0);
+    }
+
+    #[test]
+    fn test_update_config_keeps_newer_last_used() {
+        let fixture = Fixture::new();
+        let path = fixture.add_prefix("sample");
+        let manager = fixture.manager();
+        PrefixConfig::new("Sample".into(), "win64".into())
+            .save_to_file(&path)
+            .unwrap();
+
+        let stale = PrefixConfig::load_from_file(&path).unwrap().unwrap();
+        assert_eq!(stale.last_used, None);
+        PrefixConfig::mark_used_on_disk(&path).unwrap();
+        let used = PrefixConfig::load_from_file(&path)
+            .unwrap()
+            .unwrap()
+            .last_used;
+        assert!(used.is_some());
+        manager.update_config(&path, &stale).unwrap();
+
+        let saved = PrefixConfig::load_from_file(&path).unwrap().unwrap();
+        assert_eq!(saved.last_used, used);
+    }
 }
@@ -0,0 +1,174 @@
+use crate::Manager;
+use base::config::{PrefixConfig, RegisteredExecutable};
+use base::error::{PrefixError, Result};
+use log::info;
+use std::path::{Path, PathBuf};
+
+/// Outcome of [`Manager::migrate_to_architecture`].
+#[derive(Debug, Clone)]
+pub struct ArchMigration {
+    /// The newly created prefix.
+    pub prefix_path: PathBuf,
+    /// Executables registered in the new prefix as-is; they live outside the
+    /// old prefix, so they keep working.
+    pub migrated: Vec<RegisteredExecutable>,
+    /// Executables installed inside the old prefix. They have to be
+    /// reinstalled into the new one and are not registered there.
+    pub needs_reinstall: Vec<RegisteredExecutable>,
+    /// Executables left behind because the file no longer exists or it is
+    /// 64-bit and the new prefix is win32.
+    pub dropped: Vec<RegisteredExecutable>,
+}
+
+/// Sort `executables` into (migrated, needs reinstall, dropped) for a move
+/// out of `source_prefix` into a prefix of `architecture`.
+fn partition_executables(
+    executables: &[RegisteredExecutable],
+    source_prefix: &Path,
+    architecture: &str,
+) -> (
+    Vec<RegisteredExecutable>,
+    Vec<RegisteredExecutable>,
+    Vec<RegisteredExecutable>,
+) {
+    let mut migrated = Vec::new();
+    let mut needs_reinstall = Vec::new();
+    let mut dropped = Vec::new();
+    for exe in executables {
+        let incompatible = architecture == "win32" && exe.architecture.as_deref() == Some("x64");
+        if !exe.executable_path.exists() || incompatible {
+            dropped.push(exe.clone());
+        } else if exe.executable_path.starts_with(source_prefix) {
+            needs_reinstall.push(exe.clone());
+        } else {
+            migrated.push(exe.clone());
+        }
+    }
+    (migrated, needs_reinstall, dropped)
+}
+
+impl Manager {
+    /// Create a fresh prefix of `architecture` to replace the one at
+    /// `prefix_path`.
+    ///
+    /// Wine can't convert a prefix between win32 and win64 in place: the
+    /// system directories, registry and installed programs are all laid out
+    /// for one architecture, and re-running wineboot with a different
+    /// `WINEARCH` leaves a prefix that fails in confusing ways. So the old
+    /// prefix is left untouched and a new one named `name` is created on the
+    /// same runtime, with the prefix settings and environment copied over.
+    /// Registered executables are carried across when they still exist
+    /// outside the old prefix; see [`ArchMigration`] for the rest.
+    pub fn migrate_to_architecture(
+        &self,
+        prefix_path: &Path,
+        architecture: &str,
+        name: &str,
+    ) -> Result<ArchMigration> {
+        if architecture != "win32" && architecture != "win64" {
+            return Err(PrefixError::Validation(format!(
+                "Unknown architecture '{}'",
+                architecture
+            )));
+        }
+        let dir_name = prefix_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown");
+        let source = self.load_or_create_config(prefix_path, dir_name, &None)?;
+        if source.architecture == architecture {
+            return Err(PrefixError::Validation(format!(
+                "Prefix is already {}",
+                architecture
+            )));
+        }
+
+        let runtime_id = source
+            .wine_version
+            .clone()
+            .unwrap_or_else(|| self.read_runtime().default_id.clone());
+        let new_path = self.create_prefix_with_runtime(name, architecture, &runtime_id)?;
+
+        let (migrated, needs_reinstall, dropped) =
+            partition_executables(&source.registered_executables, prefix_path, architecture);
+
+        let mut config = PrefixConfig::load_from_file(&new_path)?.ok_or_else(|| {
+            PrefixError::NotFound(format!("No config in new prefix {}", new_path.display()))
+        })?;
+        config.description = source.description.clone();
+        config.env_vars = source.env_vars.clone();
+        for exe in &migrated {
+            // They live outside the new prefix by construction
+            let mut exe = exe.clone();
+            exe.allow_external = true;
+            config.add_executable(exe);
+        }
+        config.save_to_file(&new_path)?;
+
+        info!(
+            "[prefix] migrated '{}' to {} at '{}': {} apps carried over, {} to reinstall, {} dropped",
+            source.name,
+            architecture,
+            new_path.display(),
+            migrated.len(),
+            needs_reinstall.len(),
+            dropped.len()
+        );
+        Ok(ArchMigration {
+            prefix_path: new_path,
+            migrated,
+            needs_reinstall,
+            dropped,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base::config::RegisteredExecutableBuilder;
+
+    fn exe(path: &Path, architecture: Option<&str>) -> RegisteredExecutable {
+        let mut exe = RegisteredExecutableBuilder::new()
+            .name("App")
+            .executable_path(path.to_path_buf())
+            .build()
+            .unwrap();
+        exe.architecture = architecture.map(str::to_string);
+        exe
+    }
+
+    #[test]
+    fn test_partition_executables() {
+        let dir = tempfile::tempdir().unwrap();
+        let prefix = dir.path().join("prefix");
+        let games = dir.path().join("games");
+        std::fs::create_dir_all(prefix.join("drive_c")).unwrap();
+        std::fs::create_dir_all(&games).unwrap();
+
+        let installed = prefix.join("drive_c/app.exe");
+        let portable = games.join("game.exe");
+        let portable64 = games.join("game64.exe");
+        for path in [&installed, &portable, &portable64] {
+            std::fs::write(path, b"MZ").unwrap();
+        }
+
+        let exes = vec![
+            exe(&installed, Some("x86")),
+            exe(&portable, Some("x86")),
+            exe(&portable64, Some("x64")),
+            exe(&games.join("gone.exe"), None),
+        ];
+
+        let (migrated, reinstall, dropped) = partition_executables(&exes, &prefix, "win32");
+        assert_eq!(migrated.len(), 1);
+        assert_eq!(migrated[0].executable_path, portable);
+        assert_eq!(reinstall.len(), 1);
+        assert_eq!(reinstall[0].executable_path, installed);
+        assert_eq!(dropped.len(), 2);
+
+        let (migrated, _, dropped) = partition_executables(&exes, &prefix, "win64");
+        assert_eq!(migrated.len(), 2);
+        assert_eq!(dropped.len(), 1);
+    }
+}
@@ -407,6 +407,82 @@ fn find_tequila_bin() -> String {
     "tequila".to_string()
 }
 
+// ── Wine-exported file associations ────────────────────────────────────
+
+/// A host file-type handler that Wine's `winemenubuilder` exported for a
+/// prefix: `applications/wine-extension-<ext>.desktop` plus its MIME
+/// package. These register the prefix with the host's MIME database.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HostAssociation {
+    /// Extension without the leading dot, as it appears in the file names.
+    pub extension: String,
+    pub desktop_file: PathBuf,
+    pub mime_package: Option<PathBuf>,
+}
+
+fn mime_packages_dir() -> PathBuf {
+    system_applications_dir()
+        .with_file_name("mime")
+        .join("packages")
+}
+
+/// List the file associations Wine exported to the host for `prefix_path`.
+///
+/// Entries are attributed by the `WINEPREFIX="…"` winemenubuilder writes
+/// into the `Exec` line; handlers for other prefixes are left out.
+pub fn list_host_associations(prefix_path: &Path) -> Vec<HostAssociation> {
+    let marker = format!("WINEPREFIX=\"{}\"", prefix_path.display());
+    let Ok(entries) = fs::read_dir(system_applications_dir()) else {
+        return Vec::new();
+    };
+    let mut found: Vec<HostAssociation> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let extension = path
+                .file_name()?
+                .to_str()?
+                .strip_prefix("wine-extension-")?
+                .strip_suffix(".desktop")?
+                .to_string();
+            if !fs::read_to_string(&path).ok()?.contains(&marker) {
+                return None;
+            }
+            let package = mime_packages_dir().join(format!("x-wine-extension-{}.xml", extension));
+            Some(HostAssociation {
+                extension,
+                desktop_file: path,
+                mime_package: package.exists().then_some(package),
+            })
+        })
+        .collect();
+    found.sort_by(|a, b| a.extension.cmp(&b.extension));
+    found
+}
+
+/// Delete a Wine-exported association and refresh the host's desktop and
+/// MIME caches. Cache refresh is best-effort; the tools may be missing.
+pub fn remove_host_association(association: &HostAssociation) -> Result<()> {
+    fs::remove_file(&association.desktop_file)?;
+    if let Some(package) = &association.mime_package {
+        fs::remove_file(package)?;
+    }
+    info!(
+        "[desktop] removed host association for .{}",
+        association.extension
+    );
+
+    let _ = std::process::Command::new("update-desktop-database")
+        .arg(system_applications_dir())
+        .output();
+    if let Some(mime_dir) = mime_packages_dir().parent() {
+        let _ = std::process::Command::new("update-mime-database")
+            .arg(mime_dir)
+            .output();
+    }
+    Ok(())
+}
+
 // ── macOS helper functions ─────────────────────────────────────────────
 
 /// Tequila Applications directory for a specific prefix.
@@ -0,0 +1,122 @@
+use crate::Manager;
+use runtime::{RuntimeManager, RuntimeSource};
+use std::process::Command;
+
+/// Versions of the tools Tequila drives, gathered for bug reports.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Diagnostics {
+    /// Name of the default Wine runtime; `None` when none is configured.
+    pub runtime: Option<String>,
+    /// `wine --version` of the default runtime; `None` if it didn't run.
+    pub wine_version: Option<String>,
+    /// `winetricks --version`; `None` if winetricks isn't installed.
+    pub winetricks_version: Option<String>,
+    /// Installed graphics translation layers, e.g. `"DXVK 2.5"`.
+    pub graphics_backends: Vec<String>,
+}
+
+impl Diagnostics {
+    /// Gather diagnostics for the default runtime of `runtimes`. This runs
+    /// `wine` and `winetricks`, so keep it off the UI thread.
+    pub fn collect(runtimes: &RuntimeManager) -> Self {
+        let runtime = runtimes.get_default();
+        let wine = runtime.and_then(|rt| match rt.source {
+            RuntimeSource::System => Some("wine".into()),
+            _ => runtime::discover_wine_binary(&rt.bundle_dir),
+        });
+        Self {
+            runtime: runtime.map(|rt| rt.name.clone()),
+            wine_version: wine.and_then(|wine| tool_version(Command::new(wine))),
+            winetricks_version: tool_version(Command::new("winetricks")),
+            graphics_backends: runtime::graphics::installed_backends()
+                .iter()
+                .map(|b| format!("{} {}", b.display_name(), b.version_string()))
+                .collect(),
+        }
+    }
+
+    /// Plain-text report for pasting into an issue: `header` first (the
+    /// app and toolkit versions the caller knows about), then the host and
+    /// what was collected, one `label: value` per line.
+    pub fn report(&self, header: &[(&str, String)]) -> String {
+        let missing = || "not found".to_string();
+        let graphics = if self.graphics_backends.is_empty() {
+            "none".to_string()
+        } else {
+            self.graphics_backends.join(", ")
+        };
+        let os = format!("{} {}", std::env::consts::OS, std::env::consts::ARCH);
+        header
+            .iter()
+            .map(|(label, value)| (*label, value.clone()))
+            .chain([
+                ("OS", os),
+                ("Wine runtime", self.runtime.clone().unwrap_or_else(missing)),
+                ("Wine", self.wine_version.clone().unwrap_or_else(missing)),
+                (
+                    "winetricks",
+                    self.winetricks_version.clone().unwrap_or_else(missing),
+                ),
+                ("Graphics backends", graphics),
+            ])
+            .map(|(label, value)| format!("{}: {}\n", label, value))
+            .collect()
+    }
+}
+
+/// First line of `<program> --version`, if it ran and succeeded.
+fn tool_version(mut cmd: Command) -> Option<String> {
+    let output = cmd.arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+}
+
+impl Manager {
+    /// [`Diagnostics::collect`] for this manager's runtimes.
+    pub fn diagnostics(&self) -> Diagnostics {
+        // A copy, so the runtime lock isn't held while wine runs
+        let runtimes = self.clone_runtime();
+        Diagnostics::collect(&runtimes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_lists_header_then_tools() {
+        let diagnostics = Diagnostics {
+            runtime: Some("System Wine".into()),
+            wine_version: Some("wine-9.0".into()),
+            winetricks_version: None,
+            graphics_backends: Vec::new(),
+        };
+        let report = diagnostics.report(&[("Tequila", "0.1.0".into())]);
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines[0], "Tequila: 0.1.0");
+        assert!(lines[1].starts_with("OS: "));
+        assert_eq!(
+            lines[2..],
+            [
+                "Wine runtime: System Wine",
+                "Wine: wine-9.0",
+                "winetricks: not found",
+                "Graphics backends: none",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_without_runtime() {
+        let diagnostics = Diagnostics::collect(&RuntimeManager::new());
+        assert_eq!(diagnostics.runtime, None);
+        assert_eq!(diagnostics.wine_version, None);
+    }
+}
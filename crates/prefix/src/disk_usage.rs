@@ -0,0 +1,183 @@
+use crate::Manager;
+use base::error::{PrefixError, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::SystemTime;
+
+/// Upper bound on prefixes measured at once. Walking is disk-bound, so a
+/// few threads are enough to overlap seeks without thrashing a hard drive.
+const MAX_SIZE_WORKERS: usize = 4;
+
+/// Directories, relative to the prefix, whose modification times make up a
+/// prefix's signature. Installing or removing an app adds or removes an
+/// entry in one of them.
+const SIGNATURE_PATHS: &[&str] = &[
+    "",
+    "drive_c",
+    "drive_c/Program Files",
+    "drive_c/Program Files (x86)",
+    "drive_c/ProgramData",
+    "drive_c/users",
+    "drive_c/windows",
+];
+
+type Signature = Vec<Option<SystemTime>>;
+
+/// Sizes measured by [`Manager::prefix_disk_usage`], with the signature of
+/// the prefix at the time.
+pub(crate) type DiskUsageCache = Mutex<HashMap<PathBuf, (Signature, u64)>>;
+
+/// Cheap stand-in for "the prefix changed": a few `stat` calls instead of a
+/// walk. Files growing deep inside a directory go unnoticed until one of
+/// the signature directories changes too.
+fn signature(prefix_path: &Path) -> Signature {
+    SIGNATURE_PATHS
+        .iter()
+        .map(|dir| {
+            fs::metadata(prefix_path.join(dir))
+                .and_then(|m| m.modified())
+                .ok()
+        })
+        .collect()
+}
+
+/// Total size of the regular files under `path`. `None` if `cancel` was set
+/// before the walk finished.
+pub(crate) fn dir_size(path: &Path, cancel: Option<&AtomicBool>) -> Option<u64> {
+    let mut total = 0;
+    for entry in walkdir::WalkDir::new(path).into_iter().flatten() {
+        if cancel.is_some_and(|c| c.load(Ordering::Relaxed)) {
+            return None;
+        }
+        if entry.file_type().is_file() {
+            total += entry.metadata().map_or(0, |m| m.len());
+        }
+    }
+    Some(total)
+}
+
+impl Manager {
+    /// Measure the disk usage of each of `prefix_paths` on a bounded pool
+    /// of worker threads, returning sizes in the same order. `on_size` is
+    /// called from the workers as each size resolves, so a list can fill in
+    /// row by row.
+    ///
+    /// Sizes are cached by path and a cheap signature of the prefix, so
+    /// refreshing an unchanged list doesn't walk it again. Setting `cancel`
+    /// stops every walk early and returns an error.
+    pub fn prefix_disk_usage<F>(
+        &self,
+        prefix_paths: &[PathBuf],
+        cancel: Option<Arc<AtomicBool>>,
+        on_size: F,
+    ) -> Result<Vec<u64>>
+    where
+        F: Fn(&Path, u64) + Sync,
+    {
+        let cancel = cancel.as_deref();
+        let next = AtomicUsize::new(0);
+        let sizes = Mutex::new(vec![0; prefix_paths.len()]);
+        let workers = thread::available_parallelism()
+            .map_or(1, |n| n.get())
+            .min(MAX_SIZE_WORKERS)
+            .min(prefix_paths.len());
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| {
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(path) = prefix_paths.get(i) else {
+                            break;
+                        };
+                        let Some(size) = self.cached_disk_usage(path, cancel) else {
+                            break;
+                        };
+                        on_size(path, size);
+                        sizes.lock().unwrap()[i] = size;
+                    }
+                });
+            }
+        });
+
+        if cancel.is_some_and(|c| c.load(Ordering::Relaxed)) {
+            return Err(PrefixError::Process("Cancelled".to_string()));
+        }
+        Ok(sizes.into_inner().unwrap())
+    }
+
+    /// Size of one prefix, from the cache while its signature still
+    /// matches. `None` if canceled.
+    fn cached_disk_usage(&self, prefix_path: &Path, cancel: Option<&AtomicBool>) -> Option<u64> {
+        let signature = signature(prefix_path);
+        if let Some((cached, size)) = self.disk_usage.lock().unwrap().get(prefix_path)
+            && *cached == signature
+        {
+            return Some(*size);
+        }
+        let size = dir_size(prefix_path, cancel)?;
+        self.disk_usage
+            .lock()
+            .unwrap()
+            .insert(prefix_path.to_path_buf(), (signature, size));
+        Some(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::Fixture;
+
+    #[test]
+    fn test_prefix_disk_usage_caches_until_signature_changes() {
+        let fixture = Fixture::new();
+        let first = fixture.add_prefix("first");
+        let second = fixture.add_prefix("second");
+        let manager = fixture.manager();
+        std::fs::write(second.join("drive_c/data.bin"), [0u8; 100]).unwrap();
+
+        let reported = Mutex::new(Vec::new());
+        let paths = [first.clone(), second.clone()];
+        let sizes = manager
+            .prefix_disk_usage(&paths, None, |path, size| {
+                reported.lock().unwrap().push((path.to_path_buf(), size))
+            })
+            .unwrap();
+        // Each has the 2-byte sample exe; the second also has data.bin
+        assert_eq!(sizes, [2, 102]);
+        let mut reported = reported.into_inner().unwrap();
+        reported.sort();
+        assert_eq!(reported, [(first.clone(), 2), (second, 102)]);
+
+        // Growth inside an app's directory leaves the signature alone
+        let app_dir = first.join("drive_c/Program Files/Sample App");
+        std::fs::write(app_dir.join("save.dat"), [0u8; 10]).unwrap();
+        let sizes = manager.prefix_disk_usage(&paths[..1], None, |_, _| {});
+        assert_eq!(sizes.unwrap(), [2]);
+
+        // A newly installed app doesn't
+        let new_app = first.join("drive_c/Program Files/New App");
+        std::fs::create_dir(&new_app).unwrap();
+        std::fs::write(new_app.join("new.exe"), b"MZ").unwrap();
+        let sizes = manager.prefix_disk_usage(&paths[..1], None, |_, _| {});
+        assert_eq!(sizes.unwrap(), [14]);
+    }
+
+    #[test]
+    fn test_prefix_disk_usage_cancelled() {
+        let fixture = Fixture::new();
+        let path = fixture.add_prefix("sample");
+        let cancel = Arc::new(AtomicBool::new(true));
+        let result = fixture
+            .manager()
+            .prefix_disk_usage(&[path], Some(cancel), |_, _| {
+                panic!("no size is reported once cancelled")
+            });
+        assert!(result.is_err());
+        assert!(fixture.manager().disk_usage.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,246 @@
+//! Wine drive letters: the `dosdevices/<letter>:` symlinks in a prefix.
+//!
+//! `c:` points at the prefix's own `drive_c` and is left alone. `z:` maps
+//! the host root; it can be removed, but Windows paths outside `drive_c`
+//! (see [`windows_path`](crate::windows_path)) stop resolving without it.
+
+use base::error::{PrefixError, Result};
+use log::info;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// One `dosdevices` drive letter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DriveMapping {
+    /// Lowercase drive letter, without the colon.
+    pub letter: char,
+    /// The symlink target as stored, possibly relative to `dosdevices`.
+    pub target: PathBuf,
+    /// Whether the target currently resolves to a directory.
+    pub target_exists: bool,
+}
+
+impl DriveMapping {
+    /// `D:` style label.
+    pub fn label(&self) -> String {
+        format!("{}:", self.letter.to_ascii_uppercase())
+    }
+
+    /// The system drive, which can't be changed here.
+    pub fn is_system(&self) -> bool {
+        self.letter == 'c'
+    }
+
+    /// The drive exposing the host root.
+    pub fn is_host_root(&self) -> bool {
+        self.letter == 'z'
+    }
+}
+
+fn dosdevices(prefix_path: &Path) -> PathBuf {
+    prefix_path.join("dosdevices")
+}
+
+/// Parse a `dosdevices` entry name like `d:`. Raw device entries (`d::`)
+/// and ports (`com1`) are not drives.
+fn drive_letter(name: &str) -> Option<char> {
+    let mut chars = name.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(letter), Some(':'), None) if letter.is_ascii_alphabetic() => {
+            Some(letter.to_ascii_lowercase())
+        }
+        _ => None,
+    }
+}
+
+/// Check a letter the user picked for a new or removed mapping.
+fn checked_letter(letter: char) -> Result<char> {
+    let letter = letter.to_ascii_lowercase();
+    if !letter.is_ascii_lowercase() {
+        return Err(PrefixError::Validation(format!(
+            "'{}' is not a drive letter",
+            letter
+        )));
+    }
+    if letter == 'c' {
+        return Err(PrefixError::Validation(
+            "C: is the prefix's system drive and can't be changed".to_string(),
+        ));
+    }
+    Ok(letter)
+}
+
+/// List the drive letters of a prefix, sorted by letter. A prefix without
+/// `dosdevices` has none.
+pub fn list_drives(prefix_path: &Path) -> Result<Vec<DriveMapping>> {
+    let dir = dosdevices(prefix_path);
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut drives: Vec<DriveMapping> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let letter = drive_letter(entry.file_name().to_str()?)?;
+            let target = fs::read_link(entry.path()).ok()?;
+            Some(DriveMapping {
+                letter,
+                target_exists: entry.path().is_dir(),
+                target,
+            })
+        })
+        .collect();
+    drives.sort_by_key(|d| d.letter);
+    Ok(drives)
+}
+
+/// Map `letter` to the host directory `target`. The letter must be free
+/// and the target an existing directory.
+pub fn add_drive(prefix_path: &Path, letter: char, target: &Path) -> Result<()> {
+    let letter = checked_letter(letter)?;
+    if !target.is_dir() {
+        return Err(PrefixError::NotFound(format!(
+            "Directory does not exist: {}",
+            target.display()
+        )));
+    }
+    let dir = dosdevices(prefix_path);
+    let link = dir.join(format!("{}:", letter));
+    // symlink_metadata so dangling links count as taken too
+    if link.symlink_metadata().is_ok() {
+        return Err(PrefixError::AlreadyExists(format!(
+            "Drive {}: is already mapped",
+            letter.to_ascii_uppercase()
+        )));
+    }
+    fs::create_dir_all(&dir)?;
+    std::os::unix::fs::symlink(target, &link)?;
+    info!(
+        "[drives] mapped {}: to {} in {}",
+        letter,
+        target.display(),
+        prefix_path.display()
+    );
+    Ok(())
+}
+
+/// Remove the mapping for `letter`. Only the symlink is deleted, never the
+/// directory it points at.
+pub fn remove_drive(prefix_path: &Path, letter: char) -> Result<()> {
+    let letter = checked_letter(letter)?;
+    let link = dosdevices(prefix_path).join(format!("{}:", letter));
+    match link.symlink_metadata() {
+        Ok(meta) if meta.file_type().is_symlink() => {}
+        Ok(_) => {
+            return Err(PrefixError::Validation(format!(
+                "{} is not a symlink",
+                link.display()
+            )));
+        }
+        Err(_) => {
+            return Err(PrefixError::NotFound(format!(
+                "Drive {}: is not mapped",
+                letter.to_ascii_uppercase()
+            )));
+        }
+    }
+    fs::remove_file(&link)?;
+    info!(
+        "[drives] removed {}: from {}",
+        letter,
+        prefix_path.display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prefix_with_defaults() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let prefix = dir.path();
+        fs::create_dir_all(prefix.join("drive_c")).unwrap();
+        fs::create_dir_all(prefix.join("dosdevices")).unwrap();
+        std::os::unix::fs::symlink("../drive_c", prefix.join("dosdevices/c:")).unwrap();
+        std::os::unix::fs::symlink("/", prefix.join("dosdevices/z:")).unwrap();
+        // Raw device entry, not a drive
+        std::os::unix::fs::symlink("/dev/null", prefix.join("dosdevices/c::")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_list_drives_reads_link_targets() {
+        let dir = prefix_with_defaults();
+        let drives = list_drives(dir.path()).unwrap();
+        let summary: Vec<(char, &Path, bool)> = drives
+            .iter()
+            .map(|d| (d.letter, d.target.as_path(), d.target_exists))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ('c', Path::new("../drive_c"), true),
+                ('z', Path::new("/"), true),
+            ]
+        );
+        assert!(drives[0].is_system());
+        assert!(drives[1].is_host_root());
+
+        assert!(list_drives(&dir.path().join("missing")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_add_and_remove_drive() {
+        let dir = prefix_with_defaults();
+        let games = tempfile::tempdir().unwrap();
+
+        add_drive(dir.path(), 'D', games.path()).unwrap();
+        let d = list_drives(dir.path())
+            .unwrap()
+            .into_iter()
+            .find(|d| d.letter == 'd')
+            .unwrap();
+        assert_eq!(d.target, games.path());
+        assert_eq!(d.label(), "D:");
+
+        assert!(matches!(
+            add_drive(dir.path(), 'd', games.path()),
+            Err(PrefixError::AlreadyExists(_))
+        ));
+
+        remove_drive(dir.path(), 'd').unwrap();
+        assert!(games.path().is_dir());
+        assert!(matches!(
+            remove_drive(dir.path(), 'd'),
+            Err(PrefixError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_drive_changes_are_validated() {
+        let dir = prefix_with_defaults();
+        let games = tempfile::tempdir().unwrap();
+
+        assert!(matches!(
+            add_drive(dir.path(), 'e', &games.path().join("missing")),
+            Err(PrefixError::NotFound(_))
+        ));
+        assert!(matches!(
+            add_drive(dir.path(), 'c', games.path()),
+            Err(PrefixError::Validation(_))
+        ));
+        assert!(matches!(
+            remove_drive(dir.path(), 'c'),
+            Err(PrefixError::Validation(_))
+        ));
+        assert!(matches!(
+            add_drive(dir.path(), '1', games.path()),
+            Err(PrefixError::Validation(_))
+        ));
+
+        remove_drive(dir.path(), 'z').unwrap();
+        assert!(!dir.path().join("dosdevices/z:").exists());
+    }
+}
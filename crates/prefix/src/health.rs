@@ -0,0 +1,142 @@
+use crate::Manager;
+use base::error::{PrefixError, Result};
+use log::info;
+use registry::WineRegistry;
+use std::fs;
+use std::path::Path;
+
+/// A structural problem found by [`Manager::check_prefix`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum PrefixIssue {
+    /// `drive_c/windows/system32` is missing.
+    MissingSystem32,
+    /// A registry hive (`system.reg`, `user.reg`) is missing.
+    MissingRegistry(String),
+    /// A registry hive exists but fails to parse.
+    UnreadableRegistry { file: String, error: String },
+    /// `dosdevices/c:` is missing or doesn't resolve to `drive_c`.
+    BrokenDriveC,
+    /// The config's architecture disagrees with the `#arch=` line Wine
+    /// writes into the registry hives.
+    ArchitectureMismatch { config: String, registry: String },
+}
+
+impl PrefixIssue {
+    /// Whether [`Manager::repair_prefix`] can fix this issue. A corrupt hive
+    /// or the wrong architecture needs manual recovery or a new prefix.
+    pub fn is_repairable(&self) -> bool {
+        matches!(
+            self,
+            PrefixIssue::MissingSystem32
+                | PrefixIssue::MissingRegistry(_)
+                | PrefixIssue::BrokenDriveC
+        )
+    }
+}
+
+const REGISTRY_FILES: [&str; 2] = ["system.reg", "user.reg"];
+
+/// Read the `#arch=win32|win64` header Wine writes near the top of a hive.
+fn registry_arch(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()?
+        .lines()
+        .take(10)
+        .find_map(|line| line.strip_prefix("#arch="))
+        .map(|arch| arch.trim().to_string())
+}
+
+fn drive_c_link_ok(prefix_path: &Path) -> bool {
+    let link = prefix_path.join("dosdevices").join("c:");
+    match (
+        link.canonicalize(),
+        prefix_path.join("drive_c").canonicalize(),
+    ) {
+        (Ok(target), Ok(drive_c)) => target == drive_c,
+        _ => false,
+    }
+}
+
+impl Manager {
+    /// Verify the on-disk structure of a prefix. An empty list means the
+    /// prefix looks healthy.
+    pub fn check_prefix(&self, prefix_path: &Path) -> Result<Vec<PrefixIssue>> {
+        if !prefix_path.is_dir() {
+            return Err(PrefixError::NotFound(format!(
+                "Prefix not found: {}",
+                prefix_path.display()
+            )));
+        }
+        let mut issues = Vec::new();
+
+        if !prefix_path.join("drive_c/windows/system32").is_dir() {
+            issues.push(PrefixIssue::MissingSystem32);
+        }
+
+        for file in REGISTRY_FILES {
+            let path = prefix_path.join(file);
+            if !path.exists() {
+                issues.push(PrefixIssue::MissingRegistry(file.to_string()));
+            } else if let Err(e) = WineRegistry::check_file(&path) {
+                issues.push(PrefixIssue::UnreadableRegistry {
+                    file: file.to_string(),
+                    error: e.to_string(),
+                });
+            }
+        }
+
+        if !drive_c_link_ok(prefix_path) {
+            issues.push(PrefixIssue::BrokenDriveC);
+        }
+
+        if let Some(config) = base::config::PrefixConfig::load_from_file(prefix_path)? {
+            let registry = registry_arch(&prefix_path.join("system.reg"));
+            if let Some(registry) = registry.filter(|arch| *arch != config.architecture) {
+                issues.push(PrefixIssue::ArchitectureMismatch {
+                    config: config.architecture,
+                    registry,
+                });
+            }
+        }
+
+        Ok(issues)
+    }
+
+    /// Fix the repairable issues of a prefix: restore the `dosdevices/c:`
+    /// link and let `wineboot -u` recreate missing system files and hives.
+    /// Blocks until wineboot exits.
+    pub fn repair_prefix(&self, prefix_path: &Path) -> Result<()> {
+        let dir_name = prefix_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown");
+        let config = self.load_or_create_config(prefix_path, dir_name, &None)?;
+
+        if !drive_c_link_ok(prefix_path) {
+            let dosdevices = prefix_path.join("dosdevices");
+            let link = dosdevices.join("c:");
+            fs::create_dir_all(&dosdevices)?;
+            // symlink_metadata so dangling links are replaced too
+            if link.symlink_metadata().is_ok() {
+                fs::remove_file(&link)?;
+            }
+            std::os::unix::fs::symlink("../drive_c", &link)?;
+            info!("[health] restored dosdevices/c: for '{}'", config.name);
+        }
+
+        self.check_wine_available("wine", &config)?;
+        let output = self
+            .build_wine_command_with_args(&["wineboot", "-u"], &config, prefix_path)
+            .env("WINEDEBUG", "-all")
+            .output()
+            .map_err(|e| PrefixError::Process(format!("Failed to run wineboot: {}", e)))?;
+        if !output.status.success() {
+            return Err(PrefixError::Wine(format!(
+                "wineboot -u failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        info!("[health] ran wineboot -u for '{}'", config.name);
+        Ok(())
+    }
+}
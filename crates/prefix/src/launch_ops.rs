@@ -1,11 +1,43 @@
 use crate::Manager;
-use crate::wine_processes::apply_runtime_env;
+use crate::wine_processes::{apply_prefix_env, apply_runtime_env};
 use base::config::PrefixConfig;
 use base::error::{PrefixError, Result};
-use log::info;
+use log::{info, warn};
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command};
 
+/// Built-in Wine programs that can be started for a prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WineTool {
+    Regedit,
+    Control,
+    Taskmgr,
+    Uninstaller,
+    Winefile,
+}
+
+impl WineTool {
+    pub const ALL: [WineTool; 5] = [
+        WineTool::Regedit,
+        WineTool::Control,
+        WineTool::Taskmgr,
+        WineTool::Uninstaller,
+        WineTool::Winefile,
+    ];
+
+    /// Program name passed to `wine`.
+    pub fn program(&self) -> &'static str {
+        match self {
+            WineTool::Regedit => "regedit",
+            WineTool::Control => "control",
+            WineTool::Taskmgr => "taskmgr",
+            WineTool::Uninstaller => "uninstaller",
+            WineTool::Winefile => "winefile",
+        }
+    }
+}
+
 impl Manager {
     /// Check that the given binary (`"wine"`, `"winecfg"`, …) is available
     /// for the current runtime configuration.  Returns a clear error message
@@ -85,11 +117,14 @@ impl Manager {
         self.check_wine_available("wine", &config)?;
 
         info!("[launch] opening winecfg for prefix '{}'", config.name);
-        let child = self
-            .build_wine_command_with_args(&["winecfg"], &config, prefix_path)
-            .current_dir(prefix_path)
-            .spawn()
-            .map_err(|e| PrefixError::Process(format!("Failed to run winecfg: {}", e)))?;
+        let child = spawn_tool(
+            self.build_wine_command_with_args(&["winecfg"], &config, prefix_path)
+                .current_dir(prefix_path),
+            "winecfg",
+        )?;
+        if let Err(e) = PrefixConfig::mark_used_on_disk(prefix_path) {
+            warn!("[launch] failed to record prefix use: {}", e);
+        }
         Ok(child)
     }
 
@@ -104,12 +139,93 @@ impl Manager {
         self.check_wine_available("wine", &config)?;
 
         info!("[launch] opening regedit for prefix '{}'", config.name);
-        let child = self
-            .build_wine_command_with_args(&["regedit"], &config, prefix_path)
-            .current_dir(prefix_path)
-            .spawn()
-            .map_err(|e| PrefixError::Process(format!("Failed to run regedit: {}", e)))?;
-        Ok(child)
+        spawn_tool(
+            self.build_wine_command_with_args(&["regedit"], &config, prefix_path)
+                .current_dir(prefix_path),
+            "regedit",
+        )
+    }
+
+    /// Start one of Wine's built-in tools for a prefix. The process is reaped
+    /// on a background thread; the tool runs detached from the caller.
+    pub fn run_wine_tool(&self, prefix_path: &Path, tool: WineTool) -> Result<()> {
+        let dir_name = prefix_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown");
+        let config = self.load_or_create_config(prefix_path, dir_name, &None)?;
+
+        self.check_wine_available("wine", &config)?;
+
+        info!(
+            "[launch] opening {} for prefix '{}'",
+            tool.program(),
+            config.name
+        );
+        // winefile opens on its working directory, so start it in C:
+        let cwd = match tool {
+            WineTool::Winefile => prefix_path.join("drive_c"),
+            _ => prefix_path.to_path_buf(),
+        };
+        let child = spawn_tool(
+            self.build_wine_command_with_args(&[tool.program()], &config, prefix_path)
+                .current_dir(cwd),
+            tool.program(),
+        )?;
+        detach_child(child);
+        Ok(())
+    }
+
+    /// Run an installer (`.exe` or `.msi`) in a prefix and block until it
+    /// and every process it started have exited, so a scan right after sees
+    /// what it installed. A non-zero exit (often just a cancelled install)
+    /// is only logged.
+    pub fn run_installer(&self, prefix_path: &Path, installer: &Path) -> Result<()> {
+        let dir_name = prefix_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown");
+        let config = self.load_or_create_config(prefix_path, dir_name, &None)?;
+
+        self.check_wine_available("wine", &config)?;
+
+        info!(
+            "[launch] running installer {} in prefix '{}'",
+            installer.display(),
+            config.name
+        );
+        let status = self
+            .build_wine_command(&config, prefix_path)
+            .args(installer_args(installer))
+            .current_dir(installer.parent().unwrap_or(prefix_path))
+            .status()
+            .map_err(|e| PrefixError::Process(format!("Failed to run installer: {}", e)))?;
+        if !status.success() {
+            warn!(
+                "[launch] installer {} exited with {}",
+                installer.display(),
+                status
+            );
+        }
+        // Installers often hand off to a child and exit early
+        self.build_wine_command_for_exe("wineserver", &config, prefix_path)
+            .arg("-w")
+            .env("WINEDEBUG", "-all")
+            .status()
+            .map_err(|e| PrefixError::Process(format!("Failed to run wineserver: {}", e)))?;
+
+        let file_name = installer
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let note = format!("Ran installer {}", file_name);
+        if let Err(e) = self.add_prefix_note(prefix_path, &note, true) {
+            warn!("[launch] failed to note installer run: {}", e);
+        }
+        if let Err(e) = PrefixConfig::mark_used_on_disk(prefix_path) {
+            warn!("[launch] failed to record prefix use: {}", e);
+        }
+        Ok(())
     }
 
     /// Core helper: build a `Command` with runtime env applied (WINEPREFIX, PATH, WINEDLLPATH, etc.).
@@ -120,6 +236,7 @@ impl Manager {
         } else {
             cmd.env("WINEPREFIX", prefix_path);
         }
+        apply_prefix_env(&mut cmd, config);
         cmd
     }
 
@@ -136,6 +253,7 @@ impl Manager {
         } else {
             cmd.env("WINEPREFIX", prefix_path);
         }
+        apply_prefix_env(&mut cmd, config);
         cmd
     }
 
@@ -154,6 +272,36 @@ impl Manager {
     }
 }
 
+/// Start a Wine tool without waiting on it. Only `spawn` is used here, so a
+/// hung tool can never stall the caller; a missing binary fails immediately.
+pub(crate) fn spawn_tool(cmd: &mut Command, tool: &str) -> Result<Child> {
+    cmd.spawn()
+        .map_err(|e| PrefixError::Process(format!("Failed to run {}: {}", tool, e)))
+}
+
+/// Reap `child` on a background thread so it doesn't linger as a zombie
+/// once the caller has no further use for the handle.
+pub fn detach_child(mut child: Child) {
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+}
+
+/// Arguments after `wine` that start `installer`: `.msi` packages go
+/// through `msiexec /i`, anything else is run directly.
+fn installer_args(installer: &Path) -> Vec<OsString> {
+    let is_msi = installer
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("msi"));
+    let mut args: Vec<OsString> = if is_msi {
+        vec!["msiexec".into(), "/i".into()]
+    } else {
+        Vec::new()
+    };
+    args.push(installer.as_os_str().to_owned());
+    args
+}
+
 /// Search PATH for a named executable using `which`.
 fn find_in_path(name: &str) -> Option<PathBuf> {
     let output = std::process::Command::new("which")
@@ -166,3 +314,68 @@ fn find_in_path(name: &str) -> Option<PathBuf> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::Fixture;
+
+    const MISSING_BINARY: &str = "tequila-test-missing-wine-binary";
+
+    #[test]
+    fn test_tool_launches_fail_when_runtime_is_missing() {
+        let fixture = Fixture::new();
+        let path = fixture.add_prefix("tools");
+        let manager = fixture.manager();
+        {
+            let mut runtimes = manager.write_runtime();
+            let bundle_dir = fixture.wine_dir().join("missing-runtime");
+            let id = runtimes
+                .register_version("0.0-missing", String::new(), bundle_dir)
+                .id
+                .clone();
+            runtimes.set_default(&id);
+        }
+
+        assert!(matches!(
+            manager.run_winecfg(&path),
+            Err(PrefixError::NotFound(_))
+        ));
+        assert!(matches!(
+            manager.run_regedit(&path),
+            Err(PrefixError::NotFound(_))
+        ));
+        for tool in WineTool::ALL {
+            assert!(matches!(
+                manager.run_wine_tool(&path, tool),
+                Err(PrefixError::NotFound(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_installer_args_route_msi_through_msiexec() {
+        assert_eq!(
+            installer_args(Path::new("/dl/Setup.MSI")),
+            ["msiexec", "/i", "/dl/Setup.MSI"]
+        );
+        assert_eq!(
+            installer_args(Path::new("/dl/setup.exe")),
+            ["/dl/setup.exe"]
+        );
+    }
+
+    #[test]
+    fn test_check_wine_available_missing_binary() {
+        let fixture = Fixture::new();
+        let path = fixture.add_prefix("tools");
+        let config = fixture
+            .manager()
+            .load_or_create_config(&path, "tools", &None)
+            .unwrap();
+        let result = fixture
+            .manager()
+            .check_wine_available(MISSING_BINARY, &config);
+        assert!(matches!(result, Err(PrefixError::NotFound(_))));
+    }
+}
@@ -1,26 +1,44 @@
 mod app_ops;
+mod arch_ops;
 pub mod desktop;
+mod diagnostics;
+mod disk_usage;
+pub mod drives;
+mod health;
 mod launch_ops;
 mod manager;
 pub mod prefix;
+pub mod prefix_log;
 mod prefix_ops;
 mod process_tracker;
 mod runtime_ops;
+mod steam;
+mod template_ops;
+#[cfg(test)]
+mod test_fixtures;
 mod wine_processes;
 
+pub use app_ops::AppTransfer;
+pub use arch_ops::ArchMigration;
+pub use diagnostics::Diagnostics;
+pub use health::PrefixIssue;
+pub use launch_ops::{WineTool, detach_child};
 pub use manager::Manager;
-pub use prefix::Prefix;
-pub use prefix::prefix_label;
+pub use prefix::{ArchMismatch, LaunchHandle, Prefix, Rescan, architecture_mismatch};
+pub use prefix::{prefix_label, sort_by_last_used, windows_path};
 pub use prefix::resolve_or_extract_icon;
 pub use prefix_ops::TQL_EXTENSION;
 pub use process_tracker::ProcessTracker;
-pub use wine_processes::apply_runtime_env;
+pub use steam::{ProtonPrefix, proton_app_id};
+pub use wine_processes::{
+    WineProcess, apply_prefix_env, apply_runtime_env, prefix_processes, wineserver_running,
+};
 
 // Re-exports from sub-crates for UI convenience
 pub use base::config;
 pub use base::{
-    self, GraphicsBackend, GraphicsConfig, PrefixConfig, PrefixError, PrefixInfo,
-    RegisteredExecutable, WinePrefix,
+    self, GraphicsBackend, GraphicsConfig, LaunchProfile, NoteEntry, PrefixConfig, PrefixError,
+    PrefixInfo, RegisteredExecutable, WinePrefix,
 };
 pub use registry;
 pub use registry::keys;
@@ -28,8 +46,8 @@ pub use registry::{RegEditor, RegistryEditor, WineRegistry};
 pub use runtime;
 pub use runtime::download;
 pub use runtime::{Runtime, RuntimeManager, RuntimeSource};
-pub use scan::{ApplicationScanner, IconCache};
-pub use store::{PrefixStore, Settings};
+pub use scan::{ApplicationScanner, DEFAULT_EXECUTABLE_EXTENSIONS, IconCache};
+pub use store::{PrefixStore, PrefixTemplate, Settings};
 
 // ── GitHub API client ────────────────────────────────────────────────
 
@@ -1,3 +1,4 @@
+use crate::disk_usage::DiskUsageCache;
 use runtime::RuntimeManager;
 use std::fmt;
 use std::path::PathBuf;
@@ -10,6 +11,7 @@ pub struct Manager {
     pub(crate) scanner: Arc<scan::ApplicationScanner>,
     pub(crate) runtime_manager: Arc<RwLock<RuntimeManager>>,
     pub(crate) store: Arc<PrefixStore>,
+    pub(crate) disk_usage: Arc<DiskUsageCache>,
 }
 
 impl fmt::Debug for Manager {
@@ -27,7 +29,19 @@ impl Manager {
         store: Arc<PrefixStore>,
     ) -> Self {
         let mut runtime_manager = RuntimeManager::new();
+        let scanner = scan::ApplicationScanner::new(icon_cache);
         if let Some(settings) = store::Settings::load() {
+            scanner.set_include_scripts(settings.scan_scripts);
+            scanner.set_executable_extensions(
+                scan::DEFAULT_EXECUTABLE_EXTENSIONS
+                    .iter()
+                    .copied()
+                    .chain(settings.extra_scan_extensions.iter().map(String::as_str)),
+            );
+            if let Some(max) = settings.max_registry_reads {
+                registry::set_max_concurrent_reads(max);
+            }
+            crate::prefix_log::set_enabled(settings.prefix_logging);
             let mut rm: RuntimeManager = settings.into();
             rm.ensure_system_runtime();
             runtime_manager = rm;
@@ -36,9 +50,10 @@ impl Manager {
         }
         Self {
             wine_dir,
-            scanner: Arc::new(scan::ApplicationScanner::new(icon_cache)),
+            scanner: Arc::new(scanner),
             runtime_manager: Arc::new(RwLock::new(runtime_manager)),
             store,
+            disk_usage: Arc::default(),
         }
     }
 
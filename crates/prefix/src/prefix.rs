@@ -1,15 +1,21 @@
-use base::config::{PrefixConfig, RegisteredExecutable};
+use base::config::{LaunchProfile, PrefixConfig, RegisteredExecutable};
 use base::error::{PrefixError, Result};
 use base::{PrefixInfo, WinePrefix};
 use log::{debug, error, info};
+use scan::{FileStamp, ScanCache};
+use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
-use std::process::{Child, Command};
+use std::process::{Child, Command, Stdio};
 use std::sync::{Arc, RwLock};
 
 use runtime::RuntimeManager;
 use store::PrefixStore;
 
+use crate::launch_ops::spawn_tool;
+use crate::prefix_log;
+use crate::wine_processes::wineserver_running;
+
 /// A self-contained Wine prefix with shared access to global services.
 ///
 /// Unlike the passive [`WinePrefix`] data struct, `Prefix` owns its path and
@@ -29,7 +35,6 @@ pub struct Prefix {
     // ── Shared global services ──────────────────────────────────────
     pub(crate) scanner: Arc<scan::ApplicationScanner>,
     pub(crate) runtime_manager: Arc<RwLock<RuntimeManager>>,
-    #[allow(dead_code)]
     pub(crate) store: Arc<PrefixStore>,
 }
 
@@ -39,6 +44,39 @@ impl PartialEq for Prefix {
     }
 }
 
+/// Outcome of [`Prefix::rescan_applications_async`].
+#[derive(Debug, Clone)]
+pub struct Rescan {
+    /// Everything found, as a full scan would report it.
+    pub executables: Vec<RegisteredExecutable>,
+    /// Paths not in the previously saved scan. Empty when there was none.
+    pub new_paths: Vec<PathBuf>,
+}
+
+/// Handle to an executable started by [`Prefix::launch_executable_async`].
+pub struct LaunchHandle {
+    pid: u32,
+    child: tokio::process::Child,
+}
+
+impl LaunchHandle {
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// Wait for the process to exit and return its exit code.
+    ///
+    /// Resolves to `None` when the process was terminated by a signal.
+    pub async fn wait(mut self) -> Result<Option<i32>> {
+        let status = self
+            .child
+            .wait()
+            .await
+            .map_err(|e| PrefixError::Process(format!("Failed to wait for process: {}", e)))?;
+        Ok(status.code())
+    }
+}
+
 impl Prefix {
     /// Build a `Prefix` from an already-loaded [`WinePrefix`] and a [`Manager`].
     pub fn from_wine_prefix(prefix: &WinePrefix, mgr: &super::Manager) -> Self {
@@ -115,14 +153,7 @@ impl Prefix {
 
     /// Calculate the total on-disk size of the prefix directory.
     pub fn calculate_size(&self) -> Result<u64> {
-        let total_size = walkdir::WalkDir::new(&self.path)
-            .into_iter()
-            .flatten()
-            .filter(|entry| entry.file_type().is_file())
-            .filter_map(|entry| entry.metadata().ok())
-            .map(|metadata| metadata.len())
-            .sum();
-        Ok(total_size)
+        Ok(crate::disk_usage::dir_size(&self.path, None).unwrap_or_default())
     }
 
     /// Build a [`PrefixInfo`] summary.
@@ -136,6 +167,7 @@ impl Prefix {
             architecture: self.config.architecture.clone(),
             creation_date: self.config.creation_date,
             last_modified: self.config.last_modified,
+            wineserver_running: wineserver_running(&self.path),
         })
     }
 
@@ -146,10 +178,26 @@ impl Prefix {
         self.config.save_to_file(&self.path)
     }
 
-    /// Add an executable and persist.
-    pub fn add_executable(&mut self, executable: RegisteredExecutable) -> Result<()> {
-        self.config.add_executable(executable);
-        self.save_config()
+    /// Add an executable and persist. Returns `false` (and saves nothing)
+    /// when the executable is already registered. Executables outside the
+    /// prefix's `drive_c` are rejected unless they set `allow_external`.
+    pub fn add_executable(&mut self, executable: RegisteredExecutable) -> Result<bool> {
+        if !executable.is_allowed_in_prefix(&self.path) {
+            error!(
+                "[prefix] executable outside prefix: {}",
+                executable.executable_path.display()
+            );
+            return Err(PrefixError::Validation(format!(
+                "Executable '{}' is not inside the prefix '{}'",
+                executable.executable_path.display(),
+                self.config.name
+            )));
+        }
+        if !self.config.add_executable(executable) {
+            return Ok(false);
+        }
+        self.save_config()?;
+        Ok(true)
     }
 
     /// Remove an executable by index and persist.
@@ -167,6 +215,7 @@ impl Prefix {
         executables.extend(self.scanner.scan_for_desktop_files(&self.path)?);
         executables.sort_by(|a, b| a.name.cmp(&b.name));
         executables.dedup_by(|a, b| a.name == b.name && a.executable_path == b.executable_path);
+        self.log_scan(&executables, None);
         Ok(executables)
     }
 
@@ -181,9 +230,74 @@ impl Prefix {
         );
         executables.sort_by(|a, b| a.name.cmp(&b.name));
         executables.dedup_by(|a, b| a.name == b.name && a.executable_path == b.executable_path);
+        self.log_scan(&executables, None);
         Ok(executables)
     }
 
+    /// Rescan the prefix against the scan saved in the store: executables
+    /// whose file is unchanged since then are not parsed again, and the
+    /// ones that weren't there before are reported in `new_paths`.
+    pub async fn rescan_applications_async(&self) -> Result<Rescan> {
+        let key = self.path.to_string_lossy();
+        let previous = self.store.list_scanned_executables(&key)?;
+        let stamps = self.store.list_scanned_stamps(&key)?;
+        let cache: ScanCache = previous
+            .iter()
+            .filter_map(|exe| {
+                let &(size, mtime) = stamps.get(&exe.executable_path)?;
+                let stamp = FileStamp { size, mtime };
+                Some((exe.executable_path.clone(), (stamp, exe.clone())))
+            })
+            .collect();
+
+        let mut executables = self
+            .scanner
+            .scan_prefix_incremental_async(&self.path, cache)
+            .await?;
+        executables.extend(
+            self.scanner
+                .scan_for_desktop_files_async(&self.path)
+                .await?,
+        );
+        executables.sort_by(|a, b| a.name.cmp(&b.name));
+        executables.dedup_by(|a, b| a.name == b.name && a.executable_path == b.executable_path);
+
+        let known: HashSet<&PathBuf> = previous.iter().map(|e| &e.executable_path).collect();
+        let new_paths = if known.is_empty() {
+            Vec::new()
+        } else {
+            executables
+                .iter()
+                .map(|e| &e.executable_path)
+                .filter(|path| !known.contains(path))
+                .cloned()
+                .collect()
+        };
+        self.log_scan(&executables, Some(&new_paths));
+        Ok(Rescan {
+            executables,
+            new_paths,
+        })
+    }
+
+    /// Write a scan's results to the prefix log.
+    fn log_scan(&self, executables: &[RegisteredExecutable], new_paths: Option<&[PathBuf]>) {
+        let mut lines = vec![match new_paths {
+            Some(new_paths) => format!(
+                "found {} executable(s), {} new",
+                executables.len(),
+                new_paths.len()
+            ),
+            None => format!("found {} executable(s)", executables.len()),
+        }];
+        lines.extend(
+            executables
+                .iter()
+                .map(|exe| format!("  {}: {}", exe.name, exe.executable_path.display())),
+        );
+        prefix_log::record(&self.path, "scan", &lines.join("\n"));
+    }
+
     /// Enrich executables with extracted metadata and icons.
     ///
     /// Returns `true` if any executable was modified.
@@ -200,17 +314,30 @@ impl Prefix {
                 exe.icon_path = None;
                 changed = true;
             }
-            if exe.file_description.is_none() {
+            // A missing file may come back (e.g. an unmounted drive), so only
+            // files that were actually there count as checked
+            if !exe.metadata_checked
+                && (exe.architecture.is_none() || exe.file_description.is_none())
+                && exe.executable_path.is_file()
+            {
                 let meta = scan::extract_metadata_for_exe(&exe.executable_path);
-                if meta.file_version.is_some() || meta.file_description.is_some() {
+                if exe.architecture.is_none()
+                    && let Some(architecture) = meta.architecture
+                {
+                    exe.set_architecture(architecture);
+                }
+                if exe.file_description.is_none()
+                    && (meta.file_version.is_some() || meta.file_description.is_some())
+                {
                     exe.file_version = meta.file_version;
                     exe.product_version = meta.product_version;
                     exe.company_name = meta.company_name;
                     exe.file_description = meta.file_description;
                     exe.product_name = meta.product_name;
                     exe.imported_modules = meta.imported_modules;
-                    changed = true;
                 }
+                exe.metadata_checked = true;
+                changed = true;
             }
         }
         changed
@@ -290,6 +417,7 @@ impl Prefix {
         } else {
             cmd.env("WINEPREFIX", &self.path);
         }
+        crate::wine_processes::apply_prefix_env(&mut cmd, &self.config);
         cmd
     }
 
@@ -301,6 +429,7 @@ impl Prefix {
         } else {
             cmd.env("WINEPREFIX", &self.path);
         }
+        crate::wine_processes::apply_prefix_env(&mut cmd, &self.config);
         cmd
     }
 
@@ -316,7 +445,69 @@ impl Prefix {
     // ─── Launch operations ──────────────────────────────────────────
 
     /// Launch a registered executable in this prefix.
+    ///
+    /// The child is spawned detached (its stdout/stderr are inherited), so
+    /// this is the fire-and-forget path used by the normal Run action.
     pub fn launch_executable(&self, executable: &RegisteredExecutable) -> Result<Child> {
+        let mut cmd = self.build_launch_command(executable, None)?;
+        self.spawn_launch_command(&mut cmd, executable)
+    }
+
+    /// Launch a registered executable with piped stdout/stderr/stdin.
+    ///
+    /// The caller owns the returned `Child` and is responsible for draining
+    /// its pipes (e.g. the console window streams them into a log view).
+    pub fn launch_executable_with_console(
+        &self,
+        executable: &RegisteredExecutable,
+    ) -> Result<Child> {
+        let mut cmd = self.build_launch_command(executable, None)?;
+        cmd.stdout(Stdio::piped());
+        cmd.stderr(Stdio::piped());
+        cmd.stdin(Stdio::piped());
+        info!("[launch]   (console: capturing stdout/stderr)");
+        self.spawn_launch_command(&mut cmd, executable)
+    }
+
+    /// Launch a registered executable and return a handle that can be
+    /// awaited for its exit status.
+    ///
+    /// Must be called from within a Tokio runtime. Stdout/stderr are
+    /// inherited, as with [`Prefix::launch_executable`]. `profile`, one of
+    /// the executable's launch profiles, adds its arguments, environment
+    /// and working directory.
+    pub fn launch_executable_async(
+        &self,
+        executable: &RegisteredExecutable,
+        profile: Option<&LaunchProfile>,
+    ) -> Result<LaunchHandle> {
+        let cmd = self.build_launch_command(executable, profile)?;
+        let mut cmd = tokio::process::Command::from(cmd);
+        match cmd.spawn() {
+            Ok(child) => {
+                let pid = child.id().unwrap_or_default();
+                info!("[launch] '{}' started (PID: {})", executable.name, pid);
+                Ok(LaunchHandle { pid, child })
+            }
+            Err(e) => {
+                error!("[launch] failed to launch '{}': {}", executable.name, e);
+                Err(PrefixError::Process(format!(
+                    "Failed to launch executable: {}",
+                    e
+                )))
+            }
+        }
+    }
+
+    /// Build the `wine <exe>` command for a registered executable, run
+    /// through its launch wrapper or the prefix's if either is set, with
+    /// per-executable environment variables and working directory applied,
+    /// then those of `profile`.
+    fn build_launch_command(
+        &self,
+        executable: &RegisteredExecutable,
+        profile: Option<&LaunchProfile>,
+    ) -> Result<Command> {
         if !executable.executable_path.exists() {
             error!(
                 "[launch] Executable not found: {}",
@@ -327,36 +518,120 @@ impl Prefix {
             ));
         }
 
-        self.check_wine_available("wine")?;
+        if executable.is_script() {
+            self.check_script_in_prefix(&executable.executable_path)?;
+        }
 
-        let mut cmd = self.build_wine_command_with_args(
-            &[&executable.executable_path.to_string_lossy()],
-        );
+        let loader = self.loader_for(executable)?;
+        self.check_wine_available(loader)?;
+
+        let wrapper = executable
+            .launch_wrapper
+            .as_deref()
+            .or(self.config.launch_wrapper.as_deref());
+        let mut cmd = match wrapper.map(parse_launch_wrapper).transpose()?.flatten() {
+            Some((program, args)) => {
+                let mut cmd = self.build_wine_command_for_exe(&program);
+                cmd.args(args).arg(loader);
+                cmd
+            }
+            None => self.build_wine_command_for_exe(loader),
+        };
+        if executable.is_script() {
+            // cmd.exe resolves the script itself, so hand it the Windows path
+            cmd.args(["cmd", "/c"])
+                .arg(windows_path(&self.path, &executable.executable_path));
+        } else {
+            cmd.arg(&executable.executable_path);
+        }
+        if let Some(profile) = profile {
+            cmd.args(&profile.args);
+        }
 
         info!(
             "[launch] launching '{}' in prefix '{}'",
             executable.name, self.config.name
         );
+        if let Some(profile) = profile {
+            info!("[launch]   profile '{}'", profile.name);
+        }
 
         let cmd_line: Vec<String> =
             std::iter::once(cmd.get_program().to_string_lossy().to_string())
                 .chain(cmd.get_args().map(|a| a.to_string_lossy().to_string()))
                 .collect();
         info!("[launch]   {}", cmd_line.join(" "));
+        let mut log_lines = vec![format!("launching '{}'", executable.name)];
+        if let Some(profile) = profile {
+            log_lines.push(format!("  profile '{}'", profile.name));
+        }
+        log_lines.push(format!("  {}", cmd_line.join(" ")));
 
-        // Apply per-executable environment variables
-        for (key, value) in &executable.env_vars {
+        // Apply per-executable environment variables, then the profile's
+        let profile_env = profile.into_iter().flat_map(|p| &p.env_vars);
+        for (key, value) in executable.env_vars.iter().chain(profile_env) {
             cmd.env(key, value);
             info!("[launch]   {}={}", key, value);
+            log_lines.push(format!("  {}={}", key, value));
         }
 
-        // Apply per-executable working directory (fall back to prefix path)
-        if let Some(cwd) = &executable.cwd {
-            cmd.current_dir(cwd);
-        } else {
-            cmd.current_dir(&self.path);
+        // Apply the profile's or the executable's working directory (fall
+        // back to prefix path)
+        let cwd = profile
+            .and_then(|p| p.cwd.as_ref())
+            .or(executable.cwd.as_ref())
+            .unwrap_or(&self.path);
+        cmd.current_dir(cwd);
+        log_lines.push(format!("  cwd {}", cwd.display()));
+
+        prefix_log::record(&self.path, "launch", &log_lines.join("\n"));
+        Ok(cmd)
+    }
+
+    /// Batch scripts run with the prefix's full access under `cmd /c`, so
+    /// only scripts that live inside the prefix are launched.
+    fn check_script_in_prefix(&self, script: &Path) -> Result<()> {
+        let prefix = self.path.canonicalize()?;
+        let script = script.canonicalize()?;
+        if !script.starts_with(&prefix) {
+            error!("[launch] script outside prefix: {}", script.display());
+            return Err(PrefixError::Validation(format!(
+                "Script '{}' is not inside the prefix '{}'",
+                script.display(),
+                self.config.name
+            )));
         }
+        Ok(())
+    }
 
+    /// Pick the Wine loader for an executable. Split (non-WoW64) builds ship
+    /// `wine` as the 32-bit loader and `wine64` for 64-bit images; `wine`
+    /// hands 64-bit images over on its own, so a forced 32-bit launch of one
+    /// is rejected rather than silently run as 64-bit.
+    fn loader_for(&self, executable: &RegisteredExecutable) -> Result<&'static str> {
+        if executable.force_32bit {
+            if executable.is_64bit() {
+                return Err(PrefixError::Validation(format!(
+                    "'{}' is a 64-bit executable and cannot be launched with 32-bit Wine",
+                    executable.name
+                )));
+            }
+            return Ok("wine");
+        }
+        if self.config.architecture == "win64"
+            && executable.is_64bit()
+            && self.check_wine_available("wine64").is_ok()
+        {
+            return Ok("wine64");
+        }
+        Ok("wine")
+    }
+
+    fn spawn_launch_command(
+        &self,
+        cmd: &mut Command,
+        executable: &RegisteredExecutable,
+    ) -> Result<Child> {
         match cmd.spawn() {
             Ok(child) => {
                 info!(
@@ -381,12 +656,11 @@ impl Prefix {
         self.check_wine_available("winecfg")?;
 
         info!("[launch] opening winecfg for prefix '{}'", self.config.name);
-        let child = self
-            .build_wine_command_for_exe("winecfg")
-            .current_dir(&self.path)
-            .spawn()
-            .map_err(|e| PrefixError::Process(format!("Failed to run winecfg: {}", e)))?;
-        Ok(child)
+        spawn_tool(
+            self.build_wine_command_for_exe("winecfg")
+                .current_dir(&self.path),
+            "winecfg",
+        )
     }
 
     /// Run `regedit` for this prefix.
@@ -394,13 +668,35 @@ impl Prefix {
         self.check_wine_available("wine")?;
 
         info!("[launch] opening regedit for prefix '{}'", self.config.name);
-        let child = self
-            .build_wine_command_with_args(&["regedit"])
-            .current_dir(&self.path)
-            .spawn()
-            .map_err(|e| PrefixError::Process(format!("Failed to run regedit: {}", e)))?;
-        Ok(child)
+        spawn_tool(
+            self.build_wine_command_with_args(&["regedit"])
+                .current_dir(&self.path),
+            "regedit",
+        )
+    }
+}
+
+/// Split a launch wrapper such as `"mangohud --dlsym"` into its program and
+/// arguments. A blank wrapper is no wrapper; a program that can't be found
+/// is an error rather than a launch that fails with no window.
+fn parse_launch_wrapper(wrapper: &str) -> Result<Option<(String, Vec<String>)>> {
+    let mut parts = wrapper.split_whitespace().map(str::to_string);
+    let Some(program) = parts.next() else {
+        return Ok(None);
+    };
+    let found = if program.contains('/') {
+        Path::new(&program).is_file()
+    } else {
+        find_in_path(&program).is_some()
+    };
+    if !found {
+        error!("[launch] launch wrapper not found: {}", program);
+        return Err(PrefixError::NotFound(format!(
+            "Launch wrapper '{}' was not found in PATH",
+            program
+        )));
     }
+    Ok(Some((program, parts.collect())))
 }
 
 /// Search PATH for a named executable using `which`.
@@ -459,3 +755,255 @@ pub fn prefix_label(path: &Path) -> String {
         })
         .unwrap_or_else(|| "prefix".to_string())
 }
+
+/// Convert a host path to the Windows path Wine sees it as.
+///
+/// Paths under the prefix's `drive_c` map to `C:\...`; anything else goes
+/// through the `Z:` drive, which Wine maps to the host root.
+pub fn windows_path(prefix_path: &Path, host_path: &Path) -> String {
+    let (drive, rest) = match host_path.strip_prefix(prefix_path.join("drive_c")) {
+        Ok(rest) => ("C:", rest),
+        Err(_) => ("Z:", host_path.strip_prefix("/").unwrap_or(host_path)),
+    };
+    let components: Vec<String> = rest
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    format!("{}\\{}", drive, components.join("\\"))
+}
+
+/// Why an executable's PE architecture doesn't fit the prefix it is
+/// registered in; see [`architecture_mismatch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchMismatch {
+    /// A 64-bit executable in a win32 prefix, which can't load it.
+    Needs64Bit,
+    /// A 32-bit executable in a win64 prefix that has no `syswow64`, so no
+    /// 32-bit Windows libraries to run it with.
+    Needs32Bit,
+}
+
+/// Check whether `executable` can run in the prefix at `prefix_path` with
+/// architecture `prefix_arch` (`"win32"`/`"win64"`). Executables whose
+/// architecture hasn't been read yet, and scripts, are never flagged.
+pub fn architecture_mismatch(
+    prefix_path: &Path,
+    prefix_arch: &str,
+    executable: &RegisteredExecutable,
+) -> Option<ArchMismatch> {
+    match (prefix_arch, executable.architecture.as_deref()?) {
+        ("win32", "x64") => Some(ArchMismatch::Needs64Bit),
+        ("win64", "x86") => {
+            // Only judge a prefix Wine has already set up
+            let windows = prefix_path.join("drive_c/windows");
+            let missing = windows.join("system32").is_dir() && !windows.join("syswow64").is_dir();
+            missing.then_some(ArchMismatch::Needs32Bit)
+        }
+        _ => None,
+    }
+}
+
+/// Order prefixes most recently used first. Prefixes never used keep their
+/// current relative order at the end.
+pub fn sort_by_last_used(prefixes: &mut [WinePrefix]) {
+    prefixes.sort_by_key(|prefix| std::cmp::Reverse(prefix.config.last_used));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::Fixture;
+
+    fn sample_exe(path: &Path, architecture: &str) -> RegisteredExecutable {
+        RegisteredExecutable::new("sample".to_string(), path.join("sample.exe"))
+            .with_architecture(architecture)
+    }
+
+    #[test]
+    fn test_sort_by_last_used() {
+        let prefix = |name: &str, hours_ago: Option<u64>| {
+            let mut config = PrefixConfig::new(name.to_string(), "win64".to_string());
+            config.last_used =
+                hours_ago.map(|h| config.creation_date - std::time::Duration::from_secs(h * 3600));
+            WinePrefix {
+                name: name.to_string(),
+                path: PathBuf::from(name),
+                config,
+            }
+        };
+        let mut prefixes = vec![
+            prefix("a", None),
+            prefix("b", Some(5)),
+            prefix("c", None),
+            prefix("d", Some(1)),
+        ];
+        sort_by_last_used(&mut prefixes);
+        let names: Vec<&str> = prefixes.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["d", "b", "a", "c"]);
+    }
+
+    #[test]
+    fn test_32bit_exe_defaults_to_forced_32bit_wine() {
+        let fixture = Fixture::new();
+        let path = fixture.add_prefix("sample");
+        let prefix = fixture.manager().open_prefix(&path).expect("open prefix");
+
+        let exe = sample_exe(&path, "x86");
+        assert!(exe.force_32bit);
+        assert_eq!(prefix.loader_for(&exe).expect("loader"), "wine");
+    }
+
+    #[test]
+    fn test_forced_32bit_rejects_64bit_exe() {
+        let fixture = Fixture::new();
+        let path = fixture.add_prefix("sample");
+        let prefix = fixture.manager().open_prefix(&path).expect("open prefix");
+
+        let mut exe = sample_exe(&path, "x64");
+        assert!(!exe.force_32bit);
+        exe.force_32bit = true;
+        assert!(matches!(
+            prefix.loader_for(&exe),
+            Err(PrefixError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_launch_wrapper() {
+        assert_eq!(parse_launch_wrapper("  ").unwrap(), None);
+        assert_eq!(
+            parse_launch_wrapper("env  DXVK_HUD=1").unwrap(),
+            Some(("env".to_string(), vec!["DXVK_HUD=1".to_string()]))
+        );
+        assert!(matches!(
+            parse_launch_wrapper("tequila-no-such-wrapper --flag"),
+            Err(PrefixError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_architecture_mismatch() {
+        let fixture = Fixture::new();
+        let path = fixture.add_prefix("sample");
+        let x64 = sample_exe(&path, "x64");
+        let x86 = sample_exe(&path, "x86");
+        let unknown = RegisteredExecutable::new("sample".into(), path.join("sample.exe"));
+
+        assert_eq!(
+            architecture_mismatch(&path, "win32", &x64),
+            Some(ArchMismatch::Needs64Bit)
+        );
+        assert_eq!(architecture_mismatch(&path, "win32", &x86), None);
+        assert_eq!(architecture_mismatch(&path, "win64", &x64), None);
+        assert_eq!(architecture_mismatch(&path, "win32", &unknown), None);
+
+        // A win64 prefix runs 32-bit programs through syswow64
+        assert_eq!(architecture_mismatch(&path, "win64", &x86), None);
+        std::fs::create_dir_all(path.join("drive_c/windows/system32")).unwrap();
+        assert_eq!(
+            architecture_mismatch(&path, "win64", &x86),
+            Some(ArchMismatch::Needs32Bit)
+        );
+        std::fs::create_dir_all(path.join("drive_c/windows/syswow64")).unwrap();
+        assert_eq!(architecture_mismatch(&path, "win64", &x86), None);
+    }
+
+    #[test]
+    fn test_script_outside_prefix_is_rejected() {
+        let fixture = Fixture::new();
+        let path = fixture.add_prefix("sample");
+        let prefix = fixture.manager().open_prefix(&path).expect("open prefix");
+
+        let inside = path.join("drive_c/launch.bat");
+        std::fs::write(&inside, b"@echo off").unwrap();
+        assert!(prefix.check_script_in_prefix(&inside).is_ok());
+
+        let outside = fixture.wine_dir().join("outside.cmd");
+        std::fs::write(&outside, b"@echo off").unwrap();
+        assert!(matches!(
+            prefix.check_script_in_prefix(&outside),
+            Err(PrefixError::Validation(_))
+        ));
+
+        let exe = RegisteredExecutable::new("launch".to_string(), inside);
+        assert!(exe.is_script());
+        assert!(!sample_exe(&path, "x86").is_script());
+    }
+
+    #[tokio::test]
+    async fn test_rescan_reports_new_executables() {
+        let fixture = Fixture::new();
+        let path = fixture.add_prefix("sample");
+        let prefix = fixture.manager().open_prefix(&path).expect("open prefix");
+
+        // Nothing saved yet: a first scan has nothing to compare against
+        let first = prefix.rescan_applications_async().await.expect("rescan");
+        assert!(first.new_paths.is_empty());
+        fixture
+            .manager()
+            .store()
+            .save_scanned_executables(&path.to_string_lossy(), &first.executables)
+            .unwrap();
+
+        let game_dir = path.join("drive_c/Program Files/Game");
+        std::fs::create_dir_all(&game_dir).unwrap();
+        std::fs::write(game_dir.join("game.exe"), b"MZ").unwrap();
+
+        let second = prefix.rescan_applications_async().await.expect("rescan");
+        assert_eq!(second.new_paths, [game_dir.join("game.exe")]);
+        assert_eq!(second.executables.len(), first.executables.len() + 1);
+    }
+
+    #[test]
+    fn test_add_executable_outside_prefix_is_rejected() {
+        let fixture = Fixture::new();
+        let path = fixture.add_prefix("sample");
+        let mut prefix = fixture.manager().open_prefix(&path).expect("open prefix");
+
+        let inside = path.join("drive_c/app.exe");
+        std::fs::write(&inside, b"MZ").unwrap();
+        let exe = RegisteredExecutable::new("app".to_string(), inside);
+        assert!(prefix.add_executable(exe).expect("add inside"));
+
+        // `..` can't climb out of drive_c
+        let escaped = path.join("drive_c/../outside.exe");
+        std::fs::write(path.join("outside.exe"), b"MZ").unwrap();
+        let exe = RegisteredExecutable::new("escaped".to_string(), escaped);
+        assert!(matches!(
+            prefix.add_executable(exe),
+            Err(PrefixError::Validation(_))
+        ));
+
+        let host = fixture.wine_dir().join("portable.exe");
+        std::fs::write(&host, b"MZ").unwrap();
+        let mut exe = RegisteredExecutable::new("portable".to_string(), host);
+        assert!(matches!(
+            prefix.add_executable(exe.clone()),
+            Err(PrefixError::Validation(_))
+        ));
+        exe.allow_external = true;
+        assert!(prefix.add_executable(exe).expect("add allowed external"));
+        assert_eq!(prefix.config().registered_executables.len(), 2);
+    }
+
+    #[test]
+    fn test_enrich_checks_unparseable_executables_once() {
+        let fixture = Fixture::new();
+        let path = fixture.add_prefix("sample");
+        let mut prefix = fixture.manager().open_prefix(&path).expect("open prefix");
+
+        // Too short to be a PE image, so nothing can be extracted from it
+        let exe_path = path.join("drive_c/Program Files/Sample App/sample.exe");
+        let exe = RegisteredExecutable::new("sample".to_string(), exe_path);
+        let missing = RegisteredExecutable::new("gone".to_string(), path.join("drive_c/gone.exe"));
+        prefix.config.registered_executables = vec![exe, missing];
+
+        assert!(prefix.enrich_executables());
+        let exes = &prefix.config().registered_executables;
+        assert!(exes[0].metadata_checked);
+        assert_eq!(exes[0].architecture, None);
+        // A missing file is tried again once it's back
+        assert!(!exes[1].metadata_checked);
+        assert!(!prefix.enrich_executables());
+    }
+}
@@ -0,0 +1,110 @@
+//! Optional diagnostics log kept inside each prefix, `<prefix>/tequila.log`.
+//!
+//! The console log is lost when Tequila is started from a desktop icon, so
+//! launches, scans and registry saves are also appended here when the
+//! `prefix_logging` preference is on. The file is rotated to `tequila.log.1`
+//! once it reaches [`MAX_LOG_SIZE`].
+
+use log::warn;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// File name of the log inside the prefix directory.
+pub const LOG_FILE: &str = "tequila.log";
+
+/// Size at which the log is rotated; one older file is kept.
+pub const MAX_LOG_SIZE: u64 = 1024 * 1024;
+
+/// Path of the diagnostics log for a prefix.
+pub fn log_path(prefix_path: &Path) -> PathBuf {
+    prefix_path.join(LOG_FILE)
+}
+
+/// Mirror of the `prefix_logging` preference; see [`set_enabled`].
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turn per-prefix logging on or off. Set from the settings when the
+/// [`Manager`](crate::Manager) is created and whenever the preference
+/// changes, so logging a line doesn't read the settings file.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether per-prefix logging is turned on in the settings.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Append `message` to the prefix's log, one timestamped line per line of
+/// the message. Does nothing unless logging is enabled; write errors are
+/// only reported on the console.
+pub fn record(prefix_path: &Path, area: &str, message: &str) {
+    if !enabled() {
+        return;
+    }
+    if let Err(e) = append(&log_path(prefix_path), area, message, MAX_LOG_SIZE) {
+        warn!(
+            "[prefix_log] failed to write log in {}: {}",
+            prefix_path.display(),
+            e
+        );
+    }
+}
+
+fn append(path: &Path, area: &str, message: &str, max_size: u64) -> io::Result<()> {
+    if fs::metadata(path).is_ok_and(|meta| meta.len() >= max_size) {
+        let mut rotated = path.as_os_str().to_owned();
+        rotated.push(".1");
+        fs::rename(path, rotated)?;
+    }
+    let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+    let mut entry = String::new();
+    for line in message.lines() {
+        entry.push_str(&format!("{} [{}] {}\n", timestamp, area, line));
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(entry.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_append_writes_one_line_per_message_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = log_path(dir.path());
+
+        append(&path, "launch", "wine game.exe\n  DXVK_HUD=1", MAX_LOG_SIZE).unwrap();
+        append(&path, "scan", "found 3 executable(s)", MAX_LOG_SIZE).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].ends_with(" [launch] wine game.exe"));
+        assert!(lines[1].ends_with(" [launch]   DXVK_HUD=1"));
+        assert!(lines[2].ends_with(" [scan] found 3 executable(s)"));
+    }
+
+    #[test]
+    fn test_append_rotates_at_size_cap() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = log_path(dir.path());
+        let rotated = dir.path().join("tequila.log.1");
+
+        append(&path, "scan", "first", 16).unwrap();
+        assert!(!rotated.exists());
+        // Over the cap now, so the next entry starts a fresh file
+        append(&path, "scan", "second", 16).unwrap();
+
+        assert!(fs::read_to_string(&rotated).unwrap().contains("first"));
+        let current = fs::read_to_string(&path).unwrap();
+        assert!(current.contains("second"));
+        assert!(!current.contains("first"));
+    }
+}
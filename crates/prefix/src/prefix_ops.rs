@@ -1,6 +1,6 @@
 use base::config::PrefixConfig;
 use base::error::{PrefixError, Result};
-use base::traits::WinePrefix;
+use base::traits::{PrefixInfo, WinePrefix};
 use base::{GraphicsBackend, GraphicsConfig};
 use log::{info, warn};
 use registry::keys::DllOverrideSetting;
@@ -11,11 +11,18 @@ use std::io::Write;
 use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
 use uuid::Uuid;
 
 use crate::Manager;
-use crate::wine_processes::apply_runtime_env;
+use crate::wine_processes::{
+    apply_prefix_env, apply_runtime_env, prefix_processes, wineserver_running,
+};
+
+/// Upper bound on threads used to load prefix configs during a scan.
+const MAX_SCAN_WORKERS: usize = 8;
 
 /// File extension for Tequila prefix archives (after `.zst`).
 /// Full filename: `<prefix_name>.zst.wtea`
@@ -74,34 +81,93 @@ impl Manager {
         })
     }
 
+    /// One-glance summary of a prefix for the details header.
+    ///
+    /// Unlike [`Prefix::to_info`](crate::Prefix::to_info), `wine_version`
+    /// is the version reported by the resolved runtime rather than the
+    /// runtime id stored in the config. Walks the whole prefix to measure
+    /// disk usage, so call it off the UI thread.
+    pub fn prefix_info(&self, prefix_path: &Path) -> Result<PrefixInfo> {
+        let prefix = self.open_prefix(prefix_path)?;
+        let mut info = prefix.to_info()?;
+        info.wine_version = self
+            .runtime_for_prefix(prefix.config())
+            .map(|rt| rt.wine_version)
+            .filter(|v| !v.is_empty());
+        Ok(info)
+    }
+
     pub fn scan_prefixes(&self) -> Result<Vec<WinePrefix>> {
-        let mut prefixes: Vec<WinePrefix> = Vec::new();
+        self.scan_prefixes_streaming(|_| {})
+    }
+
+    /// Like [`scan_prefixes`](Self::scan_prefixes), but validates and loads
+    /// prefixes on a bounded pool of worker threads. `on_found` is called from
+    /// the workers as each prefix finishes loading, in no particular order; the
+    /// returned list is sorted by name.
+    pub fn scan_prefixes_streaming<F>(&self, on_found: F) -> Result<Vec<WinePrefix>>
+    where
+        F: Fn(&WinePrefix) + Sync,
+    {
         let system_wine_version = self
             .read_runtime()
             .get("wine-system")
             .map(|r| r.wine_version.clone());
+        let mut candidates = Vec::new();
         for entry in fs::read_dir(&self.wine_dir)? {
-            let entry = entry?;
-            let path = entry.path();
-            if path.is_dir() && self.is_valid_wine_prefix(&path) {
-                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                    if let Ok(config) =
-                        self.load_or_create_config(&path, name, &system_wine_version)
-                    {
-                        prefixes.push(WinePrefix {
-                            name: config.name.clone(),
-                            path: path.clone(),
-                            config,
-                        });
-                    }
-                }
+            let path = entry?.path();
+            if path.is_dir() {
+                candidates.push(path);
             }
         }
+
+        let next = AtomicUsize::new(0);
+        let found = Mutex::new(Vec::with_capacity(candidates.len()));
+        let workers = thread::available_parallelism()
+            .map_or(1, |n| n.get())
+            .min(MAX_SCAN_WORKERS)
+            .min(candidates.len());
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| {
+                    while let Some(path) = candidates.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        if let Some(prefix) = self.load_prefix_entry(path, &system_wine_version) {
+                            on_found(&prefix);
+                            found.lock().unwrap().push(prefix);
+                        }
+                    }
+                });
+            }
+        });
+
+        let mut prefixes = found.into_inner().unwrap();
         prefixes.sort_by(|a, b| a.name.cmp(&b.name));
         Ok(prefixes)
     }
 
-    fn is_valid_wine_prefix(&self, path: &Path) -> bool {
+    fn load_prefix_entry(
+        &self,
+        path: &Path,
+        system_wine_version: &Option<String>,
+    ) -> Option<WinePrefix> {
+        if !self.is_valid_wine_prefix(path) {
+            return None;
+        }
+        let name = path.file_name()?.to_str()?;
+        match self.load_or_create_config(path, name, system_wine_version) {
+            Ok(config) => Some(WinePrefix {
+                name: config.name.clone(),
+                path: path.to_path_buf(),
+                config,
+            }),
+            Err(e) => {
+                warn!("[prefix] skipping {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    pub(crate) fn is_valid_wine_prefix(&self, path: &Path) -> bool {
         path.join("drive_c").exists()
             && path.join("system.reg").exists()
             && path.join("user.reg").exists()
@@ -120,6 +186,14 @@ impl Manager {
             if let Ok(architecture) = self.detect_architecture(prefix_path) {
                 config.architecture = architecture;
             }
+            // Date a prefix made outside Tequila by its registry, not by
+            // whenever it happens to be read
+            if let Ok(modified) =
+                fs::metadata(prefix_path.join("system.reg")).and_then(|meta| meta.modified())
+            {
+                config.creation_date = modified.into();
+                config.last_modified = modified.into();
+            }
             config
         };
         if config.wine_version.is_none() {
@@ -163,6 +237,10 @@ impl Manager {
         fs::create_dir_all(&prefix_path)?;
         let mut config = PrefixConfig::new(name.to_string(), architecture.to_string());
         config.wine_version = Some(runtime_id.to_string());
+        config.add_note(
+            format!("Prefix created ({}, runtime {})", architecture, runtime_id),
+            true,
+        );
         config.save_to_file(&prefix_path).map_err(|e| {
             let _ = fs::remove_dir_all(&prefix_path);
             e
@@ -220,10 +298,129 @@ impl Manager {
                 "Not a valid Wine prefix".to_string(),
             ));
         }
+        self.shutdown_wineserver(prefix_path)?;
         fs::remove_dir_all(prefix_path)?;
         Ok(())
     }
 
+    /// Manage a prefix that lives outside the wine directory (e.g. one
+    /// created by Lutris) by linking it into the wine directory under a new
+    /// UUID. The prefix is used in place; deleting the link later leaves
+    /// the prefix itself untouched.
+    ///
+    /// `name` defaults to the directory name when empty. An existing
+    /// Tequila config in the prefix is kept, apart from the name.
+    pub fn link_external_prefix(&self, external_path: &Path, name: &str) -> Result<PathBuf> {
+        let target = external_path
+            .canonicalize()
+            .map_err(|e| PrefixError::NotFound(format!("{}: {}", external_path.display(), e)))?;
+        if !self.is_valid_wine_prefix(&target) {
+            return Err(PrefixError::Validation(format!(
+                "{} is not a Wine prefix (expected drive_c, system.reg and user.reg)",
+                target.display()
+            )));
+        }
+        if let Ok(wine_dir) = self.wine_dir.canonicalize() {
+            if target.starts_with(&wine_dir) {
+                return Err(PrefixError::AlreadyExists(format!(
+                    "{} is already in the prefixes directory",
+                    target.display()
+                )));
+            }
+        }
+        if self.linked_external_paths()?.contains(&target) {
+            return Err(PrefixError::AlreadyExists(format!(
+                "{} is already linked",
+                target.display()
+            )));
+        }
+
+        let link_path = self.wine_dir.join(Uuid::new_v4().to_string());
+        std::os::unix::fs::symlink(&target, &link_path)?;
+        let dir_name = target
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("external");
+        let name = match name.trim() {
+            "" => dir_name,
+            name => name,
+        };
+        let result = self
+            .load_or_create_config(&link_path, name, &None)
+            .and_then(|mut config| {
+                config.name = name.to_string();
+                config.add_note(format!("Linked from {}", target.display()), true);
+                config.save_to_file(&link_path)
+            });
+        if let Err(e) = result {
+            let _ = fs::remove_file(&link_path);
+            return Err(e);
+        }
+        info!(
+            "[prefix] linked external prefix {} as {}",
+            target.display(),
+            link_path.display()
+        );
+        Ok(link_path)
+    }
+
+    /// Targets of the prefixes linked in with
+    /// [`link_external_prefix`](Self::link_external_prefix).
+    pub(crate) fn linked_external_paths(&self) -> Result<Vec<PathBuf>> {
+        let mut targets = Vec::new();
+        for entry in fs::read_dir(&self.wine_dir)? {
+            if let Ok(target) = fs::read_link(entry?.path()) {
+                targets.push(target);
+            }
+        }
+        Ok(targets)
+    }
+
+    /// Stop the wineserver serving a prefix (`wineserver -k`) and wait for
+    /// it to exit, so nothing writes into the prefix while it is removed.
+    /// Does nothing when no wineserver is running.
+    pub fn shutdown_wineserver(&self, prefix_path: &Path) -> Result<()> {
+        if !wineserver_running(prefix_path) {
+            return Ok(());
+        }
+        let dir_name = prefix_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown");
+        let config = self.load_or_create_config(prefix_path, dir_name, &None)?;
+        for flag in ["-k", "-w"] {
+            self.build_wine_command_for_exe("wineserver", &config, prefix_path)
+                .arg(flag)
+                .env("WINEDEBUG", "-all")
+                .output()
+                .map_err(|e| PrefixError::Process(format!("Failed to run wineserver: {}", e)))?;
+        }
+        if wineserver_running(prefix_path) {
+            return Err(PrefixError::Process(format!(
+                "wineserver for '{}' is still running",
+                config.name
+            )));
+        }
+        info!("[prefix] stopped wineserver for '{}'", config.name);
+        Ok(())
+    }
+
+    /// Stop the prefix's wineserver if only Wine's own background programs
+    /// are left running in it. Returns whether it was stopped.
+    pub fn stop_idle_wineserver(&self, prefix_path: &Path) -> Result<bool> {
+        if !wineserver_running(prefix_path) {
+            return Ok(false);
+        }
+        let busy = prefix_processes(prefix_path)
+            .iter()
+            .any(|process| !process.is_wine_service());
+        if busy {
+            return Ok(false);
+        }
+        self.shutdown_wineserver(prefix_path)?;
+        Ok(true)
+    }
+
     /// Activate a graphics backend for a prefix.
     ///
     /// 1. Symlink backend `.dll` files into prefix's `system32/` (and `syswow64/`)
@@ -291,7 +488,14 @@ impl Manager {
         // 3. Save to tequila-config.json
         let mut config = self.load_or_create_config(prefix_path, dir_name, &None)?;
         config.graphics = Some(gfx_config.clone());
-        config.update_last_modified();
+        config.add_note(
+            format!(
+                "Installed {} ({})",
+                gfx_config.display_name(),
+                gfx_config.version
+            ),
+            true,
+        );
         config.save_to_file(prefix_path)?;
 
         info!(
@@ -384,7 +588,14 @@ impl Manager {
             version: format!("dxvk-{}+vkd3d-{}", dxvk_ver, vkd3d_ver),
         };
         config.graphics = Some(gfx_config.clone());
-        config.update_last_modified();
+        config.add_note(
+            format!(
+                "Installed {} ({})",
+                gfx_config.display_name(),
+                gfx_config.version
+            ),
+            true,
+        );
         config.save_to_file(prefix_path)?;
 
         info!(
@@ -455,7 +666,7 @@ impl Manager {
         // 3. Clear config
         info!("[prefix]   step 3/3: clearing config...");
         config.graphics = None;
-        config.update_last_modified();
+        config.add_note(format!("Removed {}", gfx_config.display_name()), true);
         config.save_to_file(prefix_path)?;
         info!("[prefix]   step 3/3: done");
 
@@ -608,7 +819,14 @@ impl Manager {
             version: version.clone(),
         };
         config.graphics = Some(gfx_config.clone());
-        config.update_last_modified();
+        config.add_note(
+            format!(
+                "Installed {} ({})",
+                gfx_config.display_name(),
+                gfx_config.version
+            ),
+            true,
+        );
         config.save_to_file(prefix_path)?;
 
         info!(
@@ -774,7 +992,14 @@ impl Manager {
             version,
         };
         config.graphics = Some(gfx_config.clone());
-        config.update_last_modified();
+        config.add_note(
+            format!(
+                "Installed {} ({})",
+                gfx_config.display_name(),
+                gfx_config.version
+            ),
+            true,
+        );
         config.save_to_file(prefix_path)?;
 
         info!(
@@ -1056,6 +1281,15 @@ impl Manager {
                 exe.executable_path = prefix_path.join(&exe.executable_path);
                 changed = true;
             }
+            // Paths outside the exported prefix came over verbatim from
+            // another machine and likely point nowhere (or somewhere else)
+            if !exe.is_allowed_in_prefix(prefix_path) {
+                warn!(
+                    "[import] executable '{}' points outside the prefix: {}",
+                    exe.name,
+                    exe.executable_path.display()
+                );
+            }
         }
         if changed {
             let cleaned = serde_json::to_string_pretty(&config)?;
@@ -1359,9 +1593,21 @@ impl Manager {
         let mut cmd = std::process::Command::new("true");
         if let Some(runtime) = self.runtime_for_prefix(&config) {
             apply_runtime_env(&mut cmd, &runtime, prefix_path);
+            // `WINE` is what winetricks and most helper scripts look for.
+            if let Some(wine_bin) = runtime::discover_wine_binary(&runtime.bundle_dir) {
+                cmd.env("WINE", wine_bin);
+            }
         } else {
             cmd.env("WINEPREFIX", prefix_path);
         }
+        let wine_arch = if config.architecture == "win32" {
+            "win32"
+        } else {
+            "win64"
+        };
+        cmd.env("WINEARCH", wine_arch);
+        // Applied last so the prefix's own env vars win over the defaults.
+        apply_prefix_env(&mut cmd, &config);
 
         let mut exports = String::new();
         let mut banner = String::new();
@@ -1395,3 +1641,196 @@ impl Manager {
         Ok(script)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::Fixture;
+
+    #[test]
+    fn test_scan_prefixes_discovers_fixture_prefix() {
+        let fixture = Fixture::new();
+        let path = fixture.add_prefix("sample");
+
+        let prefixes = fixture.manager().scan_prefixes().expect("scan prefixes");
+        assert_eq!(prefixes.len(), 1);
+        assert_eq!(prefixes[0].path, path);
+        assert_eq!(prefixes[0].name, "sample");
+    }
+
+    #[test]
+    fn test_scan_prefixes_skips_incomplete_dirs() {
+        let fixture = Fixture::new();
+        fixture.add_prefix("complete");
+        let partial = fixture.wine_dir().join("partial");
+        fs::create_dir_all(partial.join("drive_c")).unwrap();
+        fs::write(partial.join("system.reg"), "").unwrap();
+
+        let prefixes = fixture.manager().scan_prefixes().expect("scan prefixes");
+        let names: Vec<&str> = prefixes.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["complete"]);
+    }
+
+    #[test]
+    fn test_scan_prefixes_streaming_reports_each_prefix_and_sorts() {
+        let fixture = Fixture::new();
+        for name in ["delta", "alpha", "charlie", "bravo", "echo"] {
+            fixture.add_prefix(name);
+        }
+
+        let seen = Mutex::new(Vec::new());
+        let prefixes = fixture
+            .manager()
+            .scan_prefixes_streaming(|p| seen.lock().unwrap().push(p.name.clone()))
+            .expect("scan prefixes");
+
+        let names: Vec<&str> = prefixes.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["alpha", "bravo", "charlie", "delta", "echo"]);
+        let mut seen = seen.into_inner().unwrap();
+        seen.sort();
+        assert_eq!(seen, names);
+    }
+
+    #[test]
+    fn test_is_valid_wine_prefix() {
+        let fixture = Fixture::new();
+        let path = fixture.add_prefix("sample");
+        assert!(fixture.manager().is_valid_wine_prefix(&path));
+
+        fs::remove_file(path.join("user.reg")).unwrap();
+        assert!(!fixture.manager().is_valid_wine_prefix(&path));
+    }
+
+    #[test]
+    fn test_load_or_create_config_creates_valid_config() {
+        let fixture = Fixture::new();
+        let path = fixture.add_prefix("sample");
+
+        let config = fixture
+            .manager()
+            .load_or_create_config(&path, "sample", &Some("9.0".to_string()))
+            .expect("create config");
+        assert_eq!(config.name, "sample");
+        assert_eq!(config.architecture, "win64");
+        assert_eq!(config.wine_version.as_deref(), Some("9.0"));
+        assert!(config.registered_executables.is_empty());
+
+        // Filling in the Wine version persists the new config
+        let saved = PrefixConfig::load_from_file(&path)
+            .expect("read config")
+            .expect("config saved");
+        assert_eq!(saved.name, "sample");
+        assert_eq!(saved.wine_version.as_deref(), Some("9.0"));
+    }
+
+    #[test]
+    fn test_load_or_create_config_without_wine_version_is_stable() {
+        let fixture = Fixture::new();
+        let path = fixture.add_prefix("sample");
+        let manager = fixture.manager();
+
+        let first = manager
+            .load_or_create_config(&path, "sample", &None)
+            .unwrap();
+        let second = manager
+            .load_or_create_config(&path, "sample", &None)
+            .unwrap();
+        assert_eq!(first.last_modified, second.last_modified);
+        assert_eq!(first.creation_date, second.creation_date);
+        // Reading alone doesn't write a config into the prefix
+        assert!(PrefixConfig::load_from_file(&path).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_load_or_create_config_keeps_existing_config() {
+        let fixture = Fixture::new();
+        let path = fixture.add_prefix("sample");
+        let mut existing = PrefixConfig::new("Custom Name".to_string(), "win32".to_string());
+        existing.wine_version = Some("8.0".to_string());
+        existing.save_to_file(&path).unwrap();
+
+        let config = fixture
+            .manager()
+            .load_or_create_config(&path, "sample", &Some("9.0".to_string()))
+            .expect("load config");
+        assert_eq!(config.name, "Custom Name");
+        assert_eq!(config.architecture, "win32");
+        assert_eq!(config.wine_version.as_deref(), Some("8.0"));
+    }
+
+    #[test]
+    fn test_link_external_prefix_registers_without_moving() {
+        let fixture = Fixture::new();
+        let outside = tempfile::tempdir().unwrap();
+        let external = outside.path().join("lutris-game");
+        fs::create_dir_all(external.join("drive_c/Program Files")).unwrap();
+        fs::write(external.join("system.reg"), "").unwrap();
+        fs::write(external.join("user.reg"), "").unwrap();
+
+        let link = fixture
+            .manager()
+            .link_external_prefix(&external, "")
+            .expect("link prefix");
+        let prefixes = fixture.manager().scan_prefixes().expect("scan prefixes");
+        assert_eq!(prefixes.len(), 1);
+        assert_eq!(prefixes[0].name, "lutris-game");
+        assert_eq!(
+            prefixes[0].external_path(),
+            Some(external.canonicalize().unwrap())
+        );
+        assert!(PrefixConfig::load_from_file(&external).unwrap().is_some());
+
+        // Linking again is refused, and unlinking keeps the prefix itself
+        assert!(
+            fixture
+                .manager()
+                .link_external_prefix(&external, "Again")
+                .is_err()
+        );
+        fixture
+            .manager()
+            .delete_prefix(&link)
+            .expect("unlink prefix");
+        assert!(!link.exists());
+        assert!(external.join("system.reg").exists());
+    }
+
+    #[test]
+    fn test_link_external_prefix_rejects_invalid_dirs() {
+        let fixture = Fixture::new();
+        let outside = tempfile::tempdir().unwrap();
+        assert!(matches!(
+            fixture
+                .manager()
+                .link_external_prefix(outside.path(), "Empty"),
+            Err(PrefixError::Validation(_))
+        ));
+
+        let managed = fixture.add_prefix("managed");
+        assert!(matches!(
+            fixture.manager().link_external_prefix(&managed, "Managed"),
+            Err(PrefixError::AlreadyExists(_))
+        ));
+        assert_eq!(fs::read_dir(fixture.wine_dir()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_generate_terminal_script_exports_prefix_environment() {
+        let fixture = Fixture::new();
+        let path = fixture.add_prefix("sample");
+        let mut config = PrefixConfig::new("Sample".to_string(), "win32".to_string());
+        config
+            .env_vars
+            .insert("WINEESYNC".to_string(), "1".to_string());
+        config.save_to_file(&path).unwrap();
+
+        let script = fixture
+            .manager()
+            .generate_terminal_script(&path)
+            .expect("generate script");
+        assert!(script.contains(&format!("export WINEPREFIX='{}'", path.display())));
+        assert!(script.contains("export WINEARCH='win32'"));
+        assert!(script.contains("export WINEESYNC='1'"));
+        assert!(script.contains(&format!("cd '{}'", path.display())));
+    }
+}
@@ -0,0 +1,214 @@
+//! Discovery of the prefixes Proton keeps for Steam games under
+//! `steamapps/compatdata/<appid>/pfx`.
+
+use crate::Manager;
+use base::error::Result;
+use log::debug;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Steam install locations relative to the home directory: the native
+/// client (`~/.steam/steam` is usually a symlink to the second) and Flatpak.
+const STEAM_ROOTS: [&str; 3] = [
+    ".steam/steam",
+    ".local/share/Steam",
+    ".var/app/com.valvesoftware.Steam/data/Steam",
+];
+
+/// A Proton prefix found in a Steam library.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtonPrefix {
+    /// Steam app ID the prefix belongs to.
+    pub app_id: String,
+    /// Game name from the library's `appmanifest_<id>.acf`, if installed.
+    pub game_name: Option<String>,
+    /// The `pfx` directory itself.
+    pub path: PathBuf,
+    /// Already linked into the wine directory.
+    pub linked: bool,
+}
+
+impl ProtonPrefix {
+    /// The game name, or the app ID when Steam has no manifest for it.
+    pub fn display_name(&self) -> String {
+        self.game_name
+            .clone()
+            .unwrap_or_else(|| format!("Steam app {}", self.app_id))
+    }
+}
+
+/// The Steam app ID of a prefix at `compatdata/<appid>/pfx`.
+pub fn proton_app_id(path: &Path) -> Option<&str> {
+    if path.file_name()? != "pfx" {
+        return None;
+    }
+    let app_dir = path.parent()?;
+    if app_dir.parent()?.file_name()? != "compatdata" {
+        return None;
+    }
+    app_dir
+        .file_name()?
+        .to_str()
+        .filter(|id| !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Split a VDF/ACF line of the form `"key"  "value"`.
+fn vdf_pair(line: &str) -> Option<(&str, &str)> {
+    let mut parts = line.trim().split('"');
+    let (_, key, _, value) = (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+    Some((key, value))
+}
+
+/// Every library of a Steam install: the install itself plus the `path`
+/// entries of `steamapps/libraryfolders.vdf`.
+fn steam_libraries(root: &Path) -> Vec<PathBuf> {
+    let mut libraries = vec![root.to_path_buf()];
+    if let Ok(vdf) = fs::read_to_string(root.join("steamapps/libraryfolders.vdf")) {
+        libraries.extend(
+            vdf.lines()
+                .filter_map(vdf_pair)
+                .filter(|(key, _)| *key == "path")
+                .map(|(_, path)| PathBuf::from(path)),
+        );
+    }
+    libraries
+}
+
+/// Read the game name out of `appmanifest_<id>.acf` in any library.
+fn game_name(libraries: &[PathBuf], app_id: &str) -> Option<String> {
+    libraries.iter().find_map(|library| {
+        let manifest = library.join(format!("steamapps/appmanifest_{}.acf", app_id));
+        fs::read_to_string(manifest)
+            .ok()?
+            .lines()
+            .filter_map(vdf_pair)
+            .find(|(key, _)| *key == "name")
+            .map(|(_, name)| name.to_string())
+    })
+}
+
+impl Manager {
+    /// Find Proton prefixes in the Steam libraries of the current user,
+    /// sorted by display name.
+    pub fn discover_proton_prefixes(&self) -> Result<Vec<ProtonPrefix>> {
+        let Some(home) = dirs::home_dir() else {
+            return Ok(Vec::new());
+        };
+        let roots: Vec<PathBuf> = STEAM_ROOTS.iter().map(|root| home.join(root)).collect();
+        self.proton_prefixes_in(&roots)
+    }
+
+    /// Like [`discover_proton_prefixes`](Self::discover_proton_prefixes),
+    /// for the Steam installs at `roots`. Missing roots are skipped.
+    pub(crate) fn proton_prefixes_in(&self, roots: &[PathBuf]) -> Result<Vec<ProtonPrefix>> {
+        let mut libraries: Vec<PathBuf> = Vec::new();
+        for root in roots.iter().filter(|root| root.join("steamapps").is_dir()) {
+            for library in steam_libraries(root) {
+                // Roots and library entries often point at the same place
+                if let Ok(library) = library.canonicalize()
+                    && !libraries.contains(&library)
+                {
+                    libraries.push(library);
+                }
+            }
+        }
+
+        let linked = self.linked_external_paths()?;
+        let mut found = Vec::new();
+        for library in &libraries {
+            let Ok(entries) = fs::read_dir(library.join("steamapps/compatdata")) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path().join("pfx");
+                let Some(app_id) = proton_app_id(&path).map(str::to_string) else {
+                    continue;
+                };
+                if !self.is_valid_wine_prefix(&path) {
+                    debug!("[steam] skipping incomplete prefix {}", path.display());
+                    continue;
+                }
+                found.push(ProtonPrefix {
+                    game_name: game_name(&libraries, &app_id),
+                    linked: linked.contains(&path),
+                    app_id,
+                    path,
+                });
+            }
+        }
+        found.sort_by_key(|p| p.display_name().to_lowercase());
+        Ok(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::Fixture;
+
+    fn add_proton_prefix(library: &Path, app_id: &str) -> PathBuf {
+        let pfx = library.join(format!("steamapps/compatdata/{}/pfx", app_id));
+        fs::create_dir_all(pfx.join("drive_c")).unwrap();
+        fs::write(pfx.join("system.reg"), "").unwrap();
+        fs::write(pfx.join("user.reg"), "").unwrap();
+        pfx
+    }
+
+    #[test]
+    fn test_proton_prefixes_in_reads_libraries_and_manifests() {
+        let fixture = Fixture::new();
+        let dir = tempfile::tempdir().unwrap();
+        let steam = dir.path().join("Steam");
+        let extra = dir.path().join("SteamLibrary");
+        fs::create_dir_all(steam.join("steamapps")).unwrap();
+        fs::write(
+            steam.join("steamapps/libraryfolders.vdf"),
+            format!(
+                "\"libraryfolders\"\n{{\n\t\"0\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t}}\n\t\"1\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t}}\n}}\n",
+                steam.display(),
+                extra.display()
+            ),
+        )
+        .unwrap();
+        add_proton_prefix(&steam, "220");
+        let linked = add_proton_prefix(&extra, "70");
+        fs::write(
+            extra.join("steamapps/appmanifest_70.acf"),
+            "\"AppState\"\n{\n\t\"appid\"\t\t\"70\"\n\t\"name\"\t\t\"Half-Life\"\n}\n",
+        )
+        .unwrap();
+        // Not a prefix: no hives yet
+        fs::create_dir_all(steam.join("steamapps/compatdata/480/pfx/drive_c")).unwrap();
+        fixture.manager().link_external_prefix(&linked, "").unwrap();
+
+        let found = fixture
+            .manager()
+            .proton_prefixes_in(&[steam.clone(), dir.path().join("missing")])
+            .unwrap();
+        let summary: Vec<(&str, String, bool)> = found
+            .iter()
+            .map(|p| (p.app_id.as_str(), p.display_name(), p.linked))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("70", "Half-Life".to_string(), true),
+                ("220", "Steam app 220".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_proton_app_id() {
+        assert_eq!(
+            proton_app_id(Path::new("/s/steamapps/compatdata/1091500/pfx")),
+            Some("1091500")
+        );
+        assert_eq!(
+            proton_app_id(Path::new("/s/steamapps/compatdata/1091500")),
+            None
+        );
+        assert_eq!(proton_app_id(Path::new("/s/prefixes/game/pfx")), None);
+        assert_eq!(proton_app_id(Path::new("/s/compatdata/abc/pfx")), None);
+    }
+}
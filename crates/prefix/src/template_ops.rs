@@ -0,0 +1,186 @@
+use crate::Manager;
+use base::error::{PrefixError, Result};
+use log::info;
+use registry::keys::DllOverrideSetting;
+use registry::{RegEditor, RegistryEditor, WineRegistry};
+use std::path::{Path, PathBuf};
+use store::PrefixTemplate;
+
+impl Manager {
+    /// Write a template's registry settings into an initialised prefix.
+    ///
+    /// The Windows version also updates the NT version info in `system.reg`,
+    /// as the registry editor does. winetricks verbs are not run here; see
+    /// [`Manager::run_winetricks`].
+    pub async fn apply_template(
+        &self,
+        prefix_path: &PathBuf,
+        template: &PrefixTemplate,
+    ) -> Result<()> {
+        let mut editor = RegistryEditor::with_prefix(prefix_path).await?;
+        if let Some(version) = &template.windows_version {
+            editor.set_windows_version(version).await?;
+        }
+        if let Some(renderer) = &template.d3d_renderer {
+            editor.set_d3d_renderer(renderer).await?;
+        }
+        for (dll, setting_str) in &template.dll_overrides {
+            let setting = DllOverrideSetting::from_string(setting_str).ok_or_else(|| {
+                PrefixError::Validation(format!("Invalid override setting: {}", setting_str))
+            })?;
+            editor.add_dll_override(dll, setting).await?;
+        }
+        editor.save_registry(prefix_path).await?;
+
+        let system_path = prefix_path.join("system.reg");
+        if let (Some(version), true) = (&template.windows_version, system_path.exists()) {
+            let system = WineRegistry::load_from_file(&system_path).await?;
+            RegistryEditor::set_nt_version_info(&system, version).await?;
+            system.save_to_file(&system_path).await?;
+        }
+
+        let dir_name = prefix_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown");
+        let mut config = self.load_or_create_config(prefix_path, dir_name, &None)?;
+        config.add_note(format!("Applied template '{}'", template.name), true);
+        config.save_to_file(prefix_path)?;
+
+        info!(
+            "[template] applied '{}' to prefix '{}'",
+            template.name, config.name
+        );
+        Ok(())
+    }
+
+    /// Capture the settings a template can hold from an existing prefix,
+    /// so it can serve as the baseline for new ones.
+    pub async fn template_from_prefix(
+        &self,
+        prefix_path: &PathBuf,
+        name: &str,
+    ) -> Result<PrefixTemplate> {
+        let editor = RegistryEditor::with_prefix(prefix_path).await?;
+        let dll_overrides = editor
+            .get_dll_overrides()
+            .await?
+            .into_iter()
+            .map(|o| (o.dll, o.setting.to_string().to_string()))
+            .collect();
+        Ok(PrefixTemplate {
+            name: name.to_string(),
+            windows_version: editor.get_windows_version().await?,
+            d3d_renderer: editor.get_d3d_renderer().await?,
+            dll_overrides,
+            winetricks_verbs: Vec::new(),
+        })
+    }
+
+    /// Capture `prefix_path` as a template named `name` and store it in the
+    /// settings, replacing any template of that name. The winetricks verbs of
+    /// a replaced template are kept, since they can't be read from a prefix.
+    pub async fn save_template_from_prefix(
+        &self,
+        prefix_path: &PathBuf,
+        name: &str,
+    ) -> Result<PrefixTemplate> {
+        let mut template = self.template_from_prefix(prefix_path, name).await?;
+        let mut settings = store::Settings::load().unwrap_or_else(|| self.clone_runtime().into());
+        if let Some(old) = settings.prefix_templates.iter().find(|t| t.name == name) {
+            template.winetricks_verbs = old.winetricks_verbs.clone();
+        }
+        settings.prefix_templates.retain(|t| t.name != name);
+        settings.prefix_templates.push(template.clone());
+        settings.save()?;
+        info!("[template] saved '{}' from {}", name, prefix_path.display());
+        Ok(template)
+    }
+
+    /// Install winetricks verbs into a prefix with `winetricks -q`, using
+    /// the prefix's runtime. Blocks until winetricks exits.
+    pub fn run_winetricks(&self, prefix_path: &Path, verbs: &[String]) -> Result<()> {
+        if verbs.is_empty() {
+            return Ok(());
+        }
+        let dir_name = prefix_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown");
+        let config = self.load_or_create_config(prefix_path, dir_name, &None)?;
+
+        let mut cmd = self.build_wine_command_for_exe("winetricks", &config, prefix_path);
+        // `WINE` is what winetricks looks for before falling back to PATH.
+        if let Some(runtime) = self.runtime_for_prefix(&config)
+            && let Some(wine_bin) = runtime::discover_wine_binary(&runtime.bundle_dir)
+        {
+            cmd.env("WINE", wine_bin);
+        }
+        info!(
+            "[template] running winetricks {} for prefix '{}'",
+            verbs.join(" "),
+            config.name
+        );
+        let output = cmd
+            .arg("-q")
+            .args(verbs)
+            .env("WINEDEBUG", "-all")
+            .output()
+            .map_err(|e| PrefixError::Process(format!("Failed to run winetricks: {}", e)))?;
+        if !output.status.success() {
+            return Err(PrefixError::Process(format!(
+                "winetricks {} failed: {}",
+                verbs.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::Fixture;
+    use std::collections::BTreeMap;
+
+    #[tokio::test]
+    async fn test_apply_template_round_trips_through_capture() {
+        let fixture = Fixture::new();
+        let prefix_path = fixture.add_prefix("templated");
+        for hive in ["system.reg", "user.reg"] {
+            WineRegistry::new()
+                .save_to_file(&prefix_path.join(hive))
+                .await
+                .unwrap();
+        }
+        let template = PrefixTemplate {
+            name: "Gaming".to_string(),
+            windows_version: Some("win10".to_string()),
+            d3d_renderer: Some("vulkan".to_string()),
+            dll_overrides: BTreeMap::from([(
+                "d3dcompiler_47".to_string(),
+                "native,builtin".to_string(),
+            )]),
+            winetricks_verbs: Vec::new(),
+        };
+
+        fixture
+            .manager()
+            .apply_template(&prefix_path, &template)
+            .await
+            .unwrap();
+
+        let editor = RegistryEditor::with_prefix(&prefix_path).await.unwrap();
+        assert_eq!(
+            editor.get_windows_version().await.unwrap().as_deref(),
+            Some("win10")
+        );
+        let captured = fixture
+            .manager()
+            .template_from_prefix(&prefix_path, "Gaming")
+            .await
+            .unwrap();
+        assert_eq!(captured, template);
+    }
+}
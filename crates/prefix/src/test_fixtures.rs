@@ -0,0 +1,50 @@
+//! On-disk fixtures for `Manager` tests: a temporary Wine directory that
+//! fake prefixes can be laid out in. Everything is removed on drop.
+
+use crate::Manager;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+pub(crate) struct Fixture {
+    // Kept alive for the lifetime of the fixture; dropping it deletes the tree
+    _dir: TempDir,
+    manager: Manager,
+}
+
+impl Fixture {
+    /// Create an empty Wine directory with its own icon cache and state DB.
+    pub(crate) fn new() -> Self {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let wine_dir = dir.path().join("prefixes");
+        std::fs::create_dir_all(&wine_dir).expect("create wine dir");
+        let icon_cache =
+            Arc::new(scan::IconCache::open(dir.path().join("icons")).expect("open icon cache"));
+        let store =
+            Arc::new(store::PrefixStore::open(&dir.path().join("state.db")).expect("open store"));
+        let manager = Manager::new(wine_dir, icon_cache, store);
+        Self { _dir: dir, manager }
+    }
+
+    pub(crate) fn manager(&self) -> &Manager {
+        &self.manager
+    }
+
+    pub(crate) fn wine_dir(&self) -> &Path {
+        self.manager.wine_dir()
+    }
+
+    /// Lay out a minimal 64-bit prefix named `dir_name`: `drive_c` with both
+    /// Program Files dirs, empty `system.reg`/`user.reg`, and a sample exe.
+    pub(crate) fn add_prefix(&self, dir_name: &str) -> PathBuf {
+        let path = self.wine_dir().join(dir_name);
+        let app_dir = path.join("drive_c/Program Files/Sample App");
+        std::fs::create_dir_all(&app_dir).expect("create app dir");
+        std::fs::create_dir_all(path.join("drive_c/Program Files (x86)"))
+            .expect("create x86 program files");
+        std::fs::write(path.join("system.reg"), "").expect("write system.reg");
+        std::fs::write(path.join("user.reg"), "").expect("write user.reg");
+        std::fs::write(app_dir.join("sample.exe"), b"MZ").expect("write sample exe");
+        path
+    }
+}
@@ -3,9 +3,120 @@ use base::GraphicsBackend;
 use log::{info, warn};
 use runtime::Runtime;
 use runtime::graphics;
+use std::os::unix::fs::MetadataExt;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/// Whether a wineserver is currently serving `prefix_path`.
+///
+/// wineserver listens on `/tmp/.wine-<uid>/server-<dev>-<inode>/socket`,
+/// keyed by the prefix directory's device and inode, and removes the socket
+/// on exit. Wine refuses prefixes not owned by the current user, so the
+/// directory owner doubles as the uid.
+pub fn wineserver_running(prefix_path: &Path) -> bool {
+    let Ok(meta) = std::fs::metadata(prefix_path) else {
+        return false;
+    };
+    PathBuf::from(format!("/tmp/.wine-{}", meta.uid()))
+        .join(format!("server-{:x}-{:x}", meta.dev(), meta.ino()))
+        .join("socket")
+        .exists()
+}
+
+/// A Windows program running under Wine, found by scanning `/proc`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WineProcess {
+    pub pid: u32,
+    /// Lower-cased file name of the `.exe`, e.g. `setup.exe`.
+    pub exe_name: String,
+}
+
+/// Programs Wine starts in a prefix on its own and keeps running for as long
+/// as the wineserver does.
+const WINE_SERVICES: &[&str] = &[
+    "services.exe",
+    "winedevice.exe",
+    "plugplay.exe",
+    "explorer.exe",
+    "rpcss.exe",
+    "svchost.exe",
+    "conhost.exe",
+    "start.exe",
+];
+
+impl WineProcess {
+    /// Whether this is one of Wine's own background programs rather than
+    /// something the user started.
+    pub fn is_wine_service(&self) -> bool {
+        WINE_SERVICES.contains(&self.exe_name.as_str())
+    }
+}
+
+/// Windows programs currently running in `prefix_path`.
+///
+/// Best effort: processes are matched on the `WINEPREFIX` in their
+/// environment, which is only readable for our own processes, and named after
+/// the first `.exe` argument on their command line. This catches programs
+/// the process tracker never saw, such as ones a launcher started or ones
+/// still running after the tracked `wine` wrapper exited. Always empty where
+/// there is no `/proc`.
+pub fn prefix_processes(prefix_path: &Path) -> Vec<WineProcess> {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    let prefix = prefix_path
+        .canonicalize()
+        .unwrap_or_else(|_| prefix_path.to_path_buf());
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let pid: u32 = entry.file_name().to_str()?.parse().ok()?;
+            let environ = std::fs::read(entry.path().join("environ")).ok()?;
+            let wine_prefix = environ_value(&environ, "WINEPREFIX")?;
+            let wine_prefix = Path::new(&wine_prefix);
+            let same =
+                wine_prefix == prefix || wine_prefix.canonicalize().is_ok_and(|p| p == prefix);
+            if !same {
+                return None;
+            }
+            let cmdline = std::fs::read(entry.path().join("cmdline")).ok()?;
+            Some(WineProcess {
+                pid,
+                exe_name: exe_name_from_cmdline(&cmdline)?,
+            })
+        })
+        .collect()
+}
+
+/// Look up `key` in a NUL-separated `/proc/<pid>/environ` block.
+fn environ_value(environ: &[u8], key: &str) -> Option<String> {
+    environ.split(|b| *b == 0).find_map(|var| {
+        let var = std::str::from_utf8(var).ok()?;
+        let (k, v) = var.split_once('=')?;
+        (k == key).then(|| v.to_string())
+    })
+}
+
+/// Lower-cased file name of the first `.exe` in a NUL-separated command line.
+/// Handles both Unix and Windows separators, since Wine rewrites `argv[0]`
+/// to the Windows path.
+fn exe_name_from_cmdline(cmdline: &[u8]) -> Option<String> {
+    cmdline.split(|b| *b == 0).find_map(|arg| {
+        let arg = String::from_utf8_lossy(arg).to_lowercase();
+        let name = arg.rsplit(['/', '\\']).next()?;
+        name.ends_with(".exe").then(|| name.to_string())
+    })
+}
+
+/// Apply the prefix-level environment from `tequila-config.json`. Call this
+/// before any per-executable variables so those win on conflicts.
+pub fn apply_prefix_env(cmd: &mut Command, config: &PrefixConfig) {
+    for (key, value) in &config.env_vars {
+        cmd.env(key, value);
+        info!("[spawn] {}={}", key, value);
+    }
+}
+
 pub fn apply_runtime_env(cmd: &mut Command, runtime: &Runtime, prefix_path: &Path) {
     cmd.env("WINEPREFIX", prefix_path);
     info!("[spawn] WINEPREFIX={}", prefix_path.to_string_lossy());
@@ -211,4 +322,41 @@ fn find_gstreamer_dir() -> Option<PathBuf> {
     } else {
         None
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exe_name_from_cmdline() {
+        let cmdline = b"C:\\Program Files\\Game\\Game.EXE\0-windowed\0";
+        assert_eq!(exe_name_from_cmdline(cmdline).as_deref(), Some("game.exe"));
+
+        let cmdline = b"/usr/bin/wine64-preloader\0/home/me/setup.exe\0";
+        assert_eq!(exe_name_from_cmdline(cmdline).as_deref(), Some("setup.exe"));
+
+        assert_eq!(exe_name_from_cmdline(b"/usr/bin/wineserver\0"), None);
+    }
+
+    #[test]
+    fn test_is_wine_service() {
+        let process = |exe_name: &str| WineProcess {
+            pid: 1,
+            exe_name: exe_name.to_string(),
+        };
+        assert!(process("winedevice.exe").is_wine_service());
+        assert!(process("explorer.exe").is_wine_service());
+        assert!(!process("game.exe").is_wine_service());
+    }
+
+    #[test]
+    fn test_environ_value() {
+        let environ = b"HOME=/home/me\0WINEPREFIX=/pfx\0WINEDEBUG=-all\0";
+        assert_eq!(
+            environ_value(environ, "WINEPREFIX").as_deref(),
+            Some("/pfx")
+        );
+        assert_eq!(environ_value(environ, "WINEARCH"), None);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::keys::*;
+use crate::traits::RegEditor;
+use base::error::Result;
+
+/// Groups of prefix-wide settings that can be copied from one prefix to
+/// another. Each group maps onto a set of `RegEditor` getters/setters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SettingGroup {
+    WindowsVersion,
+    Direct3D,
+    Audio,
+    Drivers,
+    Dpi,
+    VirtualDesktop,
+}
+
+impl SettingGroup {
+    pub const ALL: [SettingGroup; 6] = [
+        SettingGroup::WindowsVersion,
+        SettingGroup::Direct3D,
+        SettingGroup::Audio,
+        SettingGroup::Drivers,
+        SettingGroup::Dpi,
+        SettingGroup::VirtualDesktop,
+    ];
+}
+
+/// Copy the selected setting groups from `source` into `target`.
+///
+/// Values missing in the source are left untouched in the target, except
+/// for the virtual desktop, where "not set" means disabled and is copied as
+/// such. The caller is responsible for saving `target` afterwards.
+pub async fn copy_settings<S, T>(
+    source: &S,
+    target: &mut T,
+    groups: &[SettingGroup],
+) -> Result<()>
+where
+    S: RegEditor + ?Sized,
+    T: RegEditor + ?Sized,
+{
+    for group in groups {
+        match group {
+            SettingGroup::WindowsVersion => {
+                if let Some(version) = source.get_windows_version().await? {
+                    target.set_windows_version(&version).await?;
+                }
+            }
+            SettingGroup::Direct3D => {
+                if let Some(renderer) = source.get_d3d_renderer().await? {
+                    target.set_d3d_renderer(&renderer).await?;
+                }
+                if let Some(csmt) = source.get_d3d_csmt().await? {
+                    target.set_d3d_csmt(csmt != 0).await?;
+                }
+                if let Some(mode) = source.get_offscreen_rendering_mode().await? {
+                    target.set_offscreen_rendering_mode(&mode).await?;
+                }
+                if let Some(size) = source.get_video_memory_size().await? {
+                    target.set_video_memory_size(size).await?;
+                }
+            }
+            SettingGroup::Audio => {
+                if let Some(driver) = source.get_audio_driver().await? {
+                    target.set_audio_driver(&driver).await?;
+                }
+            }
+            SettingGroup::Drivers => {
+                if let Some(driver) = source.get_graphics_driver().await? {
+                    target.set_graphics_driver(&driver).await?;
+                }
+                if let Some(settings) = source.get_x11_driver_settings().await? {
+                    target.set_x11_driver_settings(&settings).await?;
+                }
+                if let Some(settings) = source.get_mac_driver_settings().await? {
+                    target.set_mac_driver_settings(&settings).await?;
+                }
+            }
+            SettingGroup::Dpi => {
+                if let Some(settings) = source.get_dpi_settings().await? {
+                    target.set_dpi_settings(&settings).await?;
+                }
+            }
+            SettingGroup::VirtualDesktop => {
+                let settings = source
+                    .get_virtual_desktop()
+                    .await?
+                    .unwrap_or(VirtualDesktopSettings::new(false, 0, 0));
+                target.set_virtual_desktop(&settings).await?;
+            }
+        }
+    }
+    Ok(())
+}
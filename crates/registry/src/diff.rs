@@ -0,0 +1,135 @@
+use regashii::{Registry, Value};
+use std::collections::BTreeMap;
+
+use crate::registry::value_name_str;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    /// Only present in the new registry.
+    Added,
+    /// Only present in the old registry.
+    Removed,
+    /// Present in both with different data.
+    Changed,
+}
+
+/// One difference between two registries, as produced by
+/// [`WineRegistry::diff`](crate::WineRegistry::diff).
+///
+/// `value` is `None` for an entry describing a whole key. Added or removed
+/// keys are followed by one entry per value they hold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegistryDiffEntry {
+    pub kind: DiffKind,
+    pub key: String,
+    pub value: Option<String>,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+type KeyValues = BTreeMap<String, BTreeMap<String, Value>>;
+
+fn key_values(registry: &Registry) -> KeyValues {
+    let mut keys = KeyValues::new();
+    for (name, key) in registry.keys() {
+        let mut values = BTreeMap::new();
+        for (value_name, value) in key.values() {
+            values.insert(value_name_str(value_name), value.clone());
+        }
+        keys.insert(name.raw().to_string(), values);
+    }
+    keys
+}
+
+fn whole_key(
+    kind: DiffKind,
+    key: &str,
+    values: &BTreeMap<String, Value>,
+    out: &mut Vec<RegistryDiffEntry>,
+) {
+    out.push(RegistryDiffEntry {
+        kind,
+        key: key.to_string(),
+        value: None,
+        old: None,
+        new: None,
+    });
+    for (name, value) in values {
+        let (old, new) = match kind {
+            DiffKind::Removed => (Some(value.clone()), None),
+            _ => (None, Some(value.clone())),
+        };
+        out.push(RegistryDiffEntry {
+            kind,
+            key: key.to_string(),
+            value: Some(name.clone()),
+            old,
+            new,
+        });
+    }
+}
+
+/// Compare two parsed registries. Output is sorted by key, then value name.
+pub(crate) fn diff_registries(old: &Registry, new: &Registry) -> Vec<RegistryDiffEntry> {
+    let old = key_values(old);
+    let mut new = key_values(new);
+    let mut out = Vec::new();
+
+    for (key, old_values) in &old {
+        let Some(mut new_values) = new.remove(key) else {
+            whole_key(DiffKind::Removed, key, old_values, &mut out);
+            continue;
+        };
+        for (name, old_value) in old_values {
+            let (kind, new_value) = match new_values.remove(name) {
+                Some(v) if v == *old_value => continue,
+                Some(v) => (DiffKind::Changed, Some(v)),
+                None => (DiffKind::Removed, None),
+            };
+            out.push(RegistryDiffEntry {
+                kind,
+                key: key.clone(),
+                value: Some(name.clone()),
+                old: Some(old_value.clone()),
+                new: new_value,
+            });
+        }
+        for (name, new_value) in new_values {
+            out.push(RegistryDiffEntry {
+                kind: DiffKind::Added,
+                key: key.clone(),
+                value: Some(name),
+                old: None,
+                new: Some(new_value),
+            });
+        }
+    }
+    for (key, new_values) in &new {
+        whole_key(DiffKind::Added, key, new_values, &mut out);
+    }
+
+    out.sort_by(|a, b| a.key.cmp(&b.key).then_with(|| a.value.cmp(&b.value)));
+    out
+}
+
+/// `bytes` as two-digit lowercase hex, joined by `separator`.
+pub fn hex_bytes(bytes: &[u8], separator: &str) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Short human-readable form of a value, in the style of a `.reg` file.
+pub fn display_value(value: &Value) -> String {
+    match value {
+        Value::Sz(s) => format!("\"{}\"", s),
+        Value::ExpandSz(s) => format!("str(2):\"{}\"", s),
+        Value::Dword(n) => format!("dword:{:08x}", n),
+        Value::MultiSz(parts) => format!("str(7):\"{}\"", parts.join("\\0")),
+        Value::Binary(bytes) => format!("hex:{}", hex_bytes(bytes, ",")),
+        Value::Delete => "-".to_string(),
+        other => format!("{:?}", other),
+    }
+}
@@ -3,12 +3,63 @@ use crate::keys::*;
 use crate::traits::RegEditor;
 use async_trait::async_trait;
 use base::error::{PrefixError, Result};
-use regashii::Value;
+use regashii::{Key, KeyKind, Registry, Value, ValueName};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Interpret a non-string value as a string: numbers in decimal, a
+/// multi-string by its first entry.
+fn coerce_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::Sz(s) | Value::ExpandSz(s) => Some(s.clone()),
+        Value::Dword(d) | Value::DwordBigEndian(d) => Some(d.to_string()),
+        Value::Qword(q) => Some(q.to_string()),
+        Value::MultiSz(parts) => parts.first().cloned(),
+        _ => None,
+    }
+}
+
+/// Interpret a non-DWORD value as a DWORD: decimal or `0x` hex strings,
+/// QWORDs that fit, and 4-byte little-endian binary data.
+fn coerce_to_dword(value: &Value) -> Option<u32> {
+    match value {
+        Value::Dword(d) | Value::DwordBigEndian(d) => Some(*d),
+        Value::Sz(s) | Value::ExpandSz(s) => {
+            let s = s.trim();
+            match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                None => s.parse().ok(),
+            }
+        }
+        Value::Qword(q) => u32::try_from(*q).ok(),
+        Value::Binary(bytes) => <[u8; 4]>::try_from(bytes.as_slice())
+            .ok()
+            .map(u32::from_le_bytes),
+        _ => None,
+    }
+}
+
+fn log_coercion(key_path: &str, value_name: &str, value: &Value, wanted: &str, ok: bool) {
+    let outcome = if ok {
+        "using the converted value"
+    } else {
+        "ignoring it"
+    };
+    log::warn!(
+        "[registry] {}\\{} is stored as {:?}, expected {}; {}",
+        key_path,
+        value_name,
+        value,
+        wanted,
+        outcome
+    );
+}
 
 pub struct RegistryEditor {
     pub registry: WineRegistry,
+    /// Copy of `registry` as last loaded or saved; see
+    /// [`pending_changes`](Self::pending_changes).
+    baseline: WineRegistry,
     prefix_path: Option<PathBuf>,
 }
 
@@ -25,18 +76,29 @@ impl RegistryEditor {
     pub fn new() -> Self {
         Self {
             registry: WineRegistry::new(),
+            baseline: WineRegistry::new(),
             prefix_path: None,
         }
     }
 
     pub async fn with_prefix(prefix_path: &PathBuf) -> Result<Self> {
         let registry = WineRegistry::load_from_prefix(prefix_path).await?;
+        let baseline = WineRegistry::new();
+        baseline.copy_from(&registry).await;
         Ok(Self {
             registry,
+            baseline,
             prefix_path: Some(prefix_path.clone()),
         })
     }
 
+    /// Values that would change on disk if the registry were saved now:
+    /// everything edited since it was last loaded or saved. Removed values
+    /// are reported as `Value::Delete`.
+    pub async fn pending_changes(&self) -> Result<Vec<(String, String, Value)>> {
+        self.registry.changes_since(&self.baseline).await
+    }
+
     fn get_registry_path(prefix_path: &PathBuf) -> Result<PathBuf> {
         let reg_path = prefix_path
             .join("user.reg")
@@ -45,39 +107,88 @@ impl RegistryEditor {
         Ok(reg_path)
     }
 
+    /// Pick the desktop entry that describes the virtual desktop: the one
+    /// named by `Desktop`, else `Default`, else the first by name.
+    fn desktop_geometry(settings: &DesktopSettings) -> Option<(String, DesktopSize)> {
+        let named = settings
+            .desktop
+            .as_deref()
+            .filter(|d| !d.is_empty())
+            .into_iter()
+            .chain(std::iter::once("Default"))
+            .find_map(|name| settings.desktops.get_key_value(name));
+        named
+            .or_else(|| settings.desktops.iter().min_by(|a, b| a.0.cmp(b.0)))
+            .map(|(name, size)| (name.clone(), size.clone()))
+    }
+
+    /// Read a string value. A value another tool stored with a numeric
+    /// type is converted (with a warning) rather than reported as missing.
     async fn get_string_value(&self, key_path: &str, value_name: &str) -> Result<Option<String>> {
-        if let Some(value) = self.registry.get_value(key_path, value_name).await? {
-            match value {
-                Value::Sz(s) => Ok(Some(s)),
-                Value::ExpandSz(s) => Ok(Some(s)),
-                _ => Ok(None),
-            }
-        } else {
-            Ok(None)
-        }
+        let Some(value) = self.registry.get_value(key_path, value_name).await? else {
+            return Ok(None);
+        };
+        let coerced = match &value {
+            Value::Sz(s) | Value::ExpandSz(s) => return Ok(Some(s.clone())),
+            other => coerce_to_string(other),
+        };
+        log_coercion(key_path, value_name, &value, "string", coerced.is_some());
+        Ok(coerced)
     }
 
+    /// Write a string value, keeping the type of an existing value: a
+    /// `REG_EXPAND_SZ` stays one so `%VAR%` references keep expanding, and a
+    /// `REG_MULTI_SZ` takes one entry per line. Anything else, including a
+    /// new value, is written as `REG_SZ`.
     async fn set_string_value(
         &mut self,
         key_path: &str,
         value_name: &str,
         value: &str,
     ) -> Result<()> {
-        let reg_value = Value::Sz(value.to_string());
+        let reg_value = match self.registry.get_value(key_path, value_name).await? {
+            Some(Value::ExpandSz(_)) => Value::ExpandSz(value.to_string()),
+            Some(Value::MultiSz(_)) => Value::MultiSz(value.lines().map(String::from).collect()),
+            _ => Value::Sz(value.to_string()),
+        };
         self.registry
             .set_value(key_path, value_name, reg_value)
             .await
     }
 
-    async fn get_dword_value(&self, key_path: &str, value_name: &str) -> Result<Option<u32>> {
-        if let Some(value) = self.registry.get_value(key_path, value_name).await? {
-            match value {
-                Value::Dword(d) => Ok(Some(d)),
-                _ => Ok(None),
-            }
+    /// Write a string value as `REG_EXPAND_SZ` (`expand`) or `REG_SZ`,
+    /// whatever type it had before. Wine expands `%VAR%` references only in
+    /// the former, which `PATH`-like values under `Environment` rely on.
+    pub async fn set_string_value_typed(
+        &mut self,
+        key_path: &str,
+        value_name: &str,
+        value: &str,
+        expand: bool,
+    ) -> Result<()> {
+        let reg_value = if expand {
+            Value::ExpandSz(value.to_string())
         } else {
-            Ok(None)
-        }
+            Value::Sz(value.to_string())
+        };
+        self.registry
+            .set_value(key_path, value_name, reg_value)
+            .await
+    }
+
+    /// Read a DWORD value. A number stored as a string, QWORD or 4-byte
+    /// binary value is converted (with a warning) rather than reported as
+    /// missing.
+    async fn get_dword_value(&self, key_path: &str, value_name: &str) -> Result<Option<u32>> {
+        let Some(value) = self.registry.get_value(key_path, value_name).await? else {
+            return Ok(None);
+        };
+        let coerced = match &value {
+            Value::Dword(d) => return Ok(Some(*d)),
+            other => coerce_to_dword(other),
+        };
+        log_coercion(key_path, value_name, &value, "DWORD", coerced.is_some());
+        Ok(coerced)
     }
 
     async fn set_dword_value(
@@ -92,7 +203,85 @@ impl RegistryEditor {
             .await
     }
 
-    fn validate_key_path(key_path: &str) -> Result<()> {
+    /// Merge the keys of a standard `.reg` file into this registry.
+    ///
+    /// Only `HKEY_CURRENT_USER` keys are accepted (this editor targets
+    /// `user.reg`). Keys and values failing `validate_key_path` /
+    /// `validate_value_name` are skipped and listed in the report.
+    /// `[-KEY]` deletes a key and `"name"=-` a value.
+    pub async fn import_reg_file(&mut self, path: &Path) -> Result<RegImportReport> {
+        let path = path.to_path_buf();
+        let imported = tokio::task::spawn_blocking(move || Registry::deserialize_file(&path))
+            .await
+            .map_err(|e| PrefixError::RegistryError(format!("Task join error: {}", e)))?
+            .map_err(|e| PrefixError::RegistryError(format!("Failed to parse .reg file: {}", e)))?;
+
+        let mut report = RegImportReport::default();
+        let mut accepted_keys = Vec::new();
+        for (name, key) in imported.keys() {
+            let raw = name.raw();
+            let Some(key_path) = raw
+                .strip_prefix("HKEY_CURRENT_USER\\")
+                .or_else(|| raw.strip_prefix("HKCU\\"))
+            else {
+                report.rejected.push(ValidationError::new(
+                    raw.to_string(),
+                    None,
+                    "Only HKEY_CURRENT_USER keys can be imported".to_string(),
+                ));
+                continue;
+            };
+            if let Err(e) = Self::validate_key_path(key_path) {
+                report
+                    .rejected
+                    .push(ValidationError::new(raw.to_string(), None, e.to_string()));
+                continue;
+            }
+
+            if key.kind() == KeyKind::Delete {
+                accepted_keys.push((key_path.to_string(), Key::deleted()));
+                report.imported_keys += 1;
+                continue;
+            }
+
+            let mut accepted = match key.kind() {
+                KeyKind::Replace => Key::replaced(),
+                _ => Key::new(),
+            };
+            let mut value_count = 0;
+            for (value_name, value) in key.values() {
+                if let ValueName::Named(n) = value_name
+                    && let Err(e) = Self::validate_value_name(n)
+                {
+                    report.rejected.push(ValidationError::new(
+                        raw.to_string(),
+                        Some(n.clone()),
+                        e.to_string(),
+                    ));
+                    continue;
+                }
+                accepted = accepted.with(value_name.clone(), value.clone());
+                value_count += 1;
+            }
+
+            accepted_keys.push((key_path.to_string(), accepted));
+            report.imported_keys += 1;
+            report.imported_values += value_count;
+        }
+        self.registry.merge_keys(accepted_keys).await?;
+
+        log::info!(
+            "[registry] imported {} keys ({} values), rejected {}",
+            report.imported_keys,
+            report.imported_values,
+            report.rejected.len()
+        );
+        Ok(report)
+    }
+
+    /// Check that `key_path` is one Tequila may write: non-empty and under
+    /// `Software` or `Control Panel`.
+    pub fn validate_key_path(key_path: &str) -> Result<()> {
         if key_path.is_empty() {
             return Err(PrefixError::ValidationError(
                 "Key path cannot be empty".to_string(),
@@ -107,6 +296,54 @@ impl RegistryEditor {
         Ok(())
     }
 
+    /// Parse hex bytes as typed into a binary value editor: pairs of hex
+    /// digits separated by spaces or commas, with an optional `hex:` prefix
+    /// so a `.reg` line can be pasted as-is. The inverse of
+    /// [`RegistryEditor::format_binary`].
+    pub fn parse_binary(text: &str) -> Result<Vec<u8>> {
+        let text = text.trim();
+        let text = text.strip_prefix("hex:").unwrap_or(text);
+        text.split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|byte| !byte.is_empty())
+            .map(|byte| {
+                if byte.len() != 2 {
+                    return Err(PrefixError::ValidationError(format!(
+                        "Invalid byte '{}': expected two hex digits",
+                        byte
+                    )));
+                }
+                u8::from_str_radix(byte, 16).map_err(|_| {
+                    PrefixError::ValidationError(format!("Invalid hex byte '{}'", byte))
+                })
+            })
+            .collect()
+    }
+
+    /// `REG_BINARY` data for editing: space-separated hex pairs, sixteen to
+    /// a line.
+    pub fn format_binary(bytes: &[u8]) -> String {
+        bytes
+            .chunks(16)
+            .map(|line| crate::diff::hex_bytes(line, " "))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Accept `.ext` style extensions: a leading dot followed by at least one
+    /// character that can appear in a key name.
+    fn validate_extension(extension: &str) -> Result<()> {
+        let valid = extension.len() > 1
+            && extension.starts_with('.')
+            && !extension[1..].contains(|c: char| c == '.' || c == '\\' || c.is_whitespace());
+        if !valid {
+            return Err(PrefixError::ValidationError(format!(
+                "Invalid file extension: {}",
+                extension
+            )));
+        }
+        Ok(())
+    }
+
     fn validate_value_name(value_name: &str) -> Result<()> {
         if value_name.is_empty() {
             return Err(PrefixError::ValidationError(
@@ -124,10 +361,80 @@ impl RegistryEditor {
         }
         Ok(())
     }
-}
 
-fn is_mac_option_true(v: &str) -> bool {
-    matches!(v, "Y" | "y" | "T" | "t" | "1")
+    /// Collect the set `Y`/`N` flags of a settings struct into one batch for
+    /// [`WineRegistry::set_values`].
+    fn flag_values(
+        key_path: &str,
+        flags: &[(&str, Option<bool>)],
+    ) -> Result<Vec<(String, String, Value)>> {
+        let mut values = Vec::new();
+        for (name, flag) in flags {
+            if let Some(v) = flag {
+                Self::validate_value_name(name)?;
+                let data = if *v { "Y" } else { "N" };
+                values.push((
+                    key_path.to_string(),
+                    name.to_string(),
+                    Value::Sz(data.to_string()),
+                ));
+            }
+        }
+        Ok(values)
+    }
+
+    /// Write the build number and service pack matching `version` under
+    /// [`NT_CURRENT_VERSION_KEY`] in `system` (`system.reg`), so programs
+    /// that read them there agree with `Software\\Wine\\Version`.
+    ///
+    /// Versions without NT version info (the 9x/3.1 family) leave the key
+    /// untouched.
+    pub async fn set_nt_version_info(system: &WineRegistry, version: &str) -> Result<()> {
+        let parsed = WindowsVersion::from_string(version).ok_or_else(|| {
+            PrefixError::ValidationError(format!("Invalid Windows version: {}", version))
+        })?;
+        let Some(info) = parsed.nt_version_info() else {
+            return Ok(());
+        };
+
+        // Windows 10 keeps reporting 6.3 here and exposes its real version
+        // through the `Current*VersionNumber` DWORDs instead
+        let current_version = if info.major >= 10 {
+            "6.3".to_string()
+        } else {
+            format!("{}.{}", info.major, info.minor)
+        };
+        let build = info.build.to_string();
+        let csd_version = if info.csd_version.is_empty() {
+            Value::Delete
+        } else {
+            Value::Sz(info.csd_version.to_string())
+        };
+        let (major, minor) = if info.major >= 10 {
+            (Value::Dword(info.major), Value::Dword(info.minor))
+        } else {
+            (Value::Delete, Value::Delete)
+        };
+        let values = [
+            ("CurrentVersion", Value::Sz(current_version)),
+            ("CurrentBuild", Value::Sz(build.clone())),
+            ("CurrentBuildNumber", Value::Sz(build)),
+            ("CSDVersion", csd_version),
+            ("CurrentMajorVersionNumber", major),
+            ("CurrentMinorVersionNumber", minor),
+        ];
+        system
+            .set_values(
+                values
+                    .into_iter()
+                    .map(|(name, value)| {
+                        (NT_CURRENT_VERSION_KEY.to_string(), name.to_string(), value)
+                    })
+                    .collect(),
+            )
+            .await?;
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -135,6 +442,7 @@ impl RegEditor for RegistryEditor {
     async fn load_registry(&mut self, prefix_path: &PathBuf) -> Result<()> {
         let registry_path = Self::get_registry_path(prefix_path)?;
         self.registry = WineRegistry::load_from_file(&registry_path).await?;
+        self.baseline.copy_from(&self.registry).await;
         self.prefix_path = Some(prefix_path.clone());
         Ok(())
     }
@@ -142,6 +450,7 @@ impl RegEditor for RegistryEditor {
     async fn save_registry(&self, prefix_path: &PathBuf) -> Result<()> {
         let registry_path = Self::get_registry_path(prefix_path)?;
         self.registry.save_to_file(&registry_path).await?;
+        self.baseline.copy_from(&self.registry).await;
         Ok(())
     }
 
@@ -237,6 +546,76 @@ impl RegEditor for RegistryEditor {
         }
     }
 
+    async fn get_direct_input_settings(&self) -> Result<Option<DirectInputSettings>> {
+        let key_path = "Software\\Wine\\DirectInput";
+        let mouse_warp_override = self.get_string_value(key_path, "MouseWarpOverride").await?;
+        // Wine reads the dead zone as a string and parses it itself
+        let default_dead_zone = self.get_string_value(key_path, "DefaultDeadZone").await?;
+        if mouse_warp_override.is_none() && default_dead_zone.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(DirectInputSettings {
+            mouse_warp_override: mouse_warp_override
+                .and_then(|v| MouseWarpOverride::from_string(&v)),
+            default_dead_zone: default_dead_zone.and_then(|v| v.trim().parse().ok()),
+        }))
+    }
+
+    async fn set_direct_input_settings(&mut self, settings: &DirectInputSettings) -> Result<()> {
+        let key_path = "Software\\Wine\\DirectInput";
+        Self::validate_key_path(key_path)?;
+        let mut values = Vec::new();
+        if let Some(mode) = &settings.mouse_warp_override {
+            values.push((
+                key_path.to_string(),
+                "MouseWarpOverride".to_string(),
+                Value::Sz(mode.to_string().to_string()),
+            ));
+        }
+        if let Some(dead_zone) = settings.default_dead_zone {
+            if !JOYSTICK_DEAD_ZONE_RANGE.contains(&dead_zone) {
+                return Err(PrefixError::ValidationError(
+                    "Joystick dead zone must be between 0 and 10000".to_string(),
+                ));
+            }
+            values.push((
+                key_path.to_string(),
+                "DefaultDeadZone".to_string(),
+                Value::Sz(dead_zone.to_string()),
+            ));
+        }
+        self.registry.set_values(values).await
+    }
+
+    async fn get_joystick_overrides(&self) -> Result<Vec<JoystickOverride>> {
+        let key_path = "Software\\Wine\\DirectInput\\Joysticks";
+        let mut overrides = Vec::new();
+        let values = self.registry.get_key_values(key_path).await?;
+        for (name, value) in values {
+            if let Value::Sz(state_str) = value {
+                if let Some(state) = JoystickState::from_string(&state_str) {
+                    overrides.push(JoystickOverride { name, state });
+                }
+            }
+        }
+        Ok(overrides)
+    }
+
+    async fn set_joystick_state(&mut self, name: &str, state: JoystickState) -> Result<()> {
+        let key_path = "Software\\Wine\\DirectInput\\Joysticks";
+        Self::validate_key_path(key_path)?;
+        Self::validate_value_name(name)?;
+        self.set_string_value(key_path, name, state.to_string())
+            .await
+    }
+
+    async fn remove_joystick_override(&mut self, name: &str) -> Result<()> {
+        let key_path = "Software\\Wine\\DirectInput\\Joysticks";
+        Self::validate_key_path(key_path)?;
+        Self::validate_value_name(name)?;
+        self.registry.delete_value(key_path, name).await
+    }
+
     async fn get_audio_driver(&self) -> Result<Option<String>> {
         self.get_string_value("Software\\Wine\\Drivers\\Audio", "")
             .await
@@ -377,6 +756,72 @@ impl RegEditor for RegistryEditor {
         self.registry.delete_value(key_path, dll).await
     }
 
+    async fn get_file_associations(&self) -> Result<Vec<FileAssociation>> {
+        let classes = "Software\\Classes\\";
+        let mut associations = Vec::new();
+        for key_path in self.registry.find_keys(classes).await? {
+            let Some(extension) = key_path.strip_prefix(classes) else {
+                continue;
+            };
+            if !extension.starts_with('.') || extension.contains('\\') {
+                continue;
+            }
+            let Some(prog_id) = self.get_string_value(&key_path, "(default)").await? else {
+                continue;
+            };
+            if prog_id.is_empty() {
+                continue;
+            }
+            let command = self
+                .get_string_value(
+                    &format!("{}{}\\shell\\open\\command", classes, prog_id),
+                    "(default)",
+                )
+                .await?;
+            associations.push(FileAssociation {
+                extension: extension.to_string(),
+                prog_id,
+                command,
+            });
+        }
+        associations.sort_by(|a, b| a.extension.cmp(&b.extension));
+        Ok(associations)
+    }
+
+    async fn set_file_association(&mut self, extension: &str, command: &str) -> Result<()> {
+        Self::validate_extension(extension)?;
+        if command.trim().is_empty() {
+            return Err(PrefixError::ValidationError(
+                "Open command cannot be empty".to_string(),
+            ));
+        }
+        let prog_id = format!("{}{}", TEQUILA_PROG_ID_PREFIX, &extension[1..]);
+        let ext_path = format!("Software\\Classes\\{}", extension);
+        let command_path = format!("Software\\Classes\\{}\\shell\\open\\command", prog_id);
+        Self::validate_key_path(&ext_path)?;
+        self.set_string_value(&ext_path, "(default)", &prog_id)
+            .await?;
+        self.set_string_value(&command_path, "(default)", command)
+            .await
+    }
+
+    async fn remove_file_association(&mut self, extension: &str) -> Result<()> {
+        Self::validate_extension(extension)?;
+        let ext_path = format!("Software\\Classes\\{}", extension);
+        let prog_id = self.get_string_value(&ext_path, "(default)").await?;
+        self.registry.delete_key(&ext_path).await?;
+        // Only our own ProgIDs are dropped; others may serve several types
+        if let Some(prog_id) = prog_id.filter(|p| p.starts_with(TEQUILA_PROG_ID_PREFIX)) {
+            let prog_path = format!("Software\\Classes\\{}", prog_id);
+            for sub in ["\\shell\\open\\command", "\\shell\\open", "\\shell", ""] {
+                self.registry
+                    .delete_key(&format!("{}{}", prog_path, sub))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
     async fn get_video_memory_size(&self) -> Result<Option<u32>> {
         self.get_dword_value("Software\\Wine\\Direct3D", "VideoMemorySize")
             .await
@@ -386,7 +831,7 @@ impl RegEditor for RegistryEditor {
         let key_path = "Software\\Wine\\Direct3D";
         Self::validate_key_path(key_path)?;
         Self::validate_value_name("VideoMemorySize")?;
-        if size_mb == 0 || size_mb > 16384 {
+        if !VIDEO_MEMORY_SIZE_RANGE.contains(&size_mb) {
             return Err(PrefixError::ValidationError(
                 "Video memory size must be between 1 and 16384 MB".to_string(),
             ));
@@ -425,79 +870,64 @@ impl RegEditor for RegistryEditor {
     async fn set_shader_model_settings(&mut self, settings: &ShaderModelSettings) -> Result<()> {
         let key_path = "Software\\Wine\\Direct3D";
         Self::validate_key_path(key_path)?;
-        if let Some(vs) = settings.max_shader_model_vs {
-            Self::validate_value_name("MaxShaderModelVS")?;
-            self.set_dword_value(key_path, "MaxShaderModelVS", vs)
-                .await?;
-        }
-        if let Some(ps) = settings.max_shader_model_ps {
-            Self::validate_value_name("MaxShaderModelPS")?;
-            self.set_dword_value(key_path, "MaxShaderModelPS", ps)
-                .await?;
-        }
-        if let Some(gs) = settings.max_shader_model_gs {
-            Self::validate_value_name("MaxShaderModelGS")?;
-            self.set_dword_value(key_path, "MaxShaderModelGS", gs)
-                .await?;
-        }
-        if let Some(hs) = settings.max_shader_model_hs {
-            Self::validate_value_name("MaxShaderModelHS")?;
-            self.set_dword_value(key_path, "MaxShaderModelHS", hs)
-                .await?;
-        }
-        if let Some(ds) = settings.max_shader_model_ds {
-            Self::validate_value_name("MaxShaderModelDS")?;
-            self.set_dword_value(key_path, "MaxShaderModelDS", ds)
-                .await?;
-        }
-        if let Some(cs) = settings.max_shader_model_cs {
-            Self::validate_value_name("MaxShaderModelCS")?;
-            self.set_dword_value(key_path, "MaxShaderModelCS", cs)
-                .await?;
+        let fields = [
+            ("MaxShaderModelVS", settings.max_shader_model_vs),
+            ("MaxShaderModelPS", settings.max_shader_model_ps),
+            ("MaxShaderModelGS", settings.max_shader_model_gs),
+            ("MaxShaderModelHS", settings.max_shader_model_hs),
+            ("MaxShaderModelDS", settings.max_shader_model_ds),
+            ("MaxShaderModelCS", settings.max_shader_model_cs),
+        ];
+        let mut values = Vec::new();
+        for (name, model) in fields {
+            if let Some(model) = model {
+                Self::validate_value_name(name)?;
+                values.push((key_path.to_string(), name.to_string(), Value::Dword(model)));
+            }
         }
-        Ok(())
+        self.registry.set_values(values).await
     }
 
     async fn get_virtual_desktop(&self) -> Result<Option<VirtualDesktopSettings>> {
-        let desktop_settings = self.get_desktop_settings().await?;
-        if let Some(settings) = desktop_settings {
-            if let Some(desktop_name) = settings.desktop {
-                if let Some(size) = settings.desktops.get(&desktop_name) {
-                    return Ok(Some(VirtualDesktopSettings {
-                        enabled: true,
-                        width: size.width,
-                        height: size.height,
-                    }));
-                }
-            }
-        }
-        Ok(None)
+        let Some(settings) = self.get_desktop_settings().await? else {
+            return Ok(None);
+        };
+        let Some((_, size)) = Self::desktop_geometry(&settings) else {
+            return Ok(None);
+        };
+        // winecfg may name the desktop anything; any non-empty name with a
+        // geometry entry means the virtual desktop is on.
+        let enabled = settings.desktop.as_deref().is_some_and(|d| !d.is_empty());
+        Ok(Some(VirtualDesktopSettings {
+            enabled,
+            width: size.width,
+            height: size.height,
+        }))
     }
 
     async fn set_virtual_desktop(&mut self, settings: &VirtualDesktopSettings) -> Result<()> {
+        let key_path = "Software\\Wine\\Explorer";
+        Self::validate_key_path(key_path)?;
+        let current = self.get_desktop_settings().await?;
         if settings.enabled {
+            // Reuse the existing desktop name so winecfg's entry is updated
+            // in place rather than orphaned.
+            let name = current
+                .as_ref()
+                .and_then(|c| Self::desktop_geometry(c).map(|(name, _)| name))
+                .unwrap_or_else(|| "Default".to_string());
             let desktop_settings = DesktopSettings {
-                desktop: Some("Default".to_string()),
-                desktops: {
-                    let mut desktops = HashMap::new();
-                    desktops.insert(
-                        "Default".to_string(),
-                        DesktopSize::new(settings.width, settings.height),
-                    );
-                    desktops
-                },
+                desktop: Some(name.clone()),
+                desktops: HashMap::from([(
+                    name,
+                    DesktopSize::new(settings.width, settings.height),
+                )]),
                 show_systray: false,
             };
             self.set_desktop_settings(&desktop_settings).await
         } else {
-            let key_path = "Software\\Wine\\Explorer";
-            Self::validate_key_path(key_path)?;
-            self.registry.delete_value(key_path, "Desktop").await?;
-            let desktops_path = format!("{}\\Desktops", key_path);
-            self.registry
-                .delete_value(&desktops_path, "Default")
-                .await?;
-            Ok(())
+            // Keep the `Desktops` geometry so re-enabling restores it.
+            self.registry.delete_value(key_path, "Desktop").await
         }
     }
 
@@ -507,6 +937,9 @@ impl RegEditor for RegistryEditor {
             return Ok(None);
         }
         let mut app_settings = AppSettings::new(app_name.to_string());
+        if let Some(version_str) = self.get_string_value(&key_path, "Version").await? {
+            app_settings.windows_version = WindowsVersion::from_string(&version_str);
+        }
         let dll_overrides_path = format!("{}\\DllOverrides", key_path);
         let values = self.registry.get_key_values(&dll_overrides_path).await?;
         for (dll, value) in values {
@@ -540,35 +973,46 @@ impl RegEditor for RegistryEditor {
     async fn set_app_settings(&mut self, app_name: &str, settings: &AppSettings) -> Result<()> {
         let base_key_path = format!("Software\\Wine\\AppDefaults\\{}", app_name);
         Self::validate_key_path(&base_key_path)?;
-        if !settings.dll_overrides.is_empty() {
-            let dll_overrides_path = format!("{}\\DllOverrides", base_key_path);
-            for dll_override in &settings.dll_overrides {
-                Self::validate_value_name(&dll_override.dll)?;
-                self.set_string_value(
-                    &dll_overrides_path,
-                    &dll_override.dll,
-                    dll_override.setting.to_string(),
-                )
-                .await?;
-            }
+        let d3d_path = format!("{}\\Direct3D", base_key_path);
+        let dll_overrides_path = format!("{}\\DllOverrides", base_key_path);
+        let mut values = Vec::new();
+        if let Some(version) = &settings.windows_version {
+            Self::validate_value_name("Version")?;
+            values.push((
+                base_key_path.clone(),
+                "Version".to_string(),
+                Value::Sz(version.to_string().to_string()),
+            ));
+        }
+        for dll_override in &settings.dll_overrides {
+            Self::validate_value_name(&dll_override.dll)?;
+            values.push((
+                dll_overrides_path.clone(),
+                dll_override.dll.clone(),
+                Value::Sz(dll_override.setting.to_string().to_string()),
+            ));
         }
         if let Some(renderer) = &settings.d3d_renderer {
-            let d3d_path = format!("{}\\Direct3D", base_key_path);
             Self::validate_value_name("renderer")?;
-            self.set_string_value(&d3d_path, "renderer", renderer.to_string())
-                .await?;
+            values.push((
+                d3d_path.clone(),
+                "renderer".to_string(),
+                Value::Sz(renderer.to_string().to_string()),
+            ));
         }
         if let Some(mode) = &settings.offscreen_rendering_mode {
-            let d3d_path = format!("{}\\Direct3D", base_key_path);
             Self::validate_value_name("OffscreenRenderingMode")?;
-            self.set_string_value(&d3d_path, "OffscreenRenderingMode", mode.to_string())
-                .await?;
+            values.push((
+                d3d_path.clone(),
+                "OffscreenRenderingMode".to_string(),
+                Value::Sz(mode.to_string().to_string()),
+            ));
         }
         for (key, value) in &settings.custom_settings {
             Self::validate_value_name(key)?;
-            self.set_string_value(&base_key_path, key, value).await?;
+            values.push((base_key_path.clone(), key.clone(), Value::Sz(value.clone())));
         }
-        Ok(())
+        self.registry.set_values(values).await
     }
 
     async fn remove_app_settings(&mut self, app_name: &str) -> Result<()> {
@@ -577,6 +1021,29 @@ impl RegEditor for RegistryEditor {
         self.registry.delete_key(&key_path).await
     }
 
+    async fn set_app_windows_version(
+        &mut self,
+        app_name: &str,
+        version: Option<&str>,
+    ) -> Result<()> {
+        let key_path = format!("Software\\Wine\\AppDefaults\\{}", app_name);
+        Self::validate_key_path(&key_path)?;
+        Self::validate_value_name("Version")?;
+        match version {
+            Some(version) => {
+                let Some(parsed_version) = WindowsVersion::from_string(version) else {
+                    return Err(PrefixError::ValidationError(format!(
+                        "Invalid Windows version: {}",
+                        version
+                    )));
+                };
+                self.set_string_value(&key_path, "Version", parsed_version.to_string())
+                    .await
+            }
+            None => self.registry.delete_value(&key_path, "Version").await,
+        }
+    }
+
     async fn get_x11_driver_settings(&self) -> Result<Option<X11DriverSettings>> {
         let key_path = "Software\\Wine\\X11 Driver";
         let decorated = self.get_string_value(key_path, "Decorated").await?;
@@ -597,6 +1064,9 @@ impl RegEditor for RegistryEditor {
         let managed = self.get_string_value(key_path, "Managed").await?;
         let use_xrandr = self.get_string_value(key_path, "UseXRandR").await?;
         let use_xvid_mode = self.get_string_value(key_path, "UseXVidMode").await?;
+        let use_primary_selection = self
+            .get_string_value(key_path, "UsePrimarySelection")
+            .await?;
         if decorated.is_none()
             && client_side_graphics.is_none()
             && client_side_with_render.is_none()
@@ -607,39 +1077,43 @@ impl RegEditor for RegistryEditor {
             && managed.is_none()
             && use_xrandr.is_none()
             && use_xvid_mode.is_none()
+            && use_primary_selection.is_none()
         {
             return Ok(None);
         }
         let mut settings = X11DriverSettings::new();
         if let Some(v) = decorated {
-            settings.decorated = Some(v != "N");
+            settings.decorated = Some(is_option_true(&v));
         }
         if let Some(v) = client_side_graphics {
-            settings.client_side_graphics = Some(v != "N");
+            settings.client_side_graphics = Some(is_option_true(&v));
         }
         if let Some(v) = client_side_with_render {
-            settings.client_side_with_render = Some(v != "N");
+            settings.client_side_with_render = Some(is_option_true(&v));
         }
         if let Some(v) = client_side_antialias_with_render {
-            settings.client_side_antialias_with_render = Some(v != "N");
+            settings.client_side_antialias_with_render = Some(is_option_true(&v));
         }
         if let Some(v) = client_side_antialias_with_core {
-            settings.client_side_antialias_with_core = Some(v != "N");
+            settings.client_side_antialias_with_core = Some(is_option_true(&v));
         }
         if let Some(v) = grab_fullscreen {
-            settings.grab_fullscreen = Some(v == "Y");
+            settings.grab_fullscreen = Some(is_option_true(&v));
         }
         if let Some(v) = grab_pointer {
-            settings.grab_pointer = Some(v != "N");
+            settings.grab_pointer = Some(is_option_true(&v));
         }
         if let Some(v) = managed {
-            settings.managed = Some(v != "N");
+            settings.managed = Some(is_option_true(&v));
         }
         if let Some(v) = use_xrandr {
-            settings.use_xrandr = Some(v != "N");
+            settings.use_xrandr = Some(is_option_true(&v));
         }
         if let Some(v) = use_xvid_mode {
-            settings.use_xvid_mode = Some(v == "Y");
+            settings.use_xvid_mode = Some(is_option_true(&v));
+        }
+        if let Some(v) = use_primary_selection {
+            settings.use_primary_selection = Some(is_option_true(&v));
         }
         Ok(Some(settings))
     }
@@ -647,65 +1121,29 @@ impl RegEditor for RegistryEditor {
     async fn set_x11_driver_settings(&mut self, settings: &X11DriverSettings) -> Result<()> {
         let key_path = "Software\\Wine\\X11 Driver";
         Self::validate_key_path(key_path)?;
-        if let Some(v) = settings.decorated {
-            Self::validate_value_name("Decorated")?;
-            self.set_string_value(key_path, "Decorated", if v { "Y" } else { "N" })
-                .await?;
-        }
-        if let Some(v) = settings.client_side_graphics {
-            Self::validate_value_name("ClientSideGraphics")?;
-            self.set_string_value(key_path, "ClientSideGraphics", if v { "Y" } else { "N" })
-                .await?;
-        }
-        if let Some(v) = settings.client_side_with_render {
-            Self::validate_value_name("ClientSideWithRender")?;
-            self.set_string_value(key_path, "ClientSideWithRender", if v { "Y" } else { "N" })
-                .await?;
-        }
-        if let Some(v) = settings.client_side_antialias_with_render {
-            Self::validate_value_name("ClientSideAntiAliasWithRender")?;
-            self.set_string_value(
-                key_path,
-                "ClientSideAntiAliasWithRender",
-                if v { "Y" } else { "N" },
-            )
-            .await?;
-        }
-        if let Some(v) = settings.client_side_antialias_with_core {
-            Self::validate_value_name("ClientSideAntiAliasWithCore")?;
-            self.set_string_value(
-                key_path,
-                "ClientSideAntiAliasWithCore",
-                if v { "Y" } else { "N" },
-            )
-            .await?;
-        }
-        if let Some(v) = settings.grab_fullscreen {
-            Self::validate_value_name("GrabFullscreen")?;
-            self.set_string_value(key_path, "GrabFullscreen", if v { "Y" } else { "N" })
-                .await?;
-        }
-        if let Some(v) = settings.grab_pointer {
-            Self::validate_value_name("GrabPointer")?;
-            self.set_string_value(key_path, "GrabPointer", if v { "Y" } else { "N" })
-                .await?;
-        }
-        if let Some(v) = settings.managed {
-            Self::validate_value_name("Managed")?;
-            self.set_string_value(key_path, "Managed", if v { "Y" } else { "N" })
-                .await?;
-        }
-        if let Some(v) = settings.use_xrandr {
-            Self::validate_value_name("UseXRandR")?;
-            self.set_string_value(key_path, "UseXRandR", if v { "Y" } else { "N" })
-                .await?;
-        }
-        if let Some(v) = settings.use_xvid_mode {
-            Self::validate_value_name("UseXVidMode")?;
-            self.set_string_value(key_path, "UseXVidMode", if v { "Y" } else { "N" })
-                .await?;
-        }
-        Ok(())
+        let values = Self::flag_values(
+            key_path,
+            &[
+                ("Decorated", settings.decorated),
+                ("ClientSideGraphics", settings.client_side_graphics),
+                ("ClientSideWithRender", settings.client_side_with_render),
+                (
+                    "ClientSideAntiAliasWithRender",
+                    settings.client_side_antialias_with_render,
+                ),
+                (
+                    "ClientSideAntiAliasWithCore",
+                    settings.client_side_antialias_with_core,
+                ),
+                ("GrabFullscreen", settings.grab_fullscreen),
+                ("GrabPointer", settings.grab_pointer),
+                ("Managed", settings.managed),
+                ("UseXRandR", settings.use_xrandr),
+                ("UseXVidMode", settings.use_xvid_mode),
+                ("UsePrimarySelection", settings.use_primary_selection),
+            ],
+        )?;
+        self.registry.set_values(values).await
     }
 
     async fn get_dpi_settings(&self) -> Result<Option<DpiSettings>> {
@@ -765,28 +1203,28 @@ impl RegEditor for RegistryEditor {
         }
         let mut settings = MacDriverSettings::new();
         if let Some(v) = allow_vertical_sync {
-            settings.allow_vertical_sync = Some(is_mac_option_true(&v));
+            settings.allow_vertical_sync = Some(is_option_true(&v));
         }
         if let Some(v) = capture_displays_for_fullscreen {
-            settings.capture_displays_for_fullscreen = Some(is_mac_option_true(&v));
+            settings.capture_displays_for_fullscreen = Some(is_option_true(&v));
         }
         if let Some(v) = use_precise_scrolling {
-            settings.use_precise_scrolling = Some(is_mac_option_true(&v));
+            settings.use_precise_scrolling = Some(is_option_true(&v));
         }
         if let Some(v) = retina_mode {
-            settings.retina_mode = Some(is_mac_option_true(&v));
+            settings.retina_mode = Some(is_option_true(&v));
         }
         if let Some(v) = left_option_is_alt {
-            settings.left_option_is_alt = Some(is_mac_option_true(&v));
+            settings.left_option_is_alt = Some(is_option_true(&v));
         }
         if let Some(v) = right_option_is_alt {
-            settings.right_option_is_alt = Some(is_mac_option_true(&v));
+            settings.right_option_is_alt = Some(is_option_true(&v));
         }
         if let Some(v) = left_command_is_ctrl {
-            settings.left_command_is_ctrl = Some(is_mac_option_true(&v));
+            settings.left_command_is_ctrl = Some(is_option_true(&v));
         }
         if let Some(v) = right_command_is_ctrl {
-            settings.right_command_is_ctrl = Some(is_mac_option_true(&v));
+            settings.right_command_is_ctrl = Some(is_option_true(&v));
         }
         if let Some(float_str) = windows_float_when_inactive {
             if let Some(float_mode) = WindowsFloatWhenInactive::from_string(&float_str) {
@@ -799,56 +1237,31 @@ impl RegEditor for RegistryEditor {
     async fn set_mac_driver_settings(&mut self, settings: &MacDriverSettings) -> Result<()> {
         let key_path = "Software\\Wine\\Mac Driver";
         Self::validate_key_path(key_path)?;
-        if let Some(v) = settings.allow_vertical_sync {
-            Self::validate_value_name("AllowVerticalSync")?;
-            self.set_string_value(key_path, "AllowVerticalSync", if v { "Y" } else { "N" })
-                .await?;
-        }
-        if let Some(v) = settings.capture_displays_for_fullscreen {
-            Self::validate_value_name("CaptureDisplaysForFullscreen")?;
-            self.set_string_value(
-                key_path,
-                "CaptureDisplaysForFullscreen",
-                if v { "Y" } else { "N" },
-            )
-            .await?;
-        }
-        if let Some(v) = settings.use_precise_scrolling {
-            Self::validate_value_name("UsePreciseScrolling")?;
-            self.set_string_value(key_path, "UsePreciseScrolling", if v { "Y" } else { "N" })
-                .await?;
-        }
-        if let Some(v) = settings.retina_mode {
-            Self::validate_value_name("RetinaMode")?;
-            self.set_string_value(key_path, "RetinaMode", if v { "Y" } else { "N" })
-                .await?;
-        }
+        let mut values = Self::flag_values(
+            key_path,
+            &[
+                ("AllowVerticalSync", settings.allow_vertical_sync),
+                (
+                    "CaptureDisplaysForFullscreen",
+                    settings.capture_displays_for_fullscreen,
+                ),
+                ("UsePreciseScrolling", settings.use_precise_scrolling),
+                ("RetinaMode", settings.retina_mode),
+                ("LeftOptionIsAlt", settings.left_option_is_alt),
+                ("RightOptionIsAlt", settings.right_option_is_alt),
+                ("LeftCommandIsCtrl", settings.left_command_is_ctrl),
+                ("RightCommandIsCtrl", settings.right_command_is_ctrl),
+            ],
+        )?;
         if let Some(float_mode) = &settings.windows_float_when_inactive {
             Self::validate_value_name("WindowsFloatWhenInactive")?;
-            self.set_string_value(key_path, "WindowsFloatWhenInactive", float_mode.to_string())
-                .await?;
-        }
-        if let Some(v) = settings.left_option_is_alt {
-            Self::validate_value_name("LeftOptionIsAlt")?;
-            self.set_string_value(key_path, "LeftOptionIsAlt", if v { "Y" } else { "N" })
-                .await?;
-        }
-        if let Some(v) = settings.right_option_is_alt {
-            Self::validate_value_name("RightOptionIsAlt")?;
-            self.set_string_value(key_path, "RightOptionIsAlt", if v { "Y" } else { "N" })
-                .await?;
-        }
-        if let Some(v) = settings.left_command_is_ctrl {
-            Self::validate_value_name("LeftCommandIsCtrl")?;
-            self.set_string_value(key_path, "LeftCommandIsCtrl", if v { "Y" } else { "N" })
-                .await?;
-        }
-        if let Some(v) = settings.right_command_is_ctrl {
-            Self::validate_value_name("RightCommandIsCtrl")?;
-            self.set_string_value(key_path, "RightCommandIsCtrl", if v { "Y" } else { "N" })
-                .await?;
+            values.push((
+                key_path.to_string(),
+                "WindowsFloatWhenInactive".to_string(),
+                Value::Sz(float_mode.to_string().to_string()),
+            ));
         }
-        Ok(())
+        self.registry.set_values(values).await
     }
 
     async fn validate_registry(&self) -> Result<Vec<ValidationError>> {
@@ -878,3 +1291,574 @@ impl RegEditor for RegistryEditor {
         self.registry.find_keys("").await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IMPORT_REG: &str = "Windows Registry Editor Version 5.00
+
+[HKEY_CURRENT_USER\\Software\\Wine]
+\"Version\"=\"win10\"
+\"Stale\"=-
+
+[HKEY_CURRENT_USER\\Software\\Wine\\Direct3D]
+\"renderer\"=\"vulkan\"
+\"bad*name\"=\"x\"
+
+[-HKEY_CURRENT_USER\\Software\\Wine\\Obsolete]
+
+[HKEY_LOCAL_MACHINE\\Software\\Wine]
+\"Machine\"=\"1\"
+
+[HKEY_CURRENT_USER\\System\\Other]
+\"Value\"=\"1\"
+";
+
+    /// A prefix whose `user.reg` holds `Software\Wine` (`Version`, `Keep`
+    /// and `Stale`), `Software\Wine\Direct3D` (`csmt`) and
+    /// `Software\Wine\Obsolete`.
+    async fn import_fixture() -> (tempfile::TempDir, RegistryEditor) {
+        let dir = tempfile::tempdir().unwrap();
+        let seed = WineRegistry::new();
+        for (key_path, name, value) in [
+            ("Software\\Wine", "Version", Value::Sz("win7".into())),
+            ("Software\\Wine", "Keep", Value::Sz("yes".into())),
+            ("Software\\Wine", "Stale", Value::Sz("old".into())),
+            ("Software\\Wine\\Direct3D", "csmt", Value::Dword(1)),
+            ("Software\\Wine\\Obsolete", "Old", Value::Dword(1)),
+        ] {
+            seed.set_value(key_path, name, value).await.unwrap();
+        }
+        let user_reg = dir.path().join("user.reg");
+        seed.save_to_file(&user_reg).await.unwrap();
+        std::fs::write(dir.path().join("import.reg"), IMPORT_REG).unwrap();
+        let editor = RegistryEditor::with_prefix(&dir.path().to_path_buf())
+            .await
+            .unwrap();
+        (dir, editor)
+    }
+
+    #[tokio::test]
+    async fn test_import_reg_file_merges_into_existing_keys() {
+        let (dir, mut editor) = import_fixture().await;
+        let report = editor
+            .import_reg_file(&dir.path().join("import.reg"))
+            .await
+            .unwrap();
+        assert_eq!(report.imported_keys, 3);
+        // Version, Stale and renderer
+        assert_eq!(report.imported_values, 3);
+
+        let wine = editor
+            .registry
+            .get_key_values("Software\\Wine")
+            .await
+            .unwrap();
+        assert_eq!(wine.get("Keep"), Some(&Value::Sz("yes".into())));
+        let d3d = editor
+            .registry
+            .get_key_values("Software\\Wine\\Direct3D")
+            .await
+            .unwrap();
+        assert_eq!(d3d.get("csmt"), Some(&Value::Dword(1)));
+        assert_eq!(
+            editor.pending_changes().await.unwrap(),
+            vec![
+                (
+                    "Software\\Wine".to_string(),
+                    "Stale".to_string(),
+                    Value::Delete
+                ),
+                (
+                    "Software\\Wine".to_string(),
+                    "Version".to_string(),
+                    Value::Sz("win10".into())
+                ),
+                (
+                    "Software\\Wine\\Direct3D".to_string(),
+                    "renderer".to_string(),
+                    Value::Sz("vulkan".into())
+                ),
+                (
+                    "Software\\Wine\\Obsolete".to_string(),
+                    "Old".to_string(),
+                    Value::Delete
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_reg_file_rejects_other_hives_and_invalid_names() {
+        let (dir, mut editor) = import_fixture().await;
+        let report = editor
+            .import_reg_file(&dir.path().join("import.reg"))
+            .await
+            .unwrap();
+        let mut rejected: Vec<(&str, Option<&str>)> = report
+            .rejected
+            .iter()
+            .map(|r| (r.key_path.as_str(), r.value_name.as_deref()))
+            .collect();
+        rejected.sort();
+        assert_eq!(
+            rejected,
+            [
+                (
+                    "HKEY_CURRENT_USER\\Software\\Wine\\Direct3D",
+                    Some("bad*name")
+                ),
+                ("HKEY_CURRENT_USER\\System\\Other", None),
+                ("HKEY_LOCAL_MACHINE\\Software\\Wine", None),
+            ]
+        );
+        assert_eq!(
+            editor
+                .registry
+                .get_value("Software\\Wine", "Machine")
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let bytes: Vec<u8> = (0..20).collect();
+        let text = RegistryEditor::format_binary(&bytes);
+        assert_eq!(text.lines().count(), 2);
+        assert!(text.starts_with("00 01 02"));
+        assert_eq!(RegistryEditor::parse_binary(&text).unwrap(), bytes);
+
+        assert_eq!(
+            RegistryEditor::parse_binary("hex:de,AD, be ef").unwrap(),
+            [0xde, 0xad, 0xbe, 0xef]
+        );
+        assert!(RegistryEditor::parse_binary("").unwrap().is_empty());
+        assert!(RegistryEditor::parse_binary("abc").is_err());
+        assert!(RegistryEditor::parse_binary("zz").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pending_changes_tracks_edits_since_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let user_reg = dir.path().join("user.reg");
+        let seed = WineRegistry::new();
+        seed.set_value("Software\\Wine", "Version", Value::Sz("win7".to_string()))
+            .await
+            .unwrap();
+        seed.set_value("Software\\Wine\\Direct3D", "csmt", Value::Dword(1))
+            .await
+            .unwrap();
+        seed.save_to_file(&user_reg).await.unwrap();
+
+        let prefix_path = dir.path().to_path_buf();
+        let mut editor = RegistryEditor::with_prefix(&prefix_path).await.unwrap();
+        assert!(editor.pending_changes().await.unwrap().is_empty());
+
+        editor.set_windows_version("win10").await.unwrap();
+        editor
+            .registry
+            .delete_value("Software\\Wine\\Direct3D", "csmt")
+            .await
+            .unwrap();
+        assert_eq!(
+            editor.pending_changes().await.unwrap(),
+            vec![
+                (
+                    "Software\\Wine".to_string(),
+                    "Version".to_string(),
+                    Value::Sz("win10".to_string())
+                ),
+                (
+                    "Software\\Wine\\Direct3D".to_string(),
+                    "csmt".to_string(),
+                    Value::Delete
+                ),
+            ]
+        );
+
+        editor.save_registry(&prefix_path).await.unwrap();
+        assert!(editor.pending_changes().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_string_edits_keep_the_existing_value_type() {
+        let mut editor = RegistryEditor::new();
+        let env = "Environment";
+        editor
+            .registry
+            .set_value(env, "PATH", Value::ExpandSz("%SystemRoot%".to_string()))
+            .await
+            .unwrap();
+        editor
+            .registry
+            .set_value(env, "Dirs", Value::MultiSz(vec!["C:\\a".to_string()]))
+            .await
+            .unwrap();
+
+        editor
+            .set_string_value(env, "PATH", "%SystemRoot%;C:\\tools")
+            .await
+            .unwrap();
+        editor
+            .set_string_value(env, "Dirs", "C:\\a\nC:\\b")
+            .await
+            .unwrap();
+        editor.set_string_value(env, "New", "plain").await.unwrap();
+
+        assert_eq!(
+            editor.registry.get_value(env, "PATH").await.unwrap(),
+            Some(Value::ExpandSz("%SystemRoot%;C:\\tools".to_string()))
+        );
+        assert_eq!(
+            editor.registry.get_value(env, "Dirs").await.unwrap(),
+            Some(Value::MultiSz(vec![
+                "C:\\a".to_string(),
+                "C:\\b".to_string()
+            ]))
+        );
+        assert_eq!(
+            editor.registry.get_value(env, "New").await.unwrap(),
+            Some(Value::Sz("plain".to_string()))
+        );
+
+        // An explicit choice overrides the stored type either way
+        editor
+            .set_string_value_typed(env, "New", "%TEMP%", true)
+            .await
+            .unwrap();
+        editor
+            .set_string_value_typed(env, "PATH", "C:\\tools", false)
+            .await
+            .unwrap();
+        assert_eq!(
+            editor.registry.get_value(env, "New").await.unwrap(),
+            Some(Value::ExpandSz("%TEMP%".to_string()))
+        );
+        assert_eq!(
+            editor.registry.get_value(env, "PATH").await.unwrap(),
+            Some(Value::Sz("C:\\tools".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_typed_getters_coerce_mismatched_value_types() {
+        let editor = RegistryEditor::new();
+        let d3d = "Software\\Wine\\Direct3D";
+        for (name, value) in [
+            ("csmt", Value::Sz(" 1 ".to_string())),
+            ("VideoMemorySize", Value::Sz("0x800".to_string())),
+            ("MaxShaderModelVS", Value::Qword(5)),
+            ("MaxShaderModelPS", Value::Binary(vec![4, 0, 0, 0])),
+            ("MaxShaderModelGS", Value::Sz("high".to_string())),
+            ("renderer", Value::Dword(2)),
+        ] {
+            editor.registry.set_value(d3d, name, value).await.unwrap();
+        }
+
+        for (name, expected) in [
+            ("csmt", Some(1)),
+            ("VideoMemorySize", Some(2048)),
+            ("MaxShaderModelVS", Some(5)),
+            ("MaxShaderModelPS", Some(4)),
+            ("MaxShaderModelGS", None),
+        ] {
+            let value = editor.get_dword_value(d3d, name).await.unwrap();
+            assert_eq!(value, expected, "{name}");
+        }
+        assert_eq!(
+            editor.get_string_value(d3d, "renderer").await.unwrap(),
+            Some("2".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_nt_version_info_follows_version() {
+        let system = WineRegistry::new();
+
+        RegistryEditor::set_nt_version_info(&system, "win10")
+            .await
+            .unwrap();
+        assert_eq!(
+            system
+                .get_value(NT_CURRENT_VERSION_KEY, "CurrentBuildNumber")
+                .await
+                .unwrap(),
+            Some(Value::Sz("19045".to_string()))
+        );
+        assert_eq!(
+            system
+                .get_value(NT_CURRENT_VERSION_KEY, "CurrentMajorVersionNumber")
+                .await
+                .unwrap(),
+            Some(Value::Dword(10))
+        );
+
+        RegistryEditor::set_nt_version_info(&system, "win7")
+            .await
+            .unwrap();
+        assert_eq!(
+            system
+                .get_value(NT_CURRENT_VERSION_KEY, "CurrentVersion")
+                .await
+                .unwrap(),
+            Some(Value::Sz("6.1".to_string()))
+        );
+        assert_eq!(
+            system
+                .get_value(NT_CURRENT_VERSION_KEY, "CSDVersion")
+                .await
+                .unwrap(),
+            Some(Value::Sz("Service Pack 1".to_string()))
+        );
+        assert_ne!(
+            system
+                .get_value(NT_CURRENT_VERSION_KEY, "CurrentMajorVersionNumber")
+                .await
+                .unwrap(),
+            Some(Value::Dword(10))
+        );
+
+        // 9x versions have no NT version info and leave the key alone
+        RegistryEditor::set_nt_version_info(&system, "win98")
+            .await
+            .unwrap();
+        assert_eq!(
+            system
+                .get_value(NT_CURRENT_VERSION_KEY, "CurrentBuildNumber")
+                .await
+                .unwrap(),
+            Some(Value::Sz("7601".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_direct_input_settings_and_joysticks() {
+        let mut editor = RegistryEditor::new();
+        assert_eq!(editor.get_direct_input_settings().await.unwrap(), None);
+
+        let settings = DirectInputSettings {
+            mouse_warp_override: Some(MouseWarpOverride::Force),
+            default_dead_zone: Some(500),
+        };
+        editor.set_direct_input_settings(&settings).await.unwrap();
+        assert_eq!(
+            editor
+                .registry
+                .get_value("Software\\Wine\\DirectInput", "DefaultDeadZone")
+                .await
+                .unwrap(),
+            Some(Value::Sz("500".to_string()))
+        );
+        assert_eq!(
+            editor.get_direct_input_settings().await.unwrap(),
+            Some(settings)
+        );
+
+        let out_of_range = DirectInputSettings {
+            default_dead_zone: Some(10001),
+            ..Default::default()
+        };
+        assert!(
+            editor
+                .set_direct_input_settings(&out_of_range)
+                .await
+                .is_err()
+        );
+
+        editor
+            .set_joystick_state("Xbox Wireless Controller", JoystickState::Disabled)
+            .await
+            .unwrap();
+        assert_eq!(
+            editor.get_joystick_overrides().await.unwrap(),
+            vec![JoystickOverride {
+                name: "Xbox Wireless Controller".to_string(),
+                state: JoystickState::Disabled,
+            }]
+        );
+        editor
+            .remove_joystick_override("Xbox Wireless Controller")
+            .await
+            .unwrap();
+        assert!(editor.get_joystick_overrides().await.unwrap().is_empty());
+    }
+
+    fn x11_settings_all(flag: bool) -> X11DriverSettings {
+        X11DriverSettings {
+            decorated: Some(flag),
+            client_side_graphics: Some(flag),
+            client_side_with_render: Some(flag),
+            client_side_antialias_with_render: Some(flag),
+            client_side_antialias_with_core: Some(flag),
+            grab_fullscreen: Some(flag),
+            grab_pointer: Some(flag),
+            managed: Some(flag),
+            use_xrandr: Some(flag),
+            use_xvid_mode: Some(flag),
+            use_primary_selection: Some(flag),
+        }
+    }
+
+    fn mac_settings_all(flag: bool) -> MacDriverSettings {
+        MacDriverSettings {
+            allow_vertical_sync: Some(flag),
+            capture_displays_for_fullscreen: Some(flag),
+            use_precise_scrolling: Some(flag),
+            retina_mode: Some(flag),
+            windows_float_when_inactive: Some(WindowsFloatWhenInactive::NonFullscreen),
+            left_option_is_alt: Some(flag),
+            right_option_is_alt: Some(flag),
+            left_command_is_ctrl: Some(flag),
+            right_command_is_ctrl: Some(flag),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_driver_flags_round_trip() {
+        for flag in [true, false] {
+            let mut editor = RegistryEditor::new();
+            let x11 = x11_settings_all(flag);
+            let mac = mac_settings_all(flag);
+            editor.set_x11_driver_settings(&x11).await.unwrap();
+            editor.set_mac_driver_settings(&mac).await.unwrap();
+
+            assert_eq!(editor.get_x11_driver_settings().await.unwrap(), Some(x11));
+            assert_eq!(editor.get_mac_driver_settings().await.unwrap(), Some(mac));
+
+            // Both drivers store flags the same way
+            let expected = Value::Sz(if flag { "Y" } else { "N" }.to_string());
+            for (key, name) in [
+                ("Software\\Wine\\X11 Driver", "Decorated"),
+                ("Software\\Wine\\X11 Driver", "GrabFullscreen"),
+                ("Software\\Wine\\X11 Driver", "UseXVidMode"),
+                ("Software\\Wine\\Mac Driver", "AllowVerticalSync"),
+                ("Software\\Wine\\Mac Driver", "RetinaMode"),
+            ] {
+                let stored = editor.registry.get_value(key, name).await.unwrap();
+                assert_eq!(stored.as_ref(), Some(&expected), "{name}");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_driver_flags_read_like_wine() {
+        let editor = RegistryEditor::new();
+        let x11 = "Software\\Wine\\X11 Driver";
+        let mac = "Software\\Wine\\Mac Driver";
+        // Values as winecfg, users or older tools may have written them
+        for (key, name, data) in [
+            (x11, "Decorated", "n"),
+            (x11, "GrabFullscreen", "y"),
+            (x11, "Managed", "true"),
+            (x11, "UseXRandR", "0"),
+            (x11, "UseXVidMode", "1"),
+            (x11, "GrabPointer", ""),
+            (mac, "AllowVerticalSync", "yes"),
+            (mac, "UsePreciseScrolling", "false"),
+            (mac, "LeftOptionIsAlt", "T"),
+        ] {
+            editor
+                .registry
+                .set_value(key, name, Value::Sz(data.to_string()))
+                .await
+                .unwrap();
+        }
+
+        let x11 = editor.get_x11_driver_settings().await.unwrap().unwrap();
+        assert_eq!(x11.decorated, Some(false));
+        assert_eq!(x11.grab_fullscreen, Some(true));
+        assert_eq!(x11.managed, Some(true));
+        assert_eq!(x11.use_xrandr, Some(false));
+        assert_eq!(x11.use_xvid_mode, Some(true));
+        assert_eq!(x11.grab_pointer, Some(false));
+        assert_eq!(x11.client_side_graphics, None);
+
+        let mac = editor.get_mac_driver_settings().await.unwrap().unwrap();
+        assert_eq!(mac.allow_vertical_sync, Some(true));
+        assert_eq!(mac.use_precise_scrolling, Some(false));
+        assert_eq!(mac.left_option_is_alt, Some(true));
+        assert_eq!(mac.retina_mode, None);
+    }
+
+    #[tokio::test]
+    async fn test_retina_mode_reads_either_case() {
+        let mut editor = RegistryEditor::new();
+        let mac = "Software\\Wine\\Mac Driver";
+        for (data, expected) in [("Y", true), ("y", true), ("N", false), ("n", false)] {
+            editor
+                .registry
+                .set_value(mac, "RetinaMode", Value::Sz(data.to_string()))
+                .await
+                .unwrap();
+            let settings = editor.get_mac_driver_settings().await.unwrap().unwrap();
+            assert_eq!(settings.retina_mode, Some(expected), "stored {:?}", data);
+        }
+
+        // Written back in one case, whatever was stored before
+        for (flag, stored) in [(true, "Y"), (false, "N")] {
+            let mut settings = MacDriverSettings::new();
+            settings.retina_mode = Some(flag);
+            editor.set_mac_driver_settings(&settings).await.unwrap();
+            assert_eq!(
+                editor.registry.get_value(mac, "RetinaMode").await.unwrap(),
+                Some(Value::Sz(stored.to_string()))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dpi_round_trip() {
+        let mut editor = RegistryEditor::new();
+        assert_eq!(editor.get_dpi_settings().await.unwrap(), None);
+
+        let settings = DpiSettings {
+            log_pixels: Some(144),
+        };
+        editor.set_dpi_settings(&settings).await.unwrap();
+        assert_eq!(
+            editor
+                .registry
+                .get_value("Control Panel\\Desktop", "LogPixels")
+                .await
+                .unwrap(),
+            Some(Value::Dword(144))
+        );
+        assert_eq!(editor.get_dpi_settings().await.unwrap(), Some(settings));
+
+        assert!(
+            editor
+                .set_dpi_settings(&DpiSettings {
+                    log_pixels: Some(72)
+                })
+                .await
+                .is_err()
+        );
+        assert_eq!(
+            editor.get_dpi_settings().await.unwrap().unwrap().log_pixels,
+            Some(144)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_x11_primary_selection_round_trip() {
+        let mut editor = RegistryEditor::new();
+        let mut settings = X11DriverSettings::new();
+        settings.use_primary_selection = Some(true);
+        editor.set_x11_driver_settings(&settings).await.unwrap();
+        assert_eq!(
+            editor
+                .registry
+                .get_value("Software\\Wine\\X11 Driver", "UsePrimarySelection")
+                .await
+                .unwrap(),
+            Some(Value::Sz("Y".to_string()))
+        );
+        assert_eq!(
+            editor.get_x11_driver_settings().await.unwrap(),
+            Some(settings)
+        );
+    }
+}
@@ -1,5 +1,29 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+/// Accepted `Direct3D\VideoMemorySize` values, in MB.
+pub const VIDEO_MEMORY_SIZE_RANGE: RangeInclusive<u32> = 1..=16384;
+
+/// Accepted `DirectInput\DefaultDeadZone` values, in ten-thousandths of
+/// the axis range.
+pub const JOYSTICK_DEAD_ZONE_RANGE: RangeInclusive<u32> = 0..=10000;
+
+/// `HKEY_LOCAL_MACHINE` key (in `system.reg`) where Windows NT reports its
+/// own version.
+pub const NT_CURRENT_VERSION_KEY: &str = "Software\\Microsoft\\Windows NT\\CurrentVersion";
+
+/// Version details an NT-family Windows reports under
+/// [`NT_CURRENT_VERSION_KEY`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NtVersionInfo {
+    pub major: u32,
+    pub minor: u32,
+    pub build: u32,
+    /// Service pack name (`CSDVersion`); empty when there is none.
+    pub csd_version: &'static str,
+}
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum WindowsVersion {
@@ -58,6 +82,33 @@ impl WindowsVersion {
             _ => None,
         }
     }
+
+    /// Version, build and service pack of the final release of this
+    /// version, as reported under [`NT_CURRENT_VERSION_KEY`]. `None` for
+    /// the Windows 9x/3.1 family, which has no such key.
+    pub fn nt_version_info(&self) -> Option<NtVersionInfo> {
+        let (major, minor, build, csd_version) = match self {
+            WindowsVersion::Win10 => (10, 0, 19045, ""),
+            WindowsVersion::Win81 => (6, 3, 9600, ""),
+            WindowsVersion::Win8 => (6, 2, 9200, ""),
+            WindowsVersion::Win7 => (6, 1, 7601, "Service Pack 1"),
+            WindowsVersion::Win2008 | WindowsVersion::Vista => (6, 0, 6002, "Service Pack 2"),
+            WindowsVersion::Win2003 => (5, 2, 3790, "Service Pack 2"),
+            WindowsVersion::WinXP => (5, 1, 2600, "Service Pack 3"),
+            WindowsVersion::Win2K => (5, 0, 2195, "Service Pack 4"),
+            WindowsVersion::NT40 => (4, 0, 1381, "Service Pack 6a"),
+            WindowsVersion::WinME
+            | WindowsVersion::Win98
+            | WindowsVersion::Win95
+            | WindowsVersion::Win31 => return None,
+        };
+        Some(NtVersionInfo {
+            major,
+            minor,
+            build,
+            csd_version,
+        })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -135,6 +186,46 @@ impl MouseWarpOverride {
     }
 }
 
+/// Joystick options shared by every device, under `Software\Wine\DirectInput`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DirectInputSettings {
+    pub mouse_warp_override: Option<MouseWarpOverride>,
+    /// Axis dead zone applied when a game doesn't set its own; see
+    /// [`JOYSTICK_DEAD_ZONE_RANGE`].
+    pub default_dead_zone: Option<u32>,
+}
+
+/// Whether DirectInput exposes a joystick, as listed under
+/// `Software\Wine\DirectInput\Joysticks` by device name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum JoystickState {
+    Enabled,
+    Disabled,
+}
+
+impl JoystickState {
+    pub fn to_string(&self) -> &'static str {
+        match self {
+            JoystickState::Enabled => "enabled",
+            JoystickState::Disabled => "disabled",
+        }
+    }
+
+    pub fn from_string(s: &str) -> Option<Self> {
+        match s {
+            "enabled" => Some(JoystickState::Enabled),
+            "disabled" => Some(JoystickState::Disabled),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JoystickOverride {
+    pub name: String,
+    pub state: JoystickState,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DesktopSettings {
     pub desktop: Option<String>,
@@ -189,6 +280,28 @@ pub struct DllOverride {
     pub setting: DllOverrideSetting,
 }
 
+/// ProgIDs created by Tequila for file associations are named
+/// `Tequila.<extension>` so they can be removed together with it.
+pub const TEQUILA_PROG_ID_PREFIX: &str = "Tequila.";
+
+/// A file type registered under `HKCU\Software\Classes`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileAssociation {
+    /// Extension with its leading dot, e.g. `.psd`.
+    pub extension: String,
+    pub prog_id: String,
+    /// The ProgID's `shell\open\command`, if it has one.
+    pub command: Option<String>,
+}
+
+impl FileAssociation {
+    /// Whether the association was created by Tequila rather than by an
+    /// installer or Wine itself.
+    pub fn is_managed(&self) -> bool {
+        self.prog_id.starts_with(TEQUILA_PROG_ID_PREFIX)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DllOverrideSetting {
     Native,
@@ -264,6 +377,7 @@ impl VirtualDesktopSettings {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AppSettings {
     pub name: String,
+    pub windows_version: Option<WindowsVersion>,
     pub dll_overrides: Vec<DllOverride>,
     pub d3d_renderer: Option<D3DRenderer>,
     pub offscreen_rendering_mode: Option<OffscreenRenderingMode>,
@@ -275,6 +389,7 @@ impl AppSettings {
     pub fn new(name: String) -> Self {
         Self {
             name,
+            windows_version: None,
             dll_overrides: Vec::new(),
             d3d_renderer: None,
             offscreen_rendering_mode: None,
@@ -301,6 +416,14 @@ impl ValidationError {
     }
 }
 
+/// Outcome of merging a `.reg` file into a registry.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RegImportReport {
+    pub imported_keys: usize,
+    pub imported_values: usize,
+    pub rejected: Vec<ValidationError>,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AudioDriver {
     Pulse,
@@ -331,6 +454,31 @@ impl AudioDriver {
             _ => None,
         }
     }
+
+    /// The driver matching the host's sound server, if one can be found.
+    /// PipeWire is served through its PulseAudio socket, so it maps to
+    /// `Pulse` as well.
+    pub fn detect_host() -> Option<Self> {
+        if cfg!(target_os = "macos") {
+            return Some(AudioDriver::CoreAudio);
+        }
+        if std::env::var_os("PULSE_SERVER").is_some() {
+            return Some(AudioDriver::Pulse);
+        }
+        if let Some(runtime_dir) = std::env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from)
+            && (runtime_dir.join("pulse/native").exists()
+                || runtime_dir.join("pipewire-0").exists())
+        {
+            return Some(AudioDriver::Pulse);
+        }
+        if Path::new("/proc/asound").exists() {
+            return Some(AudioDriver::ALSA);
+        }
+        if Path::new("/dev/dsp").exists() {
+            return Some(AudioDriver::OSS);
+        }
+        None
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -359,6 +507,13 @@ impl GraphicsDriver {
     }
 }
 
+/// Read a driver flag the way Wine's `IS_OPTION_TRUE` does: only the first
+/// character counts, so `"y"`, `"Yes"`, `"true"` and `"1"` are all on and
+/// anything else, including `"n"` and an empty string, is off.
+pub fn is_option_true(v: &str) -> bool {
+    matches!(v.chars().next(), Some('Y' | 'y' | 'T' | 't' | '1'))
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct X11DriverSettings {
     pub decorated: Option<bool>,
@@ -371,6 +526,9 @@ pub struct X11DriverSettings {
     pub managed: Option<bool>,
     pub use_xrandr: Option<bool>,
     pub use_xvid_mode: Option<bool>,
+    /// Sync the Windows clipboard with the X `PRIMARY` selection
+    /// (middle-click paste) instead of `CLIPBOARD`.
+    pub use_primary_selection: Option<bool>,
 }
 
 impl X11DriverSettings {
@@ -386,6 +544,7 @@ impl X11DriverSettings {
             managed: None,
             use_xrandr: None,
             use_xvid_mode: None,
+            use_primary_selection: None,
         }
     }
 }
@@ -1,12 +1,20 @@
 pub mod cache;
+pub mod copy;
+pub mod diff;
 pub mod editor;
 pub mod keys;
+pub mod queue;
 pub mod registry;
+pub mod snapshot;
 pub mod traits;
 
 pub use cache::hash_file;
+pub use copy::{SettingGroup, copy_settings};
+pub use diff::{DiffKind, RegistryDiffEntry};
 pub use editor::RegistryEditor;
 pub use keys::*;
+pub use queue::EditQueue;
 pub use regashii::Value;
-pub use registry::WineRegistry;
+pub use registry::{DEFAULT_MAX_CONCURRENT_READS, WineRegistry, set_max_concurrent_reads};
+pub use snapshot::RegistrySnapshot;
 pub use traits::RegEditor;
@@ -0,0 +1,120 @@
+//! Serialized execution of registry edits.
+
+use std::future::Future;
+use std::pin::Pin;
+use tokio::sync::{mpsc, oneshot};
+
+type Job = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Runs queued jobs one at a time, in the order they were pushed, on a
+/// single background task.
+///
+/// Edits to a shared [`RegistryEditor`](crate::RegistryEditor) are mostly
+/// get-modify-set on a whole key group (e.g. the X11 driver flags), so two
+/// of them running at once can lose one update. Pushing every edit through
+/// the same queue rules that out.
+///
+/// Must be created inside a Tokio runtime. The task exits once every clone
+/// of the queue has been dropped and the remaining jobs have run.
+#[derive(Debug, Clone)]
+pub struct EditQueue {
+    tx: mpsc::UnboundedSender<Job>,
+}
+
+impl EditQueue {
+    pub fn new() -> Self {
+        let (tx, mut rx) = mpsc::unbounded_channel::<Job>();
+        tokio::spawn(async move {
+            while let Some(job) = rx.recv().await {
+                job.await;
+            }
+        });
+        Self { tx }
+    }
+
+    /// Run `job` after everything pushed before it has finished.
+    pub fn push(&self, job: impl Future<Output = ()> + Send + 'static) {
+        if self.tx.send(Box::pin(job)).is_err() {
+            log::warn!("[registry] edit queue stopped; dropping edit");
+        }
+    }
+
+    /// Wait until every job pushed before this call has finished.
+    pub async fn flush(&self) {
+        let (done_tx, done_rx) = oneshot::channel();
+        self.push(async move {
+            let _ = done_tx.send(());
+        });
+        let _ = done_rx.await;
+    }
+}
+
+impl Default for EditQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RegEditor, RegistryEditor, WineRegistry, X11DriverSettings};
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_rapid_x11_toggles_keep_last_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let prefix_path = dir.path().to_path_buf();
+        WineRegistry::new()
+            .save_to_file(&prefix_path.join("user.reg"))
+            .await
+            .unwrap();
+        let editor = RegistryEditor::with_prefix(&prefix_path).await.unwrap();
+        let editor = Arc::new(Mutex::new(editor));
+        let queue = EditQueue::new();
+
+        // Alternate each flag many times, the way quick checkbox clicks
+        // arrive; the lock is released between the read and the write, as
+        // in the editor tab handlers.
+        for i in 0..40 {
+            let editor = Arc::clone(&editor);
+            queue.push(async move {
+                let current = editor.lock().await.get_x11_driver_settings().await;
+                let mut settings = current.unwrap().unwrap_or_else(X11DriverSettings::new);
+                tokio::task::yield_now().await;
+                match i % 3 {
+                    0 => settings.decorated = Some(i % 2 == 0),
+                    1 => settings.managed = Some(i % 2 == 0),
+                    _ => settings.use_primary_selection = Some(i % 2 == 0),
+                }
+                editor
+                    .lock()
+                    .await
+                    .set_x11_driver_settings(&settings)
+                    .await
+                    .unwrap();
+            });
+        }
+        {
+            let editor = Arc::clone(&editor);
+            let prefix_path = prefix_path.clone();
+            queue.push(async move {
+                editor
+                    .lock()
+                    .await
+                    .save_registry(&prefix_path)
+                    .await
+                    .unwrap();
+            });
+        }
+        queue.flush().await;
+
+        // Last writes: decorated at i = 39, managed at 37, primary at 38
+        let saved = RegistryEditor::with_prefix(&prefix_path).await.unwrap();
+        let settings = saved.get_x11_driver_settings().await.unwrap().unwrap();
+        assert_eq!(settings.decorated, Some(false));
+        assert_eq!(settings.managed, Some(false));
+        assert_eq!(settings.use_primary_selection, Some(true));
+    }
+}
@@ -1,9 +1,125 @@
 use base::error::{PrefixError, Result};
-use regashii::{Format, Key, Registry, Value, ValueName};
+use regashii::{Format, Key, KeyKind, Registry, Value, ValueName};
 use std::collections::HashMap;
-use std::path::PathBuf;
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+use tokio::sync::{RwLock, Semaphore};
+
+use crate::diff::{RegistryDiffEntry, diff_registries};
+
+/// Name a value the way the editor APIs do: `"(default)"` for the unnamed one.
+pub(crate) fn value_name_str(name: &ValueName) -> String {
+    match name {
+        ValueName::Default => "(default)".to_string(),
+        ValueName::Named(name) => name.clone(),
+    }
+}
+
+/// Inverse of [`value_name_str`]. An empty name is also the default value,
+/// which is how Wine itself names it.
+fn value_name_from_str(name: String) -> ValueName {
+    if name.is_empty() || name == "(default)" {
+        ValueName::Default
+    } else {
+        ValueName::Named(name)
+    }
+}
+
+/// How many times a `.reg` file is read before a failure is reported.
+const LOAD_ATTEMPTS: u32 = 4;
+/// Pause before the first retry; doubled after each further failure.
+const LOAD_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// Whether a failed read is worth repeating: the file was busy or cut short
+/// mid-write, as opposed to missing, unreadable or not parsing.
+fn is_transient(error: &regashii::error::Read) -> bool {
+    matches!(
+        error,
+        regashii::error::Read::Io(e) if matches!(
+            e.kind(),
+            io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted | io::ErrorKind::UnexpectedEof
+        )
+    )
+}
+
+/// Parse the `.reg` file at `path`, retrying transient IO errors with
+/// backoff. wineserver rewrites these files while a prefix is running, so a
+/// read right after launching an app can find them busy; that settles within
+/// a few hundred milliseconds. Any other error is returned at once. Blocking.
+fn deserialize_with_retry(path: &Path) -> std::result::Result<Registry, String> {
+    let mut delay = LOAD_RETRY_DELAY;
+    let mut attempt = 1;
+    loop {
+        match Registry::deserialize_file(path) {
+            Ok(registry) => return Ok(registry),
+            Err(e) if attempt < LOAD_ATTEMPTS && is_transient(&e) => {
+                log::debug!(
+                    "[registry] reading {} failed (attempt {}): {}; retrying",
+                    path.display(),
+                    attempt,
+                    e
+                );
+                std::thread::sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            }
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+}
+
+/// Default for [`set_max_concurrent_reads`].
+pub const DEFAULT_MAX_CONCURRENT_READS: usize = 8;
+
+/// Permits for parsing `.reg` files, shared by every load in the process.
+static READ_PERMITS: LazyLock<Mutex<Arc<Semaphore>>> =
+    LazyLock::new(|| Mutex::new(Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_READS))));
+
+/// Limit how many `.reg` files are parsed at once, across all prefixes.
+/// Each parse holds a blocking thread, so opening many prefixes together
+/// would otherwise queue one per file; on a spinning disk a low limit also
+/// avoids seeking between them. Reads already running finish under the old
+/// limit. Zero is treated as one.
+pub fn set_max_concurrent_reads(max: usize) {
+    *READ_PERMITS.lock().unwrap() = Arc::new(Semaphore::new(max.max(1)));
+}
+
+/// Run the blocking `read` once a read permit is free.
+async fn spawn_read<T, F>(read: F) -> std::result::Result<T, tokio::task::JoinError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let permits = Arc::clone(&READ_PERMITS.lock().unwrap());
+    let _permit = permits
+        .acquire_owned()
+        .await
+        .expect("read permits are never closed");
+    tokio::task::spawn_blocking(read).await
+}
+
+/// Write `values` into the key at `key_path`, keeping the values it already
+/// holds. `Registry::with` replaces a key wholesale, so the existing key is
+/// extended rather than rebuilt from scratch.
+fn merge_values(
+    registry: Registry,
+    key_path: String,
+    values: impl IntoIterator<Item = (ValueName, Value)>,
+) -> Registry {
+    let mut key = Key::new();
+    for (name, existing) in registry.keys() {
+        if name.raw() == key_path {
+            key = existing.clone();
+            break;
+        }
+    }
+    for (name, value) in values {
+        key = key.with(name, value);
+    }
+    registry.with(key_path, key)
+}
 
 #[derive(Debug, Clone)]
 pub struct WineRegistry {
@@ -22,8 +138,8 @@ impl WineRegistry {
 
     pub async fn load_from_file(path: &PathBuf) -> Result<Self> {
         let path_clone = path.clone();
-        tokio::task::spawn_blocking(move || {
-            let registry = Registry::deserialize_file(&path_clone).map_err(|e| {
+        spawn_read(move || {
+            let registry = deserialize_with_retry(&path_clone).map_err(|e| {
                 PrefixError::RegistryError(format!("Failed to load registry: {}", e))
             })?;
             Ok::<Self, PrefixError>(WineRegistry {
@@ -35,42 +151,33 @@ impl WineRegistry {
         .map_err(|e| PrefixError::RegistryError(format!("Task join error: {}", e)))?
     }
 
+    /// Parse `path` and discard the result, to check that it is a readable
+    /// registry file. Blocking; unlike the loaders this runs on the caller's
+    /// thread.
+    pub fn check_file(path: &Path) -> Result<()> {
+        Registry::deserialize_file(path).map(|_| ()).map_err(|e| {
+            PrefixError::RegistryError(format!("Failed to parse {}: {}", path.display(), e))
+        })
+    }
+
     pub async fn load_from_prefix(prefix_path: &PathBuf) -> Result<Self> {
         let system_reg_path = prefix_path.join("system.reg");
         let user_reg_path = prefix_path.join("user.reg");
         let userdef_reg_path = prefix_path.join("userdef.reg");
 
-        let (system_result, user_result, userdef_result) = tokio::join!(
-            tokio::task::spawn_blocking({
-                let path = system_reg_path.clone();
-                move || {
-                    if path.exists() {
-                        Registry::deserialize_file(&path).map(Some)
-                    } else {
-                        Ok(None)
-                    }
-                }
-            }),
-            tokio::task::spawn_blocking({
-                let path = user_reg_path.clone();
-                move || {
-                    if path.exists() {
-                        Registry::deserialize_file(&path).map(Some)
-                    } else {
-                        Ok(None)
-                    }
-                }
-            }),
-            tokio::task::spawn_blocking({
-                let path = userdef_reg_path.clone();
-                move || {
-                    if path.exists() {
-                        Registry::deserialize_file(&path).map(Some)
-                    } else {
-                        Ok(None)
-                    }
+        let read = |path: PathBuf| {
+            spawn_read(move || {
+                if path.exists() {
+                    deserialize_with_retry(&path).map(Some)
+                } else {
+                    Ok(None)
                 }
             })
+        };
+        let (system_result, user_result, userdef_result) = tokio::join!(
+            read(system_reg_path),
+            read(user_reg_path),
+            read(userdef_reg_path)
         );
 
         let mut merged_registry = Registry::new(Format::Regedit5);
@@ -82,7 +189,12 @@ impl WineRegistry {
                 merged_registry = system_registry;
             }
             Ok(None) => {}
-            Err(e) => log::warn!("[registry] failed to load system.reg: {}", e),
+            Err(e) => {
+                return Err(PrefixError::RegistryError(format!(
+                    "Failed to load system.reg: {}",
+                    e
+                )));
+            }
         }
 
         match userdef_result.map_err(|e| {
@@ -104,7 +216,12 @@ impl WineRegistry {
                 merged_registry = user_registry;
             }
             Ok(None) => {}
-            Err(e) => log::warn!("[registry] failed to load user.reg: {}", e),
+            Err(e) => {
+                return Err(PrefixError::RegistryError(format!(
+                    "Failed to load user.reg: {}",
+                    e
+                )));
+            }
         }
 
         Ok(WineRegistry {
@@ -136,10 +253,7 @@ impl WineRegistry {
             for (name, key) in reg.keys() {
                 if name.raw() == key_path {
                     for (val_name, value) in key.values() {
-                        let val_name_str = match val_name {
-                            ValueName::Default => "(default)".to_string(),
-                            ValueName::Named(name) => name.clone(),
-                        };
+                        let val_name_str = value_name_str(val_name);
                         if val_name_str == value_name {
                             return Ok(Some(value.clone()));
                         }
@@ -159,14 +273,64 @@ impl WineRegistry {
         let value_name = value_name.to_string();
         tokio::task::spawn_blocking(move || {
             let mut reg = registry.blocking_write();
-            let val_name = if value_name == "(default)" {
-                ValueName::Default
-            } else {
-                ValueName::Named(value_name)
-            };
-            let key = Key::new().with(val_name, value);
+            let val_name = value_name_from_str(value_name);
+            *reg = merge_values((*reg).clone(), key_path, [(val_name, value)]);
+            Ok::<(), PrefixError>(())
+        })
+        .await
+        .map_err(|e| PrefixError::RegistryError(format!("Task join error: {}", e)))?
+    }
+
+    /// Apply several `(key_path, value_name, value)` writes under one write
+    /// lock and a single clone of the registry. Pass `Value::Delete` to
+    /// remove a value. Writes are applied in order.
+    pub async fn set_values(&self, values: Vec<(String, String, Value)>) -> Result<()> {
+        if values.is_empty() {
+            return Ok(());
+        }
+        let registry = self.registry.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut reg = registry.blocking_write();
             let mut registry = (*reg).clone();
-            registry = registry.with(key_path.clone(), key);
+            for (key_path, value_name, value) in values {
+                registry = merge_values(
+                    registry,
+                    key_path,
+                    [(value_name_from_str(value_name), value)],
+                );
+            }
+            *reg = registry;
+            Ok::<(), PrefixError>(())
+        })
+        .await
+        .map_err(|e| PrefixError::RegistryError(format!("Task join error: {}", e)))?
+    }
+
+    /// Merge whole keys into the registry in one write, as `.reg` files
+    /// describe them: a deleted key (`[-KEY]`) is deleted, a replaced one
+    /// loses its other values, and any other key's values are merged into
+    /// what is there. `Value::Delete` values (`"name"=-`) delete the value.
+    pub(crate) async fn merge_keys(&self, keys: Vec<(String, Key)>) -> Result<()> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+        let registry = self.registry.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut reg = registry.blocking_write();
+            let mut registry = (*reg).clone();
+            for (key_path, key) in keys {
+                registry = match key.kind() {
+                    KeyKind::Delete => registry.with(key_path, Key::deleted()),
+                    KeyKind::Replace => registry.with(key_path, key),
+                    KeyKind::Add => {
+                        let values = key
+                            .values()
+                            .iter()
+                            .map(|(name, value)| (name.clone(), value.clone()));
+                        merge_values(registry, key_path, values)
+                    }
+                };
+            }
             *reg = registry;
             Ok::<(), PrefixError>(())
         })
@@ -180,15 +344,8 @@ impl WineRegistry {
         let value_name = value_name.to_string();
         tokio::task::spawn_blocking(move || {
             let mut reg = registry.blocking_write();
-            let val_name = if value_name == "(default)" {
-                ValueName::Default
-            } else {
-                ValueName::Named(value_name)
-            };
-            let key = Key::new().with(val_name, Value::Delete);
-            let mut registry = (*reg).clone();
-            registry = registry.with(key_path.clone(), key);
-            *reg = registry;
+            let val_name = value_name_from_str(value_name);
+            *reg = merge_values((*reg).clone(), key_path, [(val_name, Value::Delete)]);
             Ok::<(), PrefixError>(())
         })
         .await
@@ -228,6 +385,39 @@ impl WineRegistry {
         .map_err(|e| PrefixError::RegistryError(format!("Task join error: {}", e)))?
     }
 
+    /// Key paths that complete `partial` by one level, for suggesting paths
+    /// as they are typed. Registry keys are case-insensitive, so matching is
+    /// too. `"Software\\Wi"` suggests `"Software\\Wine"`, and
+    /// `"Software\\Wine\\"` the keys directly under it, sorted.
+    pub async fn complete_key_path(&self, partial: &str) -> Result<Vec<String>> {
+        let registry = self.registry.clone();
+        let partial = partial.to_string();
+        tokio::task::spawn_blocking(move || {
+            let reg = registry.blocking_read();
+            let needle = partial.to_lowercase();
+            let mut completions = Vec::new();
+            for name in reg.keys().keys() {
+                let key_path = name.raw();
+                if key_path.len() <= partial.len()
+                    || !key_path.is_char_boundary(partial.len())
+                    || key_path[..partial.len()].to_lowercase() != needle
+                {
+                    continue;
+                }
+                // Cut at the end of the component being typed
+                let end = key_path[partial.len()..]
+                    .find('\\')
+                    .map_or(key_path.len(), |i| partial.len() + i);
+                completions.push(key_path[..end].to_string());
+            }
+            completions.sort_by_key(|path| path.to_lowercase());
+            completions.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+            Ok::<Vec<String>, PrefixError>(completions)
+        })
+        .await
+        .map_err(|e| PrefixError::RegistryError(format!("Task join error: {}", e)))?
+    }
+
     pub async fn get_key_values(&self, key_path: &str) -> Result<HashMap<String, Value>> {
         let registry = self.registry.clone();
         let key_path = key_path.to_string();
@@ -237,10 +427,7 @@ impl WineRegistry {
             for (name, key) in reg.keys() {
                 if name.raw() == key_path {
                     for (val_name, value) in key.values() {
-                        let val_name_str = match val_name {
-                            ValueName::Default => "(default)".to_string(),
-                            ValueName::Named(name) => name.clone(),
-                        };
+                        let val_name_str = value_name_str(val_name);
                         values.insert(val_name_str, value.clone());
                     }
                     break;
@@ -268,6 +455,99 @@ impl WineRegistry {
         .map_err(|e| PrefixError::RegistryError(format!("Task join error: {}", e)))?
     }
 
+    /// Compare with `other`, treating `self` as the old side. Keys and value
+    /// names are matched exactly, as `find_keys` and `get_key_values` report
+    /// them; both registries are read in a single pass rather than per key.
+    pub async fn diff(&self, other: &WineRegistry) -> Result<Vec<RegistryDiffEntry>> {
+        let old = self.registry.clone();
+        let new = other.registry.clone();
+        tokio::task::spawn_blocking(move || {
+            let old = old.blocking_read();
+            let new = new.blocking_read();
+            Ok::<Vec<RegistryDiffEntry>, PrefixError>(diff_registries(&old, &new))
+        })
+        .await
+        .map_err(|e| PrefixError::RegistryError(format!("Task join error: {}", e)))?
+    }
+
+    /// Values that differ from `baseline`, as `(key_path, value_name, value)`
+    /// writes in the form [`set_values`](Self::set_values) takes: removed
+    /// values are reported as `Value::Delete`. Sorted by key, then value name.
+    pub async fn changes_since(
+        &self,
+        baseline: &WineRegistry,
+    ) -> Result<Vec<(String, String, Value)>> {
+        let changes = baseline
+            .diff(self)
+            .await?
+            .into_iter()
+            .filter_map(|entry| {
+                let value_name = entry.value?;
+                Some((entry.key, value_name, entry.new.unwrap_or(Value::Delete)))
+            })
+            .collect();
+        Ok(changes)
+    }
+
+    /// Replace the contents of this registry with a copy of `other`'s.
+    pub(crate) async fn copy_from(&self, other: &WineRegistry) {
+        let contents = other.registry.read().await.clone();
+        *self.registry.write().await = contents;
+    }
+
+    /// Serialize keys to the standard `Windows Registry Editor Version 5.00`
+    /// text format. Pass an empty slice to export every key.
+    ///
+    /// Key names are qualified with the hive this registry file belongs to
+    /// (`HKEY_LOCAL_MACHINE` for `system.reg`, `HKEY_CURRENT_USER` otherwise).
+    pub async fn export_reg(&self, keys: &[String]) -> Result<String> {
+        Self::export_regs(&[self], keys).await
+    }
+
+    /// Like [`export_reg`](Self::export_reg), for several registry files in
+    /// one export, e.g. `user.reg` and `system.reg` each under its own hive.
+    pub async fn export_regs(registries: &[&WineRegistry], keys: &[String]) -> Result<String> {
+        let sources: Vec<_> = registries
+            .iter()
+            .map(|r| (r.registry.clone(), r.hive_root()))
+            .collect();
+        let keys = keys.to_vec();
+        tokio::task::spawn_blocking(move || {
+            let mut exported = Registry::new(Format::Regedit5);
+            for (registry, hive) in sources {
+                let reg = registry.blocking_read();
+                for (name, key) in reg.keys() {
+                    let raw = name.raw();
+                    if !keys.is_empty() && !keys.iter().any(|k| k == raw) {
+                        continue;
+                    }
+                    let qualified = if raw.starts_with("HKEY_") {
+                        raw.to_string()
+                    } else {
+                        format!("{}\\{}", hive, raw)
+                    };
+                    exported = exported.with(qualified, key.clone());
+                }
+            }
+            Ok::<String, PrefixError>(exported.serialize())
+        })
+        .await
+        .map_err(|e| PrefixError::RegistryError(format!("Task join error: {}", e)))?
+    }
+
+    /// The root hive key names in this registry are relative to.
+    pub fn hive_root(&self) -> &'static str {
+        match self
+            .path
+            .as_ref()
+            .and_then(|p| p.file_name())
+            .and_then(|n| n.to_str())
+        {
+            Some("system.reg") => "HKEY_LOCAL_MACHINE",
+            _ => "HKEY_CURRENT_USER",
+        }
+    }
+
     pub fn path(&self) -> Option<&PathBuf> {
         self.path.as_ref()
     }
@@ -278,3 +558,217 @@ impl Default for WineRegistry {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_transient_read_errors_are_retried() {
+        let busy = regashii::error::Read::Io(io::ErrorKind::WouldBlock.into());
+        assert!(is_transient(&busy));
+
+        let dir = tempfile::tempdir().unwrap();
+        let missing = Registry::deserialize_file(dir.path().join("user.reg")).unwrap_err();
+        assert!(!is_transient(&missing));
+        assert!(!is_transient(&regashii::error::Read::UnsupportedEncoding));
+        assert!(deserialize_with_retry(&dir.path().join("user.reg")).is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_spawn_read_respects_limit() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        set_max_concurrent_reads(2);
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let reads = (0..6).map(|_| {
+            let running = Arc::clone(&running);
+            let peak = Arc::clone(&peak);
+            spawn_read(move || {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                std::thread::sleep(Duration::from_millis(20));
+                running.fetch_sub(1, Ordering::SeqCst);
+            })
+        });
+        for read in reads.map(tokio::spawn).collect::<Vec<_>>() {
+            read.await.unwrap().unwrap();
+        }
+        set_max_concurrent_reads(DEFAULT_MAX_CONCURRENT_READS);
+
+        // Other tests may hold a permit meanwhile, so only the cap is exact
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[tokio::test]
+    async fn test_set_value_preserves_sibling_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("user.reg");
+        let key_path = "Software\\Wine\\Direct3D";
+
+        let registry = WineRegistry::new();
+        registry
+            .set_value(key_path, "renderer", Value::Sz("vulkan".to_string()))
+            .await
+            .unwrap();
+        registry
+            .set_value(key_path, "csmt", Value::Dword(1))
+            .await
+            .unwrap();
+        registry
+            .set_values(vec![(
+                key_path.to_string(),
+                "VideoMemorySize".to_string(),
+                Value::Dword(4096),
+            )])
+            .await
+            .unwrap();
+        registry.save_to_file(&path).await.unwrap();
+
+        let reloaded = WineRegistry::load_from_file(&path).await.unwrap();
+        assert_eq!(
+            reloaded.get_value(key_path, "renderer").await.unwrap(),
+            Some(Value::Sz("vulkan".to_string()))
+        );
+        assert_eq!(
+            reloaded.get_value(key_path, "csmt").await.unwrap(),
+            Some(Value::Dword(1))
+        );
+        assert_eq!(
+            reloaded
+                .get_value(key_path, "VideoMemorySize")
+                .await
+                .unwrap(),
+            Some(Value::Dword(4096))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_reg_qualifies_and_filters_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = WineRegistry::new();
+        registry
+            .set_value("Software\\Wine", "Version", Value::Sz("win10".into()))
+            .await
+            .unwrap();
+        registry
+            .set_value("Software\\Other", "Flag", Value::Dword(1))
+            .await
+            .unwrap();
+
+        let exported = registry
+            .export_reg(&["Software\\Wine".to_string()])
+            .await
+            .unwrap();
+        assert!(exported.starts_with("Windows Registry Editor Version 5.00"));
+        assert!(exported.contains("[HKEY_CURRENT_USER\\Software\\Wine]"));
+        assert!(!exported.contains("Software\\Other"));
+
+        // The export imports back as the same values
+        let reg_file = dir.path().join("export.reg");
+        std::fs::write(&reg_file, &exported).unwrap();
+        let mut editor = crate::RegistryEditor::new();
+        let report = editor.import_reg_file(&reg_file).await.unwrap();
+        assert!(report.rejected.is_empty());
+        assert_eq!(
+            editor
+                .registry
+                .get_value("Software\\Wine", "Version")
+                .await
+                .unwrap(),
+            Some(Value::Sz("win10".into()))
+        );
+        assert_eq!(
+            editor
+                .registry
+                .get_value("Software\\Other", "Flag")
+                .await
+                .unwrap(),
+            None
+        );
+
+        // Keys of system.reg belong to HKEY_LOCAL_MACHINE
+        let system_reg = dir.path().join("system.reg");
+        registry.save_to_file(&system_reg).await.unwrap();
+        let system = WineRegistry::load_from_file(&system_reg).await.unwrap();
+        let exported = system.export_reg(&[]).await.unwrap();
+        assert!(exported.contains("[HKEY_LOCAL_MACHINE\\Software\\Wine]"));
+        assert!(exported.contains("[HKEY_LOCAL_MACHINE\\Software\\Other]"));
+
+        // Both files in one export, each under its own hive
+        let user = WineRegistry::new();
+        user.set_value(
+            "Control Panel\\Desktop",
+            "FontSmoothing",
+            Value::Sz("2".into()),
+        )
+        .await
+        .unwrap();
+        let exported = WineRegistry::export_regs(&[&user, &system], &[])
+            .await
+            .unwrap();
+        assert_eq!(exported.matches("Windows Registry Editor").count(), 1);
+        assert!(exported.contains("[HKEY_CURRENT_USER\\Control Panel\\Desktop]"));
+        assert!(exported.contains("[HKEY_LOCAL_MACHINE\\Software\\Wine]"));
+    }
+
+    #[tokio::test]
+    async fn test_complete_key_path() {
+        let registry = WineRegistry::new();
+        for key_path in [
+            "Software\\Wine\\Direct3D",
+            "Software\\Wine\\DllOverrides",
+            "Software\\Wine\\Drivers\\Audio",
+            "Software\\Microsoft",
+        ] {
+            registry
+                .set_value(key_path, "value", Value::Dword(1))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            registry.complete_key_path("software\\wi").await.unwrap(),
+            ["Software\\Wine"]
+        );
+        assert_eq!(
+            registry
+                .complete_key_path("Software\\Wine\\D")
+                .await
+                .unwrap(),
+            [
+                "Software\\Wine\\Direct3D",
+                "Software\\Wine\\DllOverrides",
+                "Software\\Wine\\Drivers",
+            ]
+        );
+        assert!(
+            registry
+                .complete_key_path("Software\\Valve")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_empty_value_name_is_default_value() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("user.reg");
+        let key_path = "Software\\Wine\\Drivers\\Audio";
+
+        let registry = WineRegistry::new();
+        registry
+            .set_value(key_path, "", Value::Sz(String::new()))
+            .await
+            .unwrap();
+        registry.save_to_file(&path).await.unwrap();
+
+        let reloaded = WineRegistry::load_from_file(&path).await.unwrap();
+        assert_eq!(
+            reloaded.get_value(key_path, "(default)").await.unwrap(),
+            Some(Value::Sz(String::new()))
+        );
+    }
+}
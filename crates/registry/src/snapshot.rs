@@ -0,0 +1,42 @@
+use base::error::Result;
+use std::path::{Path, PathBuf};
+
+/// In-memory copy of a prefix's `.reg` files, taken before a save so the
+/// save can be undone.
+#[derive(Debug, Clone)]
+pub struct RegistrySnapshot {
+    files: Vec<(PathBuf, Option<Vec<u8>>)>,
+}
+
+impl RegistrySnapshot {
+    /// Capture `user.reg` and `system.reg` of a prefix. Files that do not
+    /// exist yet are recorded as absent and removed again on restore.
+    pub fn capture(prefix_path: &Path) -> Result<Self> {
+        let mut files = Vec::new();
+        for name in ["user.reg", "system.reg"] {
+            let path = prefix_path.join(name);
+            let contents = if path.exists() {
+                Some(std::fs::read(&path)?)
+            } else {
+                None
+            };
+            files.push((path, contents));
+        }
+        Ok(Self { files })
+    }
+
+    /// Write the captured contents back to disk.
+    pub fn restore(&self) -> Result<()> {
+        for (path, contents) in &self.files {
+            match contents {
+                Some(bytes) => std::fs::write(path, bytes)?,
+                None => {
+                    if path.exists() {
+                        std::fs::remove_file(path)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
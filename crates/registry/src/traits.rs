@@ -17,6 +17,11 @@ pub trait RegEditor: Send + Sync {
     async fn set_offscreen_rendering_mode(&mut self, mode: &str) -> Result<()>;
     async fn get_mouse_warp_override(&self) -> Result<Option<String>>;
     async fn set_mouse_warp_override(&mut self, mode: &str) -> Result<()>;
+    async fn get_direct_input_settings(&self) -> Result<Option<DirectInputSettings>>;
+    async fn set_direct_input_settings(&mut self, settings: &DirectInputSettings) -> Result<()>;
+    async fn get_joystick_overrides(&self) -> Result<Vec<JoystickOverride>>;
+    async fn set_joystick_state(&mut self, name: &str, state: JoystickState) -> Result<()>;
+    async fn remove_joystick_override(&mut self, name: &str) -> Result<()>;
     async fn get_audio_driver(&self) -> Result<Option<String>>;
     async fn set_audio_driver(&mut self, driver: &str) -> Result<()>;
     async fn get_graphics_driver(&self) -> Result<Option<String>>;
@@ -29,6 +34,9 @@ pub trait RegEditor: Send + Sync {
     async fn get_dll_overrides(&self) -> Result<Vec<DllOverride>>;
     async fn add_dll_override(&mut self, dll: &str, setting: DllOverrideSetting) -> Result<()>;
     async fn remove_dll_override(&mut self, dll: &str) -> Result<()>;
+    async fn get_file_associations(&self) -> Result<Vec<FileAssociation>>;
+    async fn set_file_association(&mut self, extension: &str, command: &str) -> Result<()>;
+    async fn remove_file_association(&mut self, extension: &str) -> Result<()>;
     async fn get_video_memory_size(&self) -> Result<Option<u32>>;
     async fn set_video_memory_size(&mut self, size_mb: u32) -> Result<()>;
     async fn get_shader_model_settings(&self) -> Result<Option<ShaderModelSettings>>;
@@ -38,6 +46,11 @@ pub trait RegEditor: Send + Sync {
     async fn get_app_settings(&self, app_name: &str) -> Result<Option<AppSettings>>;
     async fn set_app_settings(&mut self, app_name: &str, settings: &AppSettings) -> Result<()>;
     async fn remove_app_settings(&mut self, app_name: &str) -> Result<()>;
+    async fn set_app_windows_version(
+        &mut self,
+        app_name: &str,
+        version: Option<&str>,
+    ) -> Result<()>;
     async fn get_x11_driver_settings(&self) -> Result<Option<X11DriverSettings>>;
     async fn set_x11_driver_settings(&mut self, settings: &X11DriverSettings) -> Result<()>;
     async fn get_dpi_settings(&self) -> Result<Option<DpiSettings>>;
@@ -1,8 +1,16 @@
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OptionalExtension, params};
+use sha2::{Digest, Sha256};
 use std::fmt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
 
+/// Extracted icons, stored once per distinct PNG.
+///
+/// `icons` is keyed by the SHA-256 of the PNG bytes, so executables sharing
+/// an icon (a launcher reused by many games, say) share one row and one file.
+/// `exe_icons` maps an executable path to its icon hash, and remembers the
+/// file's size and mtime so a changed exe is extracted again.
 pub struct IconCache {
     db: Mutex<Connection>,
     cache_dir: PathBuf,
@@ -16,6 +24,53 @@ impl fmt::Debug for IconCache {
     }
 }
 
+/// Size and modification time of an executable, used to notice that it has
+/// changed since its icon was cached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileStamp {
+    pub size: u64,
+    pub mtime: i64,
+}
+
+impl FileStamp {
+    pub fn of(path: &Path) -> Option<Self> {
+        let meta = std::fs::metadata(path).ok()?;
+        let mtime = meta
+            .modified()
+            .ok()?
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        Some(Self {
+            size: meta.len(),
+            mtime,
+        })
+    }
+}
+
+/// Delete the cached `.png`/`.ico` files in `cache_dir`, returning how many
+/// were removed.
+fn remove_icon_files(cache_dir: &Path) -> usize {
+    let mut removed = 0;
+    for entry in std::fs::read_dir(cache_dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        let ext = path.extension().and_then(|e| e.to_str());
+        if (ext == Some("ico") || ext == Some("png")) && std::fs::remove_file(path).is_ok() {
+            removed += 1;
+        }
+    }
+    removed
+}
+
+fn table_exists(db: &Connection, table: &str) -> bool {
+    db.query_row(
+        "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        params![table],
+        |_| Ok(()),
+    )
+    .is_ok()
+}
+
 impl IconCache {
     pub fn open(cache_dir: PathBuf) -> Result<Self, String> {
         std::fs::create_dir_all(&cache_dir)
@@ -29,20 +84,13 @@ impl IconCache {
             .and_then(|mut stmt| stmt.query_row([], |row| row.get::<_, Vec<u8>>(0)))
             .map(|data| data.get(0..4) == Some(b"\x00\x00\x01\x00"))
             .unwrap_or(false);
+        // Older caches keyed icons by the hash of the whole executable; those
+        // rows can't be mapped back to a path, so start over.
+        let keyed_by_exe = table_exists(&db, "icons") && !table_exists(&db, "exe_icons");
 
-        if needs_migration {
+        if needs_migration || keyed_by_exe {
             db.execute_batch("DROP TABLE IF EXISTS icons;").ok();
-            for entry in std::fs::read_dir(&cache_dir)
-                .into_iter()
-                .flatten()
-                .flatten()
-            {
-                let path = entry.path();
-                let ext = path.extension().and_then(|e| e.to_str());
-                if ext == Some("ico") || ext == Some("png") {
-                    std::fs::remove_file(path).ok();
-                }
-            }
+            remove_icon_files(&cache_dir);
         }
 
         db.execute_batch(
@@ -50,6 +98,12 @@ impl IconCache {
                 sha256 TEXT PRIMARY KEY,
                 icon_blob BLOB NOT NULL,
                 created_at INTEGER NOT NULL DEFAULT (unixepoch())
+            );
+            CREATE TABLE IF NOT EXISTS exe_icons (
+                exe_path TEXT PRIMARY KEY,
+                exe_size INTEGER NOT NULL,
+                exe_mtime INTEGER NOT NULL,
+                icon_sha256 TEXT
             );",
         )
         .map_err(|e| format!("Failed to create icons table: {}", e))?;
@@ -69,14 +123,17 @@ impl IconCache {
             .ok()
     }
 
-    pub fn put(&self, sha256: &str, png_data: &[u8]) -> Result<(), String> {
+    /// Store `png_data` under the hash of its contents and return that hash.
+    /// Storing an icon that is already cached is a no-op.
+    pub fn put(&self, png_data: &[u8]) -> Result<String, String> {
+        let sha256 = hex::encode(Sha256::digest(png_data));
         let db = self.db.lock().map_err(|e| format!("Lock error: {}", e))?;
         db.execute(
-            "INSERT OR REPLACE INTO icons (sha256, icon_blob) VALUES (?1, ?2)",
+            "INSERT OR IGNORE INTO icons (sha256, icon_blob) VALUES (?1, ?2)",
             params![sha256, png_data],
         )
         .map_err(|e| format!("Failed to store icon: {}", e))?;
-        Ok(())
+        Ok(sha256)
     }
 
     pub fn icon_path(&self, sha256: &str) -> Option<PathBuf> {
@@ -96,8 +153,132 @@ impl IconCache {
         Some(png_path)
     }
 
-    pub fn has_icon(&self, sha256: &str) -> Option<bool> {
-        let data = self.get(sha256)?;
-        Some(!data.is_empty())
+    /// Cached icon hash for `exe_path`: `Some(Some(hash))` for an icon,
+    /// `Some(None)` when the exe is known to have none, and `None` when it
+    /// hasn't been seen or has changed since (per `stamp`).
+    pub fn exe_icon(&self, exe_path: &Path, stamp: FileStamp) -> Option<Option<String>> {
+        let db = self.db.lock().ok()?;
+        let (size, mtime, sha256) = db
+            .query_row(
+                "SELECT exe_size, exe_mtime, icon_sha256 FROM exe_icons WHERE exe_path = ?1",
+                params![exe_path.to_string_lossy()],
+                |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, i64>(1)?,
+                        row.get::<_, Option<String>>(2)?,
+                    ))
+                },
+            )
+            .optional()
+            .ok()??;
+        (size as u64 == stamp.size && mtime == stamp.mtime).then_some(sha256)
+    }
+
+    /// Record the icon extracted from `exe_path` (or `None` if it has none)
+    /// and return the icon's hash.
+    pub fn put_exe_icon(
+        &self,
+        exe_path: &Path,
+        stamp: FileStamp,
+        png_data: Option<&[u8]>,
+    ) -> Result<Option<String>, String> {
+        let sha256 = png_data.map(|data| self.put(data)).transpose()?;
+        let db = self.db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        db.execute(
+            "INSERT OR REPLACE INTO exe_icons (exe_path, exe_size, exe_mtime, icon_sha256)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                exe_path.to_string_lossy(),
+                stamp.size as i64,
+                stamp.mtime,
+                sha256
+            ],
+        )
+        .map_err(|e| format!("Failed to store icon mapping: {}", e))?;
+        Ok(sha256)
+    }
+
+    /// Number of distinct icons and their total size in bytes.
+    pub fn usage(&self) -> (usize, u64) {
+        let Ok(db) = self.db.lock() else {
+            return (0, 0);
+        };
+        db.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(LENGTH(icon_blob)), 0) FROM icons",
+            [],
+            |row| Ok((row.get::<_, i64>(0)? as usize, row.get::<_, i64>(1)? as u64)),
+        )
+        .unwrap_or((0, 0))
+    }
+
+    /// Drop every cached icon and mapping, and delete the icon files on disk.
+    /// Icons are extracted again the next time they're needed.
+    pub fn clear(&self) -> Result<(), String> {
+        let db = self.db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        db.execute_batch("DELETE FROM exe_icons; DELETE FROM icons;")
+            .map_err(|e| format!("Failed to clear icon cache: {}", e))?;
+        let removed = remove_icon_files(&self.cache_dir);
+        log::info!("[icons] cleared icon cache ({} files removed)", removed);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STAMP: FileStamp = FileStamp {
+        size: 10,
+        mtime: 1_700_000_000,
+    };
+
+    #[test]
+    fn test_identical_icons_share_one_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = IconCache::open(dir.path().to_path_buf()).unwrap();
+
+        let a = cache
+            .put_exe_icon(Path::new("/games/a.exe"), STAMP, Some(b"png".as_slice()))
+            .unwrap();
+        let b = cache
+            .put_exe_icon(Path::new("/games/b.exe"), STAMP, Some(b"png".as_slice()))
+            .unwrap();
+
+        assert_eq!(a, b);
+        assert_eq!(cache.usage(), (1, 3));
+        assert_eq!(cache.exe_icon(Path::new("/games/b.exe"), STAMP), Some(a));
+    }
+
+    #[test]
+    fn test_exe_icon_misses_when_exe_changed() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = IconCache::open(dir.path().to_path_buf()).unwrap();
+        let exe = Path::new("/games/a.exe");
+
+        cache.put_exe_icon(exe, STAMP, None).unwrap();
+        assert_eq!(cache.exe_icon(exe, STAMP), Some(None));
+
+        let changed = FileStamp { size: 11, ..STAMP };
+        assert_eq!(cache.exe_icon(exe, changed), None);
+    }
+
+    #[test]
+    fn test_clear_removes_icons_and_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = IconCache::open(dir.path().to_path_buf()).unwrap();
+        let exe = Path::new("/games/a.exe");
+
+        let sha256 = cache
+            .put_exe_icon(exe, STAMP, Some(b"png".as_slice()))
+            .unwrap()
+            .unwrap();
+        let file = cache.icon_path(&sha256).unwrap();
+        assert!(file.exists());
+
+        cache.clear().unwrap();
+        assert!(!file.exists());
+        assert_eq!(cache.usage(), (0, 0));
+        assert_eq!(cache.exe_icon(exe, STAMP), None);
     }
 }
@@ -1,28 +1,42 @@
 pub mod icon_cache;
 pub mod icon_extract;
 
-pub use icon_cache::IconCache;
+pub use icon_cache::{FileStamp, IconCache};
 
-use base::config::RegisteredExecutable;
+use base::config::{RegisteredExecutable, SCRIPT_EXTENSIONS};
 use base::error::{PrefixError, Result};
 use base::traits::Scanner;
-use exe::VecPE;
 use exe::types::VSVersionInfo;
-use sha2::{Digest, Sha256};
+use exe::{Arch, PE, VecPE};
+use std::collections::HashMap;
 use std::fs;
 use std::io::Read;
 use std::io::Seek;
 use std::panic::{AssertUnwindSafe, catch_unwind};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
 use walkdir::WalkDir;
 
+/// File extensions scanned for unless configured otherwise.
+pub const DEFAULT_EXECUTABLE_EXTENSIONS: &[&str] = &["exe"];
+
 pub struct ApplicationScanner {
     app_dirs: Vec<&'static str>,
-    executable_extensions: Vec<&'static str>,
+    /// Lowercase, without the leading dot. Shared between clones like
+    /// `include_scripts`.
+    executable_extensions: Arc<RwLock<Vec<String>>>,
+    /// Also pick up `.bat`/`.cmd` launcher scripts. Shared between clones so
+    /// a settings change reaches scans already handed a copy.
+    include_scripts: Arc<AtomicBool>,
     icon_cache: Arc<IconCache>,
 }
 
+/// Results of an earlier scan keyed by executable path, each with the stamp
+/// of the file it was read from. See
+/// [`ApplicationScanner::scan_prefix_incremental`].
+pub type ScanCache = HashMap<PathBuf, (FileStamp, RegisteredExecutable)>;
+
 #[derive(Debug, Default)]
 pub struct ExecutableMetadata {
     pub file_version: Option<String>,
@@ -31,6 +45,8 @@ pub struct ExecutableMetadata {
     pub file_description: Option<String>,
     pub product_name: Option<String>,
     pub imported_modules: Vec<String>,
+    /// `"x86"` or `"x64"`, from the PE optional header.
+    pub architecture: Option<String>,
 }
 
 impl ApplicationScanner {
@@ -44,7 +60,13 @@ impl ApplicationScanner {
                 "drive_c/ProgramData/Desktop",
                 "drive_c/windows",
             ],
-            executable_extensions: vec!["exe"],
+            executable_extensions: Arc::new(RwLock::new(
+                DEFAULT_EXECUTABLE_EXTENSIONS
+                    .iter()
+                    .map(|e| e.to_string())
+                    .collect(),
+            )),
+            include_scripts: Arc::new(AtomicBool::new(false)),
             icon_cache,
         }
     }
@@ -53,22 +75,78 @@ impl ApplicationScanner {
         &self.icon_cache
     }
 
+    /// Whether scans also register `.bat`/`.cmd` scripts.
+    pub fn set_include_scripts(&self, include: bool) {
+        self.include_scripts.store(include, Ordering::Relaxed);
+    }
+
+    /// Replace the file extensions scans pick up as executables, e.g.
+    /// `["exe", "msi"]`. Case and a leading dot are ignored. Scripts are
+    /// controlled separately by [`set_include_scripts`](Self::set_include_scripts).
+    pub fn set_executable_extensions<I, S>(&self, extensions: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut normalized: Vec<String> = Vec::new();
+        for ext in extensions {
+            let ext = ext.as_ref().trim().trim_start_matches('.').to_lowercase();
+            if !ext.is_empty() && !normalized.contains(&ext) {
+                normalized.push(ext);
+            }
+        }
+        *self.executable_extensions.write().unwrap() = normalized;
+    }
+
+    /// The file extensions currently scanned for, lowercase.
+    pub fn executable_extensions(&self) -> Vec<String> {
+        self.executable_extensions.read().unwrap().clone()
+    }
+
+    fn is_executable_extension(&self, ext: &str) -> bool {
+        self.executable_extensions
+            .read()
+            .unwrap()
+            .iter()
+            .any(|e| e.eq_ignore_ascii_case(ext))
+    }
+
+    fn is_scannable_extension(&self, ext: &str) -> bool {
+        self.is_executable_extension(ext)
+            || (self.include_scripts.load(Ordering::Relaxed)
+                && SCRIPT_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+    }
+
     pub fn scan_prefix(&self, prefix_path: &PathBuf) -> Result<Vec<RegisteredExecutable>> {
+        self.scan_prefix_incremental(prefix_path, &ScanCache::new())
+    }
+
+    /// Scan like [`scan_prefix`](Self::scan_prefix), but take executables
+    /// whose size and mtime still match their `cache` entry from the cache
+    /// instead of parsing them again. Only new or changed files are read.
+    pub fn scan_prefix_incremental(
+        &self,
+        prefix_path: &PathBuf,
+        cache: &ScanCache,
+    ) -> Result<Vec<RegisteredExecutable>> {
         let mut executables = Vec::new();
         for app_dir in &self.app_dirs {
             let full_path = prefix_path.join(app_dir);
             if full_path.exists() && full_path.is_dir() {
-                if let Ok(mut dir_executables) = self.scan_directory(&full_path) {
+                if let Ok(mut dir_executables) = self.scan_directory(&full_path, cache) {
                     executables.append(&mut dir_executables);
                 }
             }
         }
-        executables.sort_by(|a, b| a.name.cmp(&b.name));
-        executables.dedup_by(|a, b| a.name == b.name && a.executable_path == b.executable_path);
+        sort_and_dedup(&mut executables);
         Ok(executables)
     }
 
-    fn scan_directory(&self, dir_path: &PathBuf) -> Result<Vec<RegisteredExecutable>> {
+    fn scan_directory(
+        &self,
+        dir_path: &PathBuf,
+        cache: &ScanCache,
+    ) -> Result<Vec<RegisteredExecutable>> {
         let executables: Vec<RegisteredExecutable> = WalkDir::new(dir_path)
             .max_depth(10)
             .into_iter()
@@ -79,14 +157,15 @@ impl ApplicationScanner {
                     && path
                         .extension()
                         .and_then(|e| e.to_str())
-                        .map(|ext| {
-                            self.executable_extensions
-                                .contains(&ext.to_lowercase().as_str())
-                        })
-                        .unwrap_or(false)
+                        .is_some_and(|ext| self.is_scannable_extension(ext))
             })
             .filter_map(|entry| {
                 let path = entry.path().to_path_buf();
+                if let Some((stamp, cached)) = cache.get(&path)
+                    && FileStamp::of(&path) == Some(*stamp)
+                {
+                    return Some(cached.clone());
+                }
                 match self.create_executable_from_path(&path) {
                     Ok(Some(executable)) => Some(executable),
                     Ok(None) => None,
@@ -159,6 +238,9 @@ impl ApplicationScanner {
             if !meta.imported_modules.is_empty() {
                 executable = executable.with_imported_modules(meta.imported_modules);
             }
+            if let Some(architecture) = meta.architecture {
+                executable = executable.with_architecture(architecture);
+            }
         }
 
         Ok(Some(executable))
@@ -171,7 +253,7 @@ impl ApplicationScanner {
             "windows/servicing",
             "windows/inf",
             "windows/driverstore",
-            "windows/winSxS",
+            "windows/winsxs",
             "windows/microsoft.net",
             "windows/assembly",
             "program files/common files",
@@ -249,7 +331,10 @@ impl ApplicationScanner {
     }
 
     fn extract_executable_metadata(&self, image: &VecPE) -> Option<ExecutableMetadata> {
-        let mut metadata = ExecutableMetadata::default();
+        let mut metadata = ExecutableMetadata {
+            architecture: pe_architecture(image),
+            ..Default::default()
+        };
         self.extract_version_info(image, &mut metadata);
         // NOTE: ImportDirectory::parse is intentionally omitted here because
         // exe 0.5.7 uses pkbuffer's get_slice_ref internally, which triggers an
@@ -338,9 +423,7 @@ impl ApplicationScanner {
                     .and_then(|e| e.to_str())
                     .map(|ext| match ext.to_lowercase().as_str() {
                         "lnk" | "desktop" => false,
-                        _ => self
-                            .executable_extensions
-                            .contains(&ext.to_lowercase().as_str()),
+                        _ => self.is_executable_extension(ext),
                     })
                     .unwrap_or(false)
             })
@@ -364,7 +447,8 @@ impl Clone for ApplicationScanner {
     fn clone(&self) -> Self {
         Self {
             app_dirs: self.app_dirs.clone(),
-            executable_extensions: self.executable_extensions.clone(),
+            executable_extensions: Arc::clone(&self.executable_extensions),
+            include_scripts: Arc::clone(&self.include_scripts),
             icon_cache: Arc::clone(&self.icon_cache),
         }
     }
@@ -387,6 +471,23 @@ impl ApplicationScanner {
             })?
     }
 
+    pub async fn scan_prefix_incremental_async(
+        &self,
+        prefix_path: &Path,
+        cache: ScanCache,
+    ) -> Result<Vec<RegisteredExecutable>> {
+        let prefix_path = prefix_path.to_path_buf();
+        let scanner = self.clone();
+        tokio::task::spawn_blocking(move || scanner.scan_prefix_incremental(&prefix_path, &cache))
+            .await
+            .map_err(|e| {
+                PrefixError::Io(std::io::Error::other(format!(
+                    "Failed to spawn scanning task: {}",
+                    e
+                )))
+            })?
+    }
+
     pub async fn scan_for_desktop_files_async(
         &self,
         prefix_path: &PathBuf,
@@ -404,6 +505,26 @@ impl ApplicationScanner {
     }
 }
 
+/// Sort scan results by name and drop entries that share both name and path,
+/// e.g. an executable reachable from two of the scanned directories. Sorting
+/// on the path as well keeps such duplicates adjacent for `dedup_by`.
+fn sort_and_dedup(executables: &mut Vec<RegisteredExecutable>) {
+    executables.sort_by(|a, b| {
+        a.name
+            .cmp(&b.name)
+            .then_with(|| a.executable_path.cmp(&b.executable_path))
+    });
+    executables.dedup_by(|a, b| a.name == b.name && a.executable_path == b.executable_path);
+}
+
+/// `"x86"` or `"x64"` from the image's machine type.
+fn pe_architecture(image: &VecPE) -> Option<String> {
+    match image.get_arch().ok()? {
+        Arch::X86 => Some("x86".to_string()),
+        Arch::X64 => Some("x64".to_string()),
+    }
+}
+
 /// Validate that a file has a proper PE header structure.
 ///
 /// This reads the DOS header, checks the `MZ` magic, resolves `e_lfanew`,
@@ -465,24 +586,16 @@ pub fn extract_icon_for_exe(exe_path: &Path, icon_cache: &IconCache) -> Option<P
     if !is_valid_pe_file(exe_path) {
         return None;
     }
-    let file_bytes = std::fs::read(exe_path).ok()?;
-    let sha256 = hex::encode(Sha256::digest(&file_bytes));
-    match icon_cache.has_icon(&sha256) {
-        Some(true) => return icon_cache.icon_path(&sha256),
-        Some(false) => return None,
-        None => {}
+    let stamp = FileStamp::of(exe_path)?;
+    if let Some(cached) = icon_cache.exe_icon(exe_path, stamp) {
+        return icon_cache.icon_path(&cached?);
     }
     let image = VecPE::from_disk_file(exe_path).ok()?;
-    match icon_extract::extract_icon(&image) {
-        Some(icon_data) => {
-            let _ = icon_cache.put(&sha256, &icon_data);
-            icon_cache.icon_path(&sha256)
-        }
-        None => {
-            let _ = icon_cache.put(&sha256, &[]);
-            None
-        }
-    }
+    let icon_data = icon_extract::extract_icon(&image);
+    let sha256 = icon_cache
+        .put_exe_icon(exe_path, stamp, icon_data.as_deref())
+        .ok()??;
+    icon_cache.icon_path(&sha256)
 }
 
 pub fn extract_metadata_for_exe(exe_path: &Path) -> ExecutableMetadata {
@@ -491,7 +604,10 @@ pub fn extract_metadata_for_exe(exe_path: &Path) -> ExecutableMetadata {
     }
     catch_unwind(AssertUnwindSafe(|| {
         let image = VecPE::from_disk_file(exe_path).ok()?;
-        let mut meta = ExecutableMetadata::default();
+        let mut meta = ExecutableMetadata {
+            architecture: pe_architecture(&image),
+            ..Default::default()
+        };
         if let Ok(version_info) = VSVersionInfo::parse(&image) {
             if let Some(fixed) = version_info.value {
                 meta.file_version = Some(format!(
@@ -535,3 +651,202 @@ pub fn extract_metadata_for_exe(exe_path: &Path) -> ExecutableMetadata {
     .unwrap_or_else(|_| None)
     .unwrap_or_default()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn scanner(dir: &TempDir) -> ApplicationScanner {
+        let icon_cache = IconCache::open(dir.path().join("icons")).expect("open icon cache");
+        ApplicationScanner::new(Arc::new(icon_cache))
+    }
+
+    /// Write a stub executable at `rel` under `root`. The contents are not a
+    /// valid PE image, so the scanner skips metadata extraction for it.
+    fn touch_exe(root: &Path, rel: &str) -> PathBuf {
+        let path = root.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, b"MZ").unwrap();
+        path
+    }
+
+    #[test]
+    fn test_should_skip_system_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let scanner = scanner(&dir);
+        for path in [
+            "/p/drive_c/windows/system32/notepad.exe",
+            "/p/drive_c/windows/SysWOW64/cmd.exe",
+            "/p/drive_c/windows/WinSxS/x86_foo/bar.exe",
+            "/p/drive_c/windows/Microsoft.NET/Framework/csc.exe",
+            "/p/drive_c/Program Files/Common Files/Shared/tool.exe",
+            "/p/drive_c/Program Files (x86)/Common Files/Shared/tool.exe",
+            "/p/drive_c/ProgramData/Microsoft/Windows/helper.exe",
+            "/p/drive_c/users/Public/Desktop/app.exe",
+        ] {
+            assert!(scanner.should_skip_executable(path), "{path} not skipped");
+        }
+    }
+
+    #[test]
+    fn test_should_skip_installer_executables() {
+        let dir = tempfile::tempdir().unwrap();
+        let scanner = scanner(&dir);
+        for path in [
+            "/p/drive_c/Program Files/App/unins000.exe",
+            "/p/drive_c/Program Files/App/Uninstall.exe",
+            "/p/drive_c/Program Files/App/setup.exe",
+            "/p/drive_c/Program Files/App/Updater.exe",
+            "/p/drive_c/windows/msiexec.exe",
+        ] {
+            assert!(scanner.should_skip_executable(path), "{path} not skipped");
+        }
+    }
+
+    #[test]
+    fn test_should_keep_application_executables() {
+        let dir = tempfile::tempdir().unwrap();
+        let scanner = scanner(&dir);
+        for path in [
+            "/p/drive_c/Program Files/Editor/editor.exe",
+            "/p/drive_c/Program Files (x86)/Game/game.exe",
+            "/p/drive_c/users/alice/AppData/Local/Tool/tool.exe",
+            "/p/drive_c/windows/notepad.exe",
+        ] {
+            assert!(!scanner.should_skip_executable(path), "{path} skipped");
+        }
+    }
+
+    #[test]
+    fn test_scan_prefix_skips_system_and_sorts() {
+        let dir = tempfile::tempdir().unwrap();
+        let scanner = scanner(&dir);
+        let prefix = dir.path().join("prefix");
+        touch_exe(&prefix, "drive_c/Program Files/Zeta/zeta.exe");
+        touch_exe(&prefix, "drive_c/Program Files (x86)/Alpha/alpha.exe");
+        touch_exe(&prefix, "drive_c/Program Files/Alpha/unins000.exe");
+        touch_exe(
+            &prefix,
+            "drive_c/Program Files/Common Files/Shared/shared.exe",
+        );
+        touch_exe(&prefix, "drive_c/windows/system32/notepad.exe");
+        touch_exe(&prefix, "drive_c/Program Files/Zeta/readme.txt");
+
+        let found = scanner.scan_prefix(&prefix).unwrap();
+        let names: Vec<&str> = found.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["alpha", "zeta"]);
+    }
+
+    #[test]
+    fn test_scan_prefix_includes_scripts_when_enabled() {
+        let dir = tempfile::tempdir().unwrap();
+        let scanner = scanner(&dir);
+        let prefix = dir.path().join("prefix");
+        touch_exe(&prefix, "drive_c/Program Files/Game/game.exe");
+        touch_exe(&prefix, "drive_c/Program Files/Game/launch.BAT");
+        touch_exe(&prefix, "drive_c/Program Files/Game/tools.cmd");
+
+        let names = |found: Vec<RegisteredExecutable>| -> Vec<String> {
+            found.into_iter().map(|e| e.name).collect()
+        };
+        assert_eq!(names(scanner.scan_prefix(&prefix).unwrap()), ["game"]);
+
+        scanner.set_include_scripts(true);
+        assert_eq!(
+            names(scanner.clone().scan_prefix(&prefix).unwrap()),
+            ["game", "launch", "tools"]
+        );
+    }
+
+    #[test]
+    fn test_scan_prefix_uses_configured_extensions() {
+        let dir = tempfile::tempdir().unwrap();
+        let scanner = scanner(&dir);
+        let prefix = dir.path().join("prefix");
+        touch_exe(&prefix, "drive_c/Program Files/Game/game.exe");
+        touch_exe(&prefix, "drive_c/Program Files/Game/editor.MSI");
+        touch_exe(&prefix, "drive_c/Program Files/Game/legacy.com");
+
+        let names = |found: Vec<RegisteredExecutable>| -> Vec<String> {
+            found.into_iter().map(|e| e.name).collect()
+        };
+        scanner.set_executable_extensions([".MSI", "com", "msi", ""]);
+        assert_eq!(scanner.executable_extensions(), ["msi", "com"]);
+        assert_eq!(
+            names(scanner.clone().scan_prefix(&prefix).unwrap()),
+            ["editor", "legacy"]
+        );
+
+        scanner.set_executable_extensions(DEFAULT_EXECUTABLE_EXTENSIONS);
+        assert_eq!(names(scanner.scan_prefix(&prefix).unwrap()), ["game"]);
+    }
+
+    #[test]
+    fn test_scan_prefix_keeps_same_name_at_different_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let scanner = scanner(&dir);
+        let prefix = dir.path().join("prefix");
+        let a = touch_exe(&prefix, "drive_c/Program Files/App/app.exe");
+        let b = touch_exe(&prefix, "drive_c/Program Files (x86)/App/app.exe");
+
+        let found = scanner.scan_prefix(&prefix).unwrap();
+        let paths: Vec<&PathBuf> = found.iter().map(|e| &e.executable_path).collect();
+        assert_eq!(paths.len(), 2);
+        assert!(paths.contains(&&a));
+        assert!(paths.contains(&&b));
+    }
+
+    #[test]
+    fn test_scan_prefix_incremental_reuses_unchanged_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let scanner = scanner(&dir);
+        let prefix = dir.path().join("prefix");
+        let old = touch_exe(&prefix, "drive_c/Program Files/Old/old.exe");
+        let changed = touch_exe(&prefix, "drive_c/Program Files/Changed/changed.exe");
+        touch_exe(&prefix, "drive_c/Program Files/New/new.exe");
+
+        // Cached names differ from what a parse would produce, so reuse shows
+        let cached = |path: &PathBuf, name: &str, stamp: FileStamp| {
+            let exe = RegisteredExecutable::new(name.to_string(), path.clone());
+            (path.clone(), (stamp, exe))
+        };
+        let cache: ScanCache = [
+            cached(&old, "Old (cached)", FileStamp::of(&old).unwrap()),
+            cached(
+                &changed,
+                "Changed (cached)",
+                FileStamp { size: 0, mtime: 0 },
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let found = scanner.scan_prefix_incremental(&prefix, &cache).unwrap();
+        let names: Vec<&str> = found.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["Old (cached)", "changed", "new"]);
+    }
+
+    #[test]
+    fn test_sort_and_dedup_removes_repeated_paths() {
+        let app = PathBuf::from("/p/drive_c/Program Files/App/app.exe");
+        let other = PathBuf::from("/p/drive_c/users/bob/App/app.exe");
+        let beta = PathBuf::from("/p/drive_c/Program Files/Beta/beta.exe");
+        // The same executable seen from two scan dirs, with another entry
+        // of the same name in between so the duplicates aren't adjacent.
+        let mut executables = vec![
+            RegisteredExecutable::new("app".to_string(), app.clone()),
+            RegisteredExecutable::new("beta".to_string(), beta.clone()),
+            RegisteredExecutable::new("app".to_string(), other.clone()),
+            RegisteredExecutable::new("app".to_string(), app.clone()),
+        ];
+
+        sort_and_dedup(&mut executables);
+
+        let entries: Vec<(&str, &PathBuf)> = executables
+            .iter()
+            .map(|e| (e.name.as_str(), &e.executable_path))
+            .collect();
+        assert_eq!(entries, [("app", &app), ("app", &other), ("beta", &beta)]);
+    }
+}
@@ -1,21 +1,32 @@
 use base::config::PrefixConfig;
 use base::RegisteredExecutable;
-use log::{error, info};
-use std::path::Path;
+use log::{error, info, warn};
+use std::path::{Path, PathBuf};
 
 use crate::AppService;
 
 /// Add a registered executable to a prefix and persist.
 ///
 /// The prefix config is loaded from disk, the executable is added,
-/// and the result is saved back.  Returns `true` on success.
+/// and the result is saved back.  Returns `true` on success, and `false`
+/// if saving failed, the executable was already registered, or it lies
+/// outside the prefix (see [`is_allowed_in_prefix`]).
 pub fn add_executable(
     service: &AppService,
     prefix_path: &Path,
     config: &mut PrefixConfig,
     executable: RegisteredExecutable,
 ) -> bool {
-    config.add_executable(executable);
+    if !is_allowed_in_prefix(prefix_path, &executable) {
+        return false;
+    }
+    if !config.add_executable(executable) {
+        info!(
+            "[service] executable already registered in prefix '{}'",
+            prefix_path.display()
+        );
+        return false;
+    }
     match service.update_config(prefix_path, config) {
         Ok(()) => {
             info!(
@@ -32,31 +43,55 @@ pub fn add_executable(
 }
 
 /// Add multiple registered executables to a prefix and persist.
+///
+/// Executables already registered (by path) or outside the prefix are
+/// skipped. Returns the number actually added, or `None` if saving failed.
 pub fn add_executables(
     service: &AppService,
     prefix_path: &Path,
     config: &mut PrefixConfig,
     executables: &[RegisteredExecutable],
-) -> bool {
-    for exe in executables {
-        config.add_executable(exe.clone());
+) -> Option<usize> {
+    let added = executables
+        .iter()
+        .filter(|exe| is_allowed_in_prefix(prefix_path, exe))
+        .filter(|exe| config.add_executable((*exe).clone()))
+        .count();
+    if added == 0 {
+        return Some(0);
     }
     match service.update_config(prefix_path, config) {
         Ok(()) => {
             info!(
-                "[service] added {} executables to prefix '{}'",
-                executables.len(),
-                prefix_path.display()
+                "[service] added {} executables to prefix '{}' ({} already registered)",
+                added,
+                prefix_path.display(),
+                executables.len() - added
             );
-            true
+            Some(added)
         }
         Err(e) => {
             error!("[service] failed to save config: {}", e);
-            false
+            None
         }
     }
 }
 
+/// Check that `executable` lives under the prefix's `drive_c` or was
+/// explicitly allowed outside it, logging a warning when it isn't.
+/// Registering a host path would have Wine run it from the wrong place.
+pub fn is_allowed_in_prefix(prefix_path: &Path, executable: &RegisteredExecutable) -> bool {
+    if executable.is_allowed_in_prefix(prefix_path) {
+        return true;
+    }
+    warn!(
+        "[service] refusing to register '{}': not inside prefix '{}'",
+        executable.executable_path.display(),
+        prefix_path.display()
+    );
+    false
+}
+
 /// Remove a registered executable from a prefix and persist.
 pub fn remove_executable(
     service: &AppService,
@@ -84,6 +119,110 @@ pub fn remove_executable(
     }
 }
 
+/// Remove several registered executables (matched by path) and persist once.
+pub fn remove_executables(
+    service: &AppService,
+    prefix_path: &Path,
+    config: &mut PrefixConfig,
+    paths: &[PathBuf],
+) -> bool {
+    let removed = config.remove_executables_by_path(paths);
+    if removed == 0 {
+        return false;
+    }
+    match service.update_config(prefix_path, config) {
+        Ok(()) => {
+            info!(
+                "[service] removed {} executables from '{}'",
+                removed,
+                prefix_path.display()
+            );
+            true
+        }
+        Err(e) => {
+            error!("[service] failed to save config: {}", e);
+            false
+        }
+    }
+}
+
+/// Remove registered executables whose path no longer exists and persist.
+///
+/// Returns the number of entries removed, or `None` if saving failed.
+pub fn remove_missing_executables(
+    service: &AppService,
+    prefix_path: &Path,
+    config: &mut PrefixConfig,
+) -> Option<usize> {
+    let removed = config.remove_missing_executables();
+    if removed.is_empty() {
+        return Some(0);
+    }
+    match service.update_config(prefix_path, config) {
+        Ok(()) => {
+            info!(
+                "[service] removed {} missing executables from '{}'",
+                removed.len(),
+                prefix_path.display()
+            );
+            Some(removed.len())
+        }
+        Err(e) => {
+            error!("[service] failed to save config: {}", e);
+            None
+        }
+    }
+}
+
+/// Move the executable at `from` to the position of `to` and persist the
+/// new order.
+pub fn move_executable(
+    service: &AppService,
+    prefix_path: &Path,
+    config: &mut PrefixConfig,
+    from: &Path,
+    to: &Path,
+) -> bool {
+    if !config.move_executable(from, to) {
+        return false;
+    }
+    match service.update_config(prefix_path, config) {
+        Ok(()) => {
+            info!(
+                "[service] moved executable '{}' in '{}'",
+                from.display(),
+                prefix_path.display()
+            );
+            true
+        }
+        Err(e) => {
+            error!("[service] failed to save config: {}", e);
+            false
+        }
+    }
+}
+
+/// Record a launch of `exe_path` (timestamp + count) and persist.
+///
+/// Failures are logged but never block the launch itself.
+pub fn record_launch(
+    service: &AppService,
+    prefix_path: &Path,
+    config: &mut PrefixConfig,
+    exe_path: &Path,
+) -> bool {
+    if !config.record_launch(exe_path) {
+        return false;
+    }
+    match service.update_config(prefix_path, config) {
+        Ok(()) => true,
+        Err(e) => {
+            error!("[service] failed to save launch stats: {}", e);
+            false
+        }
+    }
+}
+
 /// Update a single executable's settings and persist.
 pub fn update_executable(
     service: &AppService,
@@ -1,12 +1,20 @@
 use base::config::PrefixConfig;
-use base::RegisteredExecutable;
+use base::{LaunchProfile, RegisteredExecutable};
 use log::{error, info};
+use prefix::{LaunchHandle, WineTool, detach_child};
 use std::path::Path;
 use std::path::PathBuf;
-use std::process::Stdio;
 
 use crate::AppService;
 
+/// Stamp the prefix's `last_used`. A failure only affects the
+/// recently-used order, so it is logged rather than returned.
+fn mark_used(prefix_path: &Path) {
+    if let Err(e) = PrefixConfig::mark_used_on_disk(prefix_path) {
+        error!("[service] failed to record prefix use: {}", e);
+    }
+}
+
 /// Launch a registered executable and register it with the process tracker.
 pub fn launch_executable(
     service: &AppService,
@@ -24,6 +32,7 @@ pub fn launch_executable(
             let mut tracker = service.process_tracker().lock().unwrap();
             tracker.register(&executable.executable_path, child);
             info!("[service] launched '{}' (PID: {})", executable.name, pid);
+            mark_used(prefix_path);
             Ok(pid)
         }
         Err(e) => {
@@ -33,6 +42,63 @@ pub fn launch_executable(
     }
 }
 
+/// Launch a registered executable and return a handle that resolves with
+/// its exit code. The PID is tracked so it is still killed on shutdown.
+///
+/// Must be called from within a Tokio runtime. `profile` selects one of
+/// the executable's launch profiles; `None` is the plain launch.
+pub fn launch_executable_async(
+    service: &AppService,
+    prefix_path: &Path,
+    executable: &RegisteredExecutable,
+    profile: Option<&LaunchProfile>,
+) -> std::result::Result<LaunchHandle, String> {
+    let prefix = match service.prefix_manager().open_prefix(prefix_path) {
+        Ok(p) => p,
+        Err(e) => return Err(e.to_string()),
+    };
+
+    match prefix.launch_executable_async(executable, profile) {
+        Ok(handle) => {
+            let mut tracker = service.process_tracker().lock().unwrap();
+            tracker.track_pid(&executable.executable_path, handle.pid());
+            info!("[service] launched '{}' (PID: {})", executable.name, handle.pid());
+            mark_used(prefix_path);
+            Ok(handle)
+        }
+        Err(e) => {
+            error!("[service] failed to launch '{}': {}", executable.name, e);
+            Err(e.to_string())
+        }
+    }
+}
+
+/// Handle the exit of an executable started with [`launch_executable_async`].
+/// For prefixes with `stop_wineserver_on_exit` set, stops the wineserver
+/// once none of the prefix's applications is running any more.
+pub fn launch_exited(service: &AppService, prefix_path: &Path) {
+    let config = match PrefixConfig::load_from_file(prefix_path) {
+        Ok(Some(config)) if config.stop_wineserver_on_exit => config,
+        _ => return,
+    };
+    let running = poll_dead_processes(service);
+    if config
+        .registered_executables
+        .iter()
+        .any(|exe| running.contains(&exe.executable_path))
+    {
+        return;
+    }
+    match service.prefix_manager().stop_idle_wineserver(prefix_path) {
+        Ok(true) => info!("[service] stopped wineserver for '{}'", config.name),
+        Ok(false) => {}
+        Err(e) => error!(
+            "[service] failed to stop wineserver for '{}': {}",
+            config.name, e
+        ),
+    }
+}
+
 /// Launch winecfg for a prefix.
 pub fn launch_winecfg(
     service: &AppService,
@@ -45,8 +111,10 @@ pub fn launch_winecfg(
 
     let name = prefix.name().to_string();
     match prefix.run_winecfg() {
-        Ok(_) => {
+        Ok(child) => {
+            detach_child(child);
             info!("[service] launched winecfg for prefix '{}'", name);
+            mark_used(prefix_path);
             Ok(())
         }
         Err(e) => {
@@ -56,6 +124,24 @@ pub fn launch_winecfg(
     }
 }
 
+/// Start one of Wine's built-in tools (regedit, control panel, …) for a prefix.
+pub fn launch_wine_tool(
+    service: &AppService,
+    prefix_path: &Path,
+    tool: WineTool,
+) -> std::result::Result<(), String> {
+    match service.prefix_manager().run_wine_tool(prefix_path, tool) {
+        Ok(()) => {
+            info!("[service] launched {}", tool.program());
+            Ok(())
+        }
+        Err(e) => {
+            error!("[service] failed to launch {}: {}", tool.program(), e);
+            Err(e.to_string())
+        }
+    }
+}
+
 /// Launch the Wine uninstaller for a prefix.
 pub fn launch_uninstaller(
     service: &AppService,
@@ -106,6 +192,7 @@ pub fn launch_direct_exe(
             info!("[service] launched exe directly: {}", exe_path.display());
             let mut tracker = service.process_tracker().lock().unwrap();
             tracker.register(exe_path, child);
+            mark_used(prefix_path);
             Ok(())
         }
         Err(e) => {
@@ -127,46 +214,16 @@ pub fn launch_executable_debug(
         Err(e) => return Err(e.to_string()),
     };
 
-    if !executable.executable_path.exists() {
-        return Err("Executable file does not exist".to_string());
-    }
-
-    let mut cmd = prefix.build_wine_command_with_args(
-        &[&executable.executable_path.to_string_lossy()],
-    );
-
     info!(
         "[service] launching '{}' in debug mode for prefix '{}'",
         executable.name, prefix_path.display()
     );
 
-    // Set up pipes for debug capture
-    cmd.stdout(Stdio::piped());
-    cmd.stderr(Stdio::piped());
-    cmd.stdin(Stdio::piped());
-
-    // Apply per-executable environment variables
-    for (key, value) in &executable.env_vars {
-        cmd.env(key, value);
-    }
-
-    // Apply per-executable working directory (fall back to prefix path)
-    if let Some(cwd) = &executable.cwd {
-        cmd.current_dir(cwd);
-    } else {
-        cmd.current_dir(prefix_path);
-    }
-
-    match cmd.spawn() {
-        Ok(child) => {
-            info!("[service] debug process started (PID: {})", child.id());
-            Ok(child)
-        }
-        Err(e) => {
-            error!("[service] failed to launch debug '{}': {}", executable.name, e);
-            Err(format!("Failed to launch executable: {}", e))
-        }
-    }
+    let child = prefix
+        .launch_executable_with_console(executable)
+        .map_err(|e| e.to_string())?;
+    mark_used(prefix_path);
+    Ok(child)
 }
 
 /// Register a debug-mode PID with the process tracker so it gets killed
@@ -209,3 +266,60 @@ pub fn is_process_running(service: &AppService, path: &PathBuf) -> bool {
 pub fn kill_process(service: &AppService, path: &PathBuf) -> bool {
     service.process_tracker().lock().unwrap().kill(path)
 }
+
+/// Registered executables of a prefix that are currently running.
+///
+/// Combines the process tracker with a scan of the prefix's Wine processes,
+/// so apps keep showing as running after the `wine` wrapper we launched has
+/// exited, e.g. when a launcher hands off to the real game.
+pub fn running_executables(
+    service: &AppService,
+    prefix_path: &Path,
+    executables: &[RegisteredExecutable],
+) -> std::collections::HashSet<PathBuf> {
+    let mut paths = poll_dead_processes(service);
+    let names: std::collections::HashSet<String> = prefix::prefix_processes(prefix_path)
+        .into_iter()
+        .map(|p| p.exe_name)
+        .collect();
+    if names.is_empty() {
+        return paths;
+    }
+    for exe in executables {
+        let matches = exe
+            .executable_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| names.contains(&n.to_lowercase()));
+        if matches {
+            paths.insert(exe.executable_path.clone());
+        }
+    }
+    paths
+}
+
+/// Stop a running executable of a prefix. Kills what the process tracker
+/// holds for `path`, then sends SIGTERM to any Wine process in the prefix
+/// running an `.exe` of the same name. Returns true if anything was signalled.
+pub fn stop_executable(service: &AppService, prefix_path: &Path, path: &PathBuf) -> bool {
+    let mut stopped = kill_process(service, path);
+    let Some(name) = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .map(str::to_lowercase)
+    else {
+        return stopped;
+    };
+    for process in prefix::prefix_processes(prefix_path) {
+        if process.exe_name == name {
+            let _ = std::process::Command::new("kill")
+                .args(["-TERM", &process.pid.to_string()])
+                .output();
+            stopped = true;
+        }
+    }
+    if stopped {
+        info!("[service] stopped '{}'", path.display());
+    }
+    stopped
+}
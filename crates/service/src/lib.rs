@@ -1,5 +1,6 @@
 pub mod config_ops;
 pub mod launch;
+pub mod registry_ops;
 pub mod runtime_ops;
 pub mod state;
 pub mod sync;
@@ -73,6 +74,32 @@ impl AppService {
         }
     }
 
+    /// Scan for Wine prefixes, calling `on_found` for each one as soon as it
+    /// has loaded. Returns the full list sorted by name.
+    pub fn scan_prefixes_streaming<F>(&self, on_found: F) -> Vec<WinePrefix>
+    where
+        F: Fn(&WinePrefix) + Sync,
+    {
+        match self.prefix_manager().scan_prefixes_streaming(on_found) {
+            Ok(p) => p,
+            Err(e) => {
+                log::error!("[service] error scanning prefixes: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Number of cached icons and their total size in bytes.
+    pub fn icon_cache_usage(&self) -> (usize, u64) {
+        self.prefix_manager().scanner().icon_cache().usage()
+    }
+
+    /// Drop every cached icon; they are extracted again when next shown.
+    pub fn clear_icon_cache(&self) -> std::result::Result<(), String> {
+        let icon_cache = self.prefix_manager().scanner().icon_cache().clone();
+        icon_cache.clear()
+    }
+
     /// Delete a prefix from disk and remove it from the list.
     pub fn delete_prefix(&self, prefix_path: &Path, prefixes: &mut Vec<WinePrefix>) -> bool {
         if let Err(e) = self.prefix_manager().delete_prefix(prefix_path) {
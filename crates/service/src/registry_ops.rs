@@ -0,0 +1,42 @@
+use prefix::{RegEditor, RegistryEditor};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+/// DLL override counts per prefix, keyed by the `user.reg` modification time
+/// they were read at.
+static OVERRIDE_COUNTS: OnceLock<Mutex<HashMap<PathBuf, (SystemTime, usize)>>> = OnceLock::new();
+
+fn override_counts() -> &'static Mutex<HashMap<PathBuf, (SystemTime, usize)>> {
+    OVERRIDE_COUNTS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Number of DLL overrides set in a prefix.
+///
+/// Loading the registry is slow for large prefixes, so the count is cached
+/// until `user.reg` changes on disk.
+pub async fn dll_override_count(prefix_path: &Path) -> std::result::Result<usize, String> {
+    let modified = std::fs::metadata(prefix_path.join("user.reg"))
+        .and_then(|m| m.modified())
+        .map_err(|e| e.to_string())?;
+    if let Some(&(cached_at, count)) = override_counts().lock().unwrap().get(prefix_path)
+        && cached_at == modified
+    {
+        return Ok(count);
+    }
+
+    let editor = RegistryEditor::with_prefix(&prefix_path.to_path_buf())
+        .await
+        .map_err(|e| e.to_string())?;
+    let count = editor
+        .get_dll_overrides()
+        .await
+        .map_err(|e| e.to_string())?
+        .len();
+    override_counts()
+        .lock()
+        .unwrap()
+        .insert(prefix_path.to_path_buf(), (modified, count));
+    Ok(count)
+}
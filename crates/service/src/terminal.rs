@@ -1,4 +1,4 @@
-use log::error;
+use log::{error, warn};
 use std::fs;
 use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
@@ -37,12 +37,40 @@ pub fn open_terminal_for_prefix(
     Ok(())
 }
 
+/// The terminal command from settings, else `$TERMINAL`, split into argv.
+/// A bare program name gets `-e` appended, which nearly all emulators accept.
+fn preferred_terminal() -> Option<Vec<String>> {
+    let configured = prefix::Settings::load()
+        .and_then(|s| s.terminal)
+        .or_else(|| std::env::var("TERMINAL").ok())?;
+    let mut args: Vec<String> = configured.split_whitespace().map(str::to_string).collect();
+    match args.len() {
+        0 => return None,
+        1 => args.push("-e".to_string()),
+        _ => {}
+    }
+    Some(args)
+}
+
 /// Spawn a terminal emulator to run the given shell script.
 ///
-/// On macOS uses Terminal.app via AppleScript; on Linux tries common terminals.
+/// A terminal configured in settings (or `$TERMINAL`) is tried first. Otherwise
+/// macOS uses Terminal.app via AppleScript and Linux tries common terminals.
 pub fn open_terminal_with_script(script_path: &Path) {
     let path_str = script_path.to_string_lossy();
 
+    if let Some(args) = preferred_terminal() {
+        match std::process::Command::new(&args[0])
+            .args(&args[1..])
+            .arg("bash")
+            .arg(script_path)
+            .spawn()
+        {
+            Ok(_) => return,
+            Err(e) => warn!("[terminal] failed to start {}: {}", args[0], e),
+        }
+    }
+
     #[cfg(target_os = "macos")]
     {
         let template = include_str!("../../../scripts/tequila-terminal.applescript");
@@ -88,3 +116,30 @@ pub fn open_in_file_manager(path: &Path) {
         .arg(path_ref)
         .status();
 }
+
+/// Open the directory containing `exe_path` in the system file manager.
+///
+/// Fails if the directory no longer exists (e.g. the app was uninstalled).
+pub fn open_containing_folder(exe_path: &Path) -> std::result::Result<(), String> {
+    let dir = exe_path
+        .parent()
+        .ok_or_else(|| format!("'{}' has no parent directory", exe_path.display()))?;
+    if !dir.is_dir() {
+        error!("[terminal] folder does not exist: {}", dir.display());
+        return Err(format!("Folder does not exist: {}", dir.display()));
+    }
+    open_in_file_manager(dir);
+    Ok(())
+}
+
+/// Open a prefix's diagnostics log (`tequila.log`) with the default viewer.
+///
+/// Fails if nothing has been logged yet, e.g. because logging is off.
+pub fn open_prefix_log(prefix_path: &Path) -> std::result::Result<(), String> {
+    let log = prefix::prefix_log::log_path(prefix_path);
+    if !log.is_file() {
+        return Err(format!("No log file at {}", log.display()));
+    }
+    open_in_file_manager(&log);
+    Ok(())
+}
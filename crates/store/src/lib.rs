@@ -1,5 +1,5 @@
 mod settings;
 mod state;
 
-pub use settings::Settings;
+pub use settings::{PrefixTemplate, Settings};
 pub use state::PrefixStore;
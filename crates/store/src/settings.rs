@@ -1,5 +1,6 @@
 use runtime::{Runtime, RuntimeManager};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -23,12 +24,82 @@ pub struct Settings {
     /// Defaults to "system" when not set.
     #[serde(default = "default_language", skip_serializing_if = "String::is_empty")]
     pub language: String,
+    /// Color scheme preference.
+    /// Options: "auto" (follow the desktop), "light", "dark".
+    /// Defaults to "auto" when not set.
+    #[serde(default = "default_theme", skip_serializing_if = "String::is_empty")]
+    pub theme: String,
+    /// Architecture preselected when creating a prefix: "win64" or "win32".
+    #[serde(default = "default_architecture")]
+    pub default_architecture: String,
+    /// Terminal emulator used by "Open in Terminal", e.g. `"kitty"` or
+    /// `"wezterm start --"`. The shell script to run is appended as
+    /// `bash <script>`; a bare program name gets `-e` inserted first.
+    /// When unset, `$TERMINAL` and then a list of common terminals is tried.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terminal: Option<String>,
+    /// Whether application scans also register `.bat`/`.cmd` launcher
+    /// scripts. Off by default: most prefixes are full of helper scripts.
+    #[serde(default)]
+    pub scan_scripts: bool,
+    /// File extensions scanned for in addition to `.exe`, e.g. `["msi"]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_scan_extensions: Vec<String>,
+    /// Skip the background application scan at startup; prefixes are only
+    /// scanned when the Add popover needs results.
+    #[serde(default)]
+    pub manual_app_scan: bool,
+    /// Show registered applications as a compact list instead of icon tiles.
+    #[serde(default)]
+    pub apps_list_view: bool,
+    /// List prefixes most recently used first instead of by name.
+    #[serde(default)]
+    pub sort_prefixes_by_last_used: bool,
+    /// Append launches, scans and registry saves to `tequila.log` in each
+    /// prefix, for bug reports from users without a console.
+    #[serde(default)]
+    pub prefix_logging: bool,
+    /// How many `.reg` files may be parsed at once across all prefixes;
+    /// the registry crate's default when unset. Lower it for many prefixes
+    /// on a spinning disk.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_registry_reads: Option<usize>,
+    /// Named baselines offered when creating a prefix.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prefix_templates: Vec<PrefixTemplate>,
+}
+
+/// Registry settings and winetricks verbs applied to a new prefix once Wine
+/// has initialised it. Unset fields are left at Wine's defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PrefixTemplate {
+    pub name: String,
+    /// `Software\Wine\Version` value, e.g. `"win10"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub windows_version: Option<String>,
+    /// Direct3D `renderer` value, e.g. `"vulkan"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub d3d_renderer: Option<String>,
+    /// DLL name to override setting, e.g. `"d3dcompiler_47": "native,builtin"`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dll_overrides: BTreeMap<String, String>,
+    /// winetricks verbs installed after the registry settings, in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub winetricks_verbs: Vec<String>,
 }
 
 fn default_language() -> String {
     "system".to_string()
 }
 
+fn default_theme() -> String {
+    "auto".to_string()
+}
+
+fn default_architecture() -> String {
+    "win64".to_string()
+}
+
 impl Settings {
     pub fn path() -> PathBuf {
         let config_dir = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
@@ -44,6 +115,18 @@ impl Settings {
         serde_json::from_str(&content).ok()
     }
 
+    /// The saved settings, or the defaults when there are none yet.
+    pub fn current() -> Self {
+        Self::load().unwrap_or_else(|| RuntimeManager::new().into())
+    }
+
+    /// Apply `change` to the saved settings and write them back.
+    pub fn update(change: impl FnOnce(&mut Self)) -> std::io::Result<()> {
+        let mut settings = Self::current();
+        change(&mut settings);
+        settings.save()
+    }
+
     pub fn save(&self) -> std::io::Result<()> {
         let path = Self::path();
         if let Some(parent) = path.parent() {
@@ -56,16 +139,39 @@ impl Settings {
 
 impl From<RuntimeManager> for Settings {
     fn from(rm: RuntimeManager) -> Self {
-        // Preserve any existing `github_api_key` from the on-disk settings,
-        // so saving a `RuntimeManager` back to disk doesn't lose the key.
-        let existing_key = Self::load().and_then(|s| s.github_api_key);
+        // Preserve the existing `github_api_key` and UI preferences from the
+        // on-disk settings, so saving a `RuntimeManager` back doesn't lose them.
+        let existing = Self::load();
         Settings {
             runtimes: rm.runtimes,
             default_id: rm.default_id,
-            github_api_key: existing_key,
-            language: Self::load()
-                .map(|s| s.language)
+            github_api_key: existing.as_ref().and_then(|s| s.github_api_key.clone()),
+            language: existing
+                .as_ref()
+                .map(|s| s.language.clone())
                 .unwrap_or_else(default_language),
+            theme: existing
+                .as_ref()
+                .map(|s| s.theme.clone())
+                .unwrap_or_else(default_theme),
+            default_architecture: existing
+                .as_ref()
+                .map(|s| s.default_architecture.clone())
+                .unwrap_or_else(default_architecture),
+            terminal: existing.as_ref().and_then(|s| s.terminal.clone()),
+            scan_scripts: existing.as_ref().is_some_and(|s| s.scan_scripts),
+            extra_scan_extensions: existing
+                .as_ref()
+                .map(|s| s.extra_scan_extensions.clone())
+                .unwrap_or_default(),
+            manual_app_scan: existing.as_ref().is_some_and(|s| s.manual_app_scan),
+            apps_list_view: existing.as_ref().is_some_and(|s| s.apps_list_view),
+            sort_prefixes_by_last_used: existing
+                .as_ref()
+                .is_some_and(|s| s.sort_prefixes_by_last_used),
+            prefix_logging: existing.as_ref().is_some_and(|s| s.prefix_logging),
+            max_registry_reads: existing.as_ref().and_then(|s| s.max_registry_reads),
+            prefix_templates: existing.map(|s| s.prefix_templates).unwrap_or_default(),
         }
     }
 }
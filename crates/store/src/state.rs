@@ -1,8 +1,10 @@
 use base::config::RegisteredExecutable;
 use base::error::{PrefixError, Result};
 use rusqlite::{Connection, params};
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
 
 pub struct PrefixStore {
     db: Mutex<Connection>,
@@ -48,7 +50,30 @@ impl PrefixStore {
                 std::io::ErrorKind::Other,
                 e.to_string(),
             ))
-        })
+        })?;
+        // Columns added after the initial schema
+        Self::ensure_column(db, "scanned_executables", "architecture", "TEXT")?;
+        Self::ensure_column(db, "scanned_executables", "file_size", "INTEGER")?;
+        Self::ensure_column(db, "scanned_executables", "file_mtime", "INTEGER")
+    }
+
+    fn ensure_column(db: &Connection, table: &str, column: &str, decl: &str) -> Result<()> {
+        let mut stmt = db
+            .prepare(&format!("PRAGMA table_info({})", table))
+            .map_err(map_err)?;
+        let exists = stmt
+            .query_map([], |row| row.get::<_, String>("name"))
+            .map_err(map_err)?
+            .filter_map(|name| name.ok())
+            .any(|name| name == column);
+        if !exists {
+            db.execute(
+                &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, decl),
+                [],
+            )
+            .map_err(map_err)?;
+        }
+        Ok(())
     }
 
     pub fn get_setting(
@@ -201,10 +226,11 @@ impl PrefixStore {
             return Ok(());
         }
         let mut stmt = db.prepare(
-            "INSERT OR IGNORE INTO scanned_executables (prefix_path, executable_path, name, description, icon_path, file_version, product_version, company_name, file_description, product_name)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"
+            "INSERT OR IGNORE INTO scanned_executables (prefix_path, executable_path, name, description, icon_path, file_version, product_version, company_name, file_description, product_name, architecture, file_size, file_mtime)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)"
         ).map_err(map_err)?;
         for exe in exes {
+            let (size, mtime) = file_stamp(&exe.executable_path).unzip();
             stmt.execute(params![
                 prefix_path,
                 exe.executable_path.to_string_lossy().to_string(),
@@ -218,6 +244,9 @@ impl PrefixStore {
                 exe.company_name,
                 exe.file_description,
                 exe.product_name,
+                exe.architecture,
+                size,
+                mtime,
             ])
             .map_err(map_err)?;
         }
@@ -227,12 +256,12 @@ impl PrefixStore {
     pub fn list_scanned_executables(&self, prefix_path: &str) -> Result<Vec<RegisteredExecutable>> {
         let db = self.db.lock().unwrap();
         let mut stmt = db.prepare(
-            "SELECT name, executable_path, description, icon_path, file_version, product_version, company_name, file_description, product_name
+            "SELECT name, executable_path, description, icon_path, file_version, product_version, company_name, file_description, product_name, architecture
              FROM scanned_executables WHERE prefix_path = ?1 ORDER BY name"
         ).map_err(map_err)?;
         let exes = stmt
             .query_map(params![prefix_path], |row| {
-                Ok(RegisteredExecutable {
+                let mut exe = RegisteredExecutable {
                     name: row.get("name")?,
                     executable_path: std::path::PathBuf::from(
                         row.get::<_, String>("executable_path")?,
@@ -249,13 +278,63 @@ impl PrefixStore {
                     imported_modules: Vec::new(),
                     env_vars: std::collections::HashMap::new(),
                     cwd: None,
-                })
+                    category: None,
+                    favorite: false,
+                    last_launched: None,
+                    launch_count: 0,
+                    architecture: None,
+                    metadata_checked: false,
+                    force_32bit: false,
+                    allow_external: false,
+                    launch_profiles: Vec::new(),
+                    launch_wrapper: None,
+                };
+                if let Some(architecture) = row.get::<_, Option<String>>("architecture")? {
+                    exe.set_architecture(architecture);
+                }
+                Ok(exe)
             })
             .map_err(map_err)?
             .collect::<std::result::Result<Vec<_>, _>>()
             .map_err(map_err)?;
         Ok(exes)
     }
+
+    /// File size and mtime recorded for each scanned executable of a prefix,
+    /// keyed by path. Rows saved before stamps were recorded are left out.
+    pub fn list_scanned_stamps(&self, prefix_path: &str) -> Result<HashMap<PathBuf, (u64, i64)>> {
+        let db = self.db.lock().unwrap();
+        let mut stmt = db
+            .prepare(
+                "SELECT executable_path, file_size, file_mtime FROM scanned_executables
+                 WHERE prefix_path = ?1 AND file_size IS NOT NULL AND file_mtime IS NOT NULL",
+            )
+            .map_err(map_err)?;
+        let stamps = stmt
+            .query_map(params![prefix_path], |row| {
+                Ok((
+                    PathBuf::from(row.get::<_, String>(0)?),
+                    (row.get::<_, i64>(1)? as u64, row.get::<_, i64>(2)?),
+                ))
+            })
+            .map_err(map_err)?
+            .collect::<std::result::Result<HashMap<_, _>, _>>()
+            .map_err(map_err)?;
+        Ok(stamps)
+    }
+}
+
+/// Size and mtime (seconds) of the file at `path`, recorded with each scan
+/// result so the next scan can tell whether it changed.
+fn file_stamp(path: &Path) -> Option<(i64, i64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    let mtime = meta
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    Some((meta.len() as i64, mtime))
 }
 
 fn map_err(e: rusqlite::Error) -> PrefixError {
@@ -38,6 +38,8 @@ enum Subcmd {
         #[arg(allow_hyphen_values = true, trailing_var_arg = true)]
         args: Vec<String>,
     },
+    /// Print Tequila, Wine and tool versions for a bug report
+    Diagnostics,
 }
 
 fn main() -> ExitCode {
@@ -71,6 +73,10 @@ fn main() -> ExitCode {
                     ExitCode::FAILURE
                 }
             },
+            Subcmd::Diagnostics => {
+                print!("{}", diagnostics());
+                ExitCode::SUCCESS
+            }
         }
     }
 }
@@ -197,6 +203,7 @@ fn run(
 
     let mut cmd = Command::new("wine");
     prefix::apply_runtime_env(&mut cmd, runtime, &prefix_path);
+    prefix::apply_prefix_env(&mut cmd, &config);
 
     for (key, value) in &env_vars {
         cmd.env(key, value);
@@ -223,6 +230,9 @@ fn run(
         cmd.spawn()
             .map_err(|e| format!("failed to spawn wine: {e}"))?
     )));
+    if let Err(e) = base::config::PrefixConfig::mark_used_on_disk(&prefix_path) {
+        log::warn!("failed to record prefix use: {e}");
+    }
 
     // Kill the child process on SIGINT/SIGTERM (Ctrl+C)
     let child_for_signal = child.clone();
@@ -249,6 +259,13 @@ fn run(
     Ok(code)
 }
 
+fn diagnostics() -> String {
+    let mut runtime_manager: runtime::RuntimeManager = store::Settings::current().into();
+    runtime_manager.ensure_system_runtime();
+    prefix::Diagnostics::collect(&runtime_manager)
+        .report(&[("Tequila", env!("CARGO_PKG_VERSION").to_string())])
+}
+
 // ── Helpers ─────────────────────────────────────────────────────────────
 
 fn resolve_prefix(arg: &str, uuid_mode: bool) -> Result<PathBuf, String> {
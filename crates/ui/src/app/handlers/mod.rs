@@ -1,7 +1,13 @@
 use crate::AppMsg;
+use adw::prelude::*;
 use log::error;
+use notify::event::ModifyKind;
+use notify::{EventKind, RecursiveMode, Watcher, recommended_watcher};
+use relm4::{adw, gtk};
 use service::AppService;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
 
 /// Open prefix in file manager
 pub fn handle_open_in_file_manager(prefixes: &[base::WinePrefix], index: usize) {
@@ -23,6 +29,135 @@ pub fn handle_open_in_terminal(prefixes: &[base::WinePrefix], index: usize) {
     }
 }
 
+/// Ask for a template name (defaulting to the prefix's) and save the
+/// prefix's registry settings under it. Failures surface as `ShowError`.
+pub fn handle_save_as_template(
+    parent: &gtk::Window,
+    prefix: &base::WinePrefix,
+    sender: relm4::ComponentSender<crate::app::AppModel>,
+) {
+    let entry = gtk::Entry::builder()
+        .text(prefix.name.as_str())
+        .activates_default(true)
+        .build();
+    let alert = adw::AlertDialog::new(
+        Some(&crate::t!("prefix.template.save_title")),
+        Some(&crate::t!("prefix.template.save_body")),
+    );
+    alert.set_extra_child(Some(&entry));
+    alert.add_response("cancel", &crate::t!("dialogs.cancel"));
+    alert.add_response("save", &crate::t!("prefix.template.save"));
+    alert.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+    alert.set_default_response(Some("save"));
+    alert.set_close_response("cancel");
+    let prefix_path = prefix.path.clone();
+    alert.choose(Some(parent), None::<&gtk::gio::Cancellable>, move |response| {
+        let name = entry.text().trim().to_string();
+        if response != "save" || name.is_empty() {
+            return;
+        }
+        let pm = AppService::global().prefix_manager().clone();
+        gtk::glib::MainContext::default().spawn_local(async move {
+            if let Err(e) = pm.save_template_from_prefix(&prefix_path, &name).await {
+                error!("[template] failed to save '{}': {}", name, e);
+                let _ = sender.input(AppMsg::ShowError(
+                    crate::tf!("prefix.template.save_failed", "name" => &name, "error" => &e.to_string()),
+                ));
+            }
+        });
+    });
+}
+
+/// List the Proton prefixes found in the Steam libraries and link the
+/// ones the user checks. Already linked prefixes are shown but disabled.
+pub fn handle_show_proton_prefixes(
+    parent: &gtk::Window,
+    found: Vec<prefix::ProtonPrefix>,
+    sender: relm4::ComponentSender<crate::app::AppModel>,
+) {
+    let alert = adw::AlertDialog::new(Some(&crate::t!("app.steam_prefixes_title")), None);
+    if found.is_empty() {
+        alert.set_body(&crate::t!("app.steam_prefixes_none"));
+        alert.add_response("ok", &crate::t!("dialogs.ok"));
+        alert.set_default_response(Some("ok"));
+        alert.set_close_response("ok");
+        alert.choose(Some(parent), None::<&gtk::gio::Cancellable>, |_| {});
+        return;
+    }
+
+    alert.set_body(&crate::t!("app.steam_prefixes_body"));
+    let list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .build();
+    let mut checks = Vec::with_capacity(found.len());
+    for proton in &found {
+        let check = gtk::CheckButton::builder()
+            .active(proton.linked)
+            .sensitive(!proton.linked)
+            .valign(gtk::Align::Center)
+            .build();
+        let row = adw::ActionRow::builder()
+            .title(proton.display_name())
+            .subtitle(proton.path.display().to_string())
+            .activatable_widget(&check)
+            .build();
+        row.add_prefix(&check);
+        list.append(&row);
+        checks.push(check);
+    }
+    let scrolled = gtk::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .propagate_natural_height(true)
+        .max_content_height(320)
+        .child(&list)
+        .build();
+    alert.set_extra_child(Some(&scrolled));
+    alert.add_response("cancel", &crate::t!("dialogs.cancel"));
+    alert.add_response("link", &crate::t!("app.steam_prefixes_link"));
+    alert.set_response_appearance("link", adw::ResponseAppearance::Suggested);
+    alert.set_default_response(Some("link"));
+    alert.set_close_response("cancel");
+    alert.choose(
+        Some(parent),
+        None::<&gtk::gio::Cancellable>,
+        move |response| {
+            if response != "link" {
+                return;
+            }
+            let chosen: Vec<prefix::ProtonPrefix> = found
+                .iter()
+                .zip(&checks)
+                .filter(|(proton, check)| !proton.linked && check.is_active())
+                .map(|(proton, _)| proton.clone())
+                .collect();
+            if chosen.is_empty() {
+                return;
+            }
+            let sender = sender.clone();
+            std::thread::spawn(move || {
+                let svc = AppService::global();
+                let pm = svc.prefix_manager();
+                let failures: Vec<String> = chosen
+                    .iter()
+                    .filter_map(|proton| {
+                        pm.link_external_prefix(&proton.path, &proton.display_name())
+                            .err()
+                            .map(|e| format!("{}: {}", proton.display_name(), e))
+                    })
+                    .collect();
+                sender.input(AppMsg::RefreshPrefixes);
+                if !failures.is_empty() {
+                    error!("[steam] failed to link: {}", failures.join("; "));
+                    sender.input(AppMsg::ShowError(
+                        crate::tf!("app.link_prefix_failed", "error" => &failures.join("\n")),
+                    ));
+                }
+            });
+        },
+    );
+}
+
 /// Scan for applications and update config in-place
 pub fn handle_scan_for_applications(prefixes: &mut [base::WinePrefix], index: usize) {
     if index < prefixes.len() {
@@ -40,6 +175,58 @@ pub fn handle_scan_for_applications(prefixes: &mut [base::WinePrefix], index: us
     }
 }
 
+/// A batch action on the prefixes checked in the prefix list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchAction {
+    Delete,
+    Backup,
+    Refresh,
+}
+
+impl BatchAction {
+    /// Progress text for item `completed` of `total` (1-based).
+    pub fn progress_label(self, completed: usize, total: usize) -> String {
+        let (completed, total) = (completed.to_string(), total.to_string());
+        match self {
+            BatchAction::Delete => {
+                crate::tf!("app_page.batch_deleting", "completed" => &completed, "total" => &total)
+            }
+            BatchAction::Backup => {
+                crate::tf!("app_page.batch_backing_up", "completed" => &completed, "total" => &total)
+            }
+            BatchAction::Refresh => {
+                crate::tf!("app_page.batch_refreshing", "completed" => &completed, "total" => &total)
+            }
+        }
+    }
+}
+
+/// Run `op` on each item in turn (background thread). Progress arrives as
+/// `BatchProgress`; failures are collected as "name: error" lines into
+/// `BatchFinished`.
+pub fn handle_prefix_batch<T, F>(
+    items: Vec<(String, T)>,
+    action: BatchAction,
+    sender: relm4::ComponentSender<crate::app::AppModel>,
+    op: F,
+) where
+    T: Send + 'static,
+    F: Fn(T) -> std::result::Result<(), String> + Send + 'static,
+{
+    std::thread::spawn(move || {
+        let total = items.len();
+        let mut failures = Vec::new();
+        for (i, (name, item)) in items.into_iter().enumerate() {
+            let _ = sender.input(AppMsg::BatchProgress(action, i + 1, total));
+            if let Err(e) = op(item) {
+                error!("[app] batch action failed for '{}': {}", name, e);
+                failures.push(format!("{}: {}", name, e));
+            }
+        }
+        let _ = sender.input(AppMsg::BatchFinished(failures));
+    });
+}
+
 /// Sync all prefixes (background thread)
 pub fn handle_sync_prefixes(
     sender: relm4::ComponentSender<crate::app::AppModel>,
@@ -55,6 +242,17 @@ pub fn handle_sync_prefixes(
     });
 }
 
+/// Initial prefix load at startup (background thread). Each prefix is sent
+/// to the UI as soon as it loads so the list fills in progressively.
+pub fn handle_initial_scan(sender: relm4::ComponentSender<crate::app::AppModel>) {
+    std::thread::spawn(move || {
+        let found = sender.input_sender().clone();
+        let prefixes = AppService::global()
+            .scan_prefixes_streaming(|p| found.emit(AppMsg::PrefixFound(p.clone())));
+        let _ = sender.input(AppMsg::InitialScanComplete(prefixes));
+    });
+}
+
 /// Refresh prefix list (background thread)
 pub fn handle_refresh_prefixes(sender: relm4::ComponentSender<crate::app::AppModel>) {
     std::thread::spawn(move || {
@@ -63,6 +261,95 @@ pub fn handle_refresh_prefixes(sender: relm4::ComponentSender<crate::app::AppMod
     });
 }
 
+/// Quiet period after the last change before the prefix list is refreshed.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(1500);
+/// How long a new directory may take to become a complete prefix (e.g. while
+/// it's being copied in) before the watcher stops waiting for it.
+const WATCH_PENDING_LIMIT: Duration = Duration::from_secs(120);
+
+fn looks_like_prefix(path: &Path) -> bool {
+    path.join("system.reg").exists() && path.join("user.reg").exists()
+}
+
+/// Watch the Wine directory for prefixes appearing or disappearing and send
+/// `RefreshPrefixes` once things settle. Only direct children are watched,
+/// so a recursive copy produces one event rather than one per file. A new
+/// directory that isn't a complete prefix yet is re-checked until it is.
+///
+/// Dropping (or sending on) the returned handle stops the watcher.
+pub fn watch_wine_dir(
+    wine_dir: PathBuf,
+    sender: relm4::ComponentSender<crate::app::AppModel>,
+) -> mpsc::Sender<()> {
+    let (kill_tx, kill_rx) = mpsc::channel::<()>();
+    std::thread::spawn(move || {
+        let (tx, rx) = mpsc::channel::<Vec<PathBuf>>();
+        let mut watcher = match recommended_watcher(move |res: notify::Result<notify::Event>| {
+            let Ok(event) = res else { return };
+            if matches!(
+                event.kind,
+                EventKind::Create(_)
+                    | EventKind::Remove(_)
+                    | EventKind::Modify(ModifyKind::Name(_))
+            ) {
+                let _ = tx.send(event.paths);
+            }
+        }) {
+            Ok(w) => w,
+            Err(e) => {
+                error!("[app] wine dir watch init: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = watcher.watch(&wine_dir, RecursiveMode::NonRecursive) {
+            error!("[app] failed to watch {}: {}", wine_dir.display(), e);
+            return;
+        }
+
+        let stopped = || !matches!(kill_rx.try_recv(), Err(mpsc::TryRecvError::Empty));
+        let mut pending: Vec<(PathBuf, Instant)> = Vec::new();
+        loop {
+            match rx.recv_timeout(Duration::from_millis(500)) {
+                Ok(mut paths) => {
+                    while let Ok(more) = rx.recv_timeout(WATCH_DEBOUNCE) {
+                        paths.extend(more);
+                    }
+                    for path in paths {
+                        if path.is_dir()
+                            && !looks_like_prefix(&path)
+                            && !pending.iter().any(|(p, _)| *p == path)
+                        {
+                            pending.push((path, Instant::now()));
+                        }
+                    }
+                    if stopped() {
+                        break;
+                    }
+                    let _ = sender.input(AppMsg::RefreshPrefixes);
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    if stopped() {
+                        break;
+                    }
+                    let mut completed = false;
+                    pending.retain(|(path, since)| {
+                        if looks_like_prefix(path) {
+                            completed = true;
+                            return false;
+                        }
+                        path.is_dir() && since.elapsed() < WATCH_PENDING_LIMIT
+                    });
+                    if completed {
+                        let _ = sender.input(AppMsg::RefreshPrefixes);
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    });
+    kill_tx
+}
+
 /// Handle config update with optional graphics backend switching
 pub fn handle_config_updated(
     index: usize,
@@ -25,14 +25,21 @@ pub fn setup_menu_bar(app: gtk::Application, sender: ComponentSender<crate::app:
         let file_menu = Menu::new();
         file_menu.append(Some(&crate::t!("menu.new_prefix")), Some("app.new-prefix"));
         file_menu.append(Some(&crate::t!("menu.import_prefix")), Some("app.import-prefix"));
+        file_menu.append(Some(&crate::t!("menu.link_prefix")), Some("app.link-prefix"));
+        file_menu.append(Some(&crate::t!("menu.find_steam_prefixes")), Some("app.find-steam-prefixes"));
         file_menu.append(Some(&crate::t!("menu.preferences")), Some("app.preferences"));
         file_menu.append(Some(&crate::t!("menu.quit")), Some("app.quit"));
         menubar.append_submenu(Some(&crate::t!("menu.file")), &file_menu);
 
         let view_menu = Menu::new();
         view_menu.append(Some(&crate::t!("menu.toggle_sidebar")), Some("app.toggle-sidebar"));
+        view_menu.append(Some(&crate::t!("menu.sort_recent")), Some("app.sort-recent"));
         menubar.append_submenu(Some(&crate::t!("menu.view")), &view_menu);
 
+        let help_menu = Menu::new();
+        help_menu.append(Some(&crate::t!("menu.about")), Some("app.about"));
+        menubar.append_submenu(Some(&crate::t!("menu.help")), &help_menu);
+
         app.set_menubar(Some(&menubar));
     }
 }
@@ -58,6 +65,20 @@ fn register_menu_actions(app: &gtk::Application, sender: &ComponentSender<crate:
     app.add_action(&import_prefix_action);
     app.set_accels_for_action("app.import-prefix", &["<primary>i"]);
 
+    let link_prefix_action = SimpleAction::new("link-prefix", None);
+    let s = sender.clone();
+    link_prefix_action.connect_activate(move |_, _| {
+        s.input(AppMsg::LinkExternalPrefix);
+    });
+    app.add_action(&link_prefix_action);
+
+    let steam_prefixes_action = SimpleAction::new("find-steam-prefixes", None);
+    let s = sender.clone();
+    steam_prefixes_action.connect_activate(move |_, _| {
+        s.input(AppMsg::DiscoverProtonPrefixes);
+    });
+    app.add_action(&steam_prefixes_action);
+
     let preferences_action = SimpleAction::new("preferences", None);
     let s = sender.clone();
     preferences_action.connect_activate(move |_, _| {
@@ -74,6 +95,20 @@ fn register_menu_actions(app: &gtk::Application, sender: &ComponentSender<crate:
     app.add_action(&toggle_sidebar_action);
     app.set_accels_for_action("app.toggle-sidebar", &["<primary>backslash"]);
 
+    let sort_recent = prefix::Settings::load().is_some_and(|s| s.sort_prefixes_by_last_used);
+    let sort_recent_action = SimpleAction::new_stateful("sort-recent", None, &sort_recent.to_variant());
+    let s = sender.clone();
+    sort_recent_action.connect_activate(move |action, _| {
+        let enabled = !action.state().and_then(|v| v.get::<bool>()).unwrap_or(false);
+        action.set_state(&enabled.to_variant());
+        s.input(AppMsg::SetSortByLastUsed(enabled));
+    });
+    app.add_action(&sort_recent_action);
+
+    let about_action = SimpleAction::new("about", None);
+    about_action.connect_activate(|_, _| show_about());
+    app.add_action(&about_action);
+
     let app_quit = app.clone();
     let quit_action = SimpleAction::new("quit", None);
     quit_action.connect_activate(move |_, _| {
@@ -83,6 +118,53 @@ fn register_menu_actions(app: &gtk::Application, sender: &ComponentSender<crate:
     app.set_accels_for_action("app.quit", &["<primary>q"]);
 }
 
+/// Show the About dialog. Its troubleshooting page carries the Wine, tool
+/// and toolkit versions, with libadwaita's own copy and save buttons.
+fn show_about() {
+    let about = adw::AboutDialog::new();
+    about.set_application_name("Tequila");
+    about.set_application_icon("com.github.anson2251.tequila");
+    about.set_version(env!("CARGO_PKG_VERSION"));
+    about.set_comments("Wine Prefix Manager");
+    about.set_developer_name("Anson2251");
+    about.set_debug_info(&crate::t!("app.diagnostics_loading"));
+    about.set_debug_info_filename("tequila-diagnostics.txt");
+    let parent = gtk::gio::Application::default()
+        .and_then(|a| a.downcast::<gtk::Application>().ok())
+        .and_then(|app| app.active_window());
+    about.present(parent.as_ref());
+
+    // Running wine --version can take a moment on a cold prefix
+    gtk::glib::spawn_future_local(async move {
+        let pm = service::AppService::global().prefix_manager().clone();
+        let Ok(diagnostics) = gtk::gio::spawn_blocking(move || pm.diagnostics()).await else {
+            return;
+        };
+        let header = [
+            ("Tequila", env!("CARGO_PKG_VERSION").to_string()),
+            (
+                "GTK",
+                format!(
+                    "{}.{}.{}",
+                    gtk::major_version(),
+                    gtk::minor_version(),
+                    gtk::micro_version()
+                ),
+            ),
+            (
+                "libadwaita",
+                format!(
+                    "{}.{}.{}",
+                    adw::major_version(),
+                    adw::minor_version(),
+                    adw::micro_version()
+                ),
+            ),
+        ];
+        about.set_debug_info(&diagnostics.report(&header));
+    });
+}
+
 // ── macOS native menu (NSMenu / NSMenuItem) ──────────────────────────────
 
 #[cfg(target_os = "macos")]
@@ -106,18 +188,7 @@ objc2::define_class!(
             use objc2::msg_send;
             let tag: isize = unsafe { msg_send![sender, tag] };
             match tag {
-                1 => {
-                    let about = adw::AboutDialog::new();
-                    about.set_application_name("Tequila");
-                    about.set_application_icon("com.github.anson2251.tequila");
-                    about.set_version("0.1.0");
-                    about.set_comments("Wine Prefix Manager");
-                    about.set_developer_name("Anson2251");
-                    let parent = gtk::gio::Application::default()
-                        .and_then(|a| a.downcast::<gtk::Application>().ok())
-                        .and_then(|app| app.active_window());
-                    about.present(parent.as_ref());
-                }
+                1 => show_about(),
                 2 => {
                     if let Some(cb) = MENU_CALLBACK.get() {
                         cb(AppMsg::ShowCreatePrefixDialog);
@@ -140,6 +211,13 @@ objc2::define_class!(
                         }
                     }
                 }
+                6 => {
+                    if let Some(cb) = MENU_CALLBACK.get() {
+                        let enabled = prefix::Settings::load()
+                            .is_some_and(|s| s.sort_prefixes_by_last_used);
+                        cb(AppMsg::SetSortByLastUsed(!enabled));
+                    }
+                }
                 _ => {}
             }
         }
@@ -267,6 +345,13 @@ fn setup_macos_native_menu(_app: &gtk::Application, sender: ComponentSender<crat
         );
         view_menu.addItem(&sidebar_item);
 
+        let sort_recent_item = NSMenuItem::init(mtm.alloc::<NSMenuItem>());
+        sort_recent_item.setTitle(&NSString::from_str(&crate::t!("menu.sort_recent")));
+        sort_recent_item.setAction(Some(sel!(handleMenuAction:)));
+        sort_recent_item.setTarget(Some(&*target as &NSObject));
+        sort_recent_item.setTag(6);
+        view_menu.addItem(&sort_recent_item);
+
         // ── Edit Menu (responder-chain with nil target) ──
         let edit_menu_item = NSMenuItem::init(mtm.alloc::<NSMenuItem>());
         let edit_menu = NSMenu::init(mtm.alloc::<NSMenu>());
@@ -1,6 +1,7 @@
 pub mod handlers;
 pub mod menu;
 pub mod resources;
+pub mod search;
 pub use resources::initialize_custom_resources;
 
 use adw::prelude::*;
@@ -47,6 +48,7 @@ pub struct AppModel {
     #[tracker::do_not_track]
     pub switcher: adw::ViewSwitcher,
     pub syncing: bool,
+    pub creating_prefix: bool,
     pub sidebar_visible: bool,
     #[tracker::do_not_track]
     main_window: gtk::ApplicationWindow,
@@ -67,14 +69,47 @@ pub struct AppModel {
     import_dialog: Option<relm4::Controller<crate::prefix::import_dialog::ImportDialogModel>>,
     #[tracker::do_not_track]
     sidebar_btn: gtk::Button,
+    #[tracker::do_not_track]
+    new_prefix_btn: gtk::Button,
+    #[tracker::do_not_track]
+    creation_pulse: Option<glib::SourceId>,
+    /// Prefix to select once the next reload lands (e.g. a freshly created one)
+    #[tracker::do_not_track]
+    pending_select: Option<PathBuf>,
+    /// Prefix to scan for new applications once it is selected, after an
+    /// installer ran in it during creation
+    #[tracker::do_not_track]
+    pending_scan: Option<PathBuf>,
+    #[tracker::do_not_track]
+    global_search: search::GlobalSearch,
+    /// Stops the Wine directory watcher when the model is dropped
+    #[tracker::do_not_track]
+    _wine_dir_watch: std::sync::mpsc::Sender<()>,
 }
 
 #[derive(Debug)]
 pub enum AppMsg {
     CreatePrefix,
     DeletePrefix(usize),
+    /// A single prefix was deleted in the background.
+    PrefixDeleted(PathBuf),
+    /// Batch actions from the prefix list's select mode.
+    DeletePrefixes(Vec<PathBuf>),
+    BackupPrefixes(Vec<PathBuf>),
+    BackupPrefixesTo(Vec<PathBuf>, PathBuf),
+    RefreshSelectedPrefixes(Vec<PathBuf>),
+    /// A batch action started on item `.1` (1-based) of `.2`.
+    BatchProgress(handlers::BatchAction, usize, usize),
+    /// A batch action finished; carries one line per failed prefix.
+    BatchFinished(Vec<String>),
     ExportPrefix(usize),
     ImportPrefix,
+    /// Pick a prefix directory outside the wine directory and link it in.
+    LinkExternalPrefix,
+    /// Look for Proton prefixes in the Steam libraries.
+    DiscoverProtonPrefixes,
+    /// Offer the found Proton prefixes for linking.
+    ShowProtonPrefixes(Vec<prefix::ProtonPrefix>),
     ShowError(String),
     ShowImportDialog {
         name: String,
@@ -83,21 +118,43 @@ pub enum AppMsg {
     },
     OpenInFileManager(usize),
     OpenInTerminal(usize),
-    LaunchPrefix(usize),
+    OpenWinecfg(usize),
+    /// Ask for a name and store the prefix's settings as a template.
+    SaveAsTemplate(usize),
     LaunchExecutable(usize, usize), // prefix index, executable index
+    /// Like `LaunchExecutable`, past the architecture check.
+    LaunchConfirmed(usize, usize),
     RefreshPrefixes,
     SelectPrefix(usize),
     ShowPrefixDetails(usize),
+    /// Open a prefix on its configuration tab, where the registry lives.
+    ShowPrefixOverrides(usize),
     // ShowAppManager(usize),
     HideDetails,
     ConfigUpdated(usize, prefix::config::PrefixConfig),
     ScanForApplications(usize),
     ShowCreatePrefixDialog,
+    /// Query the global app search (empty clears the results).
+    GlobalSearch(String),
+    PrefixCreationStarted(String),
+    /// Replace the progress text while a prefix is being created.
+    PrefixCreationStatus(String),
+    PrefixCreationFinished(Option<PathBuf>),
+    /// Scan the prefix at the path for new applications once it is selected.
+    ScanAfterInstall(PathBuf),
+    /// Scan the selected prefix and show what was found in the Add popover.
+    ShowNewApplications,
     SyncComplete(Vec<WinePrefix>),
     SyncPrefixes,
     ReloadPrefixes(Vec<WinePrefix>),
+    /// A prefix finished loading during the startup scan.
+    PrefixFound(WinePrefix),
+    /// The startup scan finished; carries the full list sorted by name.
+    InitialScanComplete(Vec<WinePrefix>),
     SyncProgress(usize, usize),
     ToggleSidebar,
+    /// List prefixes most recently used first (`true`) or by name.
+    SetSortByLastUsed(bool),
     ShowSettings,
     RuntimesUpdated(RuntimeManager),
     ReinitComplete(usize, std::result::Result<(), String>),
@@ -130,6 +187,9 @@ impl SimpleComponent for AppModel {
     ) -> ComponentParts<Self> {
         let _sender_clone = sender.clone();
 
+        // Apply the saved color scheme before any window is shown
+        resources::apply_theme(&prefix::Settings::current().theme);
+
         // Build header bar early
         let header_bar = gtk::HeaderBar::new();
         #[cfg(target_os = "macos")]
@@ -182,6 +242,9 @@ impl SimpleComponent for AppModel {
         });
         header_bar.pack_end(&settings_btn);
 
+        let global_search = search::GlobalSearch::new(&sender);
+        header_bar.pack_end(&global_search.button);
+
         let switcher = adw::ViewSwitcher::builder()
             .policy(adw::ViewSwitcherPolicy::Wide)
             .build();
@@ -218,14 +281,9 @@ impl SimpleComponent for AppModel {
         );
         let service = AppService::global();
 
-        // Load prefixes from filesystem + JSON config files (fast, user-editable)
-        let prefixes = service.scan_prefixes();
-        // Trigger background scan if no cached scan results exist yet
-        let needs_sync = !prefixes.is_empty()
-            && prefixes
-                .iter()
-                .all(|p| !service.has_scanned_prefix(&p.path.to_string_lossy()));
-        info!("[app] loaded {} prefixes", prefixes.len());
+        // Prefixes are loaded in the background and streamed into the list
+        // (see `handlers::handle_initial_scan`)
+        let prefixes: Vec<WinePrefix> = Vec::new();
 
         let prefix_list = PrefixListModel::builder()
             .launch((prefixes.clone(), None))
@@ -246,6 +304,27 @@ impl SimpleComponent for AppModel {
                 crate::prefix::list::PrefixListOutput::OpenInTerminal(index) => {
                     AppMsg::OpenInTerminal(index)
                 }
+                crate::prefix::list::PrefixListOutput::OpenWinecfg(index) => {
+                    AppMsg::OpenWinecfg(index)
+                }
+                crate::prefix::list::PrefixListOutput::SaveAsTemplate(index) => {
+                    AppMsg::SaveAsTemplate(index)
+                }
+                crate::prefix::list::PrefixListOutput::ShowOverrides(index) => {
+                    AppMsg::ShowPrefixOverrides(index)
+                }
+                crate::prefix::list::PrefixListOutput::CreatePrefix => {
+                    AppMsg::ShowCreatePrefixDialog
+                }
+                crate::prefix::list::PrefixListOutput::BackupPrefixes(paths) => {
+                    AppMsg::BackupPrefixes(paths)
+                }
+                crate::prefix::list::PrefixListOutput::RefreshPrefixes(paths) => {
+                    AppMsg::RefreshSelectedPrefixes(paths)
+                }
+                crate::prefix::list::PrefixListOutput::DeletePrefixes(paths) => {
+                    AppMsg::DeletePrefixes(paths)
+                }
             });
 
         let config_tab = PrefixConfigModel::builder()
@@ -259,6 +338,12 @@ impl SimpleComponent for AppModel {
                 crate::prefix::config::PrefixConfigOutput::ConfigUpdated(config) => {
                     AppMsg::ConfigUpdated(0, config)
                 }
+                crate::prefix::config::PrefixConfigOutput::CreationStarted(name) => {
+                    AppMsg::PrefixCreationStarted(name)
+                }
+                crate::prefix::config::PrefixConfigOutput::CreationFinished(path) => {
+                    AppMsg::PrefixCreationFinished(path)
+                }
             });
 
         let svc = AppService::global();
@@ -322,6 +407,16 @@ impl SimpleComponent for AppModel {
             .set_icon_name(Some("document-properties-symbolic"));
         switcher.set_stack(Some(&content_stack));
 
+        // Move keyboard focus into the apps list when its tab becomes visible
+        {
+            let apps_sender = app_manager.sender().clone();
+            content_stack.connect_visible_child_name_notify(move |stack| {
+                if stack.visible_child_name().as_deref() == Some("apps") {
+                    let _ = apps_sender.send(crate::apps::AppManagerMsg::FocusList);
+                }
+            });
+        }
+
         // Wrapper Stack: show either empty page or tabbed content
         let content_box = gtk::Stack::builder()
             .hexpand(true)
@@ -375,10 +470,6 @@ impl SimpleComponent for AppModel {
         let sync_overlay = gtk::Overlay::new();
         sync_overlay.set_child(Some(&flap_widget));
         sync_overlay.add_overlay(&sync_overlay_box);
-        if needs_sync {
-            sync_overlay_box.set_visible(true);
-            sync_progress_label.set_label(&crate::t!("app_page.scanning"));
-        }
         // macOS: remove rounded window corners, macOS would do that
         #[cfg(target_os = "macos")]
         {
@@ -426,16 +517,23 @@ impl SimpleComponent for AppModel {
             export_dialog: None,
             import_dialog: None,
             sidebar_btn: sidebar_btn.clone(),
+            new_prefix_btn: new_prefix_btn.clone(),
+            creation_pulse: None,
+            pending_select: None,
+            pending_scan: None,
+            global_search,
             content_stack,
             content_box,
             flap,
             switcher,
             syncing: false,
+            creating_prefix: false,
             sidebar_visible: has_prefixes,
             main_window: root.clone(),
             sync_overlay: sync_overlay_box,
             sync_progress_bar,
             sync_progress_label,
+            _wine_dir_watch: handlers::watch_wine_dir(wine_dir, sender.clone()),
             tracker: 0,
         };
 
@@ -463,12 +561,7 @@ impl SimpleComponent for AppModel {
             glib::ControlFlow::Break
         });
 
-        if needs_sync {
-            let bg_sender = sender.clone();
-            glib::spawn_future_local(async move {
-                bg_sender.input(AppMsg::SyncPrefixes);
-            });
-        }
+        handlers::handle_initial_scan(sender.clone());
 
         ComponentParts { model, widgets }
     }
@@ -476,11 +569,59 @@ impl SimpleComponent for AppModel {
     fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>) {
         match msg {
             AppMsg::ShowCreatePrefixDialog => {
+                if self.creating_prefix {
+                    return;
+                }
                 let dialog = crate::prefix::create_dialog::CreatePrefixDialog::builder()
                     .launch((self.main_window.clone(),))
                     .forward(sender.input_sender(), |msg| msg);
                 self.create_prefix_dialog = Some(dialog);
             }
+            AppMsg::GlobalSearch(query) => {
+                self.global_search.populate(&self.prefixes, &query, &sender);
+            }
+            AppMsg::PrefixCreationStarted(name) => {
+                self.set_creating_prefix(true);
+                self.new_prefix_btn.set_sensitive(false);
+                self.sync_overlay.set_visible(true);
+                self.sync_progress_bar.set_fraction(0.0);
+                self.sync_progress_label
+                    .set_label(&crate::tf!("app_page.creating_prefix", "name" => &name));
+
+                let pb = self.sync_progress_bar.clone();
+                self.creation_pulse = Some(glib::timeout_add_local(
+                    std::time::Duration::from_millis(100),
+                    move || {
+                        pb.pulse();
+                        glib::ControlFlow::Continue
+                    },
+                ));
+            }
+            AppMsg::PrefixCreationStatus(label) => {
+                self.sync_progress_label.set_label(&label);
+            }
+            AppMsg::PrefixCreationFinished(path) => {
+                self.pending_select = path;
+                self.set_creating_prefix(false);
+                self.new_prefix_btn.set_sensitive(true);
+                if let Some(id) = self.creation_pulse.take() {
+                    id.remove();
+                }
+                if !self.syncing {
+                    self.sync_overlay.set_visible(false);
+                }
+                self.create_prefix_dialog = None;
+                sender.input(AppMsg::RefreshPrefixes);
+            }
+            AppMsg::ScanAfterInstall(path) => {
+                self.pending_scan = Some(path);
+            }
+            AppMsg::ShowNewApplications => {
+                self.app_manager
+                    .emit(crate::apps::AppManagerMsg::ScanForApplications);
+                self.app_manager
+                    .emit(crate::apps::AppManagerMsg::ShowAddPopover);
+            }
             AppMsg::CreatePrefix => {
                 // Legacy handler - now redirected to dialog
                 sender.input(AppMsg::ShowCreatePrefixDialog);
@@ -488,30 +629,115 @@ impl SimpleComponent for AppModel {
             AppMsg::DeletePrefix(index) => {
                 if index < self.prefixes.len() {
                     let prefix_path = self.prefixes[index].path.clone();
-
-                    if self.service.delete_prefix(&prefix_path, &mut self.prefixes) {
-                        if self.selected_prefix == Some(index) {
-                            self.selected_prefix = None;
-                        } else if let Some(selected) = self.selected_prefix {
-                            if selected > index {
-                                self.selected_prefix = Some(selected - 1);
+                    let pm = AppService::global().prefix_manager().clone();
+                    let s = sender.clone();
+                    // Stopping the prefix's wineserver waits for its apps to exit
+                    gtk::glib::spawn_future_local(async move {
+                        let path = prefix_path.clone();
+                        match gtk::gio::spawn_blocking(move || pm.delete_prefix(&path)).await {
+                            Ok(Ok(())) => s.input(AppMsg::PrefixDeleted(prefix_path)),
+                            Ok(Err(e)) => {
+                                log::error!("[app] failed to delete prefix: {}", e);
+                                s.input(AppMsg::ShowError(e.to_string()));
                             }
+                            Err(_) => log::error!("[app] prefix delete task panicked"),
                         }
-                        if self.prefixes.is_empty() {
-                            sender.input(AppMsg::HideDetails);
-                        }
-                        sender.input(AppMsg::RefreshPrefixes);
+                    });
+                }
+            }
+            AppMsg::PrefixDeleted(prefix_path) => {
+                if let Some(index) = self.prefixes.iter().position(|p| p.path == prefix_path) {
+                    self.prefixes.remove(index);
+                    log::info!("[app] deleted prefix: {}", prefix_path.display());
+                    if self.selected_prefix == Some(index) {
+                        self.selected_prefix = None;
+                    } else if let Some(selected) = self.selected_prefix
+                        && selected > index
+                    {
+                        self.selected_prefix = Some(selected - 1);
+                    }
+                    if self.prefixes.is_empty() {
+                        sender.input(AppMsg::HideDetails);
                     }
                 }
+                sender.input(AppMsg::RefreshPrefixes);
+            }
+            AppMsg::DeletePrefixes(paths) => {
+                let pm = AppService::global().prefix_manager().clone();
+                handlers::handle_prefix_batch(
+                    self.named(paths),
+                    handlers::BatchAction::Delete,
+                    sender.clone(),
+                    move |path| pm.delete_prefix(&path).map_err(|e| e.to_string()),
+                );
             }
-            AppMsg::LaunchPrefix(index) => {
+            AppMsg::BackupPrefixes(paths) => {
+                let s = sender.clone();
+                crate::dialogs::pick_folder(
+                    &self.main_window.clone().upcast::<gtk::Window>(),
+                    None,
+                    move |dir| s.input(AppMsg::BackupPrefixesTo(paths.clone(), PathBuf::from(dir))),
+                );
+            }
+            AppMsg::BackupPrefixesTo(paths, dest) => {
+                let pm = AppService::global().prefix_manager().clone();
+                // Same defaults as the export dialog: user data included, level 3
+                handlers::handle_prefix_batch(
+                    self.named(paths),
+                    handlers::BatchAction::Backup,
+                    sender.clone(),
+                    move |path| {
+                        pm.export_prefix(&path, &dest, true, 3, |_, _| {})
+                            .map(|archive| log::info!("[app] backed up to {}", archive.display()))
+                            .map_err(|e| e.to_string())
+                    },
+                );
+            }
+            AppMsg::RefreshSelectedPrefixes(paths) => {
+                let items = self
+                    .prefixes
+                    .iter()
+                    .filter(|p| paths.contains(&p.path))
+                    .map(|p| (p.name.clone(), (p.path.clone(), p.config.clone())))
+                    .collect();
+                handlers::handle_prefix_batch(
+                    items,
+                    handlers::BatchAction::Refresh,
+                    sender.clone(),
+                    |(path, config)| {
+                        let result =
+                            service::sync::scan_prefix_apps(&AppService::global(), &path, config);
+                        result.error.map_or(Ok(()), Err)
+                    },
+                );
+            }
+            AppMsg::BatchProgress(action, current, total) => {
+                self.sync_overlay.set_visible(true);
+                self.sync_progress_bar
+                    .set_fraction((current - 1) as f64 / total.max(1) as f64);
+                self.sync_progress_label
+                    .set_label(&action.progress_label(current, total));
+            }
+            AppMsg::BatchFinished(failures) => {
+                if !self.syncing && !self.creating_prefix {
+                    self.sync_overlay.set_visible(false);
+                }
+                if !failures.is_empty() {
+                    let errors = failures.join("\n");
+                    sender.input(AppMsg::ShowError(
+                        crate::tf!("app_page.batch_failed", "errors" => &errors),
+                    ));
+                }
+                sender.input(AppMsg::RefreshPrefixes);
+            }
+            AppMsg::OpenWinecfg(index) => {
                 if index < self.prefixes.len() {
                     let prefix_path = self.prefixes[index].path.clone();
 
                     if let Err(e) = service::launch::launch_winecfg(&self.service, &prefix_path) {
-                        let msg = crate::tf!("apps.launch_failed_winecfg", "error" => &e);
+                        let msg = crate::tf!("prefix.winecfg_failed", "error" => &e);
                         let alert = adw::AlertDialog::new(
-                            Some(&crate::t!("apps.launch_failed")),
+                            Some(&crate::t!("dialogs.error")),
                             Some(&msg),
                         );
                         alert.add_response("ok", &crate::t!("dialogs.ok"));
@@ -525,8 +751,59 @@ impl SimpleComponent for AppModel {
                     }
                 }
             }
+            AppMsg::SaveAsTemplate(index) => {
+                if let Some(prefix) = self.prefixes.get(index) {
+                    handlers::handle_save_as_template(
+                        &self.main_window.clone().upcast::<gtk::Window>(),
+                        prefix,
+                        sender.clone(),
+                    );
+                }
+            }
             AppMsg::LaunchExecutable(prefix_index, executable_index) => {
+                let Some(prefix) = self.prefixes.get(prefix_index) else {
+                    return;
+                };
+                let Some(executable) = prefix.config.registered_executables.get(executable_index)
+                else {
+                    return;
+                };
+                match prefix::architecture_mismatch(
+                    &prefix.path,
+                    &prefix.config.architecture,
+                    executable,
+                ) {
+                    Some(mismatch) => {
+                        let s = sender.clone();
+                        crate::apps::confirm_mismatched_launch(
+                            Some(&self.main_window.clone().upcast::<gtk::Window>()),
+                            &executable.name,
+                            mismatch,
+                            move || {
+                                s.input(AppMsg::LaunchConfirmed(prefix_index, executable_index))
+                            },
+                        );
+                    }
+                    None => sender.input(AppMsg::LaunchConfirmed(prefix_index, executable_index)),
+                }
+            }
+            AppMsg::LaunchConfirmed(prefix_index, executable_index) => {
                 if prefix_index < self.prefixes.len() {
+                    let prefix = &mut self.prefixes[prefix_index];
+                    if let Some(exe_path) = prefix
+                        .config
+                        .registered_executables
+                        .get(executable_index)
+                        .map(|e| e.executable_path.clone())
+                    {
+                        service::config_ops::record_launch(
+                            &self.service,
+                            &prefix.path,
+                            &mut prefix.config,
+                            &exe_path,
+                        );
+                    }
+
                     let prefix_path = &self.prefixes[prefix_index].path;
                     let config = &self.prefixes[prefix_index].config;
 
@@ -595,6 +872,44 @@ impl SimpleComponent for AppModel {
                     }
                 });
             }
+            AppMsg::LinkExternalPrefix => {
+                let parent: gtk::Window = self.main_window.clone().upcast();
+                let s = sender.clone();
+                crate::dialogs::pick_folder(&parent, None, move |path| {
+                    let s = s.clone();
+                    std::thread::spawn(move || {
+                        let svc = AppService::global();
+                        let pm = svc.prefix_manager();
+                        match pm.link_external_prefix(&PathBuf::from(&path), "") {
+                            Ok(_) => s.input(AppMsg::RefreshPrefixes),
+                            Err(e) => s.input(AppMsg::ShowError(
+                                crate::tf!("app.link_prefix_failed", "error" => &e.to_string()),
+                            )),
+                        }
+                    });
+                });
+            }
+            AppMsg::DiscoverProtonPrefixes => {
+                let s = sender.clone();
+                std::thread::spawn(move || {
+                    let found = AppService::global()
+                        .prefix_manager()
+                        .discover_proton_prefixes();
+                    match found {
+                        Ok(found) => s.input(AppMsg::ShowProtonPrefixes(found)),
+                        Err(e) => s.input(AppMsg::ShowError(
+                            crate::tf!("app.link_prefix_failed", "error" => &e.to_string()),
+                        )),
+                    }
+                });
+            }
+            AppMsg::ShowProtonPrefixes(found) => {
+                handlers::handle_show_proton_prefixes(
+                    &self.main_window.clone().upcast::<gtk::Window>(),
+                    found,
+                    sender.clone(),
+                );
+            }
             AppMsg::ShowError(msg) => {
                 let alert = adw::AlertDialog::new(Some(&crate::t!("dialogs.error")), Some(&msg));
                 alert.add_response("ok", &crate::t!("dialogs.ok"));
@@ -629,6 +944,14 @@ impl SimpleComponent for AppModel {
                     sender.input(AppMsg::ShowPrefixDetails(index));
                 }
             }
+            AppMsg::ShowPrefixOverrides(index) => {
+                if index < self.prefixes.len() {
+                    self.prefix_list
+                        .emit(crate::prefix::list::PrefixListMsg::SetSelected(Some(index)));
+                    self.update(AppMsg::ShowPrefixDetails(index), sender.clone());
+                    self.content_stack.set_visible_child_name("config");
+                }
+            }
             AppMsg::ShowPrefixDetails(index) => {
                 if index < self.prefixes.len() {
                     self.selected_prefix = Some(index);
@@ -775,9 +1098,11 @@ impl SimpleComponent for AppModel {
             AppMsg::ScanForApplications(index) => {
                 handlers::handle_scan_for_applications(&mut self.prefixes, index);
             }
-            AppMsg::SyncComplete(fresh) => {
+            AppMsg::SyncComplete(mut fresh) => {
+                sort_prefixes(&mut fresh);
                 self.set_syncing(false);
                 self.sync_overlay.set_visible(false);
+                let previous = self.selected_path();
                 self.prefixes = fresh.clone();
 
                 // Close sidebar if all prefixes were removed
@@ -791,9 +1116,12 @@ impl SimpleComponent for AppModel {
 
                 self.prefix_list
                     .emit(crate::prefix::list::PrefixListMsg::SetPrefixes(fresh));
+                self.restore_selection(previous, &sender);
             }
-            AppMsg::ReloadPrefixes(fresh) => {
+            AppMsg::ReloadPrefixes(mut fresh) => {
                 // Light reload: update the prefix list without app scanning or auto-select
+                sort_prefixes(&mut fresh);
+                let previous = self.selected_path();
                 self.prefixes = fresh.clone();
 
                 // Close sidebar if all prefixes were removed
@@ -807,6 +1135,33 @@ impl SimpleComponent for AppModel {
 
                 self.prefix_list
                     .emit(crate::prefix::list::PrefixListMsg::SetPrefixes(fresh));
+                self.restore_selection(previous, &sender);
+            }
+            AppMsg::PrefixFound(prefix) => {
+                if self.prefixes.is_empty() {
+                    self.sidebar_btn.set_sensitive(true);
+                    self.set_sidebar_visible(true);
+                    self.flap.set_show_sidebar(true);
+                }
+                self.prefixes.push(prefix.clone());
+                self.prefix_list
+                    .emit(crate::prefix::list::PrefixListMsg::AppendPrefix(prefix));
+            }
+            AppMsg::InitialScanComplete(prefixes) => {
+                info!("[app] loaded {} prefixes", prefixes.len());
+                // Trigger background scan if no cached scan results exist yet,
+                // unless the user only wants scans from the Add popover
+                let manual_scan = prefix::Settings::load().is_some_and(|s| s.manual_app_scan);
+                let needs_sync = !manual_scan
+                    && !prefixes.is_empty()
+                    && prefixes
+                        .iter()
+                        .all(|p| !self.service.has_scanned_prefix(&p.path.to_string_lossy()));
+                // Re-sort the arrival-ordered list
+                sender.input(AppMsg::ReloadPrefixes(prefixes));
+                if needs_sync {
+                    sender.input(AppMsg::SyncPrefixes);
+                }
             }
             AppMsg::SyncPrefixes => {
                 if !self.syncing {
@@ -826,6 +1181,13 @@ impl SimpleComponent for AppModel {
                 self.sync_progress_label
                     .set_label(&crate::tf!("app_page.sync_progress", "completed" => &completed.to_string(), "total" => &total.to_string()));
             }
+            AppMsg::SetSortByLastUsed(enabled) => {
+                if let Err(e) = prefix::Settings::update(|s| s.sort_prefixes_by_last_used = enabled)
+                {
+                    log::error!("[app] failed to save sort_prefixes_by_last_used: {}", e);
+                }
+                sender.input(AppMsg::ReloadPrefixes(self.prefixes.clone()));
+            }
             AppMsg::ToggleSidebar => {
                 if self.prefixes.is_empty() {
                     return;
@@ -852,3 +1214,73 @@ impl SimpleComponent for AppModel {
         // Update the view based on current state will be handled by Relm4 automatically
     }
 }
+
+impl AppModel {
+    /// Pair each path with its prefix's display name, for batch actions.
+    fn named(&self, paths: Vec<PathBuf>) -> Vec<(String, PathBuf)> {
+        paths
+            .into_iter()
+            .map(|path| {
+                let name = self
+                    .prefixes
+                    .iter()
+                    .find(|p| p.path == path)
+                    .map_or_else(|| path.display().to_string(), |p| p.name.clone());
+                (name, path)
+            })
+            .collect()
+    }
+
+    fn selected_path(&self) -> Option<PathBuf> {
+        self.selected_prefix
+            .and_then(|i| self.prefixes.get(i))
+            .map(|p| p.path.clone())
+    }
+
+    /// Re-apply the selection after the prefix list was replaced. A pending
+    /// selection (e.g. a newly created prefix) wins over the previous one;
+    /// if neither survived the reload, the selection and details are cleared.
+    fn restore_selection(&mut self, previous: Option<PathBuf>, sender: &ComponentSender<Self>) {
+        let pending = self.pending_select.take();
+        let is_pending = pending.is_some();
+        let target = pending.or(previous);
+        let index = target.and_then(|path| self.prefixes.iter().position(|p| p.path == path));
+
+        self.prefix_list
+            .emit(crate::prefix::list::PrefixListMsg::SetSelected(index));
+
+        match index {
+            Some(index) if is_pending => {
+                if !self.sidebar_visible {
+                    self.set_sidebar_visible(true);
+                    self.flap.set_show_sidebar(true);
+                }
+                sender.input(AppMsg::ShowPrefixDetails(index));
+                if self.pending_scan.take().as_ref() == Some(&self.prefixes[index].path) {
+                    sender.input(AppMsg::ShowNewApplications);
+                }
+            }
+            Some(index) => {
+                if self.selected_prefix != Some(index) {
+                    self.selected_prefix = Some(index);
+                    self.prefix_config
+                        .emit(crate::prefix::config::PrefixConfigMsg::SetPrefixIndex(index));
+                }
+            }
+            None => {
+                if self.selected_prefix.take().is_some() {
+                    sender.input(AppMsg::HideDetails);
+                }
+            }
+        }
+    }
+}
+
+/// Put prefixes in the sidebar order chosen in the View menu: by name, or
+/// most recently used first.
+fn sort_prefixes(prefixes: &mut [WinePrefix]) {
+    prefixes.sort_by(|a, b| a.name.cmp(&b.name));
+    if prefix::Settings::load().is_some_and(|s| s.sort_prefixes_by_last_used) {
+        prefix::sort_by_last_used(prefixes);
+    }
+}
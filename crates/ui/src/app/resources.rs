@@ -1,6 +1,6 @@
 use gtk::gdk;
 use gtk4::gio;
-use relm4::gtk;
+use relm4::{adw, gtk};
 
 pub fn initialize_custom_resources() {
     gio::resources_register_include!("icons.gresource").unwrap();
@@ -18,3 +18,16 @@ pub fn initialize_custom_resources() {
         gtk::STYLE_PROVIDER_PRIORITY_APPLICATION,
     );
 }
+
+/// Apply the stored color scheme preference ("auto", "light" or "dark").
+///
+/// Goes through `adw::StyleManager` rather than the
+/// `gtk-application-prefer-dark-theme` setting, which libadwaita ignores.
+pub fn apply_theme(theme: &str) {
+    let scheme = match theme {
+        "light" => adw::ColorScheme::ForceLight,
+        "dark" => adw::ColorScheme::ForceDark,
+        _ => adw::ColorScheme::Default,
+    };
+    adw::StyleManager::default().set_color_scheme(scheme);
+}
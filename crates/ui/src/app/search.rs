@@ -0,0 +1,137 @@
+use crate::AppMsg;
+use adw::prelude::*;
+use prefix::WinePrefix;
+use relm4::{ComponentSender, adw, gtk};
+
+/// Cap on rows shown so a short query against a large library stays snappy.
+const MAX_RESULTS: usize = 50;
+
+/// Header-bar search across every prefix's registered apps.
+pub struct GlobalSearch {
+    pub button: gtk::MenuButton,
+    results: gtk::ListBox,
+    popover: gtk::Popover,
+}
+
+impl GlobalSearch {
+    pub fn new(sender: &ComponentSender<crate::app::AppModel>) -> Self {
+        let entry = gtk::SearchEntry::builder()
+            .placeholder_text(crate::t!("search.placeholder"))
+            .width_chars(32)
+            .build();
+        let results = gtk::ListBox::builder()
+            .selection_mode(gtk::SelectionMode::None)
+            .css_classes(["boxed-list"])
+            .build();
+        let scroller = gtk::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .propagate_natural_height(true)
+            .max_content_height(400)
+            .child(&results)
+            .build();
+
+        let content = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .spacing(8)
+            .margin_top(6)
+            .margin_bottom(6)
+            .margin_start(6)
+            .margin_end(6)
+            .build();
+        content.append(&entry);
+        content.append(&scroller);
+
+        let popover = gtk::Popover::builder().child(&content).build();
+        let button = gtk::MenuButton::builder()
+            .icon_name("system-search-symbolic")
+            .tooltip_text(crate::t!("header.search"))
+            .popover(&popover)
+            .build();
+
+        let s = sender.clone();
+        entry.connect_search_changed(move |entry| {
+            s.input(AppMsg::GlobalSearch(entry.text().to_string()));
+        });
+        // Re-run the last query on open so results reflect any changes
+        let s = sender.clone();
+        let e = entry.clone();
+        popover.connect_show(move |_| {
+            e.grab_focus();
+            s.input(AppMsg::GlobalSearch(e.text().to_string()));
+        });
+
+        Self {
+            button,
+            results,
+            popover,
+        }
+    }
+
+    /// Rebuild the result rows for `query` (case-insensitive substring match
+    /// on the app name) from the already-loaded prefixes.
+    pub fn populate(
+        &self,
+        prefixes: &[WinePrefix],
+        query: &str,
+        sender: &ComponentSender<crate::app::AppModel>,
+    ) {
+        while let Some(row) = self.results.first_child() {
+            self.results.remove(&row);
+        }
+
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            self.results.set_visible(false);
+            return;
+        }
+        self.results.set_visible(true);
+
+        let matches = prefixes
+            .iter()
+            .enumerate()
+            .flat_map(|(pi, prefix)| {
+                prefix
+                    .config
+                    .registered_executables
+                    .iter()
+                    .enumerate()
+                    .map(move |(ei, exe)| (pi, ei, prefix, exe))
+            })
+            .filter(|(_, _, _, exe)| exe.name.to_lowercase().contains(&query))
+            .take(MAX_RESULTS);
+
+        let mut count = 0;
+        for (prefix_index, exe_index, prefix, exe) in matches {
+            count += 1;
+            let row = adw::ActionRow::builder()
+                .title(glib_escape(&exe.name))
+                .subtitle(glib_escape(&prefix.name))
+                .activatable(true)
+                .build();
+            row.add_suffix(&gtk::Image::from_icon_name("media-playback-start-symbolic"));
+
+            let s = sender.clone();
+            let popover = self.popover.clone();
+            row.connect_activated(move |_| {
+                popover.popdown();
+                s.input(AppMsg::LaunchExecutable(prefix_index, exe_index));
+            });
+            self.results.append(&row);
+        }
+
+        if count == 0 {
+            let label = gtk::Label::builder()
+                .label(crate::t!("search.no_results"))
+                .css_classes(["dim-label"])
+                .margin_top(12)
+                .margin_bottom(12)
+                .build();
+            self.results.append(&label);
+        }
+    }
+}
+
+/// `ActionRow` titles are parsed as Pango markup.
+fn glib_escape(text: &str) -> String {
+    gtk::glib::markup_escape_text(text).to_string()
+}
@@ -16,9 +16,18 @@ pub struct AppActionsModel {
     uninstaller_running: bool,
     exe_running: bool,
     has_desktop: bool,
+    selected_missing: bool,
+    broken_count: usize,
+    select_mode: bool,
+    list_view: bool,
+    checked_count: usize,
     desktop_tooltip: String,
     launch_tooltip: String,
     desktop_label: String,
+    /// Run-button menu section listing the selected executable's launch
+    /// profiles; rebuilt whenever the selection changes.
+    #[tracker::do_not_track]
+    profiles_menu: gtk::gio::Menu,
 }
 
 #[derive(Debug)]
@@ -30,10 +39,20 @@ pub enum AppActionsMsg {
     SetUninstallerRunning(bool),
     SetExeRunning(bool),
     SetDesktopExists(bool),
+    SetSelectedMissing(bool),
+    SetBrokenCount(usize),
+    SetCheckedCount(usize),
+    /// Names of the selected executable's launch profiles.
+    SetLaunchProfiles(Vec<String>),
+    ToggleSelectMode(bool),
+    ToggleListView(bool),
+    RemoveSelected,
     Launch,
     LaunchDebug,
+    LaunchProfile(String),
     Add,
     Remove,
+    RemoveBroken,
     ShowInfo,
     RunUninstaller,
     RunExe,
@@ -45,8 +64,13 @@ pub enum AppActionsOutput {
     Launch,
     Kill,
     LaunchDebug,
+    LaunchProfile(String),
     Add,
     Remove,
+    RemoveBroken,
+    SetSelectMode(bool),
+    SetListView(bool),
+    RemoveSelected,
     ShowInfo,
     RunUninstaller,
     RunExe,
@@ -80,9 +104,48 @@ impl AsyncComponent for AppActionsModel {
                 add_css_class: "suggested-action",
             },
 
+            gtk::ToggleButton {
+                set_icon_name: "selection-mode-symbolic",
+                set_tooltip_text: Some(&crate::t!("apps.actions.select_mode")),
+                #[track = "model.changed(AppActionsModel::is_scanning())"]
+                set_sensitive: !model.is_scanning,
+                #[track = "model.changed(AppActionsModel::select_mode())"]
+                #[block_signal(select_toggle_handler)]
+                set_active: model.select_mode,
+                connect_toggled[sender] => move |btn| {
+                    sender.input(AppActionsMsg::ToggleSelectMode(btn.is_active()));
+                } @select_toggle_handler,
+            },
+
+            gtk::ToggleButton {
+                set_icon_name: "view-list-symbolic",
+                set_tooltip_text: Some(&crate::t!("apps.actions.list_view")),
+                #[track = "model.changed(AppActionsModel::list_view())"]
+                #[block_signal(list_toggle_handler)]
+                set_active: model.list_view,
+                connect_toggled[sender] => move |btn| {
+                    sender.input(AppActionsMsg::ToggleListView(btn.is_active()));
+                } @list_toggle_handler,
+            },
+
+            gtk::Button {
+                #[track = "model.changed(AppActionsModel::select_mode())"]
+                set_visible: model.select_mode,
+                #[track = "model.changed(AppActionsModel::checked_count())"]
+                set_label: &crate::tf!("apps.actions.remove_selected", "count" => &model.checked_count.to_string()),
+                #[track = "model.changed(AppActionsModel::checked_count()) || model.changed(AppActionsModel::is_scanning())"]
+                set_sensitive: model.checked_count > 0 && !model.is_scanning,
+                connect_clicked[sender] => move |_| {
+                    sender.input(AppActionsMsg::RemoveSelected);
+                },
+                add_css_class: "destructive-action",
+            },
+
             gtk::Button {
                 set_icon_name: "user-trash-symbolic",
                 set_tooltip_text: Some(&crate::t!("apps.actions.remove")),
+                #[track = "model.changed(AppActionsModel::select_mode())"]
+                set_visible: !model.select_mode,
                 #[track = "model.changed(AppActionsModel::has_selection()) || model.changed(AppActionsModel::is_scanning())"]
                 set_sensitive: model.has_selection && !model.is_scanning,
                 connect_clicked[sender] => move |_| {
@@ -91,6 +154,19 @@ impl AsyncComponent for AppActionsModel {
                 add_css_class: "destructive-action",
             },
 
+            gtk::Button {
+                set_icon_name: "edit-clear-all-symbolic",
+                #[track = "model.changed(AppActionsModel::broken_count())"]
+                set_visible: model.broken_count > 0,
+                #[track = "model.changed(AppActionsModel::broken_count())"]
+                set_tooltip_text: Some(&crate::tf!("apps.actions.remove_broken", "count" => &model.broken_count.to_string())),
+                #[track = "model.changed(AppActionsModel::is_scanning())"]
+                set_sensitive: !model.is_scanning,
+                connect_clicked[sender] => move |_| {
+                    sender.input(AppActionsMsg::RemoveBroken);
+                },
+            },
+
             gtk::Button {
                 set_icon_name: "dialog-information-symbolic",
                 set_tooltip_text: Some(&crate::t!("apps.actions.info")),
@@ -213,12 +289,13 @@ impl AsyncComponent for AppActionsModel {
 
             // ── adw::SplitButton: native split button ──────────────────
             // Main area: Run / Kill toggle.
-            // Dropdown arrow (built-in): "Run with Debug" option.
+            // Dropdown arrow (built-in): "Run with Debug" option, then the
+            // selected executable's launch profiles.
             #[name = "launch_btn"]
             adw::SplitButton {
                 set_tooltip_text: Some(model.launch_tooltip.as_str()),
-                #[track = "model.changed(AppActionsModel::has_selection()) || model.changed(AppActionsModel::is_scanning()) || model.changed(AppActionsModel::selected_running())"]
-                set_sensitive: model.has_selection && !model.is_scanning,
+                #[track = "model.changed(AppActionsModel::has_selection()) || model.changed(AppActionsModel::is_scanning()) || model.changed(AppActionsModel::selected_running()) || model.changed(AppActionsModel::selected_missing())"]
+                set_sensitive: model.has_selection && !model.is_scanning && (model.selected_running || !model.selected_missing),
                 #[track = "model.changed(AppActionsModel::selected_running())"]
                 set_icon_name: if model.selected_running { "media-playback-stop-symbolic" } else { "media-playback-start-symbolic" },
                 #[track = "model.changed(AppActionsModel::selected_running())"]
@@ -249,9 +326,15 @@ impl AsyncComponent for AppActionsModel {
             uninstaller_running: false,
             exe_running: false,
             has_desktop: false,
+            selected_missing: false,
+            broken_count: 0,
+            select_mode: false,
+            list_view: prefix::Settings::load().is_some_and(|s| s.apps_list_view),
+            checked_count: 0,
             desktop_tooltip: crate::t!("apps.actions.create_desktop"),
             launch_tooltip: crate::t!("apps.actions.launch"),
             desktop_label: crate::t!("apps.actions.desktop"),
+            profiles_menu: gtk::gio::Menu::new(),
             tracker: 0,
         };
 
@@ -265,8 +348,19 @@ impl AsyncComponent for AppActionsModel {
                 sender.input(AppActionsMsg::LaunchDebug);
             });
         }
+        let profile_action =
+            gtk::gio::SimpleAction::new("launch-profile", Some(gtk::glib::VariantTy::STRING));
+        {
+            let sender = sender.clone();
+            profile_action.connect_activate(move |_, param| {
+                if let Some(name) = param.and_then(|p| p.get::<String>()) {
+                    sender.input(AppActionsMsg::LaunchProfile(name));
+                }
+            });
+        }
         let action_group = gtk::gio::SimpleActionGroup::new();
         action_group.add_action(&debug_action);
+        action_group.add_action(&profile_action);
         widgets.launch_btn.insert_action_group("btn", Some(&action_group));
 
         let menu = gtk::gio::Menu::new();
@@ -278,6 +372,7 @@ impl AsyncComponent for AppActionsModel {
             item.set_icon(&gtk::gio::ThemedIcon::new("bug-symbolic"));
             menu.append_item(&item);
         }
+        menu.append_section(None, &model.profiles_menu);
         widgets.launch_btn.set_menu_model(Some(&menu));
 
         AsyncComponentParts { model, widgets }
@@ -293,6 +388,9 @@ impl AsyncComponent for AppActionsModel {
         match msg {
             AppActionsMsg::SetSelection(has_selection) => {
                 self.set_has_selection(has_selection);
+                if !has_selection {
+                    self.profiles_menu.remove_all();
+                }
             }
             AppActionsMsg::SetScanning(is_scanning) => {
                 self.set_is_scanning(is_scanning);
@@ -314,6 +412,40 @@ impl AsyncComponent for AppActionsModel {
             AppActionsMsg::SetExeRunning(running) => {
                 self.set_exe_running(running);
             }
+            AppActionsMsg::SetSelectedMissing(missing) => {
+                self.set_selected_missing(missing);
+            }
+            AppActionsMsg::SetBrokenCount(count) => {
+                self.set_broken_count(count);
+            }
+            AppActionsMsg::SetCheckedCount(count) => {
+                self.set_checked_count(count);
+            }
+            AppActionsMsg::SetLaunchProfiles(names) => {
+                self.profiles_menu.remove_all();
+                for name in names {
+                    let item = gtk::gio::MenuItem::new(
+                        Some(&crate::tf!("apps.actions.run_profile", "name" => &name)),
+                        None,
+                    );
+                    item.set_action_and_target_value(
+                        Some("btn.launch-profile"),
+                        Some(&name.to_variant()),
+                    );
+                    self.profiles_menu.append_item(&item);
+                }
+            }
+            AppActionsMsg::ToggleSelectMode(enabled) => {
+                self.set_select_mode(enabled);
+                let _ = sender.output(AppActionsOutput::SetSelectMode(enabled));
+            }
+            AppActionsMsg::ToggleListView(enabled) => {
+                self.set_list_view(enabled);
+                let _ = sender.output(AppActionsOutput::SetListView(enabled));
+            }
+            AppActionsMsg::RemoveSelected => {
+                let _ = sender.output(AppActionsOutput::RemoveSelected);
+            }
             AppActionsMsg::Launch => {
                 if self.selected_running {
                     let _ = sender.output(AppActionsOutput::Kill);
@@ -324,12 +456,18 @@ impl AsyncComponent for AppActionsModel {
             AppActionsMsg::LaunchDebug => {
                 let _ = sender.output(AppActionsOutput::LaunchDebug);
             }
+            AppActionsMsg::LaunchProfile(name) => {
+                let _ = sender.output(AppActionsOutput::LaunchProfile(name));
+            }
             AppActionsMsg::Add => {
                 let _ = sender.output(AppActionsOutput::Add);
             }
             AppActionsMsg::Remove => {
                 let _ = sender.output(AppActionsOutput::Remove);
             }
+            AppActionsMsg::RemoveBroken => {
+                let _ = sender.output(AppActionsOutput::RemoveBroken);
+            }
             AppActionsMsg::ShowInfo => {
                 let _ = sender.output(AppActionsOutput::ShowInfo);
             }
@@ -343,7 +481,7 @@ impl AsyncComponent for AppActionsModel {
                 self.set_has_desktop(exists);
                 if exists {
                     self.set_desktop_tooltip(crate::t!("apps.actions.remove_desktop"));
-                    self.set_desktop_label(crate::t!("apps.actions.delete"));
+                    self.set_desktop_label(crate::t!("apps.actions.remove_from_menu"));
                 } else {
                     self.set_desktop_tooltip(crate::t!("apps.actions.create_desktop"));
                     self.set_desktop_label(crate::t!("apps.actions.desktop"));
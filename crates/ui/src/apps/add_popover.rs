@@ -8,7 +8,9 @@ use relm4::{
     component::{AsyncComponent, AsyncComponentParts, AsyncComponentSender},
     gtk,
 };
+use std::cell::Cell;
 use std::path::PathBuf;
+use std::rc::Rc;
 use std::sync::Arc;
 use tracker;
 
@@ -28,16 +30,21 @@ pub struct AddAppPopoverModel {
     prefix_path: PathBuf,
     #[tracker::do_not_track]
     icon_cache: Arc<IconCache>,
+    /// How many of the leading `available_apps` are newly found; read by
+    /// the list's header function.
+    #[tracker::do_not_track]
+    new_count: Rc<Cell<usize>>,
 }
 
 #[derive(Debug)]
 pub enum AddAppPopoverMsg {
     Show,
     Hide,
-    UpdateAvailableApps(Vec<RegisteredExecutable>, String), // exes, prefix_arch
+    UpdateAvailableApps(Vec<RegisteredExecutable>, String, usize), // exes, prefix_arch, new_count
     SelectApp(usize),
     AddSelected,
     Scan,
+    CopyFromPrefix,
     ResetProcessingFlag,
     SetScanning(bool),
     PrefixPathUpdated(PathBuf),
@@ -47,6 +54,8 @@ pub enum AddAppPopoverMsg {
 pub enum AddAppPopoverOutput {
     AddApp(Vec<usize>),
     Scan,
+    /// Copy registered applications from another prefix instead.
+    CopyFromPrefix,
     Close,
 }
 
@@ -299,6 +308,14 @@ impl AsyncComponent for AddAppPopoverModel {
                         },
                     },
 
+                    gtk::Button {
+                        set_icon_name: "edit-copy-symbolic",
+                        set_tooltip_text: Some(&crate::t!("apps.add.copy_from_tooltip")),
+                        connect_clicked[sender] => move |_| {
+                            sender.input(AddAppPopoverMsg::CopyFromPrefix);
+                        },
+                    },
+
                     gtk::Box {
                         set_hexpand: true,
                     },
@@ -348,11 +365,16 @@ impl AsyncComponent for AddAppPopoverModel {
             is_processing_selection: false,
             prefix_path,
             icon_cache,
+            new_count: Rc::new(Cell::new(0)),
             tracker: 0,
         };
 
         // Get references to the factory widgets
         let available_list_box = model.available_executables.widget();
+        available_list_box.set_header_func({
+            let new_count = Rc::clone(&model.new_count);
+            move |row, _| section_header(row, new_count.get())
+        });
 
         let widgets = view_output!();
 
@@ -360,6 +382,7 @@ impl AsyncComponent for AddAppPopoverModel {
         sender.input(AddAppPopoverMsg::UpdateAvailableApps(
             Vec::new(),
             "win64".to_string(),
+            0,
         ));
 
         AsyncComponentParts { model, widgets }
@@ -386,15 +409,20 @@ impl AsyncComponent for AddAppPopoverModel {
                 widgets.popdown();
                 let _ = sender.output(AddAppPopoverOutput::Close);
             }
-            AddAppPopoverMsg::UpdateAvailableApps(apps, prefix_arch) => {
+            AddAppPopoverMsg::UpdateAvailableApps(apps, prefix_arch, new_count) => {
                 self.available_apps = apps.clone();
+                self.new_count.set(new_count);
                 self.selected_indices.clear();
                 self.set_selected_indices(self.selected_indices.clone());
 
                 // Compute arch label for each executable
                 let arch_labels: Vec<String> = apps
                     .iter()
-                    .map(|exe| compute_arch_label(&exe.executable_path, &prefix_arch))
+                    .map(|exe| {
+                        exe.architecture.clone().unwrap_or_else(|| {
+                            compute_arch_label(&exe.executable_path, &prefix_arch)
+                        })
+                    })
                     .collect();
 
                 // Resolve (or extract) icons for display
@@ -418,6 +446,7 @@ impl AsyncComponent for AddAppPopoverModel {
                         ));
                     }
                 }
+                self.available_executables.widget().invalidate_headers();
             }
             AddAppPopoverMsg::SelectApp(index) => {
                 // println!("DEBUG: SelectApp called with index: {}", index);
@@ -473,6 +502,10 @@ impl AsyncComponent for AddAppPopoverModel {
             AddAppPopoverMsg::Scan => {
                 let _ = sender.output(AddAppPopoverOutput::Scan);
             }
+            AddAppPopoverMsg::CopyFromPrefix => {
+                sender.input(AddAppPopoverMsg::Hide);
+                let _ = sender.output(AddAppPopoverOutput::CopyFromPrefix);
+            }
             AddAppPopoverMsg::SetScanning(scanning) => {
                 self.set_is_scanning(scanning);
                 self.set_scan_button_label(if scanning { crate::t!("apps.add.scanning_btn") } else { crate::t!("apps.add.scan_btn") });
@@ -527,7 +560,34 @@ impl AsyncComponent for AddAppPopoverModel {
     }
 }
 
-/// Determine x86/x64 label for an executable based on its path and prefix architecture.
+/// Head the newly found rows (the first `new_count`) and the rest with
+/// their own titles. Without newly found apps the list has no headers.
+fn section_header(row: &gtk::ListBoxRow, new_count: usize) {
+    let index = row.index() as usize;
+    let title = if new_count == 0 {
+        None
+    } else if index == 0 {
+        Some(crate::t!("apps.add.newly_found"))
+    } else if index == new_count {
+        Some(crate::t!("apps.add.all_apps"))
+    } else {
+        None
+    };
+    let header = title.map(|title| {
+        gtk::Label::builder()
+            .label(&title)
+            .halign(gtk::Align::Start)
+            .margin_top(6)
+            .margin_bottom(6)
+            .margin_start(8)
+            .css_classes(["heading"])
+            .build()
+    });
+    row.set_header(header.as_ref());
+}
+
+/// Guess an x86/x64 label from the install path and prefix architecture, for
+/// scan results cached before the PE machine type was recorded.
 fn compute_arch_label(path: &std::path::Path, prefix_arch: &str) -> String {
     let path_lower = path.to_string_lossy().to_lowercase();
     if path_lower.contains("program files (x86)") {
@@ -0,0 +1,118 @@
+//! Two-step dialog for copying registered applications from another prefix:
+//! pick the source prefix, then the entries to copy.
+
+use adw::prelude::*;
+use prefix::AppTransfer;
+use prefix::config::RegisteredExecutable;
+use relm4::{adw, gtk};
+use std::path::{Path, PathBuf};
+
+/// Ask which prefix to copy applications from. `sources` are
+/// `(name, path)` pairs; `on_chosen` gets the selected path.
+pub fn choose_source<F>(parent: Option<&gtk::Window>, sources: Vec<(String, PathBuf)>, on_chosen: F)
+where
+    F: Fn(PathBuf) + 'static,
+{
+    let names: Vec<&str> = sources.iter().map(|(name, _)| name.as_str()).collect();
+    let source_dropdown = gtk::DropDown::from_strings(&names);
+
+    let alert = adw::AlertDialog::new(
+        Some(&crate::t!("apps.copy.title")),
+        Some(&crate::t!("apps.copy.source_desc")),
+    );
+    alert.set_extra_child(Some(&source_dropdown));
+    alert.add_response("cancel", &crate::t!("dialogs.cancel"));
+    alert.add_response("compare", &crate::t!("apps.copy.compare"));
+    alert.set_response_appearance("compare", adw::ResponseAppearance::Suggested);
+    alert.set_default_response(Some("compare"));
+    alert.set_close_response("cancel");
+    alert.choose(parent, None::<&gtk::gio::Cancellable>, move |response| {
+        if response != "compare" {
+            return;
+        }
+        if let Some((_, source)) = sources.get(source_dropdown.selected() as usize) {
+            on_chosen(source.clone());
+        }
+    });
+}
+
+/// Show the applications `source_name` has that this prefix doesn't, and
+/// pass the checked ones to `on_chosen`. Entries whose executable is
+/// missing here are listed but can't be checked.
+pub fn choose_apps<F>(
+    parent: Option<&gtk::Window>,
+    prefix_path: &Path,
+    source_name: &str,
+    transfers: Vec<AppTransfer>,
+    on_chosen: F,
+) where
+    F: Fn(Vec<RegisteredExecutable>) + 'static,
+{
+    let list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .build();
+    let checks: Vec<(RegisteredExecutable, gtk::CheckButton)> = transfers
+        .into_iter()
+        .map(|transfer| {
+            let exe = transfer.executable;
+            let check = gtk::CheckButton::builder()
+                .active(transfer.available)
+                .sensitive(transfer.available)
+                .valign(gtk::Align::Center)
+                .build();
+            let location = exe
+                .executable_path
+                .strip_prefix(prefix_path)
+                .unwrap_or(&exe.executable_path)
+                .display()
+                .to_string();
+            let subtitle = if transfer.available {
+                location
+            } else {
+                crate::tf!("apps.copy.not_installed", "path" => &location)
+            };
+            let row = adw::ActionRow::builder()
+                .title(exe.name.as_str())
+                .subtitle(subtitle)
+                .subtitle_lines(1)
+                .activatable_widget(&check)
+                .sensitive(transfer.available)
+                .build();
+            row.add_prefix(&check);
+            list.append(&row);
+            (exe, check)
+        })
+        .collect();
+
+    let scrolled = gtk::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .propagate_natural_height(true)
+        .max_content_height(320)
+        .child(&list)
+        .build();
+
+    let alert = adw::AlertDialog::new(
+        Some(&crate::t!("apps.copy.title")),
+        Some(&crate::tf!("apps.copy.apps_desc", "name" => source_name)),
+    );
+    alert.set_extra_child(Some(&scrolled));
+    alert.add_response("cancel", &crate::t!("dialogs.cancel"));
+    alert.add_response("copy", &crate::t!("apps.copy.confirm"));
+    alert.set_response_appearance("copy", adw::ResponseAppearance::Suggested);
+    alert.set_default_response(Some("copy"));
+    alert.set_close_response("cancel");
+    alert.choose(parent, None::<&gtk::gio::Cancellable>, move |response| {
+        if response != "copy" {
+            return;
+        }
+        let chosen: Vec<RegisteredExecutable> = checks
+            .iter()
+            .filter(|(_, check)| check.is_active())
+            .map(|(exe, _)| exe.clone())
+            .collect();
+        if !chosen.is_empty() {
+            on_chosen(chosen);
+        }
+    });
+}
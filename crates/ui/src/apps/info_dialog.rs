@@ -1,7 +1,11 @@
+use crate::registry_editor::general_tab::{
+    WINDOWS_VERSION_LABELS, win_code_to_index, win_index_to_code,
+};
 use adw::prelude::*;
 use prefix::IconCache;
-use prefix::config::RegisteredExecutable;
+use prefix::config::{LaunchProfile, RegisteredExecutable};
 use prefix::resolve_or_extract_icon;
+use prefix::{RegEditor, RegistryEditor};
 use relm4::{
     Component, ComponentParts, ComponentSender, Controller, RelmWidgetExt, SimpleComponent, adw,
     component::{AsyncComponent, AsyncComponentParts, AsyncComponentSender},
@@ -27,7 +31,18 @@ pub struct ExecutableInfoDialogModel {
     #[tracker::do_not_track]
     icon_path_entry_row: adw::EntryRow,
     #[tracker::do_not_track]
+    windows_version_row: adw::ComboRow,
+    #[tracker::do_not_track]
+    force_32bit_row: adw::SwitchRow,
+    #[tracker::do_not_track]
+    launch_wrapper_row: adw::EntryRow,
+    /// Per-app Windows version as last read from the registry.
+    #[tracker::do_not_track]
+    app_windows_version: Option<String>,
+    #[tracker::do_not_track]
     env_vars_editor: Option<Controller<EnvVarsEditor>>,
+    #[tracker::do_not_track]
+    launch_profiles_editor: Option<Controller<LaunchProfilesEditor>>,
 }
 
 #[derive(Debug)]
@@ -40,14 +55,35 @@ pub enum ExecutableInfoDialogMsg {
     ClearIcon,
     EditEnvVars,
     EnvVarsEdited(HashMap<String, String>),
+    EditLaunchProfiles,
+    LaunchProfilesEdited(Vec<LaunchProfile>),
+    OpenFolder,
+    /// Launch statistics changed for an executable (shown or not).
+    StatsUpdated(RegisteredExecutable),
 }
 
 #[derive(Debug)]
 pub enum ExecutableInfoDialogOutput {
     ExecutableUpdated(RegisteredExecutable),
+    OpenFolder(PathBuf),
 }
 
-fn env_vars_to_text(vars: &HashMap<String, String>) -> String {
+fn last_launched_text(exe: &RegisteredExecutable) -> String {
+    let when = exe
+        .last_launched
+        .and_then(|ts| gtk::glib::DateTime::from_unix_local(ts.timestamp()).ok())
+        .and_then(|dt| dt.format("%Y-%m-%d %H:%M").ok());
+    match when {
+        Some(when) => crate::tf!(
+            "apps.info.last_launched_value",
+            "when" => when.as_str(),
+            "count" => &exe.launch_count.to_string()
+        ),
+        None => crate::t!("apps.info.never_launched"),
+    }
+}
+
+pub(crate) fn env_vars_to_text(vars: &HashMap<String, String>) -> String {
     let mut pairs: Vec<_> = vars.iter().collect();
     pairs.sort_by(|a, b| a.0.cmp(b.0));
     pairs
@@ -73,6 +109,89 @@ fn parse_env_vars(text: &str) -> HashMap<String, String> {
         .collect()
 }
 
+/// Lay out launch profiles as `[name]` blocks with `args=`, `cwd=` and
+/// `KEY=VALUE` environment lines, the format `parse_launch_profiles` reads.
+fn launch_profiles_to_text(profiles: &[LaunchProfile]) -> String {
+    profiles
+        .iter()
+        .map(|profile| {
+            let mut lines = vec![format!("[{}]", profile.name)];
+            if !profile.args.is_empty() {
+                lines.push(format!("args={}", profile.args.join(" ")));
+            }
+            if let Some(cwd) = &profile.cwd {
+                lines.push(format!("cwd={}", cwd.display()));
+            }
+            if !profile.env_vars.is_empty() {
+                lines.push(env_vars_to_text(&profile.env_vars));
+            }
+            lines.join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Read profiles written by `launch_profiles_to_text`. Lines before the
+/// first `[name]` header and blocks with an empty name are dropped.
+fn parse_launch_profiles(text: &str) -> Vec<LaunchProfile> {
+    let mut profiles: Vec<LaunchProfile> = Vec::new();
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            profiles.push(LaunchProfile {
+                name: name.trim().to_string(),
+                ..Default::default()
+            });
+            continue;
+        }
+        let Some(profile) = profiles.last_mut() else {
+            continue;
+        };
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        match key {
+            "args" => profile.args = value.split_whitespace().map(str::to_string).collect(),
+            "cwd" => profile.cwd = (!value.is_empty()).then(|| PathBuf::from(value)),
+            "" => {}
+            _ => {
+                profile.env_vars.insert(key.to_string(), value.to_string());
+            }
+        }
+    }
+    profiles.retain(|p| !p.name.is_empty());
+    profiles
+}
+
+/// Name of the `AppDefaults` key Wine matches against: the bare exe file name.
+fn app_defaults_name(exe: &RegisteredExecutable) -> Option<String> {
+    exe.executable_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+}
+
+async fn load_app_windows_version(
+    prefix_path: &PathBuf,
+    app_name: &str,
+) -> base::error::Result<Option<String>> {
+    let editor = RegistryEditor::with_prefix(prefix_path).await?;
+    Ok(editor
+        .get_app_settings(app_name)
+        .await?
+        .and_then(|settings| settings.windows_version)
+        .map(|version| version.to_string().to_string()))
+}
+
+async fn save_app_windows_version(
+    prefix_path: &PathBuf,
+    app_name: &str,
+    version: Option<&str>,
+) -> base::error::Result<()> {
+    let mut editor = RegistryEditor::with_prefix(prefix_path).await?;
+    editor.set_app_windows_version(app_name, version).await?;
+    editor.save_registry(prefix_path).await
+}
+
 impl ExecutableInfoDialogModel {
     /// Resolve the icon path for the currently displayed executable,
     /// honouring both absolute and prefix-relative paths. Falls back to
@@ -84,6 +203,15 @@ impl ExecutableInfoDialogModel {
     }
 }
 
+fn architecture_text(executable: Option<&RegisteredExecutable>) -> String {
+    match executable.and_then(|e| e.architecture.as_deref()) {
+        Some("x86") => crate::t!("apps.info.arch_x86"),
+        Some("x64") => crate::t!("apps.info.arch_x64"),
+        Some(other) => other.to_string(),
+        None => crate::t!("apps.info.n_a"),
+    }
+}
+
 fn env_vars_subtitle(executable: Option<&RegisteredExecutable>) -> String {
     match executable.and_then(|e| {
         if e.env_vars.is_empty() {
@@ -97,6 +225,16 @@ fn env_vars_subtitle(executable: Option<&RegisteredExecutable>) -> String {
     }
 }
 
+fn launch_profiles_subtitle(executable: Option<&RegisteredExecutable>) -> String {
+    match executable
+        .map(|e| e.launch_profiles.len())
+        .filter(|&n| n > 0)
+    {
+        Some(count) => crate::tf!("apps.info.launch_profiles_set", "count" => &count.to_string()),
+        None => crate::t!("apps.info.launch_profiles_none"),
+    }
+}
+
 // ── EnvVarsEditor: popup component (like create_dialog.rs) ───────────────
 
 #[derive(Debug)]
@@ -221,6 +359,121 @@ impl SimpleComponent for EnvVarsEditor {
     }
 }
 
+// ── LaunchProfilesEditor: popup component (like EnvVarsEditor) ──────────
+
+#[derive(Debug)]
+pub struct LaunchProfilesEditor {
+    text_view: gtk::TextView,
+    dialog: gtk::Window,
+}
+
+#[derive(Debug)]
+pub enum LaunchProfilesEditorMsg {
+    Apply,
+}
+
+#[relm4::component(pub)]
+impl SimpleComponent for LaunchProfilesEditor {
+    type Init = (gtk::Window, String);
+    type Input = LaunchProfilesEditorMsg;
+    type Output = Vec<LaunchProfile>;
+
+    view! {
+        #[name = "dialog"]
+        gtk::Window {
+            set_title: Some(&crate::t!("apps.info.profiles_editor_title")),
+            set_modal: true,
+            set_default_width: 420,
+            set_default_height: 350,
+            set_transient_for: Some(&parent),
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 0,
+
+                gtk::Label {
+                    set_label: &crate::t!("apps.info.profiles_editor_desc"),
+                    set_halign: gtk::Align::Start,
+                    set_wrap: true,
+                    set_margin_start: 12,
+                    set_margin_top: 12,
+                    set_margin_end: 12,
+                    set_margin_bottom: 4,
+                },
+
+                gtk::Label {
+                    set_label: &crate::t!("apps.info.profiles_editor_example"),
+                    set_halign: gtk::Align::Start,
+                    set_margin_start: 12,
+                    set_margin_end: 12,
+                    set_margin_bottom: 8,
+                    add_css_class: "caption",
+                },
+
+                #[name = "text_view"]
+                gtk::TextView {
+                    set_editable: true,
+                    set_wrap_mode: gtk::WrapMode::Word,
+                    set_monospace: true,
+                    set_margin_start: 8,
+                    set_margin_end: 8,
+                    set_margin_bottom: 8,
+                    set_vexpand: true,
+                },
+            },
+        }
+    }
+
+    fn init(
+        (parent, initial_text): Self::Init,
+        _root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let header_bar = gtk::HeaderBar::new();
+        #[cfg(target_os = "macos")]
+        header_bar.set_property("use-native-controls", true);
+
+        let apply_btn = gtk::Button::builder()
+            .label(&crate::t!("apps.info.env_editor_apply"))
+            .icon_name("object-select-symbolic")
+            .css_classes(["suggested-action"])
+            .build();
+        let s = sender.clone();
+        apply_btn.connect_clicked(move |_| {
+            let _ = s.input(LaunchProfilesEditorMsg::Apply);
+        });
+
+        #[cfg(target_os = "macos")]
+        header_bar.pack_end(&apply_btn);
+        #[cfg(not(target_os = "macos"))]
+        header_bar.pack_start(&apply_btn);
+
+        let widgets = view_output!();
+        widgets.text_view.buffer().set_text(&initial_text);
+        widgets.dialog.set_titlebar(Some(&header_bar));
+
+        let model = LaunchProfilesEditor {
+            text_view: widgets.text_view.clone(),
+            dialog: widgets.dialog.clone(),
+        };
+
+        widgets.dialog.present();
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>) {
+        match msg {
+            LaunchProfilesEditorMsg::Apply => {
+                let buffer = self.text_view.buffer();
+                let text = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+                let _ = sender.output(parse_launch_profiles(&text));
+                self.dialog.close();
+            }
+        }
+    }
+}
+
 // ── Main dialog component ───────────────────────────────────────────────
 
 #[relm4::component(pub, async)]
@@ -369,6 +622,23 @@ impl AsyncComponent for ExecutableInfoDialogModel {
                                     set_hexpand: true,
                                 },
                             },
+
+                            // Architecture
+                            gtk::Box {
+                                set_orientation: gtk::Orientation::Horizontal,
+                                set_spacing: 15,
+
+                                gtk::Label {
+                                    set_label: &crate::t!("apps.info.architecture"),
+                                    set_halign: gtk::Align::Start,
+                                },
+                                gtk::Label {
+                                    #[watch]
+                                    set_label: &architecture_text(model.executable.as_ref()),
+                                    set_halign: gtk::Align::End,
+                                    set_hexpand: true,
+                                },
+                            },
                         }
                     },
 
@@ -391,6 +661,54 @@ impl AsyncComponent for ExecutableInfoDialogModel {
                             set_ellipsize: gtk::pango::EllipsizeMode::Middle,
                             set_hexpand: true,
                         },
+                        gtk::Button {
+                            set_icon_name: "folder-open-symbolic",
+                            set_tooltip_text: Some(&crate::t!("apps.actions.open_folder")),
+                            add_css_class: "flat",
+                            connect_clicked[sender] => move |_| {
+                                sender.input(ExecutableInfoDialogMsg::OpenFolder);
+                            },
+                        },
+                    },
+
+                    // ── Windows (Wine) Path ──
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Horizontal,
+                        set_spacing: 15,
+
+                        gtk::Label {
+                            set_label: &crate::t!("apps.info.windows_path"),
+                            set_halign: gtk::Align::Start,
+                        },
+                        gtk::Label {
+                            #[watch]
+                            set_label: &model.executable.as_ref()
+                                .map(|e| prefix::windows_path(&model.prefix_path, &e.executable_path))
+                                .unwrap_or_else(|| crate::t!("apps.info.n_a")),
+                            set_halign: gtk::Align::End,
+                            set_selectable: true,
+                            set_ellipsize: gtk::pango::EllipsizeMode::Middle,
+                            set_hexpand: true,
+                        },
+                    },
+
+                    // ── Last Launched ──
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Horizontal,
+                        set_spacing: 15,
+
+                        gtk::Label {
+                            set_label: &crate::t!("apps.info.last_launched"),
+                            set_halign: gtk::Align::Start,
+                        },
+                        gtk::Label {
+                            #[watch]
+                            set_label: &model.executable.as_ref()
+                                .map(last_launched_text)
+                                .unwrap_or_else(|| crate::t!("apps.info.n_a")),
+                            set_halign: gtk::Align::End,
+                            set_hexpand: true,
+                        },
                     },
                 },
 
@@ -483,6 +801,28 @@ impl AsyncComponent for ExecutableInfoDialogModel {
                             },
                         },
 
+                        // Windows Version (AppDefaults\<exe>\Version)
+                        #[name = "windows_version_row"]
+                        adw::ComboRow {
+                            set_title: &crate::t!("apps.info.windows_version"),
+                            set_subtitle: &crate::t!("apps.info.windows_version_sub"),
+                            set_model: Some(&gtk::StringList::new(&WINDOWS_VERSION_LABELS)),
+                        },
+
+                        // 32-bit Wine (only meaningful for 32-bit images)
+                        #[name = "force_32bit_row"]
+                        adw::SwitchRow {
+                            set_title: &crate::t!("apps.info.force_32bit"),
+                            set_subtitle: &crate::t!("apps.info.force_32bit_sub"),
+                        },
+
+                        // Launch Wrapper (gamemoderun, mangohud, …)
+                        #[name = "launch_wrapper_row"]
+                        adw::EntryRow {
+                            set_title: &crate::t!("apps.info.launch_wrapper"),
+                            set_tooltip_text: Some(&crate::t!("apps.info.launch_wrapper_tooltip")),
+                        },
+
                         // Environment Variables
                         adw::ActionRow {
                             set_title: &crate::t!("apps.info.env_vars"),
@@ -493,6 +833,17 @@ impl AsyncComponent for ExecutableInfoDialogModel {
                                 sender.input(ExecutableInfoDialogMsg::EditEnvVars);
                             },
                         },
+
+                        // Launch Profiles
+                        adw::ActionRow {
+                            set_title: &crate::t!("apps.info.launch_profiles"),
+                            set_activatable: true,
+                            #[watch]
+                            set_subtitle: &launch_profiles_subtitle(model.executable.as_ref()),
+                            connect_activated[sender] => move |_| {
+                                sender.input(ExecutableInfoDialogMsg::EditLaunchProfiles);
+                            },
+                        },
                     },
                 },
 
@@ -546,7 +897,12 @@ impl AsyncComponent for ExecutableInfoDialogModel {
             name_entry_row: adw::EntryRow::new(),
             cwd_entry_row: adw::EntryRow::new(),
             icon_path_entry_row: adw::EntryRow::new(),
+            windows_version_row: adw::ComboRow::new(),
+            force_32bit_row: adw::SwitchRow::new(),
+            launch_wrapper_row: adw::EntryRow::new(),
+            app_windows_version: None,
             env_vars_editor: None,
+            launch_profiles_editor: None,
             tracker: 0,
         };
 
@@ -555,6 +911,9 @@ impl AsyncComponent for ExecutableInfoDialogModel {
         model.name_entry_row = widgets.name_entry_row.clone();
         model.cwd_entry_row = widgets.cwd_entry_row.clone();
         model.icon_path_entry_row = widgets.icon_path_entry_row.clone();
+        model.windows_version_row = widgets.windows_version_row.clone();
+        model.force_32bit_row = widgets.force_32bit_row.clone();
+        model.launch_wrapper_row = widgets.launch_wrapper_row.clone();
 
         AsyncComponentParts { model, widgets }
     }
@@ -582,10 +941,47 @@ impl AsyncComponent for ExecutableInfoDialogModel {
                     .map(|p| p.display().to_string())
                     .unwrap_or_default();
                 self.icon_path_entry_row.set_text(&icon_str);
+                self.force_32bit_row.set_active(executable.force_32bit);
+                self.force_32bit_row
+                    .set_sensitive(executable.force_32bit || !executable.is_64bit());
+                self.launch_wrapper_row
+                    .set_text(executable.launch_wrapper.as_deref().unwrap_or_default());
                 self.prefix_path = prefix_path;
+                let app_name = app_defaults_name(&executable);
                 self.set_executable(Some(executable));
                 self.set_visible(true);
                 root.present();
+
+                // Read the override after presenting; parsing the .reg files
+                // shouldn't hold up the dialog.
+                self.app_windows_version = None;
+                self.windows_version_row.set_selected(0);
+                self.windows_version_row.set_sensitive(false);
+                if let Some(app_name) = app_name {
+                    match load_app_windows_version(&self.prefix_path, &app_name).await {
+                        Ok(version) => {
+                            self.windows_version_row.set_selected(
+                                win_code_to_index(version.as_deref().unwrap_or("")).unwrap_or(0),
+                            );
+                            self.app_windows_version = version;
+                            self.windows_version_row.set_sensitive(true);
+                        }
+                        Err(e) => {
+                            log::warn!("[apps] failed to read app Windows version: {}", e);
+                        }
+                    }
+                }
+            }
+            ExecutableInfoDialogMsg::StatsUpdated(updated) => {
+                // Keep the copy in sync so a later save doesn't roll the stats back
+                if let Some(exe) = self
+                    .get_mut_executable()
+                    .as_mut()
+                    .filter(|e| e.executable_path == updated.executable_path)
+                {
+                    exe.last_launched = updated.last_launched;
+                    exe.launch_count = updated.launch_count;
+                }
             }
             ExecutableInfoDialogMsg::Hide => {
                 self.set_visible(false);
@@ -608,8 +1004,47 @@ impl AsyncComponent for ExecutableInfoDialogModel {
                     } else {
                         Some(PathBuf::from(icon_text.trim()))
                     };
+                    exec.force_32bit = self.force_32bit_row.is_active();
+                    let wrapper_text = self.launch_wrapper_row.text().to_string();
+                    exec.launch_wrapper = if wrapper_text.trim().is_empty() {
+                        None
+                    } else {
+                        Some(wrapper_text.trim().to_string())
+                    };
                     self.set_executable(Some(exec.clone()));
-                    let _ = sender.output(ExecutableInfoDialogOutput::ExecutableUpdated(exec));
+                    let _ = sender.output(ExecutableInfoDialogOutput::ExecutableUpdated(
+                        exec.clone(),
+                    ));
+
+                    let code = win_index_to_code(self.windows_version_row.selected());
+                    let version = (!code.is_empty()).then_some(code);
+                    let changed = self.windows_version_row.is_sensitive()
+                        && version != self.app_windows_version.as_deref();
+                    if let Some(app_name) = app_defaults_name(&exec).filter(|_| changed) {
+                        match save_app_windows_version(&self.prefix_path, &app_name, version).await
+                        {
+                            Ok(()) => {
+                                self.app_windows_version = version.map(str::to_string);
+                            }
+                            Err(e) => {
+                                // Keep the dialog open so the alert has a parent
+                                log::error!("[apps] failed to save app Windows version: {}", e);
+                                let msg = crate::tf!(
+                                    "apps.info.windows_version_failed",
+                                    "error" => &e.to_string()
+                                );
+                                let alert = adw::AlertDialog::new(
+                                    Some(&crate::t!("dialogs.error")),
+                                    Some(&msg),
+                                );
+                                alert.add_response("ok", &crate::t!("dialogs.ok"));
+                                alert.set_default_response(Some("ok"));
+                                alert.set_close_response("ok");
+                                alert.choose(Some(root), None::<&gtk::gio::Cancellable>, |_| {});
+                                return;
+                            }
+                        }
+                    }
                 }
                 self.set_visible(false);
             }
@@ -678,6 +1113,37 @@ impl AsyncComponent for ExecutableInfoDialogModel {
                     self.set_executable(Some(exec));
                 }
             }
+            ExecutableInfoDialogMsg::EditLaunchProfiles => {
+                let parent_root = self.cwd_entry_row.root();
+                if let Some(parent) = parent_root {
+                    let parent_window = parent.downcast::<gtk::Window>().unwrap();
+                    let current_text = self
+                        .executable
+                        .as_ref()
+                        .map(|e| launch_profiles_to_text(&e.launch_profiles))
+                        .unwrap_or_default();
+
+                    let editor = LaunchProfilesEditor::builder()
+                        .launch((parent_window, current_text))
+                        .forward(sender.input_sender(), |output| {
+                            ExecutableInfoDialogMsg::LaunchProfilesEdited(output)
+                        });
+                    self.launch_profiles_editor = Some(editor);
+                }
+            }
+            ExecutableInfoDialogMsg::LaunchProfilesEdited(profiles) => {
+                if let Some(mut exec) = self.executable.clone() {
+                    exec.launch_profiles = profiles;
+                    self.set_executable(Some(exec));
+                }
+            }
+            ExecutableInfoDialogMsg::OpenFolder => {
+                if let Some(exec) = &self.executable {
+                    let _ = sender.output(ExecutableInfoDialogOutput::OpenFolder(
+                        exec.executable_path.clone(),
+                    ));
+                }
+            }
         }
     }
 }
@@ -1,12 +1,13 @@
-use gtk::prelude::*;
+use adw::prelude::*;
 use prefix::config::RegisteredExecutable;
 use prefix::{IconCache, resolve_or_extract_icon};
 use relm4::factory::{DynamicIndex, FactoryComponent, FactorySender, FactoryVecDeque};
 use relm4::{
     RelmWidgetExt,
     component::{AsyncComponent, AsyncComponentParts, AsyncComponentSender},
-    gtk,
+    adw, gtk,
 };
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tracker;
@@ -14,23 +15,106 @@ use tracker;
 #[derive(Debug)]
 #[tracker::track]
 pub struct RegisteredAppsListModel {
+    /// Container holding one collapsible section per category.
     #[tracker::do_not_track]
-    executables: FactoryVecDeque<RegisteredExecutableItem>,
+    sections_box: gtk::Box,
+    #[tracker::do_not_track]
+    sections: Vec<CategorySection>,
     registered_executables: Vec<RegisteredExecutable>,
+    /// Index into `registered_executables` of the selected item, if any.
     #[tracker::do_not_track]
-    selection_handler_id: Option<gtk::glib::SignalHandlerId>,
+    selected: Option<usize>,
     #[tracker::do_not_track]
     prefix_path: PathBuf,
     #[tracker::do_not_track]
     icon_cache: Arc<IconCache>,
+    select_mode: bool,
+    /// Compact rows instead of icon tiles.
+    list_view: bool,
+    /// Executable paths checked in select mode; keyed by path so the
+    /// selection survives the list being rebuilt.
+    #[tracker::do_not_track]
+    checked: HashSet<PathBuf>,
+    /// Sections the user collapsed.
+    #[tracker::do_not_track]
+    collapsed: HashSet<SectionKey>,
+}
+
+/// Which group an executable is shown in. Sections are ordered favorites
+/// first, then named categories, then uncategorized apps.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SectionKey {
+    Favorites,
+    Category(String),
+    Other,
+}
+
+impl SectionKey {
+    fn for_executable(exe: &RegisteredExecutable) -> Self {
+        if exe.favorite {
+            return SectionKey::Favorites;
+        }
+        match exe.category.as_deref().map(str::trim) {
+            Some(category) if !category.is_empty() => SectionKey::Category(category.to_string()),
+            _ => SectionKey::Other,
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            SectionKey::Favorites => 0,
+            SectionKey::Category(_) => 1,
+            SectionKey::Other => 2,
+        }
+    }
+
+    fn title(&self) -> String {
+        match self {
+            SectionKey::Favorites => crate::t!("apps.category_favorites"),
+            SectionKey::Category(name) => name.clone(),
+            SectionKey::Other => crate::t!("apps.category_other"),
+        }
+    }
+}
+
+/// One collapsible category group with its own grid of items.
+#[derive(Debug)]
+struct CategorySection {
+    expander: gtk::Expander,
+    items: FactoryVecDeque<RegisteredExecutableItem>,
+    /// Indices into `registered_executables`, in display order.
+    indices: Vec<usize>,
+    selection_handler: gtk::glib::SignalHandlerId,
 }
 
 #[derive(Debug)]
 pub enum RegisteredAppsListMsg {
     UpdateExecutables(Vec<RegisteredExecutable>),
     SetRunningPaths(std::collections::HashSet<std::path::PathBuf>),
+    /// Re-check which registered executables no longer exist on disk.
+    RefreshMissing,
     PrefixPathUpdated(PathBuf),
-    SelectionChanged,
+    /// Selection changed in the section at this position.
+    SelectionChanged(usize),
+    SetSelectMode(bool),
+    /// Switch between the icon grid and the compact list.
+    SetListView(bool),
+    ItemChecked(PathBuf, bool),
+    OpenFolder(PathBuf),
+    /// Stop the running executable.
+    Stop(PathBuf),
+    /// Ask the user for a new category for this executable.
+    EditCategory(PathBuf, Option<String>),
+    SectionToggled(SectionKey, bool),
+    SetFavorite(PathBuf, bool),
+    /// Focus the list, selecting the first item if nothing is selected.
+    GrabFocus,
+    /// Enter / double-click on an item: (section, position in section).
+    Activate(usize, usize),
+    /// Delete key on the current selection.
+    RemoveSelected,
+    /// An item was dragged onto another: (dragged path, target path).
+    MoveExecutable(PathBuf, PathBuf),
 }
 
 #[derive(Debug)]
@@ -39,18 +123,257 @@ pub enum RegisteredAppsListOutput {
     Launch(usize),
     Remove(usize),
     ShowInfo(usize),
+    /// The set of checked executables changed (select mode only).
+    CheckedChanged(Vec<PathBuf>),
+    OpenFolder(PathBuf),
+    /// Stop the running executable.
+    Stop(PathBuf),
+    /// Persist a new category (`None` moves the app back to "Other").
+    SetCategory(PathBuf, Option<String>),
+    SetFavorite(PathBuf, bool),
+    /// Move the first executable into the position of the second.
+    Move(PathBuf, PathBuf),
 }
 
 impl Drop for RegisteredAppsListModel {
     fn drop(&mut self) {
-        // Disconnect the signal before the factory VecDeque clears children during drop,
+        // Disconnect the signals before the factory VecDeques clear children during drop,
         // which would trigger selected_children_changed and panic-in-drop.
-        if let Some(h) = self.selection_handler_id.take() {
-            self.executables.widget().disconnect(h);
+        for section in self.sections.drain(..) {
+            section.items.widget().disconnect(section.selection_handler);
         }
     }
 }
 
+impl RegisteredAppsListModel {
+    /// Tear down and rebuild the category sections from `registered_executables`.
+    fn rebuild_sections(&mut self, sender: &AsyncComponentSender<Self>) {
+        for section in self.sections.drain(..) {
+            section.items.widget().disconnect(section.selection_handler);
+            self.sections_box.remove(&section.expander);
+        }
+        self.selected = None;
+
+        // Favorites first, named categories alphabetically, "Other" last
+        let mut keys: Vec<SectionKey> = self
+            .registered_executables
+            .iter()
+            .map(SectionKey::for_executable)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        keys.sort_by(|a, b| {
+            a.rank()
+                .cmp(&b.rank())
+                .then_with(|| a.title().to_lowercase().cmp(&b.title().to_lowercase()))
+        });
+
+        for key in keys {
+            let section_idx = self.sections.len();
+            let indices: Vec<usize> = self
+                .registered_executables
+                .iter()
+                .enumerate()
+                .filter(|(_, exe)| SectionKey::for_executable(exe) == key)
+                .map(|(i, _)| i)
+                .collect();
+
+            let flowbox = gtk::FlowBox::builder()
+                .row_spacing(15)
+                .column_spacing(15)
+                .margin_top(10)
+                .margin_bottom(10)
+                .margin_start(10)
+                .margin_end(10)
+                .selection_mode(gtk::SelectionMode::Single)
+                .activate_on_single_click(false)
+                .valign(gtk::Align::Start)
+                .halign(gtk::Align::Fill)
+                .build();
+            apply_layout(&flowbox, self.list_view);
+
+            let mut items = FactoryVecDeque::builder().launch(flowbox).forward(
+                sender.input_sender(),
+                |output| match output {
+                    RegisteredExecutableItemOutput::Toggled(path, active) => {
+                        RegisteredAppsListMsg::ItemChecked(path, active)
+                    }
+                    RegisteredExecutableItemOutput::OpenFolder(path) => {
+                        RegisteredAppsListMsg::OpenFolder(path)
+                    }
+                    RegisteredExecutableItemOutput::Stop(path) => RegisteredAppsListMsg::Stop(path),
+                    RegisteredExecutableItemOutput::EditCategory(path, current) => {
+                        RegisteredAppsListMsg::EditCategory(path, current)
+                    }
+                    RegisteredExecutableItemOutput::SetFavorite(path, favorite) => {
+                        RegisteredAppsListMsg::SetFavorite(path, favorite)
+                    }
+                },
+            );
+            {
+                let mut guard = items.guard();
+                for &idx in &indices {
+                    let exe = &self.registered_executables[idx];
+                    let resolved = resolve_or_extract_icon(exe, &self.prefix_path, &self.icon_cache);
+                    guard.push_back((exe.clone(), idx, resolved));
+                }
+                for item in guard.iter_mut() {
+                    item.select_mode = self.select_mode;
+                    item.list_view = self.list_view;
+                    item.checked = self.checked.contains(&item.executable.executable_path);
+                }
+            }
+
+            let grid = items.widget();
+            let selection_handler = grid.connect_selected_children_changed({
+                let sender = sender.clone();
+                move |_| {
+                    sender.input(RegisteredAppsListMsg::SelectionChanged(section_idx));
+                }
+            });
+            // Enter (or double-click) launches; Delete removes the selection.
+            // Arrow-key navigation is provided by the FlowBox itself.
+            grid.connect_child_activated({
+                let sender = sender.clone();
+                move |_, child| {
+                    sender.input(RegisteredAppsListMsg::Activate(
+                        section_idx,
+                        child.index() as usize,
+                    ));
+                }
+            });
+            let key_controller = gtk::EventControllerKey::new();
+            key_controller.connect_key_pressed({
+                let sender = sender.clone();
+                move |_, key, _, _| {
+                    if key == gtk::gdk::Key::Delete || key == gtk::gdk::Key::KP_Delete {
+                        sender.input(RegisteredAppsListMsg::RemoveSelected);
+                        gtk::glib::Propagation::Stop
+                    } else {
+                        gtk::glib::Propagation::Proceed
+                    }
+                }
+            });
+            grid.add_controller(key_controller);
+
+            // Dropping one item onto another moves it into that item's place
+            for (position, &idx) in indices.iter().enumerate() {
+                let Some(child) = grid.child_at_index(position as i32) else {
+                    continue;
+                };
+                let path = self.registered_executables[idx].executable_path.clone();
+
+                let drag_source = gtk::DragSource::new();
+                drag_source.set_actions(gtk::gdk::DragAction::MOVE);
+                drag_source.connect_prepare({
+                    let path = path.to_string_lossy().into_owned();
+                    move |_, _, _| Some(gtk::gdk::ContentProvider::for_value(&path.to_value()))
+                });
+                child.add_controller(drag_source);
+
+                let drop_target =
+                    gtk::DropTarget::new(String::static_type(), gtk::gdk::DragAction::MOVE);
+                drop_target.connect_drop({
+                    let sender = sender.clone();
+                    move |_, value, _, _| {
+                        let Ok(from) = value.get::<String>() else {
+                            return false;
+                        };
+                        sender.input(RegisteredAppsListMsg::MoveExecutable(
+                            PathBuf::from(from),
+                            path.clone(),
+                        ));
+                        true
+                    }
+                });
+                child.add_controller(drop_target);
+            }
+
+            let name = key.title();
+            let header = gtk::Label::builder()
+                .label(&crate::tf!("apps.category_header", "name" => &name, "count" => &indices.len().to_string()))
+                .css_classes(["heading"])
+                .build();
+            let expander = gtk::Expander::builder()
+                .label_widget(&header)
+                .expanded(!self.collapsed.contains(&key))
+                .child(grid)
+                .build();
+            expander.connect_expanded_notify({
+                let sender = sender.clone();
+                move |exp| {
+                    sender.input(RegisteredAppsListMsg::SectionToggled(
+                        key.clone(),
+                        exp.is_expanded(),
+                    ));
+                }
+            });
+            self.sections_box.append(&expander);
+
+            self.sections.push(CategorySection {
+                expander,
+                items,
+                indices,
+                selection_handler,
+            });
+        }
+    }
+
+    fn for_each_item(&mut self, mut f: impl FnMut(&mut RegisteredExecutableItem)) {
+        for section in &mut self.sections {
+            let mut guard = section.items.guard();
+            for item in guard.iter_mut() {
+                f(item);
+            }
+        }
+    }
+
+    /// Prompt for a category name; an empty entry clears the category.
+    fn prompt_category(
+        root: &gtk::Box,
+        sender: &AsyncComponentSender<Self>,
+        path: PathBuf,
+        current: Option<String>,
+    ) {
+        let entry = gtk::Entry::builder()
+            .text(current.as_deref().unwrap_or_default())
+            .placeholder_text(crate::t!("apps.category_other"))
+            .activates_default(true)
+            .build();
+        let alert = adw::AlertDialog::new(
+            Some(&crate::t!("apps.set_category_title")),
+            Some(&crate::t!("apps.set_category_desc")),
+        );
+        alert.set_extra_child(Some(&entry));
+        alert.add_response("cancel", &crate::t!("dialogs.cancel"));
+        alert.add_response("save", &crate::t!("dialogs.save"));
+        alert.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+        alert.set_default_response(Some("save"));
+        alert.set_close_response("cancel");
+        let sender = sender.clone();
+        alert.choose(Some(root), None::<&gtk::gio::Cancellable>, move |response| {
+            if response == "save" {
+                let text = entry.text().trim().to_string();
+                let category = (!text.is_empty()).then_some(text);
+                let _ = sender.output(RegisteredAppsListOutput::SetCategory(path, category));
+            }
+        });
+    }
+}
+
+/// Lay a section out as icon tiles, or one row per executable in list view.
+fn apply_layout(flowbox: &gtk::FlowBox, list_view: bool) {
+    if list_view {
+        flowbox.set_min_children_per_line(1);
+        flowbox.set_max_children_per_line(1);
+        flowbox.set_homogeneous(false);
+    } else {
+        flowbox.set_max_children_per_line(5);
+        flowbox.set_min_children_per_line(3);
+        flowbox.set_homogeneous(true);
+    }
+}
+
 // Grid-based factory component for registered executables
 #[derive(Debug)]
 struct RegisteredExecutableItem {
@@ -58,39 +381,195 @@ struct RegisteredExecutableItem {
     #[allow(dead_code)]
     index: usize,
     is_running: bool,
+    is_missing: bool,
+    select_mode: bool,
+    list_view: bool,
+    checked: bool,
     resolved_icon: Option<PathBuf>,
 }
 
+#[derive(Debug)]
+enum RegisteredExecutableItemMsg {
+    Toggled(bool),
+    OpenFolder,
+    Stop,
+    EditCategory,
+    ToggleFavorite,
+}
+
+#[derive(Debug)]
+enum RegisteredExecutableItemOutput {
+    Toggled(PathBuf, bool),
+    OpenFolder(PathBuf),
+    Stop(PathBuf),
+    EditCategory(PathBuf, Option<String>),
+    SetFavorite(PathBuf, bool),
+}
+
+impl RegisteredExecutableItem {
+    fn icon_size(&self) -> i32 {
+        if self.list_view { 32 } else { 48 }
+    }
+
+    fn css_classes(&self) -> Vec<&'static str> {
+        let mut classes = vec!["app-item"];
+        if self.is_running {
+            classes.push("running");
+        }
+        if self.is_missing {
+            classes.push("missing");
+        }
+        classes
+    }
+}
+
 #[relm4::factory]
 impl FactoryComponent for RegisteredExecutableItem {
     type Init = (RegisteredExecutable, usize, Option<PathBuf>);
-    type Input = ();
-    type Output = ();
+    type Input = RegisteredExecutableItemMsg;
+    type Output = RegisteredExecutableItemOutput;
     type CommandOutput = ();
     type ParentWidget = gtk::FlowBox;
 
     view! {
         #[root]
         gtk::Box {
-            set_orientation: gtk::Orientation::Vertical,
+            #[watch]
+            set_orientation: if self.list_view {
+                gtk::Orientation::Horizontal
+            } else {
+                gtk::Orientation::Vertical
+            },
             set_spacing: 6,
             set_margin_all: 8,
             set_width_request: 64,
             set_height_request: 64,
-            set_focusable: true,
 
             #[watch]
-            set_css_classes: if self.is_running { &["app-item", "running"] } else { &["app-item"] },
+            set_css_classes: &self.css_classes(),
+            #[watch]
+            set_tooltip_text: if self.is_missing {
+                Some(crate::t!("apps.missing_tooltip"))
+            } else {
+                None
+            }.as_deref(),
+
+                add_controller = gtk::GestureClick {
+                    set_button: gtk::gdk::BUTTON_SECONDARY,
+                    connect_pressed[context_popover] => move |_, _, _, _| {
+                        context_popover.popup();
+                    },
+                },
+
+                #[name = "context_popover"]
+                gtk::Popover {
+                    set_has_arrow: false,
+
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+
+                        gtk::Button {
+                            add_css_class: "flat",
+                            connect_clicked[sender, context_popover] => move |_| {
+                                context_popover.popdown();
+                                sender.input(RegisteredExecutableItemMsg::OpenFolder);
+                            },
+
+                            #[wrap(Some)]
+                            set_child = &gtk::Box {
+                                set_orientation: gtk::Orientation::Horizontal,
+                                set_spacing: 6,
+
+                                gtk::Image {
+                                    set_icon_name: Some("folder-open-symbolic"),
+                                },
+                                gtk::Label {
+                                    set_label: &crate::t!("apps.actions.open_folder"),
+                                },
+                            },
+                        },
+
+                        gtk::Button {
+                            add_css_class: "flat",
+                            connect_clicked[sender, context_popover] => move |_| {
+                                context_popover.popdown();
+                                sender.input(RegisteredExecutableItemMsg::EditCategory);
+                            },
+
+                            #[wrap(Some)]
+                            set_child = &gtk::Box {
+                                set_orientation: gtk::Orientation::Horizontal,
+                                set_spacing: 6,
+
+                                gtk::Image {
+                                    set_icon_name: Some("folder-symbolic"),
+                                },
+                                gtk::Label {
+                                    set_label: &crate::t!("apps.actions.set_category"),
+                                },
+                            },
+                        },
+                    },
+                },
+
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Horizontal,
+
+                    gtk::CheckButton {
+                        set_halign: gtk::Align::Start,
+                        #[watch]
+                        set_visible: self.select_mode,
+                        #[watch]
+                        #[block_signal(toggle_handler)]
+                        set_active: self.checked,
+                        connect_toggled[sender] => move |btn| {
+                            sender.input(RegisteredExecutableItemMsg::Toggled(btn.is_active()));
+                        } @toggle_handler,
+                    },
+
+                    gtk::Button {
+                        set_halign: gtk::Align::Start,
+                        set_css_classes: &["flat", "circular", "destructive-action"],
+                        set_icon_name: "media-playback-stop-symbolic",
+                        set_tooltip_text: Some(&crate::t!("apps.stop")),
+                        #[watch]
+                        set_visible: self.is_running,
+                        connect_clicked => RegisteredExecutableItemMsg::Stop,
+                    },
+
+                    gtk::Button {
+                        #[watch]
+                        set_hexpand: !self.list_view,
+                        set_halign: gtk::Align::End,
+                        set_css_classes: &["flat", "circular", "favorite-toggle"],
+                        #[watch]
+                        set_icon_name: if self.executable.favorite {
+                            "starred-symbolic"
+                        } else {
+                            "non-starred-symbolic"
+                        },
+                        #[watch]
+                        set_tooltip_text: Some(&if self.executable.favorite {
+                            crate::t!("apps.unpin_favorite")
+                        } else {
+                            crate::t!("apps.pin_favorite")
+                        }),
+                        connect_clicked => RegisteredExecutableItemMsg::ToggleFavorite,
+                    },
+                },
 
                 // Icon from file, or fallback default
                 gtk::Box {
-                    set_width_request: 48,
-                    set_height_request: 48,
+                    #[watch]
+                    set_width_request: self.icon_size(),
+                    #[watch]
+                    set_height_request: self.icon_size(),
                     set_halign: gtk::Align::Center,
                     add_css_class: "icon-bg",
 
                     gtk::Image {
-                        set_pixel_size: 48,
+                        #[watch]
+                        set_pixel_size: self.icon_size(),
                         #[watch]
                         set_from_file: self.resolved_icon.as_deref(),
                         #[watch]
@@ -100,7 +579,8 @@ impl FactoryComponent for RegisteredExecutableItem {
                         set_vexpand: true,
                     },
                     gtk::Image {
-                        set_pixel_size: 48,
+                        #[watch]
+                        set_pixel_size: self.icon_size(),
                         set_icon_name: Some("application-x-executable"),
                         #[watch]
                         set_visible: self.resolved_icon.is_none(),
@@ -113,7 +593,10 @@ impl FactoryComponent for RegisteredExecutableItem {
                 gtk::Label {
                     #[watch]
                     set_label: &self.executable.name,
-                    set_halign: gtk::Align::Center,
+                    #[watch]
+                    set_halign: if self.list_view { gtk::Align::Start } else { gtk::Align::Center },
+                    #[watch]
+                    set_hexpand: self.list_view,
                     set_ellipsize: gtk::pango::EllipsizeMode::End,
                     set_max_width_chars: 15,
                     set_lines: 2,
@@ -121,21 +604,82 @@ impl FactoryComponent for RegisteredExecutableItem {
                     set_wrap_mode: gtk::pango::WrapMode::WordChar,
                 },
 
+                gtk::Label {
+                    #[watch]
+                    set_label: self.executable.architecture.as_deref().unwrap_or(""),
+                    #[watch]
+                    set_visible: self.executable.architecture.is_some(),
+                    set_halign: gtk::Align::Center,
+                    set_css_classes: &["caption", "dim-label"],
+                },
+
+                gtk::Label {
+                    set_label: &crate::t!("apps.running"),
+                    #[watch]
+                    set_visible: self.is_running,
+                    set_halign: gtk::Align::Center,
+                    set_css_classes: &["caption", "success"],
+                },
+
+                gtk::Image {
+                    set_icon_name: Some("dialog-warning-symbolic"),
+                    set_halign: gtk::Align::Center,
+                    add_css_class: "warning",
+                    #[watch]
+                    set_visible: self.is_missing,
+                },
+
         }
     }
 
     fn init_model(init: Self::Init, _index: &DynamicIndex, _sender: FactorySender<Self>) -> Self {
         let (executable, idx, resolved_icon) = init;
+        let is_missing = !executable.executable_path.exists();
         Self {
             executable,
             index: idx,
             is_running: false,
+            is_missing,
+            select_mode: false,
+            list_view: false,
+            checked: false,
             resolved_icon,
         }
     }
 
-    fn update(&mut self, _msg: Self::Input, _sender: FactorySender<Self>) {
-        // No messages to handle - selection is handled by FlowBox
+    fn update(&mut self, msg: Self::Input, sender: FactorySender<Self>) {
+        // Single selection is handled by the FlowBox; only checkboxes report here
+        match msg {
+            RegisteredExecutableItemMsg::Toggled(active) => {
+                self.checked = active;
+                let _ = sender.output(RegisteredExecutableItemOutput::Toggled(
+                    self.executable.executable_path.clone(),
+                    active,
+                ));
+            }
+            RegisteredExecutableItemMsg::OpenFolder => {
+                let _ = sender.output(RegisteredExecutableItemOutput::OpenFolder(
+                    self.executable.executable_path.clone(),
+                ));
+            }
+            RegisteredExecutableItemMsg::Stop => {
+                let _ = sender.output(RegisteredExecutableItemOutput::Stop(
+                    self.executable.executable_path.clone(),
+                ));
+            }
+            RegisteredExecutableItemMsg::ToggleFavorite => {
+                let _ = sender.output(RegisteredExecutableItemOutput::SetFavorite(
+                    self.executable.executable_path.clone(),
+                    !self.executable.favorite,
+                ));
+            }
+            RegisteredExecutableItemMsg::EditCategory => {
+                let _ = sender.output(RegisteredExecutableItemOutput::EditCategory(
+                    self.executable.executable_path.clone(),
+                    self.executable.category.clone(),
+                ));
+            }
+        }
     }
 }
 
@@ -165,16 +709,11 @@ impl AsyncComponent for RegisteredAppsListModel {
                 set_min_content_height: 200,
 
                 #[local_ref]
-                registered_grid -> gtk::FlowBox {
-                    set_row_spacing: 15,
-                    set_column_spacing: 15,
+                sections_box -> gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
+                    set_spacing: 12,
                     set_margin_all: 10,
-                    set_max_children_per_line: 5,
-                    set_min_children_per_line: 3,
-                    set_selection_mode: gtk::SelectionMode::Single,
-                    set_homogeneous: true,
                     set_valign: gtk::Align::Start,
-                    set_halign: gtk::Align::Fill,
                 },
             },
 
@@ -207,44 +746,24 @@ impl AsyncComponent for RegisteredAppsListModel {
     ) -> AsyncComponentParts<Self> {
         let (executables_init, prefix_path, icon_cache) = init;
 
-        // Initialize factory for registered executables (grid layout)
-        let executables = FactoryVecDeque::builder()
-            .launch(gtk::FlowBox::default())
-            .detach();
-
-        let prefix_path_for_init = prefix_path.clone();
-        let icon_cache_for_init = Arc::clone(&icon_cache);
         let mut model = RegisteredAppsListModel {
-            executables,
-            registered_executables: executables_init.clone(),
-            selection_handler_id: None,
+            sections_box: gtk::Box::default(),
+            sections: Vec::new(),
+            registered_executables: executables_init,
+            selected: None,
             prefix_path,
             icon_cache,
+            select_mode: false,
+            list_view: prefix::Settings::load().is_some_and(|s| s.apps_list_view),
+            checked: HashSet::new(),
+            collapsed: HashSet::new(),
             tracker: 0,
         };
+        model.rebuild_sections(&sender);
 
-        // Initialize with provided executables — resolve + extract fallback
-        {
-            let mut guard = model.executables.guard();
-            for (idx, exe) in executables_init.iter().enumerate() {
-                let resolved =
-                    resolve_or_extract_icon(exe, &prefix_path_for_init, &icon_cache_for_init);
-                guard.push_back((exe.clone(), idx, resolved));
-            }
-        }
-
-        let registered_grid = model.executables.widget();
+        let sections_box = model.sections_box.clone();
         let widgets = view_output!();
 
-        // Connect selection-changed on the factory's FlowBox so we can block it during clear
-        let handler_id = registered_grid.connect_selected_children_changed({
-            let sender = sender.clone();
-            move |_| {
-                let _ = sender.input(RegisteredAppsListMsg::SelectionChanged);
-            }
-        });
-        model.selection_handler_id = Some(handler_id);
-
         AsyncComponentParts { model, widgets }
     }
 
@@ -252,43 +771,34 @@ impl AsyncComponent for RegisteredAppsListModel {
         &mut self,
         msg: Self::Input,
         sender: AsyncComponentSender<Self>,
-        _root: &Self::Root,
+        root: &Self::Root,
     ) {
         self.reset();
         match msg {
             RegisteredAppsListMsg::UpdateExecutables(executables) => {
                 self.registered_executables = executables.clone();
 
-                // Block selection-changed signal during clear to avoid panic
-                {
-                    let grid = self.executables.widget();
-                    if let Some(ref h) = self.selection_handler_id {
-                        grid.block_signal(h);
-                    }
-                }
-
-                let prefix_path = self.prefix_path.clone();
-                let icon_cache = Arc::clone(&self.icon_cache);
-                let mut guard = self.executables.guard();
-                guard.clear();
-                for (idx, exe) in executables.iter().enumerate() {
-                    let resolved = resolve_or_extract_icon(exe, &prefix_path, &icon_cache);
-                    guard.push_back((exe.clone(), idx, resolved));
-                }
-                drop(guard);
-
-                {
-                    let grid = self.executables.widget();
-                    if let Some(ref h) = self.selection_handler_id {
-                        grid.unblock_signal(h);
-                    }
+                // Drop checked entries that are no longer registered before
+                // rebuilding so the sections pick up the pruned state
+                let before = self.checked.len();
+                self.checked
+                    .retain(|path| executables.iter().any(|e| &e.executable_path == path));
+                self.rebuild_sections(&sender);
+                if self.checked.len() != before {
+                    let _ = sender.output(RegisteredAppsListOutput::CheckedChanged(
+                        self.checked.iter().cloned().collect(),
+                    ));
                 }
             }
             RegisteredAppsListMsg::SetRunningPaths(paths) => {
-                let mut guard = self.executables.guard();
-                for item in guard.iter_mut() {
+                self.for_each_item(|item| {
                     item.is_running = paths.contains(&item.executable.executable_path);
-                }
+                });
+            }
+            RegisteredAppsListMsg::RefreshMissing => {
+                self.for_each_item(|item| {
+                    item.is_missing = !item.executable.executable_path.exists();
+                });
             }
             RegisteredAppsListMsg::PrefixPathUpdated(prefix_path) => {
                 if self.prefix_path == prefix_path {
@@ -299,24 +809,123 @@ impl AsyncComponent for RegisteredAppsListModel {
                 // Re-resolve icons in case the prefix location changed
                 let prefix_path = self.prefix_path.clone();
                 let icon_cache = Arc::clone(&self.icon_cache);
-                let mut guard = self.executables.guard();
-                for item in guard.iter_mut() {
+                self.for_each_item(|item| {
                     item.resolved_icon =
                         resolve_or_extract_icon(&item.executable, &prefix_path, &icon_cache);
+                });
+            }
+            RegisteredAppsListMsg::SetSelectMode(enabled) => {
+                self.set_select_mode(enabled);
+                if !enabled {
+                    self.checked.clear();
+                }
+                let checked = self.checked.clone();
+                self.for_each_item(|item| {
+                    item.select_mode = enabled;
+                    item.checked = enabled && checked.contains(&item.executable.executable_path);
+                });
+                let _ = sender.output(RegisteredAppsListOutput::CheckedChanged(
+                    self.checked.iter().cloned().collect(),
+                ));
+            }
+            RegisteredAppsListMsg::SetListView(enabled) => {
+                if self.list_view == enabled {
+                    return;
+                }
+                self.set_list_view(enabled);
+                // Re-layout in place so the current selection is kept
+                for section in &self.sections {
+                    apply_layout(section.items.widget(), enabled);
+                }
+                self.for_each_item(|item| item.list_view = enabled);
+            }
+            RegisteredAppsListMsg::ItemChecked(path, active) => {
+                if active {
+                    self.checked.insert(path);
+                } else {
+                    self.checked.remove(&path);
                 }
+                let _ = sender.output(RegisteredAppsListOutput::CheckedChanged(
+                    self.checked.iter().cloned().collect(),
+                ));
             }
-            RegisteredAppsListMsg::SelectionChanged => {
-                // Get the FlowBox widget to query selected children
-                let flowbox = self.executables.widget();
-                let selected_children = flowbox.selected_children();
-
-                if let Some(child) = selected_children.first() {
-                    // Get the index of the selected child
-                    let index = child.index() as usize;
+            RegisteredAppsListMsg::OpenFolder(path) => {
+                let _ = sender.output(RegisteredAppsListOutput::OpenFolder(path));
+            }
+            RegisteredAppsListMsg::Stop(path) => {
+                let _ = sender.output(RegisteredAppsListOutput::Stop(path));
+            }
+            RegisteredAppsListMsg::EditCategory(path, current) => {
+                Self::prompt_category(root, &sender, path, current);
+            }
+            RegisteredAppsListMsg::SetFavorite(path, favorite) => {
+                let _ = sender.output(RegisteredAppsListOutput::SetFavorite(path, favorite));
+            }
+            RegisteredAppsListMsg::SectionToggled(key, expanded) => {
+                if expanded {
+                    self.collapsed.remove(&key);
+                } else {
+                    self.collapsed.insert(key);
+                }
+            }
+            RegisteredAppsListMsg::GrabFocus => {
+                let selected = self.sections.iter().find_map(|section| {
+                    section.items.widget().selected_children().into_iter().next()
+                });
+                let target = selected.or_else(|| {
+                    self.sections
+                        .iter()
+                        .find(|section| section.expander.is_expanded())
+                        .and_then(|section| section.items.widget().child_at_index(0))
+                });
+                if let Some(child) = target {
+                    if let Some(flowbox) = child.parent().and_downcast::<gtk::FlowBox>() {
+                        flowbox.select_child(&child);
+                    }
+                    child.grab_focus();
+                }
+            }
+            RegisteredAppsListMsg::Activate(section, position) => {
+                if let Some(&index) = self
+                    .sections
+                    .get(section)
+                    .and_then(|s| s.indices.get(position))
+                {
+                    let _ = sender.output(RegisteredAppsListOutput::Launch(index));
+                }
+            }
+            RegisteredAppsListMsg::RemoveSelected => {
+                if let Some(index) = self.selected {
                     if index < self.registered_executables.len() {
-                        let _ = sender.output(RegisteredAppsListOutput::Selected(index));
+                        let _ = sender.output(RegisteredAppsListOutput::Remove(index));
+                    }
+                }
+            }
+            RegisteredAppsListMsg::MoveExecutable(from, to) => {
+                if from != to {
+                    let _ = sender.output(RegisteredAppsListOutput::Move(from, to));
+                }
+            }
+            RegisteredAppsListMsg::SelectionChanged(section_idx) => {
+                let Some(section) = self.sections.get(section_idx) else {
+                    return;
+                };
+                let Some(child) = section.items.widget().selected_children().into_iter().next()
+                else {
+                    return;
+                };
+                let Some(&index) = section.indices.get(child.index() as usize) else {
+                    return;
+                };
+
+                // Keep a single selection across all sections
+                for (i, other) in self.sections.iter().enumerate() {
+                    if i != section_idx {
+                        other.items.widget().unselect_all();
                     }
                 }
+                self.selected = Some(index);
+                let _ = sender.output(RegisteredAppsListOutput::Selected(index));
             }
         }
     }
@@ -1,5 +1,6 @@
 pub mod actions;
 pub mod add_popover;
+mod copy_from;
 pub mod info_dialog;
 pub mod list;
 
@@ -24,7 +25,7 @@ use relm4::{
     view,
 };
 use service::AppService;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use tracker;
 
@@ -35,6 +36,9 @@ pub struct AppManagerModel {
     selected_executable: Option<usize>,
     #[tracker::do_not_track]
     available_executables: Vec<RegisteredExecutable>,
+    /// Executables the last rescan found that the scan before it didn't.
+    #[tracker::do_not_track]
+    newly_found: HashSet<PathBuf>,
     #[tracker::do_not_track]
     registered_apps_list: AsyncController<RegisteredAppsListModel>,
     #[tracker::do_not_track]
@@ -49,30 +53,262 @@ pub struct AppManagerModel {
     #[tracker::do_not_track]
     external_running: HashSet<PathBuf>,
     #[tracker::do_not_track]
-    debug_window: Option<relm4::component::AsyncController<DebugWindowModel>>,
+    checked_paths: Vec<PathBuf>,
+    #[tracker::do_not_track]
+    toast_overlay: adw::ToastOverlay,
+    #[tracker::do_not_track]
+    debug_windows: HashMap<u32, relm4::component::AsyncController<DebugWindowModel>>,
 }
 
 #[derive(Debug)]
 pub enum AppManagerMsg {
     ScanForApplications,
+    /// Open the Add popover, e.g. to show the results of a scan.
+    ShowAddPopover,
     AddExecutable(usize),
     AddExecutables(Vec<usize>),
+    /// Start copying registered applications from another prefix.
+    CopyFromPrefix,
+    /// Offer the applications registered in this prefix but not here.
+    CompareWithPrefix(PathBuf),
+    CopyExecutables(Vec<RegisteredExecutable>),
     RemoveExecutable(usize),
-    LaunchExecutable(usize),
+    /// Launch the executable at the index, with the named launch profile.
+    LaunchExecutable(usize, Option<String>),
+    /// Like `LaunchExecutable`, past the architecture check.
+    LaunchConfirmed(usize, Option<String>),
     LaunchDirectExe(PathBuf),
     UpdateExecutableList(Vec<RegisteredExecutable>),
     SelectExecutable(usize),
     ConfigUpdated(PrefixConfig),
     PrefixPathUpdated(PathBuf),
     ShowInfoDialog(usize),
+    OpenFolder(PathBuf),
+    FocusList,
     // Messages from child components
     RegisteredAppsList(RegisteredAppsListOutput),
     AppActions(AppActionsOutput),
     AddAppPopover(AddAppPopoverOutput),
     ExecutableInfoDialog(ExecutableInfoDialogOutput),
     LaunchExecutableDebug(usize),
-    DebugWindow(DebugWindowOutput),
+    DebugWindow(u32, DebugWindowOutput),
     PollProcesses,
+    /// Registered executables found running in the prefix at the path.
+    RunningChecked(PathBuf, HashSet<PathBuf>),
+    StopExecutable(PathBuf),
+    LaunchFailed(String, String),
+    ExecutableExited(String, Option<i32>),
+}
+
+/// Names for the Run button's launch-profile menu.
+fn profile_names(exe: &RegisteredExecutable) -> Vec<String> {
+    exe.launch_profiles.iter().map(|p| p.name.clone()).collect()
+}
+
+/// Warn that `name` most likely can't run in its prefix, and call
+/// `on_continue` if the user launches it anyway.
+pub(crate) fn confirm_mismatched_launch<F>(
+    parent: Option<&gtk::Window>,
+    name: &str,
+    mismatch: prefix::ArchMismatch,
+    on_continue: F,
+) where
+    F: FnOnce() + 'static,
+{
+    let body = match mismatch {
+        prefix::ArchMismatch::Needs64Bit => {
+            crate::tf!("apps.arch_mismatch.needs_64bit", "name" => name)
+        }
+        prefix::ArchMismatch::Needs32Bit => {
+            crate::tf!("apps.arch_mismatch.needs_32bit", "name" => name)
+        }
+    };
+    let alert = adw::AlertDialog::new(Some(&crate::t!("apps.arch_mismatch.title")), Some(&body));
+    alert.add_response("cancel", &crate::t!("dialogs.cancel"));
+    alert.add_response("launch", &crate::t!("apps.arch_mismatch.launch"));
+    alert.set_response_appearance("launch", adw::ResponseAppearance::Destructive);
+    alert.set_default_response(Some("cancel"));
+    alert.set_close_response("cancel");
+    alert.choose(parent, None::<&gtk::gio::Cancellable>, move |response| {
+        if response == "launch" {
+            on_continue();
+        }
+    });
+}
+
+/// Executables that exit with an error within this window after launch are
+/// reported to the user; anything longer-lived is assumed to have run.
+const QUICK_EXIT_WINDOW: std::time::Duration = std::time::Duration::from_secs(10);
+
+impl AppManagerModel {
+    /// Re-check registered executables against the filesystem and update
+    /// the "missing" indicators and the remove-broken action.
+    fn refresh_missing_state(&self) {
+        let executables = &self.prefix.config().registered_executables;
+        let broken = executables
+            .iter()
+            .filter(|exe| !exe.executable_path.exists())
+            .count();
+        let selected_missing = self
+            .selected_executable
+            .and_then(|i| executables.get(i))
+            .is_some_and(|exe| !exe.executable_path.exists());
+        self.registered_apps_list
+            .emit(RegisteredAppsListMsg::RefreshMissing);
+        self.app_actions.emit(AppActionsMsg::SetBrokenCount(broken));
+        self.app_actions
+            .emit(AppActionsMsg::SetSelectedMissing(selected_missing));
+    }
+
+    /// Register `exes` in this prefix and refresh the list, skipping ones
+    /// outside the prefix or already registered with a toast.
+    fn register_executables(
+        &mut self,
+        exes: Vec<RegisteredExecutable>,
+        sender: &AsyncComponentSender<Self>,
+    ) {
+        let path = self.prefix.path().to_path_buf();
+        let (exes, outside): (Vec<_>, Vec<_>) = exes
+            .into_iter()
+            .partition(|exe| exe.is_allowed_in_prefix(&path));
+        if !outside.is_empty() {
+            self.toast_overlay.add_toast(adw::Toast::new(&crate::tf!(
+                "apps.outside_prefix_skipped",
+                "count" => &outside.len().to_string()
+            )));
+        }
+
+        let Some(added) = service::config_ops::add_executables(
+            &AppService::global(),
+            &path,
+            self.prefix.config_mut(),
+            &exes,
+        ) else {
+            return;
+        };
+        let skipped = exes.len() - added;
+        if skipped > 0 {
+            self.toast_overlay.add_toast(adw::Toast::new(&crate::tf!(
+                "apps.duplicates_skipped",
+                "count" => &skipped.to_string()
+            )));
+        }
+        if added > 0 {
+            self.registered_apps_list
+                .emit(RegisteredAppsListMsg::UpdateExecutables(
+                    self.prefix.config().registered_executables.clone(),
+                ));
+            let _ = sender.output(AppManagerMsg::ConfigUpdated(self.prefix.config().clone()));
+        }
+    }
+
+    /// Keep `exes` as the add popover's candidates, newly found ones first
+    /// so the popover can list them in their own section.
+    fn set_available_executables(&mut self, mut exes: Vec<RegisteredExecutable>) {
+        exes.sort_by_key(|exe| !self.newly_found.contains(&exe.executable_path));
+        self.available_executables = exes;
+    }
+
+    fn newly_found_count(&self) -> usize {
+        self.available_executables
+            .iter()
+            .take_while(|exe| self.newly_found.contains(&exe.executable_path))
+            .count()
+    }
+
+    /// Re-check which registered executables are running, off the GTK
+    /// thread: it reads the environment of every process on the system. The
+    /// result comes back as `RunningChecked`.
+    fn refresh_running(&self, sender: &AsyncComponentSender<Self>) {
+        let prefix_path = self.prefix.path().to_path_buf();
+        let executables = self.prefix.config().registered_executables.clone();
+        let s = sender.clone();
+        gtk::glib::spawn_future_local(async move {
+            let path = prefix_path.clone();
+            let check = move || {
+                service::launch::running_executables(&AppService::global(), &path, &executables)
+            };
+            match gtk::gio::spawn_blocking(check).await {
+                Ok(paths) => s.input(AppManagerMsg::RunningChecked(prefix_path, paths)),
+                Err(_) => error!("[apps] running check task panicked"),
+            }
+        });
+    }
+
+    /// Update the list indicators and the selected app's actions from the
+    /// running executables at `paths`.
+    fn set_running(&mut self, paths: HashSet<PathBuf>) {
+        let selected_running = self
+            .selected_executable
+            .and_then(|i| self.prefix.config().registered_executables.get(i))
+            .is_some_and(|exe| paths.contains(&exe.executable_path));
+        self.set_running_paths(paths.clone());
+        self.registered_apps_list
+            .emit(RegisteredAppsListMsg::SetRunningPaths(paths));
+        self.app_actions
+            .emit(AppActionsMsg::SetSelectedRunning(selected_running));
+    }
+
+    /// Update launch statistics for the executable at `index` before it is
+    /// started, keeping an open info dialog in sync.
+    fn record_launch(&mut self, index: usize) {
+        let Some(exe_path) = self
+            .prefix
+            .config()
+            .registered_executables
+            .get(index)
+            .map(|e| e.executable_path.clone())
+        else {
+            return;
+        };
+        let path = self.prefix.path().to_path_buf();
+        service::config_ops::record_launch(
+            &AppService::global(),
+            &path,
+            self.prefix.config_mut(),
+            &exe_path,
+        );
+        if let Some(exe) = self.prefix.config().registered_executables.get(index) {
+            self.executable_info_dialog
+                .emit(ExecutableInfoDialogMsg::StatsUpdated(exe.clone()));
+        }
+    }
+
+    /// Apply an edit to one registered executable, persist the config and
+    /// rebuild the list. The selection is cleared since the item may move.
+    fn update_registered(
+        &mut self,
+        exe_path: &std::path::Path,
+        sender: &AsyncComponentSender<Self>,
+        edit: impl FnOnce(&mut RegisteredExecutable),
+    ) {
+        let Some(mut updated) = self
+            .prefix
+            .config()
+            .registered_executables
+            .iter()
+            .find(|e| e.executable_path == exe_path)
+            .cloned()
+        else {
+            return;
+        };
+        edit(&mut updated);
+        let path = self.prefix.path().to_path_buf();
+        if service::config_ops::update_executable(
+            &AppService::global(),
+            &path,
+            self.prefix.config_mut(),
+            updated,
+        ) {
+            self.set_selected_executable(None);
+            self.app_actions.emit(AppActionsMsg::SetSelection(false));
+            self.registered_apps_list
+                .emit(RegisteredAppsListMsg::UpdateExecutables(
+                    self.prefix.config().registered_executables.clone(),
+                ));
+            let _ = sender.output(AppManagerMsg::ConfigUpdated(self.prefix.config().clone()));
+        }
+    }
 }
 
 #[relm4::component(pub, async)]
@@ -85,24 +321,33 @@ impl AsyncComponent for AppManagerModel {
 
     view! {
         gtk::Box {
-            set_orientation: gtk::Orientation::Vertical,
-            set_spacing: 10,
-            set_margin_all: 10,
+            #[local_ref]
+            toast_overlay -> adw::ToastOverlay {
+                set_hexpand: true,
+                set_vexpand: true,
 
-            gtk::ScrolledWindow{
-                #[local_ref]
-                registered_apps_list_widget -> gtk::Widget {},
-            },
+                #[wrap(Some)]
+                set_child = &gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
+                    set_spacing: 10,
+                    set_margin_all: 10,
+
+                    gtk::ScrolledWindow{
+                        #[local_ref]
+                        registered_apps_list_widget -> gtk::Widget {},
+                    },
 
-            // Action bar at bottom
-            gtk::Box {
-                set_orientation: gtk::Orientation::Horizontal,
-                set_spacing: 10,
-                set_halign: gtk::Align::End,
-                set_margin_top: 10,
+                    // Action bar at bottom
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Horizontal,
+                        set_spacing: 10,
+                        set_halign: gtk::Align::End,
+                        set_margin_top: 10,
 
-                #[local_ref]
-                app_actions_widget -> gtk::Widget {},
+                        #[local_ref]
+                        app_actions_widget -> gtk::Widget {},
+                    },
+                },
             },
         }
     }
@@ -182,6 +427,7 @@ impl AsyncComponent for AppManagerModel {
             scanning: false,
             selected_executable: None,
             available_executables: Vec::new(),
+            newly_found: HashSet::new(),
             registered_apps_list,
             app_actions,
             add_app_popover,
@@ -189,7 +435,9 @@ impl AsyncComponent for AppManagerModel {
             running_paths: HashSet::new(),
             uninstaller_track_path: None,
             external_running: HashSet::new(),
-            debug_window: None,
+            checked_paths: Vec::new(),
+            toast_overlay: adw::ToastOverlay::new(),
+            debug_windows: HashMap::new(),
             tracker: 0,
         };
 
@@ -200,6 +448,7 @@ impl AsyncComponent for AppManagerModel {
             .clone()
             .upcast::<gtk::Widget>();
         let app_actions_widget = model.app_actions.widget().clone().upcast::<gtk::Widget>();
+        let toast_overlay = model.toast_overlay.clone();
 
         let widgets = view_output!();
 
@@ -231,18 +480,27 @@ impl AsyncComponent for AppManagerModel {
                     self.prefix.path().display()
                 );
 
-                match self.prefix.scan_applications_async().await {
-                    Ok(all) => {
-                        info!("[apps] scanning complete, found {} executables", all.len());
+                match self.prefix.rescan_applications_async().await {
+                    Ok(rescan) => {
+                        info!(
+                            "[apps] scanning complete, found {} executables ({} new)",
+                            rescan.executables.len(),
+                            rescan.new_paths.len()
+                        );
                         let _ = AppService::global()
                             .prefix_store()
-                            .save_scanned_executables(&self.prefix.path().to_string_lossy(), &all);
-                        self.available_executables = all.clone();
+                            .save_scanned_executables(
+                                &self.prefix.path().to_string_lossy(),
+                                &rescan.executables,
+                            );
+                        self.newly_found = rescan.new_paths.into_iter().collect();
+                        self.set_available_executables(rescan.executables);
                         // Refresh popover list with scanned results
                         self.add_app_popover
                             .emit(AddAppPopoverMsg::UpdateAvailableApps(
-                                all.clone(),
+                                self.available_executables.clone(),
                                 self.prefix.config().architecture.clone(),
+                                self.newly_found_count(),
                             ));
                     }
                     Err(e) => {
@@ -253,10 +511,20 @@ impl AsyncComponent for AppManagerModel {
                 self.add_app_popover
                     .emit(AddAppPopoverMsg::SetScanning(false));
             }
+            AppManagerMsg::ShowAddPopover => {
+                self.add_app_popover.emit(AddAppPopoverMsg::Show);
+            }
             AppManagerMsg::AddExecutable(index) => {
                 if let Some(executable) = self.available_executables.get(index) {
                     info!("[apps] adding executable: {}", executable.name);
                     let path = self.prefix.path().to_path_buf();
+                    if !executable.is_allowed_in_prefix(&path) {
+                        self.toast_overlay.add_toast(adw::Toast::new(&crate::tf!(
+                            "apps.outside_prefix_skipped",
+                            "count" => "1"
+                        )));
+                        return;
+                    }
                     if service::config_ops::add_executable(
                         &AppService::global(),
                         &path,
@@ -270,24 +538,89 @@ impl AsyncComponent for AppManagerModel {
             AppManagerMsg::AddExecutables(indices) => {
                 info!("[apps] adding {} executables: {:?}", indices.len(), indices);
 
-                let exes: Vec<_> = indices
+                let exes = indices
                     .iter()
                     .filter_map(|&i| self.available_executables.get(i).cloned())
                     .collect();
-
-                let path = self.prefix.path().to_path_buf();
-                if service::config_ops::add_executables(
-                    &AppService::global(),
-                    &path,
-                    self.prefix.config_mut(),
-                    &exes,
-                ) {
-                    self.registered_apps_list
-                        .emit(RegisteredAppsListMsg::UpdateExecutables(
-                            self.prefix.config().registered_executables.clone(),
-                        ));
-                    let _ = sender.output(AppManagerMsg::ConfigUpdated(self.prefix.config().clone()));
+                self.register_executables(exes, &sender);
+            }
+            AppManagerMsg::CopyFromPrefix => {
+                let target = self.prefix.path().to_path_buf();
+                let sources: Vec<(String, PathBuf)> = AppService::global()
+                    .prefix_manager()
+                    .scan_prefixes()
+                    .map(|prefixes| {
+                        prefixes
+                            .into_iter()
+                            .filter(|p| p.path != target)
+                            .map(|p| (p.name, p.path))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                if sources.is_empty() {
+                    self.toast_overlay
+                        .add_toast(adw::Toast::new(&crate::t!("apps.copy.no_sources")));
+                    return;
                 }
+                let parent_window = _root
+                    .ancestor(gtk::Window::static_type())
+                    .and_then(|w| w.downcast::<gtk::Window>().ok());
+                let s = sender.clone();
+                copy_from::choose_source(parent_window.as_ref(), sources, move |source| {
+                    s.input(AppManagerMsg::CompareWithPrefix(source));
+                });
+            }
+            AppManagerMsg::CompareWithPrefix(source) => {
+                let target = self.prefix.path().to_path_buf();
+                let transfers = match AppService::global()
+                    .prefix_manager()
+                    .compare_registered_apps(&source, &target)
+                {
+                    Ok(transfers) => transfers,
+                    Err(e) => {
+                        error!("[apps] failed to compare with '{}': {}", source.display(), e);
+                        self.toast_overlay.add_toast(adw::Toast::new(&e.to_string()));
+                        return;
+                    }
+                };
+                if transfers.is_empty() {
+                    self.toast_overlay
+                        .add_toast(adw::Toast::new(&crate::t!("apps.copy.nothing_new")));
+                    return;
+                }
+                let source_name = PrefixConfig::load_from_file(&source)
+                    .ok()
+                    .flatten()
+                    .map(|config| config.name)
+                    .unwrap_or_else(|| prefix::prefix_label(&source));
+                let parent_window = _root
+                    .ancestor(gtk::Window::static_type())
+                    .and_then(|w| w.downcast::<gtk::Window>().ok());
+                let s = sender.clone();
+                copy_from::choose_apps(
+                    parent_window.as_ref(),
+                    &target,
+                    &source_name,
+                    transfers,
+                    move |exes| s.input(AppManagerMsg::CopyExecutables(exes)),
+                );
+            }
+            AppManagerMsg::CopyExecutables(exes) => {
+                // Files can disappear while the dialog is open
+                let (exes, missing): (Vec<_>, Vec<_>) = exes
+                    .into_iter()
+                    .partition(|exe| exe.executable_path.exists());
+                if !missing.is_empty() {
+                    self.toast_overlay.add_toast(adw::Toast::new(&crate::tf!(
+                        "apps.copy.missing_skipped",
+                        "count" => &missing.len().to_string()
+                    )));
+                }
+                info!(
+                    "[apps] copying {} executables from another prefix",
+                    exes.len()
+                );
+                self.register_executables(exes, &sender);
             }
             AppManagerMsg::RemoveExecutable(index) => {
                 if index < self.prefix.config().registered_executables.len() {
@@ -307,37 +640,117 @@ impl AsyncComponent for AppManagerModel {
                     }
                 }
             }
-            AppManagerMsg::LaunchExecutable(index) => {
+            AppManagerMsg::LaunchExecutable(index, profile) => {
+                let Some(executable) = self.prefix.config().registered_executables.get(index)
+                else {
+                    return;
+                };
+                let mismatch = prefix::architecture_mismatch(
+                    self.prefix.path(),
+                    &self.prefix.config().architecture,
+                    executable,
+                );
+                match mismatch {
+                    Some(mismatch) => {
+                        let parent_window = _root
+                            .ancestor(gtk::Window::static_type())
+                            .and_then(|w| w.downcast::<gtk::Window>().ok());
+                        let s = sender.clone();
+                        confirm_mismatched_launch(
+                            parent_window.as_ref(),
+                            &executable.name,
+                            mismatch,
+                            move || s.input(AppManagerMsg::LaunchConfirmed(index, profile)),
+                        );
+                    }
+                    None => sender.input(AppManagerMsg::LaunchConfirmed(index, profile)),
+                }
+            }
+            AppManagerMsg::LaunchConfirmed(index, profile) => {
+                self.record_launch(index);
                 if let Some(executable) = self.prefix.config().registered_executables.get(index) {
-                    match service::launch::launch_executable(
-                        &AppService::global(),
-                        self.prefix.path(),
-                        executable,
-                    ) {
-                        Ok(_pid) => {
-                            sender.input(AppManagerMsg::PollProcesses);
-                        }
-                        Err(e) => {
-                            let parent_window = _root
-                                .ancestor(gtk::Window::static_type())
-                                .and_then(|w| w.downcast::<gtk::Window>().ok());
-                            let alert = adw::AlertDialog::new(
-                                Some("Launch Failed"),
-                                Some(&format!("Failed to launch '{}':\n\n{}", executable.name, e)),
-                            );
-                            alert.add_response("ok", "OK");
-                            alert.set_default_response(Some("ok"));
-                            alert.set_close_response("ok");
-                            alert.choose(
-                                parent_window.as_ref(),
-                                None::<&gtk::gio::Cancellable>,
-                                |_| {},
-                            );
+                    let executable = executable.clone();
+                    let profile =
+                        profile.and_then(|name| executable.launch_profile(&name).cloned());
+                    let prefix_path = self.prefix.path().to_path_buf();
+                    let sender = sender.clone();
+                    // Await the exit status off the UI path so quick failures
+                    // can be reported without blocking on long-running apps.
+                    relm4::spawn(async move {
+                        let handle = match service::launch::launch_executable_async(
+                            &AppService::global(),
+                            &prefix_path,
+                            &executable,
+                            profile.as_ref(),
+                        ) {
+                            Ok(handle) => handle,
+                            Err(e) => {
+                                sender.input(AppManagerMsg::LaunchFailed(executable.name, e));
+                                return;
+                            }
+                        };
+                        sender.input(AppManagerMsg::PollProcesses);
+
+                        let started = std::time::Instant::now();
+                        let code = match handle.wait().await {
+                            Ok(code) => code,
+                            Err(e) => {
+                                error!("[apps] {}", e);
+                                return;
+                            }
+                        };
+                        info!("[apps] '{}' exited with code {:?}", executable.name, code);
+                        sender.input(AppManagerMsg::PollProcesses);
+                        if code != Some(0) && started.elapsed() < QUICK_EXIT_WINDOW {
+                            sender.input(AppManagerMsg::ExecutableExited(executable.name, code));
                         }
-                    }
+                        // `wineserver -w` blocks until the prefix has shut down
+                        let _ = tokio::task::spawn_blocking(move || {
+                            service::launch::launch_exited(&AppService::global(), &prefix_path)
+                        })
+                        .await;
+                    });
                 }
             }
+            AppManagerMsg::LaunchFailed(name, e) => {
+                let parent_window = _root
+                    .ancestor(gtk::Window::static_type())
+                    .and_then(|w| w.downcast::<gtk::Window>().ok());
+                let alert = adw::AlertDialog::new(
+                    Some(&crate::t!("apps.launch_failed")),
+                    Some(&crate::tf!("apps.launch_failed_desc", "name" => &name, "error" => &e)),
+                );
+                alert.add_response("ok", &crate::t!("dialogs.ok"));
+                alert.set_default_response(Some("ok"));
+                alert.set_close_response("ok");
+                alert.choose(
+                    parent_window.as_ref(),
+                    None::<&gtk::gio::Cancellable>,
+                    |_| {},
+                );
+            }
+            AppManagerMsg::ExecutableExited(name, code) => {
+                let code = code
+                    .map(|c| c.to_string())
+                    .unwrap_or_else(|| crate::t!("apps.exit_code_signal"));
+                let parent_window = _root
+                    .ancestor(gtk::Window::static_type())
+                    .and_then(|w| w.downcast::<gtk::Window>().ok());
+                let alert = adw::AlertDialog::new(
+                    Some(&crate::t!("apps.exited_early")),
+                    Some(&crate::tf!("apps.exited_early_desc", "name" => &name, "code" => &code)),
+                );
+                alert.add_response("ok", &crate::t!("dialogs.ok"));
+                alert.set_default_response(Some("ok"));
+                alert.set_close_response("ok");
+                alert.choose(
+                    parent_window.as_ref(),
+                    None::<&gtk::gio::Cancellable>,
+                    |_| {},
+                );
+            }
             AppManagerMsg::LaunchExecutableDebug(index) => {
+                self.record_launch(index);
                 if let Some(executable) = self.prefix.config().registered_executables.get(index) {
                     match service::launch::launch_executable_debug(
                         &AppService::global(),
@@ -356,13 +769,13 @@ impl AsyncComponent for AppManagerModel {
                                 pid,
                             );
 
-                            // Open debug window (owns the child for pipe I/O)
+                            // Open a console window for this launch (owns the child for pipe I/O)
                             let debug_win = DebugWindowModel::builder()
                                 .launch((exec_name, child))
-                                .forward(sender.input_sender(), |output| {
-                                    AppManagerMsg::DebugWindow(output)
+                                .forward(sender.input_sender(), move |output| {
+                                    AppManagerMsg::DebugWindow(pid, output)
                                 });
-                            self.debug_window = Some(debug_win);
+                            self.debug_windows.insert(pid, debug_win);
                         }
                         Err(e) => {
                             let parent_window = _root
@@ -385,7 +798,7 @@ impl AsyncComponent for AppManagerModel {
                 }
             }
             AppManagerMsg::UpdateExecutableList(executables) => {
-                self.available_executables = executables.clone();
+                self.set_available_executables(executables);
                 self.set_selected_executable(None);
 
                 // Update the registered apps list with the current config's registered executables
@@ -409,7 +822,7 @@ impl AsyncComponent for AppManagerModel {
                         .list_scanned_executables(&self.prefix.path().to_string_lossy())
                     {
                         Ok(exes) => {
-                            self.available_executables = exes;
+                            self.set_available_executables(exes);
                         }
                         Err(e) => {
                             error!("[apps] failed to load scanned executables: {}", e);
@@ -424,22 +837,7 @@ impl AsyncComponent for AppManagerModel {
                     ));
 
                 // Restore running highlight immediately
-                let paths = service::launch::poll_dead_processes(&AppService::global());
-                self.set_running_paths(paths.clone());
-                self.registered_apps_list
-                    .emit(RegisteredAppsListMsg::SetRunningPaths(paths));
-
-                // Update selected running state
-                if let Some(i) = self.selected_executable {
-                    if let Some(exe) = self.prefix.config().registered_executables.get(i) {
-                        let running = service::launch::is_process_running(
-                            &AppService::global(),
-                            &exe.executable_path,
-                        );
-                        self.app_actions
-                            .emit(AppActionsMsg::SetSelectedRunning(running));
-                    }
-                }
+                self.refresh_running(&sender);
 
                 // Reset selection if the config has no executables or index is out of bounds
                 if self.prefix.config().registered_executables.is_empty()
@@ -450,11 +848,15 @@ impl AsyncComponent for AppManagerModel {
                     self.set_selected_executable(None);
                     self.app_actions.emit(AppActionsMsg::SetSelection(false));
                 }
+                self.refresh_missing_state();
             }
             AppManagerMsg::PrefixPathUpdated(path) => {
                 let has_prefix = !path.as_os_str().is_empty();
                 self.app_actions
                     .emit(AppActionsMsg::SetPrefixSet(has_prefix));
+                if self.prefix.path() != path {
+                    self.newly_found.clear();
+                }
                 self.prefix.set_path(path.clone());
                 self.set_prefix(self.prefix.clone());
                 self.registered_apps_list
@@ -478,7 +880,6 @@ impl AsyncComponent for AppManagerModel {
             // Handle messages from child components
             AppManagerMsg::ExecutableInfoDialog(output) => match output {
                 ExecutableInfoDialogOutput::ExecutableUpdated(updated_exec) => {
-                    let exe_path = updated_exec.executable_path.clone();
                     let path = self.prefix.path().to_path_buf();
                     if service::config_ops::update_executable(
                         &AppService::global(),
@@ -487,25 +888,11 @@ impl AsyncComponent for AppManagerModel {
                         updated_exec.clone(),
                     ) {
                         // Update desktop launcher if one exists (name/icon may have changed)
-                        if prefix::desktop::launcher_exists(self.prefix.path(), &exe_path) {
-                            let icon_cache = AppService::global()
-                                .prefix_manager()
-                                .scanner()
-                                .icon_cache()
-                                .clone();
-                            let resolved_icon = prefix::resolve_or_extract_icon(
-                                &updated_exec,
-                                self.prefix.path(),
-                                &icon_cache,
-                            );
-                            let prefix_name = self.prefix.name().to_string();
-                            if let Err(e) = prefix::desktop::create_launcher(
-                                self.prefix.path(),
-                                &prefix_name,
-                                &updated_exec.name,
-                                &exe_path,
-                                resolved_icon.as_deref(),
-                            ) {
+                        let manager = AppService::global().prefix_manager();
+                        if manager.has_desktop_entry(self.prefix.path(), &updated_exec) {
+                            if let Err(e) =
+                                manager.export_desktop_entry(self.prefix.path(), &updated_exec)
+                            {
                                 error!("[apps] failed to update desktop launcher: {}", e);
                             } else {
                                 info!(
@@ -517,6 +904,17 @@ impl AsyncComponent for AppManagerModel {
                             }
                         }
 
+                        let selected = self
+                            .selected_executable
+                            .and_then(|i| self.prefix.config().registered_executables.get(i));
+                        if selected
+                            .is_some_and(|exe| exe.executable_path == updated_exec.executable_path)
+                        {
+                            let names = profile_names(&updated_exec);
+                            self.app_actions
+                                .emit(AppActionsMsg::SetLaunchProfiles(names));
+                        }
+
                         self.registered_apps_list
                             .emit(RegisteredAppsListMsg::UpdateExecutables(
                                 self.prefix.config().registered_executables.clone(),
@@ -524,7 +922,21 @@ impl AsyncComponent for AppManagerModel {
                         let _ = sender.output(AppManagerMsg::ConfigUpdated(self.prefix.config().clone()));
                     }
                 }
+                ExecutableInfoDialogOutput::OpenFolder(exe_path) => {
+                    sender.input(AppManagerMsg::OpenFolder(exe_path));
+                }
             },
+            AppManagerMsg::FocusList => {
+                self.registered_apps_list
+                    .emit(RegisteredAppsListMsg::GrabFocus);
+            }
+            AppManagerMsg::OpenFolder(exe_path) => {
+                if let Err(e) = service::terminal::open_containing_folder(&exe_path) {
+                    error!("[apps] {}", e);
+                    self.toast_overlay
+                        .add_toast(adw::Toast::new(&crate::t!("apps.folder_missing")));
+                }
+            }
             AppManagerMsg::RegisteredAppsList(output) => {
                 debug!("[apps] received RegisteredAppsList output: {:?}", output);
                 match output {
@@ -534,23 +946,32 @@ impl AsyncComponent for AppManagerModel {
                         self.app_actions.emit(AppActionsMsg::SetSelection(true));
                         // Check if the selected app is running
                         if let Some(exe) = self.prefix.config().registered_executables.get(index) {
-                            let running = service::launch::is_process_running(
-                                &AppService::global(),
-                                &exe.executable_path,
-                            );
+                            let running = self.running_paths.contains(&exe.executable_path);
                             self.app_actions
                                 .emit(AppActionsMsg::SetSelectedRunning(running));
                             // Check desktop launcher state
-                            let has_desktop = prefix::desktop::launcher_exists(
-                                self.prefix.path(),
-                                &exe.executable_path,
-                            );
+                            let has_desktop = AppService::global()
+                                .prefix_manager()
+                                .has_desktop_entry(self.prefix.path(), exe);
                             self.app_actions
                                 .emit(AppActionsMsg::SetDesktopExists(has_desktop));
+                            self.app_actions.emit(AppActionsMsg::SetSelectedMissing(
+                                !exe.executable_path.exists(),
+                            ));
+                            self.app_actions
+                                .emit(AppActionsMsg::SetLaunchProfiles(profile_names(exe)));
                         }
                     }
                     RegisteredAppsListOutput::Launch(index) => {
-                        sender.input(AppManagerMsg::LaunchExecutable(index));
+                        let missing = self
+                            .prefix
+                            .config()
+                            .registered_executables
+                            .get(index)
+                            .is_some_and(|exe| !exe.executable_path.exists());
+                        if !missing {
+                            sender.input(AppManagerMsg::LaunchExecutable(index, None));
+                        }
                     }
                     RegisteredAppsListOutput::Remove(index) => {
                         sender.input(AppManagerMsg::RemoveExecutable(index));
@@ -558,6 +979,42 @@ impl AsyncComponent for AppManagerModel {
                     RegisteredAppsListOutput::ShowInfo(index) => {
                         sender.input(AppManagerMsg::ShowInfoDialog(index));
                     }
+                    RegisteredAppsListOutput::OpenFolder(exe_path) => {
+                        sender.input(AppManagerMsg::OpenFolder(exe_path));
+                    }
+                    RegisteredAppsListOutput::SetCategory(exe_path, category) => {
+                        self.update_registered(&exe_path, &sender, |exe| exe.category = category);
+                    }
+                    RegisteredAppsListOutput::SetFavorite(exe_path, favorite) => {
+                        self.update_registered(&exe_path, &sender, |exe| exe.favorite = favorite);
+                    }
+                    RegisteredAppsListOutput::Stop(exe_path) => {
+                        sender.input(AppManagerMsg::StopExecutable(exe_path));
+                    }
+                    RegisteredAppsListOutput::Move(from, to) => {
+                        let path = self.prefix.path().to_path_buf();
+                        if service::config_ops::move_executable(
+                            &AppService::global(),
+                            &path,
+                            self.prefix.config_mut(),
+                            &from,
+                            &to,
+                        ) {
+                            self.set_selected_executable(None);
+                            self.app_actions.emit(AppActionsMsg::SetSelection(false));
+                            self.registered_apps_list
+                                .emit(RegisteredAppsListMsg::UpdateExecutables(
+                                    self.prefix.config().registered_executables.clone(),
+                                ));
+                            let _ = sender
+                                .output(AppManagerMsg::ConfigUpdated(self.prefix.config().clone()));
+                        }
+                    }
+                    RegisteredAppsListOutput::CheckedChanged(paths) => {
+                        self.app_actions
+                            .emit(AppActionsMsg::SetCheckedCount(paths.len()));
+                        self.checked_paths = paths;
+                    }
                 }
             }
             AppManagerMsg::AppActions(output) => {
@@ -565,7 +1022,12 @@ impl AsyncComponent for AppManagerModel {
                 match output {
                     AppActionsOutput::Launch => {
                         if let Some(index) = self.selected_executable {
-                            sender.input(AppManagerMsg::LaunchExecutable(index));
+                            sender.input(AppManagerMsg::LaunchExecutable(index, None));
+                        }
+                    }
+                    AppActionsOutput::LaunchProfile(name) => {
+                        if let Some(index) = self.selected_executable {
+                            sender.input(AppManagerMsg::LaunchExecutable(index, Some(name)));
                         }
                     }
                     AppActionsOutput::LaunchDebug => {
@@ -576,20 +1038,9 @@ impl AsyncComponent for AppManagerModel {
                     AppActionsOutput::Kill => {
                         if let Some(index) = self.selected_executable {
                             if let Some(exe) = self.prefix.config().registered_executables.get(index) {
-                                let killed = service::launch::kill_process(
-                                    &AppService::global(),
-                                    &exe.executable_path,
-                                );
-                                if killed {
-                                    self.app_actions
-                                        .emit(AppActionsMsg::SetSelectedRunning(false));
-                                    // Refresh the list highlight
-                                    let paths =
-                                        service::launch::poll_dead_processes(&AppService::global());
-                                    self.set_running_paths(paths.clone());
-                                    self.registered_apps_list
-                                        .emit(RegisteredAppsListMsg::SetRunningPaths(paths));
-                                }
+                                sender.input(AppManagerMsg::StopExecutable(
+                                    exe.executable_path.clone(),
+                                ));
                             }
                         }
                     }
@@ -599,6 +1050,7 @@ impl AsyncComponent for AppManagerModel {
                             .emit(AddAppPopoverMsg::UpdateAvailableApps(
                                 self.available_executables.clone(),
                                 self.prefix.config().architecture.clone(),
+                                self.newly_found_count(),
                             ));
 
                         // Auto-scan if no cached executables
@@ -624,6 +1076,56 @@ impl AsyncComponent for AppManagerModel {
                             sender.input(AppManagerMsg::RemoveExecutable(index));
                         }
                     }
+                    AppActionsOutput::SetSelectMode(enabled) => {
+                        self.registered_apps_list
+                            .emit(RegisteredAppsListMsg::SetSelectMode(enabled));
+                    }
+                    AppActionsOutput::SetListView(enabled) => {
+                        self.registered_apps_list
+                            .emit(RegisteredAppsListMsg::SetListView(enabled));
+                        if let Err(e) = prefix::Settings::update(|s| s.apps_list_view = enabled) {
+                            error!("[apps] failed to save apps_list_view: {}", e);
+                        }
+                    }
+                    AppActionsOutput::RemoveSelected => {
+                        if self.checked_paths.is_empty() {
+                            return;
+                        }
+                        let path = self.prefix.path().to_path_buf();
+                        if service::config_ops::remove_executables(
+                            &AppService::global(),
+                            &path,
+                            self.prefix.config_mut(),
+                            &self.checked_paths,
+                        ) {
+                            self.set_selected_executable(None);
+                            self.app_actions.emit(AppActionsMsg::SetSelection(false));
+                            let _ = sender.output(AppManagerMsg::ConfigUpdated(
+                                self.prefix.config().clone(),
+                            ));
+                        }
+                    }
+                    AppActionsOutput::RemoveBroken => {
+                        let path = self.prefix.path().to_path_buf();
+                        match service::config_ops::remove_missing_executables(
+                            &AppService::global(),
+                            &path,
+                            self.prefix.config_mut(),
+                        ) {
+                            Some(0) => {}
+                            Some(removed) => {
+                                info!("[apps] removed {} broken entries", removed);
+                                self.set_selected_executable(None);
+                                self.app_actions.emit(AppActionsMsg::SetSelection(false));
+                                let _ = sender.output(AppManagerMsg::ConfigUpdated(
+                                    self.prefix.config().clone(),
+                                ));
+                            }
+                            None => {
+                                error!("[apps] failed to remove broken entries");
+                            }
+                        }
+                    }
                     AppActionsOutput::ShowInfo => {
                         if let Some(index) = self.selected_executable {
                             sender.input(AppManagerMsg::ShowInfoDialog(index));
@@ -668,24 +1170,18 @@ impl AsyncComponent for AppManagerModel {
                         if let Some(index) = self.selected_executable {
                             if let Some(exe) = self.prefix.config().registered_executables.get(index) {
                                 let prefix_path = self.prefix.path().to_path_buf();
-                                let prefix_name = self.prefix.name().to_string();
-                                let exe_name = exe.name.clone();
-                                let exe_path = exe.executable_path.clone();
                                 let parent_window = _root
                                     .ancestor(gtk::Window::static_type())
                                     .and_then(|w| w.downcast::<gtk::Window>().ok());
+                                let manager = AppService::global().prefix_manager();
 
-                                // Toggle: if launcher exists, remove it; otherwise create it
-                                if prefix::desktop::launcher_exists(&prefix_path, &exe_path)
-                                {
-                                    match prefix::desktop::remove_launcher(
-                                        &prefix_path,
-                                        &exe_path,
-                                    ) {
+                                // Toggle: if the menu entry exists, remove it; otherwise add it
+                                if manager.has_desktop_entry(&prefix_path, exe) {
+                                    match manager.remove_desktop_entry(&prefix_path, exe) {
                                         Ok(()) => {
                                             info!(
                                                 "[apps] removed desktop launcher for '{}'",
-                                                exe_name
+                                                exe.name
                                             );
                                             self.app_actions
                                                 .emit(AppActionsMsg::SetDesktopExists(false));
@@ -698,24 +1194,7 @@ impl AsyncComponent for AppManagerModel {
                                         }
                                     }
                                 } else {
-                                    let icon_cache = AppService::global()
-                                        .prefix_manager()
-                                        .scanner()
-                                        .icon_cache()
-                                        .clone();
-                                    let resolved_icon = prefix::resolve_or_extract_icon(
-                                        exe,
-                                        &prefix_path,
-                                        &icon_cache,
-                                    );
-
-                                    match prefix::desktop::create_launcher(
-                                        &prefix_path,
-                                        &prefix_name,
-                                        &exe_name,
-                                        &exe_path,
-                                        resolved_icon.as_deref(),
-                                    ) {
+                                    match manager.export_desktop_entry(&prefix_path, exe) {
                                         Ok(path) => {
                                             info!(
                                                 "[apps] created desktop launcher: {}",
@@ -730,10 +1209,10 @@ impl AsyncComponent for AppManagerModel {
                                                 e
                                             );
                                             let alert = adw::AlertDialog::new(
-                                                Some("Failed to Create Desktop Launcher"),
-                                                Some(&format!("{}", e)),
+                                                Some(&crate::t!("apps.actions.desktop_failed")),
+                                                Some(&e.to_string()),
                                             );
-                                            alert.add_response("ok", "OK");
+                                            alert.add_response("ok", &crate::t!("dialogs.ok"));
                                             alert.set_default_response(Some("ok"));
                                             alert.set_close_response("ok");
                                             alert.choose(
@@ -763,6 +1242,9 @@ impl AsyncComponent for AppManagerModel {
                     AddAppPopoverOutput::Scan => {
                         sender.input(AppManagerMsg::ScanForApplications);
                     }
+                    AddAppPopoverOutput::CopyFromPrefix => {
+                        sender.input(AppManagerMsg::CopyFromPrefix);
+                    }
                 }
             }
             AppManagerMsg::LaunchDirectExe(exe_path) => {
@@ -780,22 +1262,16 @@ impl AsyncComponent for AppManagerModel {
                     Err(e) => error!("[apps] failed to launch exe: {}", e),
                 }
             }
+            AppManagerMsg::StopExecutable(exe_path) => {
+                service::launch::stop_executable(
+                    &AppService::global(),
+                    self.prefix.path(),
+                    &exe_path,
+                );
+                self.refresh_running(&sender);
+            }
             AppManagerMsg::PollProcesses => {
-                let paths = service::launch::poll_dead_processes(&AppService::global());
-                self.set_running_paths(paths.clone());
-                self.registered_apps_list
-                    .emit(RegisteredAppsListMsg::SetRunningPaths(paths));
-                // Update selected running state
-                if let Some(i) = self.selected_executable {
-                    if let Some(exe) = self.prefix.config().registered_executables.get(i) {
-                        let running = service::launch::is_process_running(
-                            &AppService::global(),
-                            &exe.executable_path,
-                        );
-                        self.app_actions
-                            .emit(AppActionsMsg::SetSelectedRunning(running));
-                    }
-                }
+                self.refresh_running(&sender);
                 // Update uninstaller running state
                 let uninstaller_still_running = self
                     .uninstaller_track_path
@@ -815,11 +1291,18 @@ impl AsyncComponent for AppManagerModel {
                 self.app_actions.emit(AppActionsMsg::SetExeRunning(
                     !self.external_running.is_empty(),
                 ));
+                self.refresh_missing_state();
+            }
+            AppManagerMsg::RunningChecked(prefix_path, paths) => {
+                // The prefix may have been switched while checking
+                if prefix_path == self.prefix.path() {
+                    self.set_running(paths);
+                }
             }
-            AppManagerMsg::DebugWindow(output) => {
+            AppManagerMsg::DebugWindow(pid, output) => {
                 match output {
                     DebugWindowOutput::CloseRequest => {
-                        self.debug_window = None;
+                        self.debug_windows.remove(&pid);
                         sender.input(AppManagerMsg::PollProcesses);
                     }
                 }
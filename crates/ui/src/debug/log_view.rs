@@ -0,0 +1,189 @@
+use gtk::prelude::*;
+use relm4::{ComponentParts, ComponentSender, SimpleComponent, gtk};
+use std::io::{BufRead, BufReader, Read};
+use std::process::Child;
+use std::thread;
+
+/// Level of an output line, used for color highlighting.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutputLevel {
+    Stdout,
+    Stderr,
+    Info,
+    Warn,
+    Error,
+}
+
+/// Scrolling, colour-coded view of a child process's output.
+///
+/// Shared by everything that runs a long Wine process and wants to show
+/// what it prints: the debug console, component installers and the like.
+/// Either hand it a `Child` with `Run`, or keep the child yourself, pass its
+/// pipes to [`follow`] and report the exit with `Exited`.
+pub struct ProcessLogModel {
+    pub buffer: gtk::TextBuffer,
+    scrolled_window: gtk::ScrolledWindow,
+}
+
+#[derive(Debug)]
+pub enum ProcessLogMsg {
+    /// Stream a child's stdout/stderr and show its exit status. Both pipes
+    /// must be set to `Stdio::piped()`.
+    Run(Child),
+    AppendOutput(String, OutputLevel),
+    /// Dimmed line that isn't process output, e.g. echoed input.
+    AppendNote(String),
+    Exited(i32),
+}
+
+#[derive(Debug)]
+pub enum ProcessLogOutput {
+    Exited(i32),
+}
+
+#[relm4::component(pub)]
+impl SimpleComponent for ProcessLogModel {
+    type Init = ();
+    type Input = ProcessLogMsg;
+    type Output = ProcessLogOutput;
+
+    view! {
+        gtk::ScrolledWindow {
+            set_vexpand: true,
+            set_hexpand: true,
+
+            #[name = "output_view"]
+            gtk::TextView {
+                set_editable: false,
+                set_cursor_visible: true,
+                set_monospace: true,
+                set_wrap_mode: gtk::WrapMode::WordChar,
+
+                set_margin_start: 6,
+                set_margin_end: 6,
+            },
+        }
+    }
+
+    fn init(
+        _: Self::Init,
+        root: Self::Root,
+        _sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let buffer = gtk::TextBuffer::new(None);
+        buffer.create_tag(Some("dim"), &[("foreground", &"#888888".to_string())]);
+        buffer.create_tag(Some("stdout"), &[]);
+        buffer.create_tag(Some("stderr"), &[("foreground", &"#cc6666".to_string())]);
+        buffer.create_tag(
+            Some("warn"),
+            &[("foreground", &"#e5c07b".to_string()), ("weight", &700)],
+        );
+        buffer.create_tag(
+            Some("error"),
+            &[
+                ("foreground", &"#ff4444".to_string()),
+                ("weight", &700),
+                ("background", &"#330000".to_string()),
+            ],
+        );
+
+        let widgets = view_output!();
+        widgets.output_view.set_buffer(Some(&buffer));
+
+        let model = ProcessLogModel {
+            buffer,
+            scrolled_window: root.clone(),
+        };
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>) {
+        match msg {
+            ProcessLogMsg::Run(mut child) => {
+                let (Some(stdout), Some(stderr)) = (child.stdout.take(), child.stderr.take())
+                else {
+                    self.append("✗ Process output is not captured\n", "error");
+                    return;
+                };
+                follow(stdout, stderr, sender.input_sender());
+                let input = sender.input_sender().clone();
+                thread::spawn(move || {
+                    let code = child.wait().ok().and_then(|s| s.code()).unwrap_or(-1);
+                    input.emit(ProcessLogMsg::Exited(code));
+                });
+            }
+            ProcessLogMsg::AppendOutput(text, level) => {
+                let tag_name = match level {
+                    OutputLevel::Info | OutputLevel::Stdout => "stdout",
+                    OutputLevel::Stderr => "stderr",
+                    OutputLevel::Warn => "warn",
+                    OutputLevel::Error => "error",
+                };
+                self.append(&format!("{}\n", text), tag_name);
+            }
+            ProcessLogMsg::AppendNote(text) => {
+                self.append(&format!("{}\n", text), "dim");
+            }
+            ProcessLogMsg::Exited(code) => {
+                let msg = if code == 0 {
+                    format!("✓ Process exited with code {}\n", code)
+                } else {
+                    format!("✗ Process exited with code {}\n", code)
+                };
+                self.append(&msg, "dim");
+                let _ = sender.output(ProcessLogOutput::Exited(code));
+            }
+        }
+    }
+}
+
+impl ProcessLogModel {
+    /// Append `text` with the named tag and keep the view scrolled to the end.
+    fn append(&self, text: &str, tag_name: &str) {
+        let mut end = self.buffer.end_iter();
+        if let Some(tag) = self.buffer.tag_table().lookup(tag_name) {
+            self.buffer.insert_with_tags(&mut end, text, &[&tag]);
+        } else {
+            self.buffer.insert(&mut end, text);
+        }
+
+        let vadj = self.scrolled_window.vadjustment();
+        vadj.set_value(vadj.upper());
+    }
+}
+
+/// Read `stdout` and `stderr` line by line on background threads and feed
+/// them to a process log. Each reader stops when its pipe closes.
+pub fn follow(
+    stdout: impl Read + Send + 'static,
+    stderr: impl Read + Send + 'static,
+    log: &relm4::Sender<ProcessLogMsg>,
+) {
+    spawn_reader(stdout, log.clone());
+    spawn_reader(stderr, log.clone());
+}
+
+fn spawn_reader(pipe: impl Read + Send + 'static, log: relm4::Sender<ProcessLogMsg>) {
+    thread::spawn(move || {
+        for line in BufReader::new(pipe).lines() {
+            let Ok(text) = line else { break };
+            // Wine logs everything to stderr, so only marked lines stand out
+            let level = detect_level(&text).unwrap_or(OutputLevel::Stdout);
+            log.emit(ProcessLogMsg::AppendOutput(text, level));
+        }
+    });
+}
+
+/// Heuristic level detection: scans a line for common Wine/Rust log markers.
+fn detect_level(line: &str) -> Option<OutputLevel> {
+    let lower = line.to_lowercase();
+    if lower.contains("[error]") || lower.contains("err:") {
+        Some(OutputLevel::Error)
+    } else if lower.contains("[warn]") || lower.contains("warn:") {
+        Some(OutputLevel::Warn)
+    } else if lower.contains("[info]") {
+        Some(OutputLevel::Info)
+    } else {
+        None
+    }
+}
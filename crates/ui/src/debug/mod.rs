@@ -1,22 +1,17 @@
 use gtk::prelude::*;
 use relm4::{
+    Component, ComponentController, Controller,
     component::{AsyncComponent, AsyncComponentParts, AsyncComponentSender},
     gtk,
 };
-use std::io::{BufRead, BufReader, Write};
+use std::io::Write;
 use std::process::{Child, ChildStdin};
 use std::sync::{Arc, Mutex};
 use std::thread;
 
-/// Level of an output line, used for color highlighting.
-#[derive(Debug, Clone, PartialEq)]
-pub enum OutputLevel {
-    Stdout,
-    Stderr,
-    Info,
-    Warn,
-    Error,
-}
+pub mod log_view;
+
+use log_view::{ProcessLogModel, ProcessLogMsg};
 
 #[tracker::track]
 pub struct DebugWindowModel {
@@ -27,14 +22,11 @@ pub struct DebugWindowModel {
     #[tracker::do_not_track]
     pub stdin_handle: Arc<Mutex<Option<ChildStdin>>>,
     #[tracker::do_not_track]
-    pub buffer: gtk::TextBuffer,
-    #[tracker::do_not_track]
-    pub scrolled_window: gtk::ScrolledWindow,
+    pub log: Controller<ProcessLogModel>,
 }
 
 #[derive(Debug)]
 pub enum DebugWindowMsg {
-    AppendOutput(String, OutputLevel),
     ProcessExited(i32),
     SendStdin(String),
 }
@@ -62,22 +54,8 @@ impl AsyncComponent for DebugWindowModel {
                 set_orientation: gtk::Orientation::Vertical,
                 set_spacing: 0,
 
-                #[name = "scrolled"]
-                gtk::ScrolledWindow {
-                    set_vexpand: true,
-                    set_hexpand: true,
-
-                    #[name = "output_view"]
-                    gtk::TextView {
-                        set_editable: false,
-                        set_cursor_visible: true,
-                        set_monospace: true,
-                        set_wrap_mode: gtk::WrapMode::WordChar,
-
-                        set_margin_start: 6,
-                        set_margin_end: 6,
-                    },
-                },
+                #[local_ref]
+                log_widget -> gtk::ScrolledWindow {},
 
                 gtk::Separator {},
 
@@ -140,20 +118,8 @@ impl AsyncComponent for DebugWindowModel {
         let stdout = child.stdout.take().expect("debug launch: stdout must be piped");
         let stderr = child.stderr.take().expect("debug launch: stderr must be piped");
 
-        // Create text buffer with colour tags
-        let buffer = gtk::TextBuffer::new(None);
-        buffer.create_tag(Some("dim"), &[("foreground", &"#888888".to_string())]);
-        buffer.create_tag(Some("stdout"), &[]);
-        buffer.create_tag(Some("stderr"), &[("foreground", &"#cc6666".to_string())]);
-        buffer.create_tag(Some("warn"), &[
-            ("foreground", &"#e5c07b".to_string()),
-            ("weight", &700),
-        ]);
-        buffer.create_tag(Some("error"), &[
-            ("foreground", &"#ff4444".to_string()),
-            ("weight", &700),
-            ("background", &"#330000".to_string()),
-        ]);
+        let log = ProcessLogModel::builder().launch(()).detach();
+        let buffer = log.model().buffer.clone();
 
         // ── Header bar with save button ─────────────────────────────
         let header_bar = gtk::HeaderBar::new();
@@ -195,54 +161,23 @@ impl AsyncComponent for DebugWindowModel {
         header_bar.pack_start(&save_btn);
         root.set_titlebar(Some(&header_bar));
 
-        let mut model = DebugWindowModel {
+        let model = DebugWindowModel {
             executable_name: name,
             process_exited: false,
             child: Arc::new(Mutex::new(Some(child))),
             stdin_handle: Arc::new(Mutex::new(stdin_handle)),
-            buffer: buffer.clone(),
-            scrolled_window: gtk::ScrolledWindow::new(),
+            log,
             tracker: 0,
         };
 
+        let log_widget = model.log.widget().clone();
         let widgets = view_output!();
 
-        // Wire up the newly created scrolled window from view! into the model
-        model.scrolled_window = widgets.scrolled.clone();
-
-        widgets.output_view.set_buffer(Some(&buffer));
-
         // Show the window
         root.present();
 
         // ── Background threads ─────────────────────────────────────────
-        let sender_clone = sender.clone();
-        thread::spawn(move || {
-            let reader = BufReader::new(stdout);
-            for line in reader.lines() {
-                match line {
-                    Ok(text) => {
-                        let level = detect_level(&text).unwrap_or(OutputLevel::Stdout);
-                        let _ = sender_clone.input(DebugWindowMsg::AppendOutput(text, level));
-                    }
-                    Err(_) => break,
-                }
-            }
-        });
-
-        let sender_clone = sender.clone();
-        thread::spawn(move || {
-            let reader = BufReader::new(stderr);
-            for line in reader.lines() {
-                match line {
-                    Ok(text) => {
-                        let level = detect_level(&text).unwrap_or(OutputLevel::Stdout);
-                        let _ = sender_clone.input(DebugWindowMsg::AppendOutput(text, level));
-                    }
-                    Err(_) => break,
-                }
-            }
-        });
+        log_view::follow(stdout, stderr, model.log.sender());
 
         let sender_clone = sender.clone();
         let child_arc = model.child.clone();
@@ -266,42 +201,9 @@ impl AsyncComponent for DebugWindowModel {
     ) {
         self.reset();
         match msg {
-            DebugWindowMsg::AppendOutput(text, level) => {
-                let tag_name = match level {
-                    OutputLevel::Info | OutputLevel::Stdout => "stdout",
-                    OutputLevel::Stderr => "stderr",
-                    OutputLevel::Warn => "warn",
-                    OutputLevel::Error => "error",
-                };
-
-                let mut end = self.buffer.end_iter();
-                if let Some(tag) = self.buffer.tag_table().lookup(tag_name) {
-                    self.buffer
-                        .insert_with_tags(&mut end, &format!("{}\n", text), &[&tag]);
-                } else {
-                    self.buffer.insert(&mut end, &format!("{}\n", text));
-                }
-
-                // Auto-scroll to bottom
-                let vadj = self.scrolled_window.vadjustment();
-                vadj.set_value(vadj.upper());
-            }
             DebugWindowMsg::ProcessExited(code) => {
                 self.set_process_exited(true);
-                let mut end = self.buffer.end_iter();
-                let msg = if code == 0 {
-                    format!("✓ Process exited with code {}\n", code)
-                } else {
-                    format!("✗ Process exited with code {}\n", code)
-                };
-                if let Some(tag) = self.buffer.tag_table().lookup("dim") {
-                    self.buffer.insert_with_tags(&mut end, &msg, &[&tag]);
-                } else {
-                    self.buffer.insert(&mut end, &msg);
-                }
-
-                let vadj = self.scrolled_window.vadjustment();
-                vadj.set_value(vadj.upper());
+                self.log.emit(ProcessLogMsg::Exited(code));
             }
             DebugWindowMsg::SendStdin(text) => {
                 if let Some(stdin) = self.stdin_handle.lock().unwrap().as_mut() {
@@ -310,31 +212,9 @@ impl AsyncComponent for DebugWindowModel {
                 }
 
                 // Echo input to the output view
-                let mut end = self.buffer.end_iter();
-                if let Some(tag) = self.buffer.tag_table().lookup("dim") {
-                    self.buffer
-                        .insert_with_tags(&mut end, &format!("> {}\n", text), &[&tag]);
-                } else {
-                    self.buffer.insert(&mut end, &format!("> {}\n", text));
-                }
-
-                let vadj = self.scrolled_window.vadjustment();
-                vadj.set_value(vadj.upper());
+                self.log
+                    .emit(ProcessLogMsg::AppendNote(format!("> {}", text)));
             }
         }
     }
 }
-
-/// Heuristic level detection: scans a line for common Wine/Rust log markers.
-fn detect_level(line: &str) -> Option<OutputLevel> {
-    let lower = line.to_lowercase();
-    if lower.contains("[error]") || lower.contains("err:") {
-        Some(OutputLevel::Error)
-    } else if lower.contains("[warn]") || lower.contains("warn:") {
-        Some(OutputLevel::Warn)
-    } else if lower.contains("[info]") {
-        Some(OutputLevel::Info)
-    } else {
-        None
-    }
-}
@@ -0,0 +1,325 @@
+use adw::prelude::*;
+use prefix::config::PrefixConfig;
+use prefix::desktop::{HostAssociation, list_host_associations, remove_host_association};
+use prefix::keys::FileAssociation;
+use prefix::{RegEditor, RegistryEditor};
+use relm4::{ComponentParts, ComponentSender, RelmWidgetExt, SimpleComponent, adw, gtk};
+use std::path::PathBuf;
+
+// ── Model ────────────────────────────────────────────────────────────────
+
+#[derive(Debug)]
+pub struct FileAssociationsModel {
+    prefix_path: PathBuf,
+    parent_window: gtk::Window,
+    /// Registered executables offered as handlers, parallel to `exe_items`.
+    exe_paths: Vec<PathBuf>,
+    exe_items: gtk::StringList,
+    associations: Vec<FileAssociation>,
+    host_associations: Vec<HostAssociation>,
+    extension_entry: gtk::Entry,
+    exe_dropdown: gtk::DropDown,
+    registry_group: adw::PreferencesGroup,
+    host_group: adw::PreferencesGroup,
+    registry_rows: Vec<adw::ActionRow>,
+    host_rows: Vec<adw::ActionRow>,
+}
+
+#[derive(Debug)]
+pub enum FileAssociationsMsg {
+    Load,
+    Loaded(Vec<FileAssociation>, Vec<HostAssociation>),
+    Add,
+    Remove(String),
+    RemoveHost(usize),
+    ConfigUpdated(PrefixConfig),
+    PrefixPathUpdated(PathBuf),
+    ShowError(String),
+}
+
+/// Normalise user input like `PSD` or `.psd ` to `.psd`.
+fn normalize_extension(input: &str) -> String {
+    let ext = input.trim().trim_start_matches('.').to_lowercase();
+    format!(".{}", ext)
+}
+
+fn exe_model(config: &PrefixConfig) -> (gtk::StringList, Vec<PathBuf>) {
+    let names: Vec<&str> = config.executables().map(|e| e.name.as_str()).collect();
+    let paths = config
+        .executables()
+        .map(|e| e.executable_path.clone())
+        .collect();
+    (gtk::StringList::new(&names), paths)
+}
+
+// ── Component ────────────────────────────────────────────────────────────
+
+#[relm4::component(pub)]
+impl SimpleComponent for FileAssociationsModel {
+    type Init = (PathBuf, PrefixConfig, gtk::Window);
+    type Input = FileAssociationsMsg;
+    type Output = ();
+    type Widgets = FileAssociationsWidgets;
+
+    view! {
+        #[root]
+        gtk::ScrolledWindow {
+            set_vexpand: true,
+            set_hexpand: true,
+            set_hscrollbar_policy: gtk::PolicyType::Never,
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_margin_all: 18,
+                set_spacing: 18,
+
+                #[name = "registry_group"]
+                adw::PreferencesGroup {
+                    set_title: &crate::t!("associations.prefix_title"),
+                    set_description: Some(&crate::t!("associations.prefix_desc")),
+
+                    adw::ActionRow {
+                        set_title: &crate::t!("associations.add"),
+
+                        #[name = "extension_entry"]
+                        add_suffix = &gtk::Entry {
+                            set_width_chars: 8,
+                            set_valign: gtk::Align::Center,
+                            set_placeholder_text: Some(".psd"),
+                            connect_activate => FileAssociationsMsg::Add,
+                        },
+
+                        #[name = "exe_dropdown"]
+                        add_suffix = &gtk::DropDown {
+                            set_valign: gtk::Align::Center,
+                            set_model: Some(&model.exe_items),
+                        },
+
+                        add_suffix = &gtk::Button {
+                            set_icon_name: "list-add-symbolic",
+                            set_tooltip_text: Some(&crate::t!("associations.add")),
+                            set_valign: gtk::Align::Center,
+                            set_css_classes: &["flat"],
+                            connect_clicked => FileAssociationsMsg::Add,
+                        },
+                    },
+                },
+
+                #[name = "host_group"]
+                adw::PreferencesGroup {
+                    set_title: &crate::t!("associations.host_title"),
+                    set_description: Some(&crate::t!("associations.host_desc")),
+                },
+            },
+        }
+    }
+
+    fn init(
+        init: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let (prefix_path, config, parent_window) = init;
+        let (exe_items, exe_paths) = exe_model(&config);
+
+        let mut model = FileAssociationsModel {
+            prefix_path,
+            parent_window,
+            exe_paths,
+            exe_items,
+            associations: Vec::new(),
+            host_associations: Vec::new(),
+            extension_entry: gtk::Entry::new(),
+            exe_dropdown: gtk::DropDown::default(),
+            registry_group: adw::PreferencesGroup::new(),
+            host_group: adw::PreferencesGroup::new(),
+            registry_rows: Vec::new(),
+            host_rows: Vec::new(),
+        };
+
+        let widgets = view_output!();
+        model.extension_entry = widgets.extension_entry.clone();
+        model.exe_dropdown = widgets.exe_dropdown.clone();
+        model.registry_group = widgets.registry_group.clone();
+        model.host_group = widgets.host_group.clone();
+        model.refresh_rows(&sender);
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>) {
+        match msg {
+            FileAssociationsMsg::Load => {
+                let pp = self.prefix_path.clone();
+                let s = sender.clone();
+                tokio::spawn(async move {
+                    let result = async {
+                        let editor = RegistryEditor::with_prefix(&pp).await?;
+                        editor.get_file_associations().await
+                    }
+                    .await;
+                    match result {
+                        Ok(associations) => s.input(FileAssociationsMsg::Loaded(
+                            associations,
+                            list_host_associations(&pp),
+                        )),
+                        Err(e) => s.input(FileAssociationsMsg::ShowError(e.to_string())),
+                    }
+                });
+            }
+            FileAssociationsMsg::Loaded(associations, host_associations) => {
+                self.associations = associations;
+                self.host_associations = host_associations;
+                self.refresh_rows(&sender);
+            }
+            FileAssociationsMsg::Add => {
+                let text = self.extension_entry.text();
+                if text.trim().is_empty() {
+                    return;
+                }
+                let Some(exe_path) = self.exe_paths.get(self.exe_dropdown.selected() as usize)
+                else {
+                    return;
+                };
+                let extension = normalize_extension(&text);
+                let command = format!(
+                    "\"{}\" \"%1\"",
+                    prefix::windows_path(&self.prefix_path, exe_path)
+                );
+                self.extension_entry.set_text("");
+
+                let pp = self.prefix_path.clone();
+                let s = sender.clone();
+                tokio::spawn(async move {
+                    let result = async {
+                        let mut editor = RegistryEditor::with_prefix(&pp).await?;
+                        editor.set_file_association(&extension, &command).await?;
+                        editor.save_registry(&pp).await
+                    }
+                    .await;
+                    match result {
+                        Ok(()) => {
+                            log::info!("[associations] associated {} with {}", extension, command);
+                            s.input(FileAssociationsMsg::Load);
+                        }
+                        Err(e) => s.input(FileAssociationsMsg::ShowError(e.to_string())),
+                    }
+                });
+            }
+            FileAssociationsMsg::Remove(extension) => {
+                let pp = self.prefix_path.clone();
+                let s = sender.clone();
+                tokio::spawn(async move {
+                    let result = async {
+                        let mut editor = RegistryEditor::with_prefix(&pp).await?;
+                        editor.remove_file_association(&extension).await?;
+                        editor.save_registry(&pp).await
+                    }
+                    .await;
+                    match result {
+                        Ok(()) => {
+                            log::info!("[associations] removed {}", extension);
+                            s.input(FileAssociationsMsg::Load);
+                        }
+                        Err(e) => s.input(FileAssociationsMsg::ShowError(e.to_string())),
+                    }
+                });
+            }
+            FileAssociationsMsg::RemoveHost(idx) => {
+                if let Some(association) = self.host_associations.get(idx) {
+                    if let Err(e) = remove_host_association(association) {
+                        sender.input(FileAssociationsMsg::ShowError(e.to_string()));
+                    }
+                    sender.input(FileAssociationsMsg::Load);
+                }
+            }
+            FileAssociationsMsg::ConfigUpdated(config) => {
+                let (exe_items, exe_paths) = exe_model(&config);
+                self.exe_dropdown.set_model(Some(&exe_items));
+                self.exe_items = exe_items;
+                self.exe_paths = exe_paths;
+            }
+            // Loading is deferred until the page is shown
+            FileAssociationsMsg::PrefixPathUpdated(path) => self.prefix_path = path,
+            FileAssociationsMsg::ShowError(error) => {
+                log::error!("[associations] {}", error);
+                let alert = adw::AlertDialog::new(Some(&crate::t!("dialogs.error")), Some(&error));
+                alert.add_response("ok", &crate::t!("dialogs.ok"));
+                alert.set_default_response(Some("ok"));
+                alert.set_close_response("ok");
+                alert.choose(
+                    Some(&self.parent_window),
+                    None::<&gtk::gio::Cancellable>,
+                    |_| {},
+                );
+            }
+        }
+    }
+}
+
+// ── Impl ─────────────────────────────────────────────────────────────────
+
+impl FileAssociationsModel {
+    fn refresh_rows(&mut self, sender: &ComponentSender<Self>) {
+        for row in self.registry_rows.drain(..) {
+            self.registry_group.remove(&row);
+        }
+        for row in self.host_rows.drain(..) {
+            self.host_group.remove(&row);
+        }
+
+        for association in &self.associations {
+            let handler = association
+                .command
+                .as_deref()
+                .unwrap_or(&association.prog_id);
+            let row = adw::ActionRow::builder()
+                .title(&association.extension)
+                .subtitle(handler)
+                .subtitle_lines(1)
+                .build();
+            let remove_btn = remove_button();
+            let s = sender.clone();
+            let ext = association.extension.clone();
+            remove_btn.connect_clicked(move |_| {
+                s.input(FileAssociationsMsg::Remove(ext.clone()));
+            });
+            row.add_suffix(&remove_btn);
+            self.registry_group.add(&row);
+            self.registry_rows.push(row);
+        }
+
+        if self.host_associations.is_empty() {
+            let row = adw::ActionRow::builder()
+                .title(crate::t!("associations.host_none"))
+                .css_classes(["dim-label"])
+                .build();
+            self.host_group.add(&row);
+            self.host_rows.push(row);
+        }
+        for (idx, association) in self.host_associations.iter().enumerate() {
+            let row = adw::ActionRow::builder()
+                .title(format!(".{}", association.extension))
+                .subtitle(association.desktop_file.to_string_lossy())
+                .subtitle_lines(1)
+                .build();
+            let remove_btn = remove_button();
+            let s = sender.clone();
+            remove_btn.connect_clicked(move |_| {
+                s.input(FileAssociationsMsg::RemoveHost(idx));
+            });
+            row.add_suffix(&remove_btn);
+            self.host_group.add(&row);
+            self.host_rows.push(row);
+        }
+    }
+}
+
+fn remove_button() -> gtk::Button {
+    gtk::Button::builder()
+        .icon_name("user-trash-symbolic")
+        .tooltip_text(crate::t!("associations.remove"))
+        .valign(gtk::Align::Center)
+        .css_classes(["flat"])
+        .build()
+}
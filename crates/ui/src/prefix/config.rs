@@ -1,12 +1,17 @@
+use crate::apps::info_dialog::{EnvVarsEditor, env_vars_to_text};
+use crate::prefix::associations::{FileAssociationsModel, FileAssociationsMsg};
+use crate::prefix::drives::{DrivesModel, DrivesMsg};
 use crate::registry_editor::{RegistryEditorModel, RegistryEditorMsg};
 use adw::prelude::*;
 use prefix::config::PrefixConfig;
+use prefix::{ArchMigration, PrefixInfo, PrefixIssue, WineTool};
 use prefix::runtime;
 use relm4::{
     Component, ComponentController, ComponentParts, ComponentSender, Controller, SimpleComponent,
     adw, gtk,
 };
 use service::AppService;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use tracker;
 
@@ -21,6 +26,9 @@ pub struct PrefixConfigModel {
     editing: bool,
     prefix_index: usize,
     wine_runtime_display: String,
+    info: Option<PrefixInfo>,
+    /// A health check or repair is running.
+    checking_health: bool,
     selected_graphics: u32,
     #[tracker::do_not_track]
     nav: adw::NavigationView,
@@ -31,6 +39,14 @@ pub struct PrefixConfigModel {
     #[tracker::do_not_track]
     registry_page: adw::NavigationPage,
     #[tracker::do_not_track]
+    associations_ctrl: Controller<FileAssociationsModel>,
+    #[tracker::do_not_track]
+    associations_page: adw::NavigationPage,
+    #[tracker::do_not_track]
+    drives_ctrl: Controller<DrivesModel>,
+    #[tracker::do_not_track]
+    drives_page: adw::NavigationPage,
+    #[tracker::do_not_track]
     description_text: gtk::TextView,
     #[tracker::do_not_track]
     back_btn: gtk::Button,
@@ -53,6 +69,8 @@ pub struct PrefixConfigModel {
     pulse_id: Option<gtk::glib::SourceId>,
     #[tracker::do_not_track]
     progress_dialog: Option<gtk::Window>,
+    #[tracker::do_not_track]
+    env_vars_editor: Option<Controller<EnvVarsEditor>>,
     edit_save_label: String,
 }
 
@@ -65,10 +83,13 @@ pub enum PrefixConfigMsg {
     CancelEdit,
     UpdateName(String),
     UpdateDescription(String),
+    UpdateLaunchWrapper(String),
     ConfigUpdated(PrefixConfig),
     PrefixPathUpdated(PathBuf),
     SetPrefixIndex(usize),
     SetWineVersionDisplay(String),
+    LoadInfo,
+    InfoLoaded(PrefixInfo),
     SelectWineVersion,
     WineVersionChanged(u32),
     SetProgressDialog(Option<gtk::Window>),
@@ -76,12 +97,148 @@ pub enum PrefixConfigMsg {
     ReinitComplete(Result<(), String>),
     GraphicsBackendChanged(u32),
     ShowAdvancedRegistry,
+    ShowFileAssociations,
+    ShowDrives,
+    ViewLog,
+    CheckHealth,
+    HealthChecked(Result<Vec<PrefixIssue>, String>),
+    RepairPrefix,
+    RepairComplete(Result<(), String>),
+    RunWineTool(WineTool),
+    CopyWinetricksSnippet,
+    SetEnvFlag(&'static str, bool),
+    SetStopWineserver(bool),
+    EditEnvVars,
+    EnvVarsEdited(HashMap<String, String>),
     RegistryEditor(RegistryEditorMsg),
+    /// Explain why the architecture can't be switched in place and offer a
+    /// new prefix instead.
+    ChangeArchitecture,
+    /// Create a prefix of the other architecture with this name.
+    MigrateArchitecture(String),
+    MigrationComplete(Result<ArchMigration, String>),
+    /// Append a user note to the prefix's log.
+    AddNote(String),
 }
 
 #[derive(Debug)]
 pub enum PrefixConfigOutput {
     ConfigUpdated(PrefixConfig),
+    /// A new prefix with this name is being created.
+    CreationStarted(String),
+    CreationFinished(Option<PathBuf>),
+}
+
+// ── Helper: prefix-level environment toggles ─────────────────────────────
+
+const ESYNC: &str = "WINEESYNC";
+const FSYNC: &str = "WINEFSYNC";
+
+fn env_flag(config: &PrefixConfig, key: &str) -> bool {
+    config.env_vars.get(key).is_some_and(|v| v == "1")
+}
+
+fn env_vars_summary(config: &PrefixConfig) -> String {
+    if config.env_vars.is_empty() {
+        crate::t!("apps.info.env_vars_none")
+    } else {
+        crate::tf!("apps.info.env_vars_set", "count" => &config.env_vars.len().to_string())
+    }
+}
+
+// ── Helper: architecture migration ───────────────────────────────────────
+
+fn other_architecture(architecture: &str) -> &'static str {
+    if architecture == "win32" {
+        "win64"
+    } else {
+        "win32"
+    }
+}
+
+/// What happened to the registered apps, listing any that need reinstalling.
+fn migration_summary(migration: &ArchMigration) -> String {
+    let mut body = crate::tf!(
+        "prefix.arch.done_body",
+        "migrated" => &migration.migrated.len().to_string(),
+        "dropped" => &migration.dropped.len().to_string()
+    );
+    if !migration.needs_reinstall.is_empty() {
+        body.push_str("\n\n");
+        body.push_str(&crate::t!("prefix.arch.reinstall"));
+        for exe in &migration.needs_reinstall {
+            body.push_str("\n• ");
+            body.push_str(&exe.name);
+        }
+    }
+    body
+}
+
+// ── Helper: notes ────────────────────────────────────────────────────────
+
+/// The notes log, newest first; notes Tequila recorded itself are italic.
+fn notes_markup(config: &PrefixConfig) -> String {
+    if config.notes.is_empty() {
+        return format!(
+            "<span alpha=\"55%\">{}</span>",
+            gtk::glib::markup_escape_text(&crate::t!("prefix.notes_empty"))
+        );
+    }
+    config
+        .notes
+        .iter()
+        .rev()
+        .map(|note| {
+            let when = gtk::glib::DateTime::from_unix_local(note.timestamp.timestamp())
+                .ok()
+                .and_then(|dt| dt.format("%Y-%m-%d %H:%M").ok())
+                .unwrap_or_default();
+            let text = gtk::glib::markup_escape_text(&note.text);
+            if note.automatic {
+                format!("<small>{}</small>  <i>{}</i>", when, text)
+            } else {
+                format!("<small>{}</small>  {}", when, text)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// ── Helper: summary header ───────────────────────────────────────────────
+
+/// Format one header value, or a placeholder while the summary is loading.
+fn summary_value(info: &Option<PrefixInfo>, f: impl Fn(&PrefixInfo) -> String) -> String {
+    info.as_ref().map(f).unwrap_or_else(|| "…".to_string())
+}
+
+fn wineserver_status(info: &Option<PrefixInfo>) -> (String, &'static [&'static str]) {
+    match info {
+        Some(i) if i.wineserver_running => {
+            (crate::t!("prefix.summary.running"), &["caption", "success"])
+        }
+        Some(_) => (crate::t!("prefix.summary.idle"), &["caption", "dim-label"]),
+        None => (String::new(), &["caption"]),
+    }
+}
+
+// ── Helper: health check ─────────────────────────────────────────────────
+
+fn health_issue_text(issue: &PrefixIssue) -> String {
+    match issue {
+        PrefixIssue::MissingSystem32 => crate::t!("prefix.health.missing_system32"),
+        PrefixIssue::MissingRegistry(file) => {
+            crate::tf!("prefix.health.missing_registry", "file" => file)
+        }
+        PrefixIssue::UnreadableRegistry { file, error } => {
+            crate::tf!("prefix.health.unreadable_registry", "file" => file, "error" => error)
+        }
+        PrefixIssue::BrokenDriveC => crate::t!("prefix.health.broken_drive_c"),
+        PrefixIssue::ArchitectureMismatch { config, registry } => crate::tf!(
+            "prefix.health.arch_mismatch",
+            "config" => config,
+            "registry" => registry
+        ),
+    }
 }
 
 // ── Helper: build graphics dropdown items + mapping ──────────────────────
@@ -153,6 +310,151 @@ impl SimpleComponent for PrefixConfigModel {
 
             #[local_ref]
             prefs_page -> adw::PreferencesPage {
+                // ══ Summary header ══
+                adw::PreferencesGroup {
+                    gtk::Box {
+                        set_orientation: gtk::Orientation::Vertical,
+                        set_spacing: 12,
+                        set_css_classes: &["card", "prefix-summary"],
+
+                        gtk::Box {
+                            set_orientation: gtk::Orientation::Horizontal,
+                            set_spacing: 8,
+
+                            gtk::Label {
+                                #[track = "model.changed(PrefixConfigModel::config())"]
+                                set_label: &model.config.name,
+                                set_css_classes: &["title-2"],
+                                set_hexpand: true,
+                                set_ellipsize: gtk::pango::EllipsizeMode::End,
+                                set_xalign: 0.0,
+                            },
+
+                            gtk::Label {
+                                set_valign: gtk::Align::Center,
+                                #[track = "model.changed(PrefixConfigModel::info())"]
+                                set_label: &wineserver_status(&model.info).0,
+                                #[track = "model.changed(PrefixConfigModel::info())"]
+                                set_css_classes: wineserver_status(&model.info).1,
+                            },
+                        },
+
+                        gtk::Grid {
+                            set_column_spacing: 24,
+                            set_row_spacing: 12,
+                            set_column_homogeneous: true,
+
+                        attach[0, 0, 1, 1] = &gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 2,
+
+                            gtk::Label {
+                                #[track = "model.changed(PrefixConfigModel::info())"]
+                                set_label: &summary_value(&model.info, |i| i.architecture.clone()),
+                                set_css_classes: &["heading"],
+                                set_ellipsize: gtk::pango::EllipsizeMode::End,
+                                set_xalign: 0.0,
+                            },
+                            gtk::Label {
+                                set_label: &crate::t!("prefix.architecture"),
+                                set_css_classes: &["caption", "dim-label"],
+                                set_xalign: 0.0,
+                            },
+                        },
+
+                        attach[1, 0, 1, 1] = &gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 2,
+
+                            gtk::Label {
+                                #[track = "model.changed(PrefixConfigModel::info())"]
+                                set_label: &summary_value(&model.info, |i| i.wine_version.clone().unwrap_or_else(|| crate::t!("prefix.summary.no_wine"))),
+                                set_css_classes: &["heading"],
+                                set_ellipsize: gtk::pango::EllipsizeMode::End,
+                                set_xalign: 0.0,
+                            },
+                            gtk::Label {
+                                set_label: &crate::t!("prefix.wine_version"),
+                                set_css_classes: &["caption", "dim-label"],
+                                set_xalign: 0.0,
+                            },
+                        },
+
+                        attach[2, 0, 1, 1] = &gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 2,
+
+                            gtk::Label {
+                                #[track = "model.changed(PrefixConfigModel::info())"]
+                                set_label: &summary_value(&model.info, |i| gtk::glib::format_size(i.size).to_string()),
+                                set_css_classes: &["heading"],
+                                set_ellipsize: gtk::pango::EllipsizeMode::End,
+                                set_xalign: 0.0,
+                            },
+                            gtk::Label {
+                                set_label: &crate::t!("prefix.summary.disk_usage"),
+                                set_css_classes: &["caption", "dim-label"],
+                                set_xalign: 0.0,
+                            },
+                        },
+
+                        attach[0, 1, 1, 1] = &gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 2,
+
+                            gtk::Label {
+                                #[track = "model.changed(PrefixConfigModel::info())"]
+                                set_label: &summary_value(&model.info, |i| i.executable_count.to_string()),
+                                set_css_classes: &["heading"],
+                                set_ellipsize: gtk::pango::EllipsizeMode::End,
+                                set_xalign: 0.0,
+                            },
+                            gtk::Label {
+                                set_label: &crate::t!("prefix.summary.apps"),
+                                set_css_classes: &["caption", "dim-label"],
+                                set_xalign: 0.0,
+                            },
+                        },
+
+                        attach[1, 1, 1, 1] = &gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 2,
+
+                            gtk::Label {
+                                #[track = "model.changed(PrefixConfigModel::info())"]
+                                set_label: &summary_value(&model.info, |i| i.creation_date.format("%Y-%m-%d").to_string()),
+                                set_css_classes: &["heading"],
+                                set_ellipsize: gtk::pango::EllipsizeMode::End,
+                                set_xalign: 0.0,
+                            },
+                            gtk::Label {
+                                set_label: &crate::t!("prefix.detail.created"),
+                                set_css_classes: &["caption", "dim-label"],
+                                set_xalign: 0.0,
+                            },
+                        },
+
+                        attach[2, 1, 1, 1] = &gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_spacing: 2,
+
+                            gtk::Label {
+                                #[track = "model.changed(PrefixConfigModel::info())"]
+                                set_label: &summary_value(&model.info, |i| i.last_modified.format("%Y-%m-%d").to_string()),
+                                set_css_classes: &["heading"],
+                                set_ellipsize: gtk::pango::EllipsizeMode::End,
+                                set_xalign: 0.0,
+                            },
+                            gtk::Label {
+                                set_label: &crate::t!("prefix.detail.modified"),
+                                set_css_classes: &["caption", "dim-label"],
+                                set_xalign: 0.0,
+                            },
+                        },
+                        },
+                    },
+                },
+
                 // ══ General ══
                 adw::PreferencesGroup {
                     set_title: &crate::t!("prefix.general"),
@@ -177,6 +479,13 @@ impl SimpleComponent for PrefixConfigModel {
                             set_css_classes: &["caption", "monospace"],
                             set_valign: gtk::Align::Center,
                         },
+                        add_suffix = &gtk::Button {
+                            set_label: &crate::t!("prefix.arch.change"),
+                            set_tooltip_text: Some(&crate::t!("prefix.arch.change_tooltip")),
+                            set_valign: gtk::Align::Center,
+                            add_css_class: "flat",
+                            connect_clicked => PrefixConfigMsg::ChangeArchitecture,
+                        },
                     },
 
                     adw::ActionRow {
@@ -213,35 +522,37 @@ impl SimpleComponent for PrefixConfigModel {
                     set_title: &crate::t!("prefix.description"),
                 },
 
-                // ══ Info ══
+                // ══ Notes ══
                 adw::PreferencesGroup {
-                    set_title: &crate::t!("prefix.info"),
+                    set_title: &crate::t!("prefix.notes"),
+                    set_description: Some(&crate::t!("prefix.notes_desc")),
 
-                    adw::ActionRow {
-                        set_title: &crate::t!("prefix.detail.created"),
-                        set_subtitle: &crate::t!("prefix.detail.created_sub"),
-                        set_activatable: false,
-
-                        add_suffix = &gtk::Label {
-                            #[track = "model.changed(PrefixConfigModel::config())"]
-                            set_label: &model.config.creation_date.format("%Y-%m-%d").to_string(),
-                            set_css_classes: &["caption"],
-                            set_valign: gtk::Align::Center,
+                    adw::EntryRow {
+                        set_title: &crate::t!("prefix.notes_add"),
+                        set_show_apply_button: true,
+                        connect_apply[sender] => move |row| {
+                            let text = row.text().trim().to_string();
+                            if !text.is_empty() {
+                                sender.input(PrefixConfigMsg::AddNote(text));
+                            }
+                            row.set_text("");
                         },
                     },
 
-                    adw::ActionRow {
-                        set_title: &crate::t!("prefix.detail.modified"),
-                        set_subtitle: &crate::t!("prefix.detail.modified_sub"),
-                        set_activatable: false,
-
-                        add_suffix = &gtk::Label {
-                            #[track = "model.changed(PrefixConfigModel::config())"]
-                            set_label: &model.config.last_modified.format("%Y-%m-%d").to_string(),
-                            set_css_classes: &["caption"],
-                            set_valign: gtk::Align::Center,
-                        },
+                    gtk::Label {
+                        set_margin_top: 12,
+                        set_xalign: 0.0,
+                        set_wrap: true,
+                        set_wrap_mode: gtk::pango::WrapMode::WordChar,
+                        set_selectable: true,
+                        #[track = "model.changed(PrefixConfigModel::config())"]
+                        set_markup: &notes_markup(&model.config),
                     },
+                },
+
+                // ══ Info ══
+                adw::PreferencesGroup {
+                    set_title: &crate::t!("prefix.info"),
 
                     adw::ActionRow {
                         set_title: &crate::t!("prefix.detail.path"),
@@ -286,6 +597,73 @@ impl SimpleComponent for PrefixConfigModel {
                     },
                 },
 
+                // ══ Environment ══
+                adw::PreferencesGroup {
+                    set_title: &crate::t!("prefix.environment"),
+                    set_description: Some(&crate::t!("prefix.environment_desc")),
+
+                    adw::SwitchRow {
+                        set_title: &crate::t!("prefix.detail.esync"),
+                        set_subtitle: &crate::t!("prefix.detail.esync_sub"),
+                        #[track = "model.changed(PrefixConfigModel::editing())"]
+                        set_sensitive: model.editing,
+                        #[track = "model.changed(PrefixConfigModel::config())"]
+                        #[block_signal(esync_handler)]
+                        set_active: env_flag(&model.config, ESYNC),
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PrefixConfigMsg::SetEnvFlag(ESYNC, row.is_active()));
+                        } @esync_handler,
+                    },
+
+                    // fsync relies on Linux futex_waitv
+                    adw::SwitchRow {
+                        set_visible: cfg!(target_os = "linux"),
+                        set_title: &crate::t!("prefix.detail.fsync"),
+                        set_subtitle: &crate::t!("prefix.detail.fsync_sub"),
+                        #[track = "model.changed(PrefixConfigModel::editing())"]
+                        set_sensitive: model.editing,
+                        #[track = "model.changed(PrefixConfigModel::config())"]
+                        #[block_signal(fsync_handler)]
+                        set_active: env_flag(&model.config, FSYNC),
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PrefixConfigMsg::SetEnvFlag(FSYNC, row.is_active()));
+                        } @fsync_handler,
+                    },
+
+                    adw::ActionRow {
+                        set_title: &crate::t!("prefix.detail.env_vars"),
+                        set_activatable: true,
+                        #[track = "model.changed(PrefixConfigModel::config())"]
+                        set_subtitle: &env_vars_summary(&model.config),
+                        #[track = "model.changed(PrefixConfigModel::editing())"]
+                        set_sensitive: model.editing,
+                        connect_activated => PrefixConfigMsg::EditEnvVars,
+                    },
+
+                    #[name = "launch_wrapper_row"]
+                    adw::EntryRow {
+                        set_title: &crate::t!("prefix.detail.launch_wrapper"),
+                        set_tooltip_text: Some(&crate::t!("prefix.detail.launch_wrapper_tooltip")),
+                        #[track = "model.changed(PrefixConfigModel::editing())"]
+                        set_editable: model.editing,
+                        #[track = "model.changed(PrefixConfigModel::config())"]
+                        set_text: model.config.launch_wrapper.as_deref().unwrap_or_default(),
+                    },
+
+                    adw::SwitchRow {
+                        set_title: &crate::t!("prefix.detail.stop_wineserver"),
+                        set_subtitle: &crate::t!("prefix.detail.stop_wineserver_sub"),
+                        #[track = "model.changed(PrefixConfigModel::editing())"]
+                        set_sensitive: model.editing,
+                        #[track = "model.changed(PrefixConfigModel::config())"]
+                        #[block_signal(stop_wineserver_handler)]
+                        set_active: model.config.stop_wineserver_on_exit,
+                        connect_active_notify[sender] => move |row| {
+                            sender.input(PrefixConfigMsg::SetStopWineserver(row.is_active()));
+                        } @stop_wineserver_handler,
+                    },
+                },
+
                 // ══ Tools ══
                 adw::PreferencesGroup {
                     set_title: &crate::t!("prefix.tools"),
@@ -296,6 +674,97 @@ impl SimpleComponent for PrefixConfigModel {
                         set_activatable: true,
                         connect_activated => PrefixConfigMsg::ShowAdvancedRegistry,
                     },
+
+                    adw::ActionRow {
+                        set_title: &crate::t!("prefix.detail.health"),
+                        set_subtitle: &crate::t!("prefix.detail.health_sub"),
+                        set_activatable: true,
+                        #[track = "model.changed(PrefixConfigModel::checking_health())"]
+                        set_sensitive: !model.checking_health,
+                        connect_activated => PrefixConfigMsg::CheckHealth,
+
+                        add_suffix = &gtk::Spinner {
+                            #[track = "model.changed(PrefixConfigModel::checking_health())"]
+                            set_spinning: model.checking_health,
+                            #[track = "model.changed(PrefixConfigModel::checking_health())"]
+                            set_visible: model.checking_health,
+                        },
+                    },
+
+                    adw::ActionRow {
+                        set_title: &crate::t!("prefix.detail.associations"),
+                        set_subtitle: &crate::t!("prefix.detail.associations_sub"),
+                        set_activatable: true,
+                        connect_activated => PrefixConfigMsg::ShowFileAssociations,
+                    },
+
+                    adw::ActionRow {
+                        set_title: &crate::t!("prefix.detail.drives"),
+                        set_subtitle: &crate::t!("prefix.detail.drives_sub"),
+                        set_activatable: true,
+                        connect_activated => PrefixConfigMsg::ShowDrives,
+                    },
+
+                    adw::ActionRow {
+                        set_title: &crate::t!("prefix.detail.view_log"),
+                        set_subtitle: &crate::t!("prefix.detail.view_log_sub"),
+                        set_activatable: true,
+                        connect_activated => PrefixConfigMsg::ViewLog,
+                    },
+
+                    adw::ActionRow {
+                        set_title: &crate::t!("prefix.detail.copy_winetricks"),
+                        set_subtitle: &crate::t!("prefix.detail.copy_winetricks_sub"),
+                        set_activatable: true,
+                        connect_activated => PrefixConfigMsg::CopyWinetricksSnippet,
+
+                        add_suffix = &gtk::Image {
+                            set_icon_name: Some("edit-copy-symbolic"),
+                        },
+                    },
+
+                    adw::ActionRow {
+                        set_title: &crate::t!("prefix.detail.wine_tools"),
+                        set_subtitle: &crate::t!("prefix.detail.wine_tools_sub"),
+                        set_activatable: false,
+
+                        add_suffix = &gtk::Box {
+                            set_orientation: gtk::Orientation::Horizontal,
+                            set_spacing: 4,
+                            set_valign: gtk::Align::Center,
+
+                            gtk::Button {
+                                set_icon_name: "dialog-password-symbolic",
+                                set_tooltip_text: Some(&crate::t!("prefix.tool.regedit")),
+                                set_css_classes: &["flat"],
+                                connect_clicked => PrefixConfigMsg::RunWineTool(WineTool::Regedit),
+                            },
+                            gtk::Button {
+                                set_icon_name: "preferences-system-symbolic",
+                                set_tooltip_text: Some(&crate::t!("prefix.tool.control")),
+                                set_css_classes: &["flat"],
+                                connect_clicked => PrefixConfigMsg::RunWineTool(WineTool::Control),
+                            },
+                            gtk::Button {
+                                set_icon_name: "utilities-system-monitor-symbolic",
+                                set_tooltip_text: Some(&crate::t!("prefix.tool.taskmgr")),
+                                set_css_classes: &["flat"],
+                                connect_clicked => PrefixConfigMsg::RunWineTool(WineTool::Taskmgr),
+                            },
+                            gtk::Button {
+                                set_icon_name: "user-trash-symbolic",
+                                set_tooltip_text: Some(&crate::t!("prefix.tool.uninstaller")),
+                                set_css_classes: &["flat"],
+                                connect_clicked => PrefixConfigMsg::RunWineTool(WineTool::Uninstaller),
+                            },
+                            gtk::Button {
+                                set_icon_name: "system-file-manager-symbolic",
+                                set_tooltip_text: Some(&crate::t!("prefix.tool.winefile")),
+                                set_css_classes: &["flat"],
+                                connect_clicked => PrefixConfigMsg::RunWineTool(WineTool::Winefile),
+                            },
+                        },
+                    },
                 },
             },
 
@@ -377,6 +846,24 @@ impl SimpleComponent for PrefixConfigModel {
             .child(registry_ctrl.widget())
             .build();
 
+        // ── File associations page ──
+        let associations_ctrl = FileAssociationsModel::builder()
+            .launch((prefix_path.clone(), config.clone(), parent_window.clone()))
+            .detach();
+        let associations_page = adw::NavigationPage::builder()
+            .title(&crate::t!("prefix.detail.associations"))
+            .child(associations_ctrl.widget())
+            .build();
+
+        // ── Drives page ──
+        let drives_ctrl = DrivesModel::builder()
+            .launch((prefix_path.clone(), parent_window.clone()))
+            .detach();
+        let drives_page = adw::NavigationPage::builder()
+            .title(&crate::t!("prefix.detail.drives"))
+            .child(drives_ctrl.widget())
+            .build();
+
         // ── Graphics dropdown model ──
         let (graphics_items, graphics_backends) = build_graphics_model();
         let selected_graphics = graphics_index_for_config(&graphics_backends, &config);
@@ -405,11 +892,17 @@ impl SimpleComponent for PrefixConfigModel {
             editing: false,
             prefix_index: 0,
             wine_runtime_display: String::new(),
+            info: None,
+            checking_health: false,
             selected_graphics,
             nav: placeholder_nav,
             registry_ctrl,
             description_buffer: description_buffer.clone(),
             registry_page,
+            associations_ctrl,
+            associations_page,
+            drives_ctrl,
+            drives_page,
             description_text,
             back_btn,
             graphics_items,
@@ -422,6 +915,7 @@ impl SimpleComponent for PrefixConfigModel {
             progress_bar: gtk::ProgressBar::new(),
             pulse_id: None,
             progress_dialog: None,
+            env_vars_editor: None,
             edit_save_label: crate::t!("prefix.detail.edit"),
             tracker: 0,
         };
@@ -483,6 +977,14 @@ impl SimpleComponent for PrefixConfigModel {
                 s.input(PrefixConfigMsg::UpdateName(text));
             });
         }
+        {
+            let s = sender.clone();
+            widgets.launch_wrapper_row.connect_changed(move |row| {
+                s.input(PrefixConfigMsg::UpdateLaunchWrapper(row.text().to_string()));
+            });
+        }
+
+        sender.input(PrefixConfigMsg::LoadInfo);
 
         ComponentParts { model, widgets }
     }
@@ -516,6 +1018,10 @@ impl SimpleComponent for PrefixConfigModel {
             PrefixConfigMsg::UpdateDescription(desc) => {
                 self.config.description = if desc.is_empty() { None } else { Some(desc) };
             }
+            PrefixConfigMsg::UpdateLaunchWrapper(wrapper) => {
+                let wrapper = wrapper.trim();
+                self.config.launch_wrapper = (!wrapper.is_empty()).then(|| wrapper.to_string());
+            }
             PrefixConfigMsg::ConfigUpdated(config) => {
                 if let Some(ref desc) = config.description {
                     self.description_buffer.set_text(desc);
@@ -533,18 +1039,31 @@ impl SimpleComponent for PrefixConfigModel {
                 self.refresh_runtime_cache();
                 self.sync_wine_runtime_display();
                 self.sync_wine_runtime_selection();
+                sender.input(PrefixConfigMsg::LoadInfo);
             }
             PrefixConfigMsg::PrefixPathUpdated(path) => {
                 self.set_prefix_path(path.clone());
+                self.set_info(None);
+                sender.input(PrefixConfigMsg::LoadInfo);
                 if let Ok(Some(config)) = PrefixConfig::load_from_file(&path) {
                     self.set_config(config);
                     self.sync_selected_graphics();
                 }
                 self.registry_ctrl
-                    .emit(RegistryEditorMsg::PrefixPathUpdated(path));
+                    .emit(RegistryEditorMsg::PrefixPathUpdated(path.clone()));
+                self.associations_ctrl
+                    .emit(FileAssociationsMsg::PrefixPathUpdated(path.clone()));
+                self.drives_ctrl.emit(DrivesMsg::PrefixPathUpdated(path));
             }
             PrefixConfigMsg::SetPrefixIndex(index) => self.set_prefix_index(index),
             PrefixConfigMsg::SetWineVersionDisplay(d) => self.set_wine_runtime_display(d),
+            PrefixConfigMsg::LoadInfo => self.load_info(&sender),
+            PrefixConfigMsg::InfoLoaded(info) => {
+                // Drop results for a prefix we've since navigated away from
+                if info.path == self.prefix_path {
+                    self.set_info(Some(info));
+                }
+            }
             PrefixConfigMsg::SelectWineVersion => {
                 // Refresh runtime list from global state (user may have
                 // downloaded new runtimes since this model was initialised).
@@ -678,6 +1197,195 @@ impl SimpleComponent for PrefixConfigModel {
                 self.config.graphics = new_gfx;
             }
             PrefixConfigMsg::ShowAdvancedRegistry => self.nav.push(&self.registry_page),
+            PrefixConfigMsg::ShowFileAssociations => {
+                // Registered apps may have changed since the page was built
+                self.associations_ctrl
+                    .emit(FileAssociationsMsg::ConfigUpdated(self.config.clone()));
+                self.associations_ctrl.emit(FileAssociationsMsg::Load);
+                self.nav.push(&self.associations_page);
+            }
+            PrefixConfigMsg::ShowDrives => {
+                self.drives_ctrl.emit(DrivesMsg::Load);
+                self.nav.push(&self.drives_page);
+            }
+            PrefixConfigMsg::ViewLog => {
+                if let Err(e) = service::terminal::open_prefix_log(&self.prefix_path) {
+                    log::warn!("[prefix] {}", e);
+                    let alert = adw::AlertDialog::new(
+                        Some(&crate::t!("prefix.detail.no_log")),
+                        Some(&crate::t!("prefix.detail.no_log_body")),
+                    );
+                    alert.add_response("ok", &crate::t!("dialogs.ok"));
+                    alert.set_default_response(Some("ok"));
+                    alert.set_close_response("ok");
+                    alert.choose(
+                        Some(&self.parent_window),
+                        None::<&gtk::gio::Cancellable>,
+                        |_| {},
+                    );
+                }
+            }
+            PrefixConfigMsg::CheckHealth => {
+                self.set_checking_health(true);
+                let pm = AppService::global().prefix_manager().clone();
+                let pp = self.prefix_path.clone();
+                let s = sender.clone();
+                gtk::glib::spawn_future_local(async move {
+                    let result = gtk::gio::spawn_blocking(move || pm.check_prefix(&pp))
+                        .await
+                        .unwrap_or_else(|_| {
+                            Err(prefix::PrefixError::Process("health check panicked".into()))
+                        })
+                        .map_err(|e| e.to_string());
+                    s.input(PrefixConfigMsg::HealthChecked(result));
+                });
+            }
+            PrefixConfigMsg::HealthChecked(result) => {
+                self.set_checking_health(false);
+                self.show_health_report(result, &sender);
+            }
+            PrefixConfigMsg::RepairPrefix => {
+                self.set_checking_health(true);
+                let pm = AppService::global().prefix_manager().clone();
+                let pp = self.prefix_path.clone();
+                let s = sender.clone();
+                gtk::glib::spawn_future_local(async move {
+                    let result = gtk::gio::spawn_blocking(move || pm.repair_prefix(&pp))
+                        .await
+                        .unwrap_or_else(|_| {
+                            Err(prefix::PrefixError::Process("repair panicked".into()))
+                        })
+                        .map_err(|e| e.to_string());
+                    s.input(PrefixConfigMsg::RepairComplete(result));
+                });
+            }
+            PrefixConfigMsg::RepairComplete(result) => {
+                self.set_checking_health(false);
+                match result {
+                    // Re-check so the user sees what, if anything, is left
+                    Ok(()) => sender.input(PrefixConfigMsg::CheckHealth),
+                    Err(e) => {
+                        let alert = adw::AlertDialog::new(
+                            Some(&crate::t!("dialogs.error")),
+                            Some(&crate::tf!("prefix.health.repair_failed", "error" => &e)),
+                        );
+                        alert.add_response("ok", &crate::t!("dialogs.ok"));
+                        alert.set_default_response(Some("ok"));
+                        alert.set_close_response("ok");
+                        alert.choose(
+                            Some(&self.parent_window),
+                            None::<&gtk::gio::Cancellable>,
+                            |_| {},
+                        );
+                    }
+                }
+            }
+            PrefixConfigMsg::ChangeArchitecture => {
+                self.confirm_architecture_change(&sender);
+            }
+            PrefixConfigMsg::MigrateArchitecture(name) => {
+                let target = other_architecture(&self.config.architecture);
+                let _ = sender.output(PrefixConfigOutput::CreationStarted(name.clone()));
+                let pm = AppService::global().prefix_manager().clone();
+                let pp = self.prefix_path.clone();
+                let s = sender.clone();
+                gtk::glib::spawn_future_local(async move {
+                    let result = gtk::gio::spawn_blocking(move || {
+                        pm.migrate_to_architecture(&pp, target, &name)
+                    })
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(prefix::PrefixError::Process("migration panicked".into()))
+                    })
+                    .map_err(|e| e.to_string());
+                    s.input(PrefixConfigMsg::MigrationComplete(result));
+                });
+            }
+            PrefixConfigMsg::MigrationComplete(result) => {
+                let _ = sender.output(PrefixConfigOutput::CreationFinished(
+                    result.as_ref().ok().map(|m| m.prefix_path.clone()),
+                ));
+                let body = match &result {
+                    Ok(migration) => migration_summary(migration),
+                    Err(e) => crate::tf!("prefix.arch.failed", "error" => e),
+                };
+                let title = if result.is_ok() {
+                    crate::t!("prefix.arch.done_title")
+                } else {
+                    crate::t!("dialogs.error")
+                };
+                let alert = adw::AlertDialog::new(Some(&title), Some(&body));
+                alert.add_response("ok", &crate::t!("dialogs.ok"));
+                alert.set_default_response(Some("ok"));
+                alert.set_close_response("ok");
+                alert.choose(
+                    Some(&self.parent_window),
+                    None::<&gtk::gio::Cancellable>,
+                    |_| {},
+                );
+            }
+            PrefixConfigMsg::AddNote(text) => {
+                let pm = AppService::global().prefix_manager().clone();
+                let note = match pm.add_prefix_note(&self.prefix_path, &text, false) {
+                    Ok(note) => note,
+                    Err(e) => {
+                        log::error!("[prefix] failed to save note: {}", e);
+                        return;
+                    }
+                };
+                // Mirror the note locally without touching unsaved edits
+                let note = std::slice::from_ref(&note);
+                self.get_mut_config().merge_notes(note);
+                self.saved_config.merge_notes(note);
+            }
+            PrefixConfigMsg::RunWineTool(tool) => {
+                if let Err(e) = service::launch::launch_wine_tool(
+                    &AppService::global(),
+                    &self.prefix_path,
+                    tool,
+                ) {
+                    let alert = adw::AlertDialog::new(
+                        Some(&crate::t!("dialogs.error")),
+                        Some(&crate::tf!("prefix.tool.failed", "tool" => tool.program(), "error" => &e)),
+                    );
+                    alert.add_response("ok", &crate::t!("dialogs.ok"));
+                    alert.set_default_response(Some("ok"));
+                    alert.set_close_response("ok");
+                    alert.choose(
+                        Some(&self.parent_window),
+                        None::<&gtk::gio::Cancellable>,
+                        |_| {},
+                    );
+                }
+            }
+            PrefixConfigMsg::CopyWinetricksSnippet => {
+                let snippet = self.saved_config.to_winetricks_snippet(&self.prefix_path);
+                self.parent_window.clipboard().set_text(&snippet);
+            }
+            PrefixConfigMsg::SetEnvFlag(key, enabled) => {
+                // Only update in-memory config — actual save happens on SaveConfig.
+                let env_vars = &mut self.get_mut_config().env_vars;
+                if enabled {
+                    env_vars.insert(key.to_string(), "1".to_string());
+                } else {
+                    env_vars.remove(key);
+                }
+            }
+            PrefixConfigMsg::SetStopWineserver(enabled) => {
+                self.get_mut_config().stop_wineserver_on_exit = enabled;
+            }
+            PrefixConfigMsg::EditEnvVars => {
+                let editor = EnvVarsEditor::builder()
+                    .launch((
+                        self.parent_window.clone(),
+                        env_vars_to_text(&self.config.env_vars),
+                    ))
+                    .forward(sender.input_sender(), PrefixConfigMsg::EnvVarsEdited);
+                self.env_vars_editor = Some(editor);
+            }
+            PrefixConfigMsg::EnvVarsEdited(vars) => {
+                self.get_mut_config().env_vars = vars;
+            }
             PrefixConfigMsg::RegistryEditor(output) => {
                 if let RegistryEditorMsg::ConfigUpdated(config) = output {
                     self.set_config(config.clone());
@@ -720,6 +1428,110 @@ impl PrefixConfigModel {
         let _ = sender.output(PrefixConfigOutput::ConfigUpdated(self.config.clone()));
     }
 
+    /// Compute the summary header off the main thread; measuring disk usage
+    /// walks the entire prefix.
+    fn load_info(&self, sender: &ComponentSender<Self>) {
+        let pm = AppService::global().prefix_manager().clone();
+        let pp = self.prefix_path.clone();
+        let s = sender.clone();
+        gtk::glib::spawn_future_local(async move {
+            match gtk::gio::spawn_blocking(move || pm.prefix_info(&pp)).await {
+                Ok(Ok(info)) => s.input(PrefixConfigMsg::InfoLoaded(info)),
+                Ok(Err(e)) => log::warn!("[prefix] failed to load prefix info: {}", e),
+                Err(_) => log::warn!("[prefix] prefix info task panicked"),
+            }
+        });
+    }
+
+    fn show_health_report(
+        &self,
+        result: Result<Vec<PrefixIssue>, String>,
+        sender: &ComponentSender<Self>,
+    ) {
+        let issues = match result {
+            Ok(issues) => issues,
+            Err(e) => {
+                let alert = adw::AlertDialog::new(
+                    Some(&crate::t!("dialogs.error")),
+                    Some(&crate::tf!("prefix.health.check_failed", "error" => &e)),
+                );
+                alert.add_response("ok", &crate::t!("dialogs.ok"));
+                alert.set_default_response(Some("ok"));
+                alert.set_close_response("ok");
+                alert.choose(
+                    Some(&self.parent_window),
+                    None::<&gtk::gio::Cancellable>,
+                    |_| {},
+                );
+                return;
+            }
+        };
+
+        let body = if issues.is_empty() {
+            crate::t!("prefix.health.healthy")
+        } else {
+            issues
+                .iter()
+                .map(|issue| format!("• {}", health_issue_text(issue)))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let alert = adw::AlertDialog::new(Some(&crate::t!("prefix.health.title")), Some(&body));
+        alert.add_response("close", &crate::t!("dialogs.ok"));
+        alert.set_default_response(Some("close"));
+        alert.set_close_response("close");
+        if issues.iter().any(PrefixIssue::is_repairable) {
+            alert.add_response("repair", &crate::t!("prefix.health.repair"));
+            alert.set_response_appearance("repair", adw::ResponseAppearance::Suggested);
+            alert.set_default_response(Some("repair"));
+        }
+        let s = sender.clone();
+        alert.choose(
+            Some(&self.parent_window),
+            None::<&gtk::gio::Cancellable>,
+            move |response| {
+                if response == "repair" {
+                    s.input(PrefixConfigMsg::RepairPrefix);
+                }
+            },
+        );
+    }
+
+    /// Offer to create a prefix of the other architecture. Switching in place
+    /// isn't supported, and the dialog says why.
+    fn confirm_architecture_change(&self, sender: &ComponentSender<Self>) {
+        let target = other_architecture(&self.config.architecture);
+        let entry = gtk::Entry::builder()
+            .text(format!("{} ({})", self.config.name, target))
+            .activates_default(true)
+            .build();
+        let alert = adw::AlertDialog::new(
+            Some(&crate::tf!("prefix.arch.confirm_title", "arch" => target)),
+            Some(&crate::tf!(
+                "prefix.arch.confirm_body",
+                "from" => &self.config.architecture,
+                "arch" => target
+            )),
+        );
+        alert.set_extra_child(Some(&entry));
+        alert.add_response("cancel", &crate::t!("dialogs.cancel"));
+        alert.add_response("create", &crate::t!("prefix.arch.create"));
+        alert.set_response_appearance("create", adw::ResponseAppearance::Suggested);
+        alert.set_default_response(Some("create"));
+        alert.set_close_response("cancel");
+        let sender = sender.clone();
+        alert.choose(
+            Some(&self.parent_window),
+            None::<&gtk::gio::Cancellable>,
+            move |response| {
+                let name = entry.text().trim().to_string();
+                if response == "create" && !name.is_empty() {
+                    sender.input(PrefixConfigMsg::MigrateArchitecture(name));
+                }
+            },
+        );
+    }
+
     fn sync_selected_graphics(&mut self) {
         let idx = graphics_index_for_config(&self.graphics_backends, &self.config);
         // set_selected with same value is a no-op in GTK4,
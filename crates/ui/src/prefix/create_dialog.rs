@@ -3,18 +3,25 @@ use adw::prelude::*;
 use gtk::glib;
 use prefix::base::GraphicsBackend;
 use prefix::runtime;
+use prefix::{PrefixTemplate, Settings};
 use relm4::{ComponentParts, ComponentSender, SimpleComponent, adw, gtk};
 use service::AppService;
+use std::path::PathBuf;
 
 pub struct CreatePrefixDialog {
     name_entry: gtk::Entry,
+    name_error: gtk::Label,
     arch_combo: gtk::DropDown,
     runtime_combo: gtk::DropDown,
     graphics_combo: gtk::DropDown,
     graphics_backends: Vec<Option<GraphicsBackend>>, // None = no backend
+    template_combo: gtk::DropDown,
+    templates: Vec<Option<PrefixTemplate>>, // None = no template
+    /// Run once the prefix is ready, e.g. a game's `setup.exe`.
+    installer: Option<PathBuf>,
+    installer_label: gtk::Label,
+    installer_clear: gtk::Button,
     create_btn: gtk::Button,
-    progress_bar: gtk::ProgressBar,
-    progress_label: gtk::Label,
     dialog: gtk::Window,
     parent: gtk::ApplicationWindow,
 }
@@ -22,6 +29,9 @@ pub struct CreatePrefixDialog {
 #[derive(Debug)]
 pub enum CreatePrefixMsg {
     Create,
+    NameChanged,
+    ChooseInstaller,
+    InstallerChosen(Option<PathBuf>),
 }
 
 impl CreatePrefixDialog {
@@ -42,6 +52,31 @@ impl CreatePrefixDialog {
         combo
     }
 
+    /// Check a prospective prefix name, returning a user-facing error if it
+    /// is empty, contains a path separator, or collides with an existing prefix.
+    fn validate_name(name: &str) -> Option<String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Some(crate::t!("prefix.create.name_empty"));
+        }
+        if name.contains('/') || name.contains('\\') {
+            return Some(crate::t!("prefix.create.name_invalid"));
+        }
+
+        let pm = AppService::global().prefix_manager().clone();
+        if pm.wine_dir().join(name).exists() {
+            return Some(crate::tf!("prefix.create.name_exists", "name" => name));
+        }
+        let taken = pm
+            .scan_prefixes()
+            .map(|prefixes| prefixes.iter().any(|p| p.name == name))
+            .unwrap_or(false);
+        if taken {
+            return Some(crate::tf!("prefix.create.name_exists", "name" => name));
+        }
+        None
+    }
+
     fn build_graphics_combo() -> (gtk::DropDown, Vec<Option<GraphicsBackend>>) {
         let backends = runtime::graphics::installed_backends();
         let mut items = vec!["WineD3D (built-in)".to_string()];
@@ -59,6 +94,24 @@ impl CreatePrefixDialog {
 
         (combo, mapping)
     }
+
+    fn build_template_combo() -> (gtk::DropDown, Vec<Option<PrefixTemplate>>) {
+        let templates = Settings::current().prefix_templates;
+        let mut items = vec![crate::t!("prefix.create.template_none")];
+        let mut mapping: Vec<Option<PrefixTemplate>> = vec![None];
+
+        for t in templates {
+            items.push(t.name.clone());
+            mapping.push(Some(t));
+        }
+
+        let str_refs: Vec<&str> = items.iter().map(|s| s.as_str()).collect();
+        let combo = gtk::DropDown::from_strings(&str_refs);
+        combo.set_hexpand(true);
+        combo.set_selected(0);
+
+        (combo, mapping)
+    }
 }
 
 #[relm4::component(pub)]
@@ -95,6 +148,15 @@ impl SimpleComponent for CreatePrefixDialog {
                         set_placeholder_text: Some(&crate::t!("prefix.create.name_placeholder")),
                         set_hexpand: true,
                         set_width_chars: 32,
+                        connect_changed => CreatePrefixMsg::NameChanged,
+                    },
+                    #[name = "name_error"]
+                    gtk::Label {
+                        set_halign: gtk::Align::Start,
+                        set_margin_top: 4,
+                        set_wrap: true,
+                        add_css_class: "error",
+                        set_visible: false,
                     },
                 },
 
@@ -115,7 +177,7 @@ impl SimpleComponent for CreatePrefixDialog {
                         #[name = "arch_combo"]
                         gtk::DropDown {
                             set_model: Some(&gtk::StringList::new(&["win32", "win64"])),
-                            set_selected: 1u32,
+                            set_selected: u32::from(Settings::current().default_architecture != "win32"),
                             set_hexpand: true,
                         },
                     },
@@ -152,14 +214,57 @@ impl SimpleComponent for CreatePrefixDialog {
                     },
                 },
 
-                #[name = "progress_label"]
-                gtk::Label {
-                    set_label: &crate::t!("prefix.create.progress"),
-                    set_visible: false,
+                gtk::Box {
+                    set_visible: templates.len() > 1,
+                    set_hexpand: true,
+                    set_spacing: 10,
+                    set_margin_top: 10,
+                    set_orientation: gtk::Orientation::Vertical,
+
+                    gtk::Label {
+                        set_label: &crate::t!("prefix.create.template_label"),
+                        set_halign: gtk::Align::Start,
+                    },
+                    #[local_ref]
+                    template_combo -> gtk::DropDown {
+                        set_hexpand: true,
+                    },
                 },
-                #[name = "progress_bar"]
-                gtk::ProgressBar {
-                    set_visible: false,
+
+                gtk::Box {
+                    set_hexpand: true,
+                    set_spacing: 10,
+                    set_margin_top: 10,
+                    set_orientation: gtk::Orientation::Vertical,
+
+                    gtk::Label {
+                        set_label: &crate::t!("prefix.create.installer_label"),
+                        set_halign: gtk::Align::Start,
+                    },
+                    gtk::Box {
+                        set_spacing: 6,
+                        set_orientation: gtk::Orientation::Horizontal,
+
+                        #[name = "installer_label"]
+                        gtk::Label {
+                            set_label: &crate::t!("prefix.create.installer_none"),
+                            set_hexpand: true,
+                            set_halign: gtk::Align::Start,
+                            set_ellipsize: gtk::pango::EllipsizeMode::Middle,
+                            add_css_class: "dim-label",
+                        },
+                        #[name = "installer_clear"]
+                        gtk::Button {
+                            set_icon_name: "edit-clear-symbolic",
+                            set_tooltip_text: Some(&crate::t!("prefix.create.installer_clear")),
+                            set_visible: false,
+                            connect_clicked => CreatePrefixMsg::InstallerChosen(None),
+                        },
+                        gtk::Button {
+                            set_label: &crate::t!("prefix.create.installer_choose"),
+                            connect_clicked => CreatePrefixMsg::ChooseInstaller,
+                        },
+                    },
                 },
             },
         }
@@ -174,6 +279,7 @@ impl SimpleComponent for CreatePrefixDialog {
         let prefix_manager = svc.prefix_manager();
         let runtime_combo = Self::build_runtime_combo(&prefix_manager);
         let (graphics_combo, graphics_backends) = Self::build_graphics_combo();
+        let (template_combo, templates) = Self::build_template_combo();
 
         let widgets = view_output!();
 
@@ -202,13 +308,17 @@ impl SimpleComponent for CreatePrefixDialog {
 
         let model = CreatePrefixDialog {
             name_entry: widgets.name_entry.clone(),
+            name_error: widgets.name_error.clone(),
             arch_combo: widgets.arch_combo.clone(),
             runtime_combo: runtime_combo.clone(),
             graphics_combo: graphics_combo.clone(),
             graphics_backends,
+            template_combo: template_combo.clone(),
+            templates,
+            installer: None,
+            installer_label: widgets.installer_label.clone(),
+            installer_clear: widgets.installer_clear.clone(),
             create_btn,
-            progress_bar: widgets.progress_bar.clone(),
-            progress_label: widgets.progress_label.clone(),
             dialog: widgets.dialog.clone(),
             parent,
         };
@@ -218,10 +328,53 @@ impl SimpleComponent for CreatePrefixDialog {
 
     fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>) {
         match msg {
+            CreatePrefixMsg::NameChanged => {
+                self.name_entry.remove_css_class("error");
+                self.name_error.set_visible(false);
+            }
+            CreatePrefixMsg::ChooseInstaller => {
+                let s = sender.clone();
+                crate::dialogs::pick_file(
+                    &self.dialog,
+                    &crate::t!("prefix.create.installer_pick_title"),
+                    &["exe", "msi"],
+                    move |path| {
+                        if let Some(path) = path {
+                            s.input(CreatePrefixMsg::InstallerChosen(Some(PathBuf::from(path))));
+                        }
+                    },
+                );
+            }
+            CreatePrefixMsg::InstallerChosen(path) => {
+                match &path {
+                    Some(path) => {
+                        let name = path
+                            .file_name()
+                            .map(|n| n.to_string_lossy().into_owned())
+                            .unwrap_or_else(|| path.display().to_string());
+                        self.installer_label.set_label(&name);
+                        self.installer_label
+                            .set_tooltip_text(Some(&path.display().to_string()));
+                        self.installer_label.remove_css_class("dim-label");
+                    }
+                    None => {
+                        self.installer_label
+                            .set_label(&crate::t!("prefix.create.installer_none"));
+                        self.installer_label.set_tooltip_text(None);
+                        self.installer_label.add_css_class("dim-label");
+                    }
+                }
+                self.installer_clear.set_visible(path.is_some());
+                self.installer = path;
+            }
             CreatePrefixMsg::Create => {
-                let name = self.name_entry.text().to_string();
-                if name.is_empty() {
-                    log::warn!("[create] prefix name cannot be empty");
+                let name = self.name_entry.text().trim().to_string();
+                if let Some(err) = Self::validate_name(&name) {
+                    log::warn!("[create] rejected prefix name '{}': {}", name, err);
+                    self.name_entry.add_css_class("error");
+                    self.name_error.set_label(&err);
+                    self.name_error.set_visible(true);
+                    self.name_entry.grab_focus();
                     return;
                 }
 
@@ -250,27 +403,23 @@ impl SimpleComponent for CreatePrefixDialog {
                 #[cfg(target_os = "macos")]
                 let selected_backend: Option<GraphicsBackend> = None;
 
-                // Show progress, disable inputs
-                self.name_entry.set_sensitive(false);
-                self.arch_combo.set_sensitive(false);
-                self.runtime_combo.set_sensitive(false);
-                self.graphics_combo.set_sensitive(false);
-                self.progress_label.set_visible(true);
-                self.progress_bar.set_visible(true);
-                self.progress_bar.set_fraction(0.0);
-                self.create_btn.set_sensitive(false);
+                let selected_template = {
+                    let i = self.template_combo.selected() as usize;
+                    self.templates.get(i).cloned().unwrap_or(None)
+                };
+                let installer = self.installer.clone();
 
-                let pb = self.progress_bar.clone();
-                let pulse_id =
-                    glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
-                        pb.pulse();
-                        glib::ControlFlow::Continue
-                    });
+                // Hand progress over to the main window and close the dialog;
+                // the create button stays disabled in case the close is delayed
+                self.create_btn.set_sensitive(false);
+                sender
+                    .output(AppMsg::PrefixCreationStarted(name.clone()))
+                    .unwrap_or(());
+                self.dialog.close();
 
                 let prefix_name = name.clone();
                 let pm = AppService::global().prefix_manager().clone();
                 let mw = self.parent.clone();
-                let dlg = self.dialog.clone();
                 let sc = sender.clone();
                 let ctx = glib::MainContext::default();
                 ctx.spawn_local(async move {
@@ -287,8 +436,7 @@ impl SimpleComponent for CreatePrefixDialog {
                     let prefix_path = match create_result {
                         Ok(Ok(path)) => path,
                         Ok(Err(e)) => {
-                            pulse_id.remove();
-                            dlg.close();
+                            sc.output(AppMsg::PrefixCreationFinished(None)).unwrap_or(());
                             log::error!(
                                 "[create] failed to create prefix '{}': {}",
                                 prefix_name,
@@ -305,8 +453,7 @@ impl SimpleComponent for CreatePrefixDialog {
                             return;
                         }
                         Err(e) => {
-                            pulse_id.remove();
-                            dlg.close();
+                            sc.output(AppMsg::PrefixCreationFinished(None)).unwrap_or(());
                             let msg = if e.is_panic() {
                                 "panic in create_prefix".to_string()
                             } else {
@@ -340,14 +487,105 @@ impl SimpleComponent for CreatePrefixDialog {
                         }
                     }
 
-                    pulse_id.remove();
-                    dlg.close();
+                    // Step 3: Apply the template's registry settings, then its winetricks verbs
+                    if let Some(template) = selected_template {
+                        let mut result = pm.apply_template(&prefix_path, &template).await;
+                        if result.is_ok() && !template.winetricks_verbs.is_empty() {
+                            let pm_tricks = pm.clone();
+                            let path = prefix_path.clone();
+                            let verbs = template.winetricks_verbs.clone();
+                            result = tokio::task::spawn_blocking(move || {
+                                pm_tricks.run_winetricks(&path, &verbs)
+                            })
+                            .await
+                            .unwrap_or_else(|e| {
+                                Err(prefix::PrefixError::Process(e.to_string()))
+                            });
+                        }
+                        if let Err(e) = result {
+                            log::error!(
+                                "[create] failed to apply template '{}': {}",
+                                template.name,
+                                e
+                            );
+                            let alert = adw::AlertDialog::new(
+                                Some(&crate::t!("dialogs.warning")),
+                                Some(&crate::tf!("prefix.create.template_warning_msg", "template" => &template.name, "error" => &e.to_string())),
+                            );
+                            alert.add_response("ok", &crate::t!("dialogs.ok"));
+                            alert.set_default_response(Some("ok"));
+                            alert.set_close_response("ok");
+                            alert.choose(Some(&mw), None::<&gtk::gio::Cancellable>, |_| {});
+                        }
+                    }
+
                     log::info!(
                         "[create] created prefix: {} at {}",
                         prefix_name,
                         prefix_path.display()
                     );
-                    sc.output(AppMsg::RefreshPrefixes).unwrap_or(());
+
+                    // Step 4: Run the installer, then offer to scan for what it installed
+                    let Some(installer) = installer else {
+                        sc.output(AppMsg::PrefixCreationFinished(Some(prefix_path)))
+                            .unwrap_or(());
+                        return;
+                    };
+                    let installer_name = installer
+                        .file_name()
+                        .map(|n| n.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                    sc.output(AppMsg::PrefixCreationStatus(crate::tf!(
+                        "app_page.running_installer",
+                        "name" => &installer_name
+                    )))
+                    .unwrap_or(());
+                    let pm_install = pm.clone();
+                    let path = prefix_path.clone();
+                    let install_result = tokio::task::spawn_blocking(move || {
+                        pm_install.run_installer(&path, &installer)
+                    })
+                    .await
+                    .unwrap_or_else(|e| Err(prefix::PrefixError::Process(e.to_string())));
+
+                    let alert = match install_result {
+                        Ok(()) => {
+                            let alert = adw::AlertDialog::new(
+                                Some(&crate::t!("prefix.create.installer_done")),
+                                Some(&crate::tf!("prefix.create.installer_done_body", "name" => &prefix_name)),
+                            );
+                            alert.add_response("later", &crate::t!("prefix.create.installer_later"));
+                            alert.add_response("scan", &crate::t!("prefix.create.installer_scan"));
+                            alert.set_response_appearance("scan", adw::ResponseAppearance::Suggested);
+                            alert.set_default_response(Some("scan"));
+                            alert.set_close_response("later");
+                            alert
+                        }
+                        Err(e) => {
+                            log::error!(
+                                "[create] failed to run installer {}: {}",
+                                installer_name,
+                                e
+                            );
+                            let alert = adw::AlertDialog::new(
+                                Some(&crate::t!("dialogs.warning")),
+                                Some(&crate::tf!("prefix.create.installer_failed", "installer" => &installer_name, "error" => &e.to_string())),
+                            );
+                            alert.add_response("ok", &crate::t!("dialogs.ok"));
+                            alert.set_default_response(Some("ok"));
+                            alert.set_close_response("ok");
+                            alert
+                        }
+                    };
+                    alert.choose(Some(&mw), None::<&gtk::gio::Cancellable>, move |response| {
+                        // Queued before the finish so the scan waits for the new prefix
+                        if response == "scan" {
+                            sc.output(AppMsg::ScanAfterInstall(prefix_path.clone()))
+                                .unwrap_or(());
+                        }
+                        sc.output(AppMsg::PrefixCreationFinished(Some(prefix_path)))
+                            .unwrap_or(());
+                    });
                 });
             }
         }
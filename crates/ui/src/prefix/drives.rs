@@ -0,0 +1,273 @@
+use adw::prelude::*;
+use prefix::drives::{DriveMapping, add_drive, list_drives, remove_drive};
+use relm4::{ComponentParts, ComponentSender, RelmWidgetExt, SimpleComponent, adw, gtk};
+use std::path::{Path, PathBuf};
+
+// ── Model ────────────────────────────────────────────────────────────────
+
+#[derive(Debug)]
+pub struct DrivesModel {
+    prefix_path: PathBuf,
+    parent_window: gtk::Window,
+    drives: Vec<DriveMapping>,
+    /// Letters not mapped yet, parallel to the dropdown's items.
+    free_letters: Vec<char>,
+    letter_dropdown: gtk::DropDown,
+    target_entry: gtk::Entry,
+    drives_group: adw::PreferencesGroup,
+    drive_rows: Vec<adw::ActionRow>,
+}
+
+#[derive(Debug)]
+pub enum DrivesMsg {
+    Load,
+    BrowseTarget,
+    Add,
+    Remove(char),
+    RemoveConfirmed(char),
+    PrefixPathUpdated(PathBuf),
+    ShowError(String),
+}
+
+/// Letters that can still be mapped. `A:` and `B:` are left out, as in
+/// winecfg, since Windows programs treat them as floppy drives.
+fn free_letters(drives: &[DriveMapping]) -> Vec<char> {
+    ('d'..='z')
+        .filter(|letter| drives.iter().all(|d| d.letter != *letter))
+        .collect()
+}
+
+fn drive_subtitle(drive: &DriveMapping) -> String {
+    let target = drive.target.display().to_string();
+    if drive.is_system() {
+        crate::tf!("drives.system", "target" => &target)
+    } else if drive.is_host_root() {
+        crate::tf!("drives.host_root", "target" => &target)
+    } else if !drive.target_exists {
+        crate::tf!("drives.missing", "target" => &target)
+    } else {
+        target
+    }
+}
+
+// ── Component ────────────────────────────────────────────────────────────
+
+#[relm4::component(pub)]
+impl SimpleComponent for DrivesModel {
+    type Init = (PathBuf, gtk::Window);
+    type Input = DrivesMsg;
+    type Output = ();
+    type Widgets = DrivesWidgets;
+
+    view! {
+        #[root]
+        gtk::ScrolledWindow {
+            set_vexpand: true,
+            set_hexpand: true,
+            set_hscrollbar_policy: gtk::PolicyType::Never,
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_margin_all: 18,
+                set_spacing: 18,
+
+                #[name = "drives_group"]
+                adw::PreferencesGroup {
+                    set_title: &crate::t!("drives.title"),
+                    set_description: Some(&crate::t!("drives.desc")),
+
+                    adw::ActionRow {
+                        set_title: &crate::t!("drives.add"),
+
+                        #[name = "letter_dropdown"]
+                        add_suffix = &gtk::DropDown {
+                            set_valign: gtk::Align::Center,
+                        },
+
+                        #[name = "target_entry"]
+                        add_suffix = &gtk::Entry {
+                            set_width_chars: 18,
+                            set_valign: gtk::Align::Center,
+                            set_placeholder_text: Some(&crate::t!("drives.target_placeholder")),
+                            connect_activate => DrivesMsg::Add,
+                        },
+
+                        add_suffix = &gtk::Button {
+                            set_icon_name: "folder-open-symbolic",
+                            set_tooltip_text: Some(&crate::t!("drives.browse")),
+                            set_valign: gtk::Align::Center,
+                            set_css_classes: &["flat"],
+                            connect_clicked => DrivesMsg::BrowseTarget,
+                        },
+
+                        add_suffix = &gtk::Button {
+                            set_icon_name: "list-add-symbolic",
+                            set_tooltip_text: Some(&crate::t!("drives.add")),
+                            set_valign: gtk::Align::Center,
+                            set_css_classes: &["flat"],
+                            connect_clicked => DrivesMsg::Add,
+                        },
+                    },
+                },
+            },
+        }
+    }
+
+    fn init(
+        init: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let (prefix_path, parent_window) = init;
+
+        let mut model = DrivesModel {
+            prefix_path,
+            parent_window,
+            drives: Vec::new(),
+            free_letters: Vec::new(),
+            letter_dropdown: gtk::DropDown::default(),
+            target_entry: gtk::Entry::new(),
+            drives_group: adw::PreferencesGroup::new(),
+            drive_rows: Vec::new(),
+        };
+
+        let widgets = view_output!();
+        model.letter_dropdown = widgets.letter_dropdown.clone();
+        model.target_entry = widgets.target_entry.clone();
+        model.drives_group = widgets.drives_group.clone();
+        model.refresh_rows(&sender);
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>) {
+        match msg {
+            DrivesMsg::Load => {
+                match list_drives(&self.prefix_path) {
+                    Ok(drives) => self.drives = drives,
+                    Err(e) => {
+                        self.drives.clear();
+                        sender.input(DrivesMsg::ShowError(e.to_string()));
+                    }
+                }
+                self.refresh_rows(&sender);
+            }
+            DrivesMsg::BrowseTarget => {
+                let entry = self.target_entry.clone();
+                crate::dialogs::pick_folder(&self.parent_window, None, move |path| {
+                    entry.set_text(&path);
+                });
+            }
+            DrivesMsg::Add => {
+                let text = self.target_entry.text();
+                if text.trim().is_empty() {
+                    return;
+                }
+                let Some(&letter) = self
+                    .free_letters
+                    .get(self.letter_dropdown.selected() as usize)
+                else {
+                    return;
+                };
+                match add_drive(&self.prefix_path, letter, Path::new(text.trim())) {
+                    Ok(()) => self.target_entry.set_text(""),
+                    Err(e) => sender.input(DrivesMsg::ShowError(e.to_string())),
+                }
+                sender.input(DrivesMsg::Load);
+            }
+            DrivesMsg::Remove(letter) => {
+                if letter != 'z' {
+                    sender.input(DrivesMsg::RemoveConfirmed(letter));
+                    return;
+                }
+                // Paths outside drive_c are reached through Z:
+                let alert = adw::AlertDialog::new(
+                    Some(&crate::t!("drives.remove_z_title")),
+                    Some(&crate::t!("drives.remove_z_body")),
+                );
+                alert.add_response("cancel", &crate::t!("dialogs.cancel"));
+                alert.add_response("remove", &crate::t!("drives.remove"));
+                alert.set_response_appearance("remove", adw::ResponseAppearance::Destructive);
+                alert.set_default_response(Some("cancel"));
+                alert.set_close_response("cancel");
+                let s = sender.clone();
+                alert.choose(
+                    Some(&self.parent_window),
+                    None::<&gtk::gio::Cancellable>,
+                    move |response| {
+                        if response == "remove" {
+                            s.input(DrivesMsg::RemoveConfirmed(letter));
+                        }
+                    },
+                );
+            }
+            DrivesMsg::RemoveConfirmed(letter) => {
+                if let Err(e) = remove_drive(&self.prefix_path, letter) {
+                    sender.input(DrivesMsg::ShowError(e.to_string()));
+                }
+                sender.input(DrivesMsg::Load);
+            }
+            // Loading is deferred until the page is shown
+            DrivesMsg::PrefixPathUpdated(path) => self.prefix_path = path,
+            DrivesMsg::ShowError(error) => {
+                log::error!("[drives] {}", error);
+                let alert = adw::AlertDialog::new(Some(&crate::t!("dialogs.error")), Some(&error));
+                alert.add_response("ok", &crate::t!("dialogs.ok"));
+                alert.set_default_response(Some("ok"));
+                alert.set_close_response("ok");
+                alert.choose(
+                    Some(&self.parent_window),
+                    None::<&gtk::gio::Cancellable>,
+                    |_| {},
+                );
+            }
+        }
+    }
+}
+
+// ── Impl ─────────────────────────────────────────────────────────────────
+
+impl DrivesModel {
+    fn refresh_rows(&mut self, sender: &ComponentSender<Self>) {
+        for row in self.drive_rows.drain(..) {
+            self.drives_group.remove(&row);
+        }
+
+        self.free_letters = free_letters(&self.drives);
+        let labels: Vec<String> = self
+            .free_letters
+            .iter()
+            .map(|l| format!("{}:", l.to_ascii_uppercase()))
+            .collect();
+        let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+        self.letter_dropdown
+            .set_model(Some(&gtk::StringList::new(&labels)));
+
+        for drive in &self.drives {
+            let row = adw::ActionRow::builder()
+                .title(drive.label())
+                .subtitle(drive_subtitle(drive))
+                .subtitle_lines(1)
+                .build();
+            if !drive.target_exists {
+                row.add_css_class("warning");
+            }
+            if !drive.is_system() {
+                let remove_btn = gtk::Button::builder()
+                    .icon_name("user-trash-symbolic")
+                    .tooltip_text(crate::t!("drives.remove"))
+                    .valign(gtk::Align::Center)
+                    .css_classes(["flat"])
+                    .build();
+                let s = sender.clone();
+                let letter = drive.letter;
+                remove_btn.connect_clicked(move |_| {
+                    s.input(DrivesMsg::Remove(letter));
+                });
+                row.add_suffix(&remove_btn);
+            }
+            self.drives_group.add(&row);
+            self.drive_rows.push(row);
+        }
+    }
+}
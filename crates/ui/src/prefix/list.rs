@@ -3,18 +3,61 @@ use log::info;
 use prefix::WinePrefix;
 use relm4::adw::prelude::*;
 use relm4::{ComponentParts, ComponentSender, SimpleComponent, adw, gtk};
+use service::AppService;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 #[derive(Debug)]
 pub struct PrefixListModel {
     prefixes: Vec<WinePrefix>,
     selected_prefix: Option<usize>,
     list_box: gtk::ListBox,
+    /// Widgets of each row that change after it is built, parallel to
+    /// `prefixes`.
+    rows: Vec<RowWidgets>,
+    select_mode: bool,
+    /// Prefixes checked in select mode; keyed by path so checks survive a
+    /// reload that reorders the list.
+    checked: HashSet<PathBuf>,
+    /// Set to stop the disk usage measurement for the current list.
+    size_cancel: Option<Arc<AtomicBool>>,
+}
+
+#[derive(Debug)]
+struct RowWidgets {
+    /// Architecture and app count, then disk usage once it is measured.
+    detail: gtk::Label,
+    /// `detail` without the disk usage.
+    detail_text: String,
+    /// DLL override count badge.
+    badge: gtk::Button,
+    /// Shown in select mode only.
+    check: gtk::CheckButton,
 }
 
 #[derive(Debug)]
 pub enum PrefixListMsg {
     SelectPrefix(usize),
     SetPrefixes(Vec<WinePrefix>),
+    /// Append a prefix as it arrives from a streaming scan. Rows keep arrival
+    /// order until the next `SetPrefixes`.
+    AppendPrefix(WinePrefix),
+    /// Highlight a row without emitting any output (used when the app
+    /// selects a prefix on the user's behalf).
+    SetSelected(Option<usize>),
+    /// The DLL override count of a prefix finished loading.
+    SetOverrideCount(PathBuf, usize),
+    /// The disk usage of a prefix finished measuring, in bytes.
+    SetDiskUsage(PathBuf, u64),
+    ToggleSelectMode(bool),
+    /// A row's checkbox was toggled in select mode.
+    SetChecked(usize, bool),
+    BackupChecked,
+    RefreshChecked,
+    /// Ask once for all checked prefixes, then delete them.
+    DeleteChecked,
 }
 
 #[derive(Debug)]
@@ -25,6 +68,17 @@ pub enum PrefixListOutput {
     ExportPrefix(usize),
     OpenInFileManager(usize),
     OpenInTerminal(usize),
+    OpenWinecfg(usize),
+    /// Capture the prefix's registry settings as a new-prefix template.
+    SaveAsTemplate(usize),
+    /// The DLL override badge was clicked.
+    ShowOverrides(usize),
+    /// The "New Prefix" row at the end of the list was activated.
+    CreatePrefix,
+    /// Batch actions on the prefixes checked in select mode.
+    BackupPrefixes(Vec<PathBuf>),
+    RefreshPrefixes(Vec<PathBuf>),
+    DeletePrefixes(Vec<PathBuf>),
 }
 
 #[relm4::component(pub)]
@@ -35,13 +89,103 @@ impl SimpleComponent for PrefixListModel {
     type Widgets = PrefixListWidgets;
 
     view! {
-        gtk::ScrolledWindow {
-            set_vexpand: true,
-
-            #[name = "prefix_list_box"]
-            gtk::ListBox {
-                set_selection_mode: gtk::SelectionMode::Single,
-            }
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+
+            gtk::ScrolledWindow {
+                set_vexpand: true,
+
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
+
+                    #[name = "prefix_list_box"]
+                    gtk::ListBox {
+                        set_selection_mode: gtk::SelectionMode::Single,
+                    },
+
+                    // Kept out of `prefix_list_box` so row indices stay prefix indices;
+                    // a row is focusable with Tab and activates on Enter/Space
+                    gtk::ListBox {
+                        set_selection_mode: gtk::SelectionMode::None,
+                        connect_row_activated[sender] => move |_, _| {
+                            let _ = sender.output(PrefixListOutput::CreatePrefix);
+                        },
+
+                        gtk::ListBoxRow {
+                            set_tooltip_text: Some(&crate::t!("header.new_prefix")),
+
+                            gtk::Box {
+                                set_orientation: gtk::Orientation::Horizontal,
+                                set_spacing: 8,
+                                set_margin_top: 6,
+                                set_margin_bottom: 6,
+                                set_margin_start: 8,
+                                set_margin_end: 8,
+
+                                gtk::Image {
+                                    set_icon_name: Some("list-add-symbolic"),
+                                },
+                                gtk::Label {
+                                    set_label: &crate::t!("header.new_prefix"),
+                                    set_halign: gtk::Align::Start,
+                                },
+                            },
+                        },
+                    },
+                },
+            },
+
+            gtk::ActionBar {
+                pack_start = &gtk::ToggleButton {
+                    set_icon_name: "selection-mode-symbolic",
+                    set_tooltip_text: Some(&crate::t!("sidebar.select_mode")),
+                    #[watch]
+                    #[block_signal(select_toggle_handler)]
+                    set_active: model.select_mode,
+                    connect_toggled[sender] => move |btn| {
+                        sender.input(PrefixListMsg::ToggleSelectMode(btn.is_active()));
+                    } @select_toggle_handler,
+                },
+
+                pack_start = &gtk::Label {
+                    #[watch]
+                    set_visible: model.select_mode,
+                    #[watch]
+                    set_label: &crate::tf!("sidebar.checked_count", "count" => &model.checked.len().to_string()),
+                    add_css_class: "dim-label",
+                },
+
+                pack_end = &gtk::Button {
+                    set_icon_name: "user-trash-symbolic",
+                    set_tooltip_text: Some(&crate::t!("sidebar.delete_selected")),
+                    add_css_class: "destructive-action",
+                    #[watch]
+                    set_visible: model.select_mode,
+                    #[watch]
+                    set_sensitive: !model.checked.is_empty(),
+                    connect_clicked => PrefixListMsg::DeleteChecked,
+                },
+
+                pack_end = &gtk::Button {
+                    set_icon_name: "document-save-symbolic",
+                    set_tooltip_text: Some(&crate::t!("sidebar.backup_selected")),
+                    #[watch]
+                    set_visible: model.select_mode,
+                    #[watch]
+                    set_sensitive: !model.checked.is_empty(),
+                    connect_clicked => PrefixListMsg::BackupChecked,
+                },
+
+                pack_end = &gtk::Button {
+                    set_icon_name: "view-refresh-symbolic",
+                    set_tooltip_text: Some(&crate::t!("sidebar.refresh_selected")),
+                    #[watch]
+                    set_visible: model.select_mode,
+                    #[watch]
+                    set_sensitive: !model.checked.is_empty(),
+                    connect_clicked => PrefixListMsg::RefreshChecked,
+                },
+            },
         }
     }
 
@@ -52,6 +196,16 @@ impl SimpleComponent for PrefixListModel {
     ) -> ComponentParts<Self> {
         let (prefixes, selected_prefix) = init;
 
+        let mut model = PrefixListModel {
+            prefixes: prefixes.clone(),
+            selected_prefix,
+            list_box: gtk::ListBox::new(),
+            rows: Vec::new(),
+            select_mode: false,
+            checked: HashSet::new(),
+            size_cancel: None,
+        };
+
         let widgets = view_output!();
 
         let sender_clone = sender.clone();
@@ -65,13 +219,9 @@ impl SimpleComponent for PrefixListModel {
                 }
             });
 
-        let model = PrefixListModel {
-            prefixes: prefixes.clone(),
-            selected_prefix,
-            list_box: widgets.prefix_list_box.clone(),
-        };
-
-        populate(&model.prefixes, &model.list_box, &sender);
+        model.list_box = widgets.prefix_list_box.clone();
+        model.rows = populate(&model.prefixes, &model.list_box, &sender);
+        model.request_disk_usage(&sender);
 
         // Auto-select first prefix if there's exactly one
         if model.prefixes.len() == 1 {
@@ -92,14 +242,70 @@ impl SimpleComponent for PrefixListModel {
             PrefixListMsg::SetPrefixes(prefixes) => {
                 log::debug!("[list] set_prefixes received: {} items", prefixes.len());
                 self.prefixes = prefixes.clone();
-                populate(&self.prefixes, &self.list_box, &sender);
+                self.rows = populate(&self.prefixes, &self.list_box, &sender);
+                self.request_disk_usage(&sender);
+                self.checked
+                    .retain(|path| prefixes.iter().any(|p| &p.path == path));
+                self.sync_checks();
 
                 // Auto-select first prefix if there's exactly one
                 if prefixes.len() == 1 {
                     let _ = sender.output(PrefixListOutput::SelectPrefix(0));
                 }
             }
+            PrefixListMsg::AppendPrefix(prefix) => {
+                if self.prefixes.is_empty() {
+                    // Drop the "no prefixes" placeholder
+                    while let Some(row) = self.list_box.first_child() {
+                        self.list_box.remove(&row);
+                    }
+                }
+                let (row, widgets) = build_row(self.prefixes.len(), &prefix, &sender);
+                widgets.check.set_visible(self.select_mode);
+                self.list_box.append(&row);
+                self.rows.push(widgets);
+                self.prefixes.push(prefix);
+            }
+            PrefixListMsg::SetOverrideCount(path, count) => {
+                let Some(i) = self.prefixes.iter().position(|p| p.path == path) else {
+                    return;
+                };
+                if let Some(RowWidgets { badge, .. }) = self.rows.get(i) {
+                    badge.set_label(&count.to_string());
+                    badge.set_tooltip_text(Some(&crate::tf!(
+                        "sidebar.dll_overrides",
+                        "count" => &count.to_string()
+                    )));
+                    badge.set_visible(count > 0);
+                }
+            }
+            PrefixListMsg::SetDiskUsage(path, size) => {
+                let Some(i) = self.prefixes.iter().position(|p| p.path == path) else {
+                    return;
+                };
+                if let Some(row) = self.rows.get(i) {
+                    row.detail.set_label(&format!(
+                        "{} · {}",
+                        row.detail_text,
+                        gtk::glib::format_size(size)
+                    ));
+                }
+            }
+            PrefixListMsg::SetSelected(index) => {
+                self.selected_prefix = index;
+                match index.and_then(|i| self.list_box.row_at_index(i as i32)) {
+                    Some(row) => self.list_box.select_row(Some(&row)),
+                    None => self.list_box.unselect_all(),
+                }
+            }
             PrefixListMsg::SelectPrefix(index) => {
+                if self.select_mode {
+                    // Rows toggle their checkbox instead of opening the prefix
+                    if let Some(RowWidgets { check, .. }) = self.rows.get(index) {
+                        check.set_active(!check.is_active());
+                    }
+                    return;
+                }
                 if self.selected_prefix == Some(index) {
                     self.selected_prefix = None;
                     self.list_box.unselect_all();
@@ -109,15 +315,140 @@ impl SimpleComponent for PrefixListModel {
                     let _ = sender.output(PrefixListOutput::SelectPrefix(index));
                 }
             }
+            PrefixListMsg::ToggleSelectMode(enabled) => {
+                self.select_mode = enabled;
+                self.checked.clear();
+                self.sync_checks();
+                if enabled {
+                    self.list_box.set_selection_mode(gtk::SelectionMode::None);
+                } else {
+                    self.list_box.set_selection_mode(gtk::SelectionMode::Single);
+                    if let Some(row) = self
+                        .selected_prefix
+                        .and_then(|i| self.list_box.row_at_index(i as i32))
+                    {
+                        self.list_box.select_row(Some(&row));
+                    }
+                }
+            }
+            PrefixListMsg::SetChecked(index, active) => {
+                let Some(prefix) = self.prefixes.get(index) else {
+                    return;
+                };
+                if active {
+                    self.checked.insert(prefix.path.clone());
+                } else {
+                    self.checked.remove(&prefix.path);
+                }
+            }
+            PrefixListMsg::BackupChecked => {
+                let paths = self.checked_paths();
+                if !paths.is_empty() {
+                    let _ = sender.output(PrefixListOutput::BackupPrefixes(paths));
+                }
+            }
+            PrefixListMsg::RefreshChecked => {
+                let paths = self.checked_paths();
+                if !paths.is_empty() {
+                    let _ = sender.output(PrefixListOutput::RefreshPrefixes(paths));
+                }
+            }
+            PrefixListMsg::DeleteChecked => {
+                let paths = self.checked_paths();
+                if paths.is_empty() {
+                    return;
+                }
+                let names: Vec<String> = self
+                    .prefixes
+                    .iter()
+                    .filter(|p| self.checked.contains(&p.path))
+                    .map(|p| format!("• {}", p.name))
+                    .collect();
+                let alert = adw::AlertDialog::new(
+                    Some(
+                        &crate::tf!("prefix.delete.batch_title", "count" => &paths.len().to_string()),
+                    ),
+                    Some(&crate::tf!("prefix.delete.batch_confirm", "names" => &names.join("\n"))),
+                );
+                alert.add_response("cancel", &crate::t!("prefix.delete.cancel"));
+                alert.add_response("delete", &crate::t!("prefix.delete.confirm_btn"));
+                alert.set_response_appearance("delete", adw::ResponseAppearance::Destructive);
+                alert.set_default_response(Some("cancel"));
+                alert.set_close_response("cancel");
+                let s = sender.clone();
+                alert.choose(
+                    None::<&gtk::Window>,
+                    None::<&gtk::gio::Cancellable>,
+                    move |response| {
+                        if response == "delete" {
+                            s.input(PrefixListMsg::ToggleSelectMode(false));
+                            let _ = s.output(PrefixListOutput::DeletePrefixes(paths));
+                        }
+                    },
+                );
+            }
         }
     }
 }
 
+impl Drop for PrefixListModel {
+    fn drop(&mut self) {
+        if let Some(cancel) = &self.size_cancel {
+            cancel.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+impl PrefixListModel {
+    /// Measure the listed prefixes' disk usage in the background, stopping
+    /// any measurement still running for an earlier list. Sizes arrive one
+    /// by one as `SetDiskUsage`.
+    fn request_disk_usage(&mut self, sender: &ComponentSender<Self>) {
+        if let Some(cancel) = self.size_cancel.take() {
+            cancel.store(true, Ordering::Relaxed);
+        }
+        if self.prefixes.is_empty() {
+            return;
+        }
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.size_cancel = Some(Arc::clone(&cancel));
+        let paths: Vec<PathBuf> = self.prefixes.iter().map(|p| p.path.clone()).collect();
+        let pm = AppService::global().prefix_manager().clone();
+        let input = sender.input_sender().clone();
+        std::thread::spawn(move || {
+            let result = pm.prefix_disk_usage(&paths, Some(cancel), |path, size| {
+                input.emit(PrefixListMsg::SetDiskUsage(path.to_path_buf(), size))
+            });
+            if let Err(e) = result {
+                log::debug!("[list] disk usage not measured: {}", e);
+            }
+        });
+    }
+
+    /// Checked prefixes in list order.
+    fn checked_paths(&self) -> Vec<PathBuf> {
+        self.prefixes
+            .iter()
+            .filter(|p| self.checked.contains(&p.path))
+            .map(|p| p.path.clone())
+            .collect()
+    }
+
+    /// Show the row checkboxes in select mode and match them to `checked`.
+    fn sync_checks(&self) {
+        for (prefix, row) in self.prefixes.iter().zip(&self.rows) {
+            row.check.set_visible(self.select_mode);
+            row.check.set_active(self.checked.contains(&prefix.path));
+        }
+    }
+}
+
+/// Fill `list_box` with a row per prefix and return the rows' widgets.
 fn populate(
     prefixes: &[WinePrefix],
     list_box: &gtk::ListBox,
     sender: &ComponentSender<PrefixListModel>,
-) {
+) -> Vec<RowWidgets> {
     while let Some(row) = list_box.first_child() {
         list_box.remove(&row);
     }
@@ -138,126 +469,245 @@ fn populate(
                 .child(&label)
                 .build(),
         );
-        return;
+        return Vec::new();
     }
 
+    let mut rows = Vec::with_capacity(prefixes.len());
     for (i, prefix) in prefixes.iter().enumerate() {
-        let name = gtk::Label::builder()
-            .label(&prefix.name)
-            .halign(gtk::Align::Start)
-            .css_classes(["heading"])
-            .build();
+        let (row, widgets) = build_row(i, prefix, sender);
+        list_box.append(&row);
+        rows.push(widgets);
+    }
+    // Unselect all to prevent auto-selecting the first row
+    list_box.unselect_all();
+    rows
+}
 
-        let detail = gtk::Label::builder()
-            .label(&format!(
-                "{} · {} apps",
-                prefix.config.architecture,
-                prefix.config.registered_executables.len()
-            ))
-            .halign(gtk::Align::Start)
-            .css_classes(["caption", "dim-label"])
-            .build();
+/// Count the prefix's DLL overrides in the background; the result arrives
+/// as `SetOverrideCount`.
+fn request_override_count(prefix_path: PathBuf, sender: &ComponentSender<PrefixListModel>) {
+    let s = sender.clone();
+    relm4::spawn(async move {
+        match service::registry_ops::dll_override_count(&prefix_path).await {
+            Ok(count) => s.input(PrefixListMsg::SetOverrideCount(prefix_path, count)),
+            Err(e) => log::debug!(
+                "[list] no DLL override count for {}: {}",
+                prefix_path.display(),
+                e
+            ),
+        }
+    });
+}
 
-        let box_ = gtk::Box::builder()
-            .orientation(gtk::Orientation::Vertical)
-            .spacing(1)
-            .hexpand(true)
-            .margin_top(3)
-            .margin_bottom(3)
-            .margin_start(8)
-            .margin_end(8)
-            .build();
-        box_.append(&name);
-        box_.append(&detail);
+fn build_row(
+    i: usize,
+    prefix: &WinePrefix,
+    sender: &ComponentSender<PrefixListModel>,
+) -> (gtk::ListBoxRow, RowWidgets) {
+    let name = gtk::Label::builder()
+        .label(&prefix.name)
+        .halign(gtk::Align::Start)
+        .css_classes(["heading"])
+        .build();
+
+    let external = prefix.external_path();
+    let mut detail_text = format!(
+        "{} · {} apps",
+        prefix.config.architecture,
+        prefix.config.registered_executables.len()
+    );
+    match external.as_deref().map(::prefix::proton_app_id) {
+        Some(Some(_)) => detail_text.push_str(&format!(" · {}", crate::t!("sidebar.proton"))),
+        Some(None) => detail_text.push_str(&format!(" · {}", crate::t!("sidebar.external"))),
+        None => {}
+    }
+    let detail = gtk::Label::builder()
+        .label(&detail_text)
+        .halign(gtk::Align::Start)
+        .css_classes(["caption", "dim-label"])
+        .build();
+
+    let box_ = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(1)
+        .hexpand(true)
+        .margin_top(3)
+        .margin_bottom(3)
+        .margin_start(8)
+        .margin_end(8)
+        .build();
+    box_.append(&name);
+    box_.append(&detail);
+    if let Some(target) = &external {
+        box_.set_tooltip_text(Some(
+            &crate::tf!("sidebar.external_tooltip", "path" => &target.display().to_string()),
+        ));
+    }
 
-        let row = gtk::ListBoxRow::builder()
-            .selectable(true)
-            .activatable(true)
-            .child(&box_)
-            .build();
+    // Filled in once the count has been read from the registry
+    let badge = gtk::Button::builder()
+        .valign(gtk::Align::Center)
+        .visible(false)
+        .css_classes(["pill", "caption", "flat"])
+        .build();
+    let s = sender.clone();
+    badge.connect_clicked(move |_| {
+        let _ = s.output(PrefixListOutput::ShowOverrides(i));
+    });
+    request_override_count(prefix.path.clone(), sender);
+
+    let check = gtk::CheckButton::builder()
+        .valign(gtk::Align::Center)
+        .margin_start(8)
+        .visible(false)
+        .build();
+    let s = sender.clone();
+    check.connect_toggled(move |check| {
+        s.input(PrefixListMsg::SetChecked(i, check.is_active()));
+    });
+
+    let row_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .margin_end(8)
+        .build();
+    row_box.append(&check);
+    row_box.append(&box_);
+    row_box.append(&badge);
+
+    let row = gtk::ListBoxRow::builder()
+        .selectable(true)
+        .activatable(true)
+        .child(&row_box)
+        .build();
+
+    // Left-click → select
+    let s = sender.clone();
+    row.connect_activate(move |_| s.input(PrefixListMsg::SelectPrefix(i)));
+
+    // Right-click → context menu
+    let s = sender.clone();
+    let prefix_name = prefix.name.clone();
+    let is_external = external.is_some();
+    let row_ref = row.clone();
+    let gesture = gtk::GestureClick::new();
+    gesture.set_button(3); // right button
+    gesture.connect_pressed(move |_gesture, _n_press, x, y| {
+        let prefix_idx = i;
+
+        let export_action = gio::SimpleAction::new("export", None);
+        let open_fm_action = gio::SimpleAction::new("open-fm", None);
+        let open_term_action = gio::SimpleAction::new("open-term", None);
+        let winecfg_action = gio::SimpleAction::new("winecfg", None);
+        let template_action = gio::SimpleAction::new("save-template", None);
+        let delete_action = gio::SimpleAction::new("delete", None);
+        let actions = gio::SimpleActionGroup::new();
+        actions.add_action(&open_fm_action);
+        actions.add_action(&open_term_action);
+        actions.add_action(&winecfg_action);
+        actions.add_action(&export_action);
+        actions.add_action(&template_action);
+        actions.add_action(&delete_action);
+        row_ref.insert_action_group("pref", Some(&actions));
+
+        let menu = gio::Menu::new();
+        menu.append(
+            Some(&crate::t!("prefix.context.open_fm")),
+            Some("pref.open-fm"),
+        );
+        menu.append(
+            Some(&crate::t!("prefix.context.open_term")),
+            Some("pref.open-term"),
+        );
+        menu.append(
+            Some(&crate::t!("prefix.context.winecfg")),
+            Some("pref.winecfg"),
+        );
+        menu.append(
+            Some(&crate::t!("prefix.context.export")),
+            Some("pref.export"),
+        );
+        menu.append(
+            Some(&crate::t!("prefix.context.save_template")),
+            Some("pref.save-template"),
+        );
+        menu.append(
+            Some(&crate::t!("prefix.context.delete")),
+            Some("pref.delete"),
+        );
 
-        // Left-click → select
-        let s = sender.clone();
-        row.connect_activate(move |_| s.input(PrefixListMsg::SelectPrefix(i)));
-
-        // Right-click → context menu
-        let s = sender.clone();
-        let prefix_name = prefix.name.clone();
-        let row_ref = row.clone();
-        let gesture = gtk::GestureClick::new();
-        gesture.set_button(3); // right button
-        gesture.connect_pressed(move |_gesture, _n_press, x, y| {
-            let prefix_idx = i;
-
-            let export_action = gio::SimpleAction::new("export", None);
-            let open_fm_action = gio::SimpleAction::new("open-fm", None);
-            let open_term_action = gio::SimpleAction::new("open-term", None);
-            let delete_action = gio::SimpleAction::new("delete", None);
-            let actions = gio::SimpleActionGroup::new();
-            actions.add_action(&open_fm_action);
-            actions.add_action(&open_term_action);
-            actions.add_action(&export_action);
-            actions.add_action(&delete_action);
-            row_ref.insert_action_group("pref", Some(&actions));
-
-            let menu = gio::Menu::new();
-            menu.append(Some(&crate::t!("prefix.context.open_fm")), Some("pref.open-fm"));
-            menu.append(Some(&crate::t!("prefix.context.open_term")), Some("pref.open-term"));
-            menu.append(Some(&crate::t!("prefix.context.export")), Some("pref.export"));
-            menu.append(Some(&crate::t!("prefix.context.delete")), Some("pref.delete"));
-
-            let popover = gtk::PopoverMenu::from_model(Some(&menu));
-            popover.set_has_arrow(false);
-            popover.set_halign(gtk::Align::Start);
-            popover.set_parent(&row_ref);
-            let rect = gtk::gdk::Rectangle::new(x as i32, y as i32, 1, 1);
-            popover.set_pointing_to(Some(&rect));
-
-            let popover_clone = popover.clone();
-            let s_export = s.clone();
-            export_action.connect_activate(move |_, _| {
-                popover_clone.popdown();
-                let _ = s_export.output(PrefixListOutput::ExportPrefix(prefix_idx));
-            });
+        let popover = gtk::PopoverMenu::from_model(Some(&menu));
+        popover.set_has_arrow(false);
+        popover.set_halign(gtk::Align::Start);
+        popover.set_parent(&row_ref);
+        let rect = gtk::gdk::Rectangle::new(x as i32, y as i32, 1, 1);
+        popover.set_pointing_to(Some(&rect));
+
+        let popover_clone = popover.clone();
+        let s_export = s.clone();
+        export_action.connect_activate(move |_, _| {
+            popover_clone.popdown();
+            let _ = s_export.output(PrefixListOutput::ExportPrefix(prefix_idx));
+        });
 
-            let s_fm = s.clone();
-            open_fm_action.connect_activate(move |_, _| {
-                let _ = s_fm.output(PrefixListOutput::OpenInFileManager(prefix_idx));
-            });
+        let s_fm = s.clone();
+        open_fm_action.connect_activate(move |_, _| {
+            let _ = s_fm.output(PrefixListOutput::OpenInFileManager(prefix_idx));
+        });
 
-            let s_term = s.clone();
-            open_term_action.connect_activate(move |_, _| {
-                let _ = s_term.output(PrefixListOutput::OpenInTerminal(prefix_idx));
-            });
+        let s_term = s.clone();
+        open_term_action.connect_activate(move |_, _| {
+            let _ = s_term.output(PrefixListOutput::OpenInTerminal(prefix_idx));
+        });
 
-            let popover_clone2 = popover.clone();
-            let s_del = s.clone();
-            let name = prefix_name.clone();
-            delete_action.connect_activate(move |_, _| {
-                popover_clone2.popdown();
+        let s_cfg = s.clone();
+        winecfg_action.connect_activate(move |_, _| {
+            let _ = s_cfg.output(PrefixListOutput::OpenWinecfg(prefix_idx));
+        });
 
-                let alert = adw::AlertDialog::new(
-                    Some(&crate::t!("prefix.delete.title")),
-                    Some(&crate::tf!("prefix.delete.confirm", "name" => &name)),
-                );
-                alert.add_response("cancel", &crate::t!("prefix.delete.cancel"));
-                alert.add_response("delete", &crate::t!("prefix.delete.confirm_btn"));
-                alert.set_response_appearance("delete", adw::ResponseAppearance::Destructive);
-                alert.set_default_response(Some("cancel"));
-                alert.set_close_response("cancel");
-                let s = s_del.clone();
-                alert.choose(None::<&gtk::Window>, None::<&gtk::gio::Cancellable>, move |response| {
+        let s_template = s.clone();
+        template_action.connect_activate(move |_, _| {
+            let _ = s_template.output(PrefixListOutput::SaveAsTemplate(prefix_idx));
+        });
+
+        let popover_clone2 = popover.clone();
+        let s_del = s.clone();
+        let name = prefix_name.clone();
+        delete_action.connect_activate(move |_, _| {
+            popover_clone2.popdown();
+
+            let body = if is_external {
+                crate::tf!("prefix.delete.confirm_external", "name" => &name)
+            } else {
+                crate::tf!("prefix.delete.confirm", "name" => &name)
+            };
+            let alert = adw::AlertDialog::new(Some(&crate::t!("prefix.delete.title")), Some(&body));
+            alert.add_response("cancel", &crate::t!("prefix.delete.cancel"));
+            alert.add_response("delete", &crate::t!("prefix.delete.confirm_btn"));
+            alert.set_response_appearance("delete", adw::ResponseAppearance::Destructive);
+            alert.set_default_response(Some("cancel"));
+            alert.set_close_response("cancel");
+            let s = s_del.clone();
+            alert.choose(
+                None::<&gtk::Window>,
+                None::<&gtk::gio::Cancellable>,
+                move |response| {
                     if response == "delete" {
                         let _ = s.output(PrefixListOutput::DeletePrefix(prefix_idx));
                     }
-                });
-            });
-
-            popover.popup();
+                },
+            );
         });
-        row.add_controller(gesture);
-        list_box.append(&row);
-    }
-    // Unselect all to prevent auto-selecting the first row
-    list_box.unselect_all();
+
+        popover.popup();
+    });
+    row.add_controller(gesture);
+    (
+        row,
+        RowWidgets {
+            detail,
+            detail_text,
+            badge,
+            check,
+        },
+    )
 }
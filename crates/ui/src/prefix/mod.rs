@@ -1,5 +1,7 @@
+pub mod associations;
 pub mod config;
 pub mod create_dialog;
+pub mod drives;
 pub mod export_dialog;
 pub mod import_dialog;
 pub mod list;
@@ -0,0 +1,257 @@
+use adw::prelude::*;
+use prefix::registry::{DiffKind, RegistryDiffEntry, WineRegistry, diff::display_value};
+use relm4::{ComponentParts, ComponentSender, RelmWidgetExt, SimpleComponent, adw, gtk};
+use std::path::{Path, PathBuf};
+
+/// Keys rendered at most; a full diff of two unrelated prefixes can run to
+/// thousands of keys.
+const MAX_KEYS_SHOWN: usize = 500;
+
+// ── Model ────────────────────────────────────────────────────────────────
+
+#[derive(Debug)]
+pub struct RegistryDiffModel {
+    prefixes: Vec<(String, PathBuf)>,
+    comparing: bool,
+    status: String,
+    old_dropdown: gtk::DropDown,
+    new_dropdown: gtk::DropDown,
+    results: gtk::ListBox,
+}
+
+#[derive(Debug)]
+pub enum RegistryDiffMsg {
+    Compare,
+    Compared(Result<Vec<RegistryDiffEntry>, String>),
+}
+
+/// Diff `system.reg` and `user.reg` of two prefixes. Keys in the result are
+/// qualified with their hive.
+async fn compare_prefixes(old: &Path, new: &Path) -> base::error::Result<Vec<RegistryDiffEntry>> {
+    let mut entries = Vec::new();
+    for file in ["system.reg", "user.reg"] {
+        let old_reg = WineRegistry::load_from_file(&old.join(file)).await?;
+        let new_reg = WineRegistry::load_from_file(&new.join(file)).await?;
+        let hive = old_reg.hive_root();
+        for mut entry in old_reg.diff(&new_reg).await? {
+            entry.key = format!("{}\\{}", hive, entry.key);
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+fn kind_label(kind: DiffKind) -> (String, &'static str) {
+    match kind {
+        DiffKind::Added => (crate::t!("registry.diff.added"), "success"),
+        DiffKind::Removed => (crate::t!("registry.diff.removed"), "error"),
+        DiffKind::Changed => (crate::t!("registry.diff.changed"), "warning"),
+    }
+}
+
+fn kind_badge(kind: DiffKind) -> gtk::Label {
+    let (label, class) = kind_label(kind);
+    gtk::Label::builder()
+        .label(label)
+        .valign(gtk::Align::Center)
+        .css_classes(["caption", class])
+        .build()
+}
+
+fn value_subtitle(entry: &RegistryDiffEntry) -> String {
+    let show = |v: &Option<prefix::registry::Value>| v.as_ref().map(display_value);
+    match (show(&entry.old), show(&entry.new)) {
+        (Some(old), Some(new)) => format!("{} → {}", old, new),
+        (Some(value), None) | (None, Some(value)) => value,
+        (None, None) => String::new(),
+    }
+}
+
+// ── Component ────────────────────────────────────────────────────────────
+
+#[relm4::component(pub)]
+impl SimpleComponent for RegistryDiffModel {
+    /// Parent window, known prefixes, and the index to preselect as "old".
+    type Init = (gtk::Window, Vec<(String, PathBuf)>, usize);
+    type Input = RegistryDiffMsg;
+    type Output = ();
+
+    view! {
+        #[name = "dialog"]
+        gtk::Window {
+            set_title: Some(&crate::t!("registry.diff.title")),
+            set_default_width: 640,
+            set_default_height: 560,
+            set_transient_for: Some(&parent),
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_margin_all: 12,
+                set_spacing: 12,
+
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Horizontal,
+                    set_spacing: 8,
+
+                    #[name = "old_dropdown"]
+                    gtk::DropDown {
+                        set_hexpand: true,
+                        set_model: Some(&names),
+                        set_selected: initial as u32,
+                    },
+
+                    gtk::Image {
+                        set_icon_name: Some("go-next-symbolic"),
+                    },
+
+                    #[name = "new_dropdown"]
+                    gtk::DropDown {
+                        set_hexpand: true,
+                        set_model: Some(&names),
+                        set_selected: if initial == 0 { 1 } else { 0 },
+                    },
+
+                    gtk::Button {
+                        set_label: &crate::t!("registry.diff.compare"),
+                        add_css_class: "suggested-action",
+                        #[watch]
+                        set_sensitive: !model.comparing,
+                        connect_clicked => RegistryDiffMsg::Compare,
+                    },
+                },
+
+                gtk::Label {
+                    #[watch]
+                    set_label: &model.status,
+                    set_halign: gtk::Align::Start,
+                    set_wrap: true,
+                    add_css_class: "dim-label",
+                },
+
+                gtk::ScrolledWindow {
+                    set_vexpand: true,
+                    set_hscrollbar_policy: gtk::PolicyType::Never,
+
+                    #[name = "results"]
+                    gtk::ListBox {
+                        set_selection_mode: gtk::SelectionMode::None,
+                        set_valign: gtk::Align::Start,
+                        add_css_class: "boxed-list",
+                    },
+                },
+            },
+        }
+    }
+
+    fn init(
+        (parent, prefixes, initial): Self::Init,
+        _root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let labels: Vec<&str> = prefixes.iter().map(|(name, _)| name.as_str()).collect();
+        let names = gtk::StringList::new(&labels);
+
+        let mut model = RegistryDiffModel {
+            prefixes,
+            comparing: false,
+            status: crate::t!("registry.diff.hint"),
+            old_dropdown: gtk::DropDown::default(),
+            new_dropdown: gtk::DropDown::default(),
+            results: gtk::ListBox::new(),
+        };
+        let widgets = view_output!();
+        model.old_dropdown = widgets.old_dropdown.clone();
+        model.new_dropdown = widgets.new_dropdown.clone();
+        model.results = widgets.results.clone();
+
+        widgets.dialog.present();
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>) {
+        match msg {
+            RegistryDiffMsg::Compare => {
+                let pick = |dropdown: &gtk::DropDown| {
+                    self.prefixes
+                        .get(dropdown.selected() as usize)
+                        .map(|(_, path)| path.clone())
+                };
+                let (Some(old), Some(new)) = (pick(&self.old_dropdown), pick(&self.new_dropdown))
+                else {
+                    return;
+                };
+                if old == new {
+                    self.status = crate::t!("registry.diff.same_prefix");
+                    return;
+                }
+                self.comparing = true;
+                self.status = crate::t!("registry.diff.comparing");
+                let s = sender.clone();
+                tokio::spawn(async move {
+                    let result = compare_prefixes(&old, &new)
+                        .await
+                        .map_err(|e| e.to_string());
+                    s.input(RegistryDiffMsg::Compared(result));
+                });
+            }
+            RegistryDiffMsg::Compared(result) => {
+                self.comparing = false;
+                match result {
+                    Ok(entries) => self.show_entries(&entries),
+                    Err(e) => {
+                        log::error!("[registry] diff failed: {}", e);
+                        self.status = crate::tf!("registry.diff.failed", "error" => &e);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// ── Impl ─────────────────────────────────────────────────────────────────
+
+impl RegistryDiffModel {
+    /// Render entries as one expandable row per key with its values inside.
+    fn show_entries(&mut self, entries: &[RegistryDiffEntry]) {
+        while let Some(row) = self.results.first_child() {
+            self.results.remove(&row);
+        }
+
+        let groups: Vec<&[RegistryDiffEntry]> = entries.chunk_by(|a, b| a.key == b.key).collect();
+        self.status = match groups.len() {
+            0 => crate::t!("registry.diff.identical"),
+            n if n > MAX_KEYS_SHOWN => crate::tf!(
+                "registry.diff.truncated",
+                "shown" => &MAX_KEYS_SHOWN.to_string(),
+                "count" => &n.to_string()
+            ),
+            n => crate::tf!("registry.diff.count", "count" => &n.to_string()),
+        };
+
+        for group in groups.into_iter().take(MAX_KEYS_SHOWN) {
+            // Whole-key entries sort first; otherwise only values changed
+            let key_kind = group
+                .first()
+                .filter(|e| e.value.is_none())
+                .map_or(DiffKind::Changed, |e| e.kind);
+            let row = adw::ExpanderRow::builder()
+                .title(&group[0].key)
+                .use_markup(false)
+                .build();
+            row.add_suffix(&kind_badge(key_kind));
+
+            for entry in group.iter().filter(|e| e.value.is_some()) {
+                let value_row = adw::ActionRow::builder()
+                    .title(entry.value.as_deref().unwrap_or_default())
+                    .subtitle(value_subtitle(entry))
+                    .subtitle_selectable(true)
+                    .use_markup(false)
+                    .build();
+                value_row.add_suffix(&kind_badge(entry.kind));
+                row.add_row(&value_row);
+            }
+            self.results.append(&row);
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use adw::prelude::*;
+use prefix::registry::keys::AudioDriver;
 use relm4::{ComponentParts, ComponentSender, RelmWidgetExt, SimpleComponent, adw, gtk};
 use tracker;
 
@@ -68,11 +69,7 @@ impl SimpleComponent for GeneralTabModel {
                     add_suffix = &gtk::DropDown {
                         set_hexpand: true,
                         set_valign: gtk::Align::Center,
-                        set_model: Some(&gtk::StringList::new(&[
-                            "Default", "Windows 10", "Windows 8.1", "Windows 8",
-                            "Windows 7", "Windows Vista", "Windows XP", "Windows 2000",
-                            "Windows ME", "Windows 98", "Windows 95",
-                        ])),
+                        set_model: Some(&gtk::StringList::new(&WINDOWS_VERSION_LABELS)),
                         #[track = "model.changed(GeneralTabModel::windows_version())"]
                         set_selected: win_code_to_index(model.windows_version.as_deref().unwrap_or("")).unwrap_or(0),
                         #[track = "model.changed(GeneralTabModel::editing())"]
@@ -94,11 +91,9 @@ impl SimpleComponent for GeneralTabModel {
                     add_suffix = &gtk::DropDown {
                         set_hexpand: true,
                         set_valign: gtk::Align::Center,
-                        set_model: Some(&gtk::StringList::new(&[
-                            "Default", "PulseAudio", "ALSA", "OSS", "CoreAudio",
-                        ])),
+                        set_model: Some(&audio_driver_list()),
                         #[track = "model.changed(GeneralTabModel::audio_driver())"]
-                        set_selected: aud_code_to_index(model.audio_driver.as_deref().unwrap_or("")).unwrap_or(0),
+                        set_selected: aud_code_to_index(model.audio_driver.as_deref()),
                         #[track = "model.changed(GeneralTabModel::editing())"]
                         set_sensitive: model.editing,
                         connect_selected_notify[sender] => move |dd| {
@@ -243,7 +238,8 @@ impl SimpleComponent for GeneralTabModel {
                     ));
                 }
                 "audio_driver" => {
-                    self.set_audio_driver(Some(value.clone()));
+                    // "default" removes the value so Wine picks a driver itself
+                    self.set_audio_driver((value != AUDIO_DEFAULT).then(|| value.clone()));
                     let _ = sender.output(GeneralTabOutput::SettingChanged(
                         "Software\\Wine\\Drivers\\Audio".into(),
                         value,
@@ -295,7 +291,22 @@ impl SimpleComponent for GeneralTabModel {
     }
 }
 
-fn win_code_to_index(code: &str) -> Option<u32> {
+/// Dropdown labels, indexed the same as `win_code_to_index`.
+pub(crate) const WINDOWS_VERSION_LABELS: [&str; 11] = [
+    "Default",
+    "Windows 10",
+    "Windows 8.1",
+    "Windows 8",
+    "Windows 7",
+    "Windows Vista",
+    "Windows XP",
+    "Windows 2000",
+    "Windows ME",
+    "Windows 98",
+    "Windows 95",
+];
+
+pub(crate) fn win_code_to_index(code: &str) -> Option<u32> {
     Some(match code {
         "" | "none" => 0,
         "win10" => 1,
@@ -312,7 +323,7 @@ fn win_code_to_index(code: &str) -> Option<u32> {
     })
 }
 
-fn win_index_to_code(idx: u32) -> &'static str {
+pub(crate) fn win_index_to_code(idx: u32) -> &'static str {
     match idx {
         0 => "",
         1 => "win10",
@@ -329,24 +340,51 @@ fn win_index_to_code(idx: u32) -> &'static str {
     }
 }
 
-fn aud_code_to_index(code: &str) -> Option<u32> {
-    Some(match code {
-        "" => 0,
-        "pulse" => 1,
-        "alsa" => 2,
-        "oss" => 3,
-        "coreaudio" => 4,
-        _ => return None,
-    })
+/// Dropdown value meaning "no driver configured"; distinct from `""`, which
+/// disables audio.
+pub const AUDIO_DEFAULT: &str = "default";
+
+/// Driver codes in dropdown order, after the leading "Default" entry.
+const AUDIO_DRIVERS: &[(AudioDriver, &str)] = &[
+    (AudioDriver::Pulse, "PulseAudio"),
+    (AudioDriver::ALSA, "ALSA"),
+    (AudioDriver::OSS, "OSS"),
+    (AudioDriver::CoreAudio, "CoreAudio"),
+];
+
+/// Dropdown entries, with the driver matching the host's sound server
+/// marked as recommended.
+fn audio_driver_list() -> gtk::StringList {
+    let detected = AudioDriver::detect_host();
+    let list = gtk::StringList::new(&[crate::t!("registry.general.audio_default").as_str()]);
+    for &(ref driver, label) in AUDIO_DRIVERS {
+        if detected.as_ref() == Some(driver) {
+            list.append(&crate::tf!("registry.general.audio_recommended", "name" => label));
+        } else {
+            list.append(label);
+        }
+    }
+    list.append(&crate::t!("registry.general.audio_disabled"));
+    list
+}
+
+fn aud_code_to_index(code: Option<&str>) -> u32 {
+    let Some(code) = code else {
+        return 0;
+    };
+    if code.is_empty() {
+        return AUDIO_DRIVERS.len() as u32 + 1;
+    }
+    AUDIO_DRIVERS
+        .iter()
+        .position(|(driver, _)| driver.to_string() == code)
+        .map_or(0, |i| i as u32 + 1)
 }
 
 fn aud_index_to_code(idx: u32) -> &'static str {
-    match idx {
-        0 => "",
-        1 => "pulse",
-        2 => "alsa",
-        3 => "oss",
-        4 => "coreaudio",
+    match idx as usize {
+        0 => AUDIO_DEFAULT,
+        i if i <= AUDIO_DRIVERS.len() => AUDIO_DRIVERS[i - 1].0.to_string(),
         _ => "",
     }
 }
@@ -1,4 +1,5 @@
 use adw::prelude::*;
+use prefix::registry::keys::VIDEO_MEMORY_SIZE_RANGE;
 use relm4::{ComponentParts, ComponentSender, RelmWidgetExt, SimpleComponent, adw, gtk};
 use tracker;
 
@@ -18,6 +19,8 @@ pub struct GraphicsTabModel {
     csmt: Option<u32>,
     offscreen_mode: Option<String>,
     video_memory: Option<u32>,
+    /// Why the video memory entry's current text can't be applied.
+    video_memory_error: Option<String>,
 }
 
 #[derive(Debug)]
@@ -30,6 +33,8 @@ pub enum GraphicsTabInput {
 #[derive(Debug)]
 pub enum GraphicsTabOutput {
     SettingChanged(String, String),
+    /// Whether every field holds an applicable value.
+    ValidityChanged(bool),
 }
 
 #[relm4::component(pub)]
@@ -121,11 +126,15 @@ impl SimpleComponent for GraphicsTabModel {
                 // ── Video Memory ──
                 adw::ActionRow {
                     set_title: &crate::t!("registry.graphics.video_memory"),
-                    set_subtitle: &crate::t!("registry.graphics.video_memory_sub"),
+                    #[track = "model.changed(GraphicsTabModel::video_memory_error())"]
+                    set_subtitle: model.video_memory_error.as_deref()
+                        .unwrap_or(&crate::t!("registry.graphics.video_memory_sub")),
 
                     add_suffix = &gtk::Entry {
                         set_width_chars: 8,
                         set_valign: gtk::Align::Center,
+                        #[track = "model.changed(GraphicsTabModel::video_memory_error())"]
+                        set_css_classes: if model.video_memory_error.is_some() { &["error"] } else { &[] },
                         #[track = "model.changed(GraphicsTabModel::video_memory())"]
                         set_text: &model.video_memory.map(|s| s.to_string()).unwrap_or_default(),
                         set_editable: model.editing,
@@ -154,6 +163,7 @@ impl SimpleComponent for GraphicsTabModel {
             csmt: init.csmt,
             offscreen_mode: init.offscreen_mode,
             video_memory: init.video_memory,
+            video_memory_error: None,
             tracker: 0,
         };
         let widgets = view_output!();
@@ -171,6 +181,7 @@ impl SimpleComponent for GraphicsTabModel {
                 self.set_csmt(s.csmt);
                 self.set_offscreen_mode(s.offscreen_mode);
                 self.set_video_memory(s.video_memory);
+                self.set_video_memory_error_checked(None, &sender);
             }
             GraphicsTabInput::UpdateField(field, value) => match field.as_str() {
                 "renderer" => {
@@ -196,13 +207,29 @@ impl SimpleComponent for GraphicsTabModel {
                     ));
                 }
                 "video_memory" => {
-                    if let Ok(v) = value.parse::<u32>() {
-                        self.set_video_memory(Some(v));
-                        let _ = sender.output(GraphicsTabOutput::SettingChanged(
-                            "Software\\Wine\\Direct3D".into(),
-                            format!("VideoMemorySize={}", v),
-                        ));
-                    }
+                    let value = value.trim();
+                    // Empty means "leave unset"; nothing to apply or report.
+                    let error = if value.is_empty() {
+                        None
+                    } else {
+                        match value.parse::<u32>() {
+                            Ok(v) if VIDEO_MEMORY_SIZE_RANGE.contains(&v) => {
+                                self.set_video_memory(Some(v));
+                                let _ = sender.output(GraphicsTabOutput::SettingChanged(
+                                    "Software\\Wine\\Direct3D".into(),
+                                    format!("VideoMemorySize={}", v),
+                                ));
+                                None
+                            }
+                            Ok(_) => Some(crate::tf!(
+                                "registry.graphics.video_memory_range",
+                                "min" => &VIDEO_MEMORY_SIZE_RANGE.start().to_string(),
+                                "max" => &VIDEO_MEMORY_SIZE_RANGE.end().to_string()
+                            )),
+                            Err(_) => Some(crate::t!("registry.graphics.video_memory_nan")),
+                        }
+                    };
+                    self.set_video_memory_error_checked(error, &sender);
                 }
                 _ => {}
             },
@@ -210,6 +237,23 @@ impl SimpleComponent for GraphicsTabModel {
     }
 }
 
+impl GraphicsTabModel {
+    /// Update the video memory error, telling the parent when overall
+    /// validity flips so it can gate Save.
+    fn set_video_memory_error_checked(
+        &mut self,
+        error: Option<String>,
+        sender: &ComponentSender<Self>,
+    ) {
+        if error.is_some() != self.video_memory_error.is_some() {
+            let _ = sender.output(GraphicsTabOutput::ValidityChanged(error.is_none()));
+        }
+        if error != self.video_memory_error {
+            self.set_video_memory_error(error);
+        }
+    }
+}
+
 fn rdr_code_to_index(code: &str) -> Option<u32> {
     Some(match code {
         "" => 0,
@@ -0,0 +1,362 @@
+use adw::prelude::*;
+use prefix::registry::keys::{JOYSTICK_DEAD_ZONE_RANGE, JoystickState};
+use relm4::{ComponentParts, ComponentSender, RelmWidgetExt, SimpleComponent, adw, gtk};
+use tracker;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JoystickEntry {
+    /// Device name as DirectInput reports it (see `joy.cpl`).
+    pub name: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct InputSettings {
+    pub mouse_warp_override: Option<String>,
+    pub default_dead_zone: Option<u32>,
+    pub joysticks: Vec<JoystickEntry>,
+}
+
+#[derive(Debug)]
+#[tracker::track]
+pub struct InputTabModel {
+    editing: bool,
+    mouse_warp_override: Option<String>,
+    default_dead_zone: Option<u32>,
+    #[tracker::do_not_track]
+    draft_name: String,
+    #[tracker::do_not_track]
+    joysticks: Vec<JoystickEntry>,
+    #[tracker::do_not_track]
+    joystick_group: adw::PreferencesGroup,
+    #[tracker::do_not_track]
+    rows: Vec<gtk::ListBoxRow>,
+}
+
+#[derive(Debug)]
+pub enum InputTabInput {
+    SetEditing(bool),
+    LoadSettings(InputSettings),
+    UpdateField(String, String),
+    UpdateDraftName(String),
+    AddJoystick,
+    SetJoystickEnabled(usize, bool),
+    RemoveJoystick(usize),
+}
+
+#[derive(Debug)]
+pub enum InputTabOutput {
+    SettingChanged(String, String),
+}
+
+#[relm4::component(pub)]
+impl SimpleComponent for InputTabModel {
+    type Init = InputSettings;
+    type Input = InputTabInput;
+    type Output = InputTabOutput;
+
+    view! {
+        #[root]
+        gtk::ScrolledWindow {
+            set_vexpand: true,
+            set_hexpand: true,
+            set_hscrollbar_policy: gtk::PolicyType::Never,
+            set_vscrollbar_policy: gtk::PolicyType::Automatic,
+
+            gtk::Box {
+                set_orientation: gtk::Orientation::Vertical,
+                set_margin_all: 18,
+                set_spacing: 18,
+                set_vexpand: true,
+                set_hexpand: true,
+
+                adw::PreferencesGroup {
+                    set_title: &crate::t!("registry.input.title"),
+                    set_description: Some(&crate::t!("registry.input.desc")),
+
+                    // ── Mouse Warp Override ──
+                    adw::ActionRow {
+                        set_title: &crate::t!("registry.input.mouse_warp"),
+                        set_subtitle: &crate::t!("registry.input.mouse_warp_sub"),
+
+                        add_suffix = &gtk::DropDown {
+                            set_valign: gtk::Align::Center,
+                            set_model: Some(&gtk::StringList::new(&["Default", "Enable", "Disable", "Force"])),
+                            #[track = "model.changed(InputTabModel::mouse_warp_override())"]
+                            set_selected: warp_code_to_index(model.mouse_warp_override.as_deref().unwrap_or("")).unwrap_or(0),
+                            #[track = "model.changed(InputTabModel::editing())"]
+                            set_sensitive: model.editing,
+                            connect_selected_notify[sender] => move |dd| {
+                                sender.input(InputTabInput::UpdateField(
+                                    "mouse_warp_override".into(),
+                                    warp_index_to_code(dd.selected()).to_string(),
+                                ));
+                            },
+                        },
+                    },
+
+                    // ── Default Dead Zone ──
+                    adw::ActionRow {
+                        set_title: &crate::t!("registry.input.dead_zone"),
+                        set_subtitle: &crate::t!("registry.input.dead_zone_sub"),
+
+                        add_suffix = &gtk::SpinButton {
+                            set_valign: gtk::Align::Center,
+                            set_width_chars: 6,
+                            set_adjustment: &gtk::Adjustment::builder()
+                                .lower(*JOYSTICK_DEAD_ZONE_RANGE.start() as f64)
+                                .upper(*JOYSTICK_DEAD_ZONE_RANGE.end() as f64)
+                                .step_increment(100.0).page_increment(1000.0)
+                                .value(model.default_dead_zone.unwrap_or(0) as f64)
+                                .build(),
+                            #[track = "model.changed(InputTabModel::default_dead_zone())"]
+                            set_value: model.default_dead_zone.unwrap_or(0) as f64,
+                            #[track = "model.changed(InputTabModel::editing())"]
+                            set_sensitive: model.editing,
+                            connect_changed[sender] => move |spin| {
+                                sender.input(InputTabInput::UpdateField(
+                                    "default_dead_zone".into(),
+                                    spin.text().to_string(),
+                                ));
+                            },
+                        },
+                    },
+                },
+
+                #[name = "joystick_group"]
+                adw::PreferencesGroup {
+                    set_title: &crate::t!("registry.input.joysticks"),
+                    set_description: Some(&crate::t!("registry.input.joysticks_sub")),
+                }
+            }
+        }
+    }
+
+    fn init(
+        init: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let mut model = InputTabModel {
+            editing: false,
+            mouse_warp_override: init.mouse_warp_override,
+            default_dead_zone: init.default_dead_zone,
+            draft_name: String::new(),
+            joysticks: init.joysticks,
+            joystick_group: adw::PreferencesGroup::new(),
+            rows: Vec::new(),
+            tracker: 0,
+        };
+        let widgets = view_output!();
+        model.joystick_group = widgets.joystick_group.clone();
+        model.refresh_list(&sender);
+        ComponentParts { model, widgets }
+    }
+
+    fn update(&mut self, msg: Self::Input, sender: ComponentSender<Self>) {
+        self.reset();
+        match msg {
+            InputTabInput::SetEditing(v) => {
+                self.set_editing(v);
+                self.refresh_list(&sender);
+            }
+            InputTabInput::LoadSettings(s) => {
+                self.set_mouse_warp_override(s.mouse_warp_override);
+                self.set_default_dead_zone(s.default_dead_zone);
+                self.draft_name.clear();
+                self.joysticks = s.joysticks;
+                self.refresh_list(&sender);
+            }
+            InputTabInput::UpdateField(field, value) => match field.as_str() {
+                "mouse_warp_override" => {
+                    self.set_mouse_warp_override(Some(value.clone()));
+                    let _ = sender.output(InputTabOutput::SettingChanged(
+                        "Software\\Wine\\DirectInput".into(),
+                        format!("MouseWarpOverride={}", value),
+                    ));
+                }
+                "default_dead_zone" => {
+                    if let Ok(v) = value.parse::<u32>() {
+                        self.set_default_dead_zone(Some(v));
+                        let _ = sender.output(InputTabOutput::SettingChanged(
+                            "Software\\Wine\\DirectInput".into(),
+                            format!("DefaultDeadZone={}", v),
+                        ));
+                    }
+                }
+                _ => {}
+            },
+            InputTabInput::UpdateDraftName(value) => {
+                self.draft_name = value;
+            }
+            InputTabInput::AddJoystick => {
+                let name = self.draft_name.trim().to_string();
+                if name.is_empty() || self.joysticks.iter().any(|j| j.name == name) {
+                    return;
+                }
+                // Listing a device only matters to turn it off
+                self.joysticks.insert(
+                    0,
+                    JoystickEntry {
+                        name: name.clone(),
+                        enabled: false,
+                    },
+                );
+                self.draft_name.clear();
+                emit_joystick_state(&sender, &name, Some(false));
+                self.refresh_list(&sender);
+            }
+            InputTabInput::SetJoystickEnabled(idx, enabled) => {
+                if let Some(item) = self.joysticks.get_mut(idx) {
+                    if item.enabled != enabled {
+                        item.enabled = enabled;
+                        emit_joystick_state(&sender, &item.name, Some(enabled));
+                    }
+                }
+            }
+            InputTabInput::RemoveJoystick(idx) => {
+                if idx < self.joysticks.len() {
+                    let removed = self.joysticks.remove(idx);
+                    emit_joystick_state(&sender, &removed.name, None);
+                    self.refresh_list(&sender);
+                }
+            }
+        }
+    }
+}
+
+impl InputTabModel {
+    fn refresh_list(&mut self, sender: &ComponentSender<Self>) {
+        for row in self.rows.drain(..) {
+            self.joystick_group.remove(&row);
+        }
+
+        if self.editing {
+            let draft_row = gtk::ListBoxRow::new();
+            draft_row.set_selectable(false);
+            draft_row.set_activatable(false);
+
+            let draft_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+            draft_box.set_margin_start(12);
+            draft_box.set_margin_end(12);
+            draft_box.set_margin_top(6);
+            draft_box.set_margin_bottom(6);
+
+            let draft_entry = gtk::Entry::builder()
+                .hexpand(true)
+                .placeholder_text(crate::t!("registry.input.joystick_placeholder"))
+                .text(&self.draft_name)
+                .build();
+            {
+                let s = sender.clone();
+                draft_entry.connect_changed(move |entry| {
+                    s.input(InputTabInput::UpdateDraftName(entry.text().to_string()));
+                });
+            }
+            {
+                let s = sender.clone();
+                draft_entry.connect_activate(move |_| {
+                    s.input(InputTabInput::AddJoystick);
+                });
+            }
+
+            let add_btn = gtk::Button::builder()
+                .icon_name("list-add-symbolic")
+                .tooltip_text(crate::t!("registry.input.add_joystick"))
+                .build();
+            {
+                let s = sender.clone();
+                add_btn.connect_clicked(move |_| {
+                    s.input(InputTabInput::AddJoystick);
+                });
+            }
+
+            draft_box.append(&draft_entry);
+            draft_box.append(&add_btn);
+            draft_row.set_child(Some(&draft_box));
+            self.joystick_group.add(&draft_row);
+            self.rows.push(draft_row);
+        }
+
+        for (idx, entry) in self.joysticks.iter().enumerate() {
+            let row = adw::ActionRow::builder()
+                .title(&entry.name)
+                .subtitle(if entry.enabled {
+                    crate::t!("registry.input.joystick_enabled")
+                } else {
+                    crate::t!("registry.input.joystick_disabled")
+                })
+                .build();
+
+            let switch = gtk::Switch::builder()
+                .valign(gtk::Align::Center)
+                .active(entry.enabled)
+                .sensitive(self.editing)
+                .build();
+            {
+                let s = sender.clone();
+                let row = row.clone();
+                switch.connect_active_notify(move |sw| {
+                    row.set_subtitle(&if sw.is_active() {
+                        crate::t!("registry.input.joystick_enabled")
+                    } else {
+                        crate::t!("registry.input.joystick_disabled")
+                    });
+                    s.input(InputTabInput::SetJoystickEnabled(idx, sw.is_active()));
+                });
+            }
+            row.add_suffix(&switch);
+
+            let remove_btn = gtk::Button::builder()
+                .icon_name("user-trash-symbolic")
+                .tooltip_text(crate::t!("registry.input.remove_joystick"))
+                .valign(gtk::Align::Center)
+                .visible(self.editing)
+                .css_classes(["destructive-action"])
+                .build();
+            {
+                let s = sender.clone();
+                remove_btn.connect_clicked(move |_| {
+                    s.input(InputTabInput::RemoveJoystick(idx));
+                });
+            }
+            row.add_suffix(&remove_btn);
+
+            self.joystick_group.add(&row);
+            self.rows.push(row.upcast());
+        }
+    }
+}
+
+/// `None` removes the device from the list, which leaves it enabled.
+fn emit_joystick_state(sender: &ComponentSender<InputTabModel>, name: &str, enabled: Option<bool>) {
+    let state = match enabled {
+        Some(true) => JoystickState::Enabled.to_string(),
+        Some(false) => JoystickState::Disabled.to_string(),
+        None => "",
+    };
+    let _ = sender.output(InputTabOutput::SettingChanged(
+        "Software\\Wine\\DirectInput\\Joysticks".into(),
+        format!("{}={}", name, state),
+    ));
+}
+
+fn warp_code_to_index(code: &str) -> Option<u32> {
+    Some(match code {
+        "" => 0,
+        "enable" => 1,
+        "disable" => 2,
+        "force" => 3,
+        _ => return None,
+    })
+}
+
+fn warp_index_to_code(idx: u32) -> &'static str {
+    match idx {
+        0 => "",
+        1 => "enable",
+        2 => "disable",
+        3 => "force",
+        _ => "",
+    }
+}
@@ -2,31 +2,40 @@ use adw::prelude::*;
 use notify::{RecursiveMode, Watcher, recommended_watcher};
 use prefix::registry::Value;
 use prefix::registry::cache::hash_registry_files;
+use prefix::registry::diff::display_value;
 use prefix::registry::keys::*;
 use prefix::{
     PrefixError, ProcessTracker,
     config::PrefixConfig,
-    registry::{RegEditor, RegistryEditor, WineRegistry},
+    registry::{
+        EditQueue, RegEditor, RegistryEditor, RegistrySnapshot, SettingGroup, WineRegistry,
+        copy_settings,
+    },
 };
 use relm4::{
     Component, ComponentController, ComponentParts, ComponentSender, Controller, RelmWidgetExt,
     SimpleComponent, adw, gtk,
 };
 use service::AppService;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
+use std::time::SystemTime;
 use tokio::sync::{Mutex, oneshot};
 use tracker;
 
+pub mod diff_view;
 pub mod fonts_tab;
 pub mod general_tab;
 pub mod graphics_tab;
+pub mod input_tab;
 pub mod platform_tab;
 
 pub use fonts_tab::{FontsSettings, FontsTabModel};
 pub use general_tab::{GeneralSettings, GeneralTabModel};
 pub use graphics_tab::{GraphicsSettings, GraphicsTabModel};
+pub use input_tab::{InputSettings, InputTabModel};
 pub use platform_tab::{MacSettings, PlatformSettings, PlatformTabModel, X11Settings};
 
 // ── Model ────────────────────────────────────────────────────────────────
@@ -38,14 +47,46 @@ pub struct RegistryEditorModel {
     config: PrefixConfig,
     editing: bool,
     loading: bool,
+    /// Why the last registry load failed; shown in a banner with Retry.
+    load_error: Option<String>,
     winecfg_running: bool,
     regedit_running: bool,
+    /// Whether a pre-save snapshot is available to undo to.
+    can_undo: bool,
+    /// Cleared while a tab holds input that can't be applied; blocks Save.
+    tabs_valid: bool,
+    /// Notebook page currently shown; picks what "Reset" clears.
+    current_tab: u32,
+    /// `.reg` contents from just before the most recent save (single level).
+    #[tracker::do_not_track]
+    last_save_snapshot: Option<RegistrySnapshot>,
     #[tracker::do_not_track]
     pending_edit: bool,
+    /// `(section, setting)` edits from the tabs, written on Save and dropped
+    /// on Cancel.
+    #[tracker::do_not_track]
+    pending_settings: Vec<(String, String)>,
+    /// Set by Apply: the save in progress leaves edit mode on.
+    #[tracker::do_not_track]
+    keep_editing: bool,
+    /// Newest mtime of `user.reg`/`system.reg` when last loaded or saved;
+    /// a newer one at save time means something else wrote the files.
+    #[tracker::do_not_track]
+    loaded_mtime: Option<SystemTime>,
+    /// The watcher already warned about outside changes to the buffered
+    /// edits; wineserver flushes again and again while an app runs. Save
+    /// still checks for itself.
+    #[tracker::do_not_track]
+    external_change_warned: bool,
     #[tracker::do_not_track]
     registry_editor: Option<Arc<Mutex<RegistryEditor>>>,
     #[tracker::do_not_track]
     system_registry: Option<Arc<Mutex<WineRegistry>>>,
+    /// Runs every task that writes through `registry_editor` or
+    /// `system_registry`, one after another, so overlapping saves, resets
+    /// and imports can't interleave their get-modify-set edits.
+    #[tracker::do_not_track]
+    edit_queue: EditQueue,
     // Tab component controllers
     #[tracker::do_not_track]
     parent_window: gtk::Window,
@@ -58,6 +99,8 @@ pub struct RegistryEditorModel {
     #[tracker::do_not_track]
     pub platform_ctrl: Controller<PlatformTabModel>,
     #[tracker::do_not_track]
+    pub input_ctrl: Controller<InputTabModel>,
+    #[tracker::do_not_track]
     prefix_store: Arc<prefix::PrefixStore>,
     #[tracker::do_not_track]
     process_tracker: Arc<std::sync::Mutex<ProcessTracker>>,
@@ -78,6 +121,8 @@ pub struct RegistryEditorModel {
     regedit_icon: gtk::Image,
     #[tracker::do_not_track]
     regedit_spinner: gtk::Spinner,
+    #[tracker::do_not_track]
+    diff_window: Option<Controller<diff_view::RegistryDiffModel>>,
 }
 
 // ── Messages ─────────────────────────────────────────────────────────────
@@ -85,22 +130,62 @@ pub struct RegistryEditorModel {
 #[derive(Debug)]
 pub enum RegistryEditorMsg {
     ToggleEdit,
+    /// Save like the Edit→Save toggle, but stay in edit mode afterwards.
+    ApplyRegistry,
+    /// Check for outside edits, then preview what will be written.
     SaveRegistry,
+    /// `(key, value name, value)` writes awaiting confirmation; keys are
+    /// qualified with their hive.
+    PreviewChanges(Vec<(String, String, Value)>),
+    /// Write the registry files, after the preview was accepted.
+    SaveRegistryConfirmed,
     LoadRegistry,
     LoadForEdit,
     RegistryEditorLoaded(Arc<Mutex<RegistryEditor>>, Arc<Mutex<WineRegistry>>),
-    LoadSettings(GeneralSettings, GraphicsSettings, PlatformSettings, FontsSettings),
-    RegistrySaveComplete,
+    RegistryLoadFailed(String),
+    RetryLoad,
+    LoadSettings(
+        GeneralSettings,
+        GraphicsSettings,
+        PlatformSettings,
+        FontsSettings,
+        InputSettings,
+    ),
+    RegistrySaveComplete(RegistrySnapshot),
+    OverwriteExternalChanges,
     RegistrySaveError(String),
+    UndoLastSave,
     ConfigUpdated(PrefixConfig),
     CancelEdit,
     RunWinecfg,
     RunRegedit,
     RefreshReg,
+    /// The watcher saw the prefix's `.reg` files change.
+    RegFilesChanged,
     PrefixPathUpdated(PathBuf),
     /// Unified handler: (section, setting) where setting is "key=value" or just "value"
     ApplySetting(String, String),
+    SetTabsValid(bool),
     PollProcesses,
+    ExportReg,
+    ExportRegTo(PathBuf),
+    ImportReg,
+    ImportRegFrom(PathBuf),
+    ImportRegComplete(RegImportReport),
+    CopyFromPrefix,
+    /// `(name, path)` of every prefix, to pick a copy source from.
+    PromptCopySource(Vec<(String, PathBuf)>),
+    CompareWithPrefix,
+    /// `(name, path)` of every prefix, to compare against.
+    OpenDiffWindow(Vec<(String, PathBuf)>),
+    TabSwitched(u32),
+    ResetTab,
+    /// Page to reset and what it would remove, as `(title, subtitle,
+    /// detail)` rows; asks before resetting.
+    ConfirmReset(u32, Vec<(String, String, String)>),
+    ResetTabConfirmed(u32),
+    CopyFromPrefixRun(PathBuf, Vec<SettingGroup>),
+    ShowError(String),
 }
 
 // ── Component ────────────────────────────────────────────────────────────
@@ -133,11 +218,25 @@ impl SimpleComponent for RegistryEditorModel {
                     set_hexpand: true,
                     set_vexpand: true,
 
+                    adw::Banner {
+                        set_button_label: Some(&crate::t!("registry.retry")),
+                        #[watch]
+                        set_revealed: model.load_error.is_some(),
+                        #[watch]
+                        set_title: &model.load_error.as_deref().map_or(String::new(), |e| {
+                            crate::tf!("registry.load_failed", "error" => e)
+                        }),
+                        connect_button_clicked => RegistryEditorMsg::RetryLoad,
+                    },
+
                     #[name = "notebook"]
                     gtk::Notebook {
                         set_hexpand: true,
                         set_vexpand: true,
                         set_show_border: false,
+                        connect_switch_page[sender] => move |_, _, page| {
+                            sender.input(RegistryEditorMsg::TabSwitched(page));
+                        },
 
                         append_page: (
                             &model.general_ctrl.widget().clone(),
@@ -158,6 +257,11 @@ impl SimpleComponent for RegistryEditorModel {
                             &model.platform_ctrl.widget().clone(),
                             Some(&gtk::Label::builder().label(&crate::t!("registry.tab.platform")).build())
                         ),
+
+                        append_page: (
+                            &model.input_ctrl.widget().clone(),
+                            Some(&gtk::Label::builder().label(&crate::t!("registry.tab.input")).build())
+                        ),
                     },
 
                     // Control buttons
@@ -186,12 +290,63 @@ impl SimpleComponent for RegistryEditorModel {
                             set_hexpand: true,
                         },
 
+                        gtk::Button {
+                            set_icon_name: "document-revert-symbolic",
+                            set_tooltip_text: Some(&crate::t!("registry.tooltip.undo_save")),
+                            #[watch]
+                            set_sensitive: model.can_undo && !model.editing,
+                            connect_clicked => RegistryEditorMsg::UndoLastSave,
+                        },
+
+                        gtk::Button {
+                            set_icon_name: "document-open-symbolic",
+                            set_tooltip_text: Some(&crate::t!("registry.tooltip.import")),
+                            #[watch]
+                            set_sensitive: !model.editing,
+                            connect_clicked => RegistryEditorMsg::ImportReg,
+                        },
+
+                        gtk::Button {
+                            set_icon_name: "edit-clear-all-symbolic",
+                            set_tooltip_text: Some(&crate::t!("registry.tooltip.reset_tab")),
+                            #[watch]
+                            set_sensitive: !model.editing && reset_targets(model.current_tab).is_some(),
+                            connect_clicked => RegistryEditorMsg::ResetTab,
+                        },
+
+                        gtk::Button {
+                            set_icon_name: "edit-copy-symbolic",
+                            set_tooltip_text: Some(&crate::t!("registry.tooltip.copy_from")),
+                            #[watch]
+                            set_sensitive: !model.editing,
+                            connect_clicked => RegistryEditorMsg::CopyFromPrefix,
+                        },
+
+                        gtk::Button {
+                            set_icon_name: "view-dual-symbolic",
+                            set_tooltip_text: Some(&crate::t!("registry.tooltip.compare")),
+                            connect_clicked => RegistryEditorMsg::CompareWithPrefix,
+                        },
+
+                        gtk::Button {
+                            set_icon_name: "document-save-as-symbolic",
+                            set_tooltip_text: Some(&crate::t!("registry.tooltip.export")),
+                            connect_clicked => RegistryEditorMsg::ExportReg,
+                        },
+
                         gtk::Button {
                             set_icon_name: "view-refresh-symbolic",
                             set_tooltip_text: Some(&crate::t!("registry.tooltip.reload")),
                             connect_clicked => RegistryEditorMsg::RefreshReg,
                         },
 
+                        gtk::Label {
+                            set_label: &crate::t!("registry.unsaved"),
+                            add_css_class: "dim-label",
+                            #[watch]
+                            set_visible: model.editing && !model.pending_settings.is_empty(),
+                        },
+
                         gtk::Separator {
                             set_orientation: gtk::Orientation::Vertical,
                         },
@@ -203,9 +358,21 @@ impl SimpleComponent for RegistryEditorModel {
                             set_tooltip_text: Some(&model.edit_save_tooltip),
                             #[watch]
                             set_css_classes: if model.editing { &["suggested-action"] } else { &[] },
+                            #[watch]
+                            set_sensitive: !model.editing || model.tabs_valid,
                             connect_clicked => RegistryEditorMsg::ToggleEdit,
                         },
 
+                        gtk::Button {
+                            set_icon_name: "document-save-symbolic",
+                            set_tooltip_text: Some(&crate::t!("registry.tooltip.apply")),
+                            #[watch]
+                            set_visible: model.editing,
+                            #[watch]
+                            set_sensitive: model.tabs_valid && !model.pending_settings.is_empty(),
+                            connect_clicked => RegistryEditorMsg::ApplyRegistry,
+                        },
+
                         gtk::Button {
                             set_icon_name: "edit-undo-symbolic",
                             set_tooltip_text: Some(&model.cancel_tooltip),
@@ -222,11 +389,14 @@ impl SimpleComponent for RegistryEditorModel {
                 gtk::Box {
                     set_orientation: gtk::Orientation::Vertical,
                     set_spacing: 10,
-                    set_margin_top: 10,
+                    set_valign: gtk::Align::Center,
+                    set_vexpand: true,
 
                     gtk::Spinner {
+                        start: (),
                         set_halign: gtk::Align::Center,
-                        set_margin_bottom: 10,
+                        set_width_request: 32,
+                        set_height_request: 32,
                     },
 
                     gtk::Label {
@@ -272,6 +442,9 @@ impl SimpleComponent for RegistryEditorModel {
                 graphics_tab::GraphicsTabOutput::SettingChanged(k, v) => {
                     RegistryEditorMsg::ApplySetting(k, v)
                 }
+                graphics_tab::GraphicsTabOutput::ValidityChanged(valid) => {
+                    RegistryEditorMsg::SetTabsValid(valid)
+                }
             });
 
         let fonts_ctrl = FontsTabModel::builder()
@@ -290,6 +463,14 @@ impl SimpleComponent for RegistryEditorModel {
                 }
             });
 
+        let input_ctrl = InputTabModel::builder()
+            .launch(InputSettings::default())
+            .forward(sender.input_sender(), |msg| match msg {
+                input_tab::InputTabOutput::SettingChanged(k, v) => {
+                    RegistryEditorMsg::ApplySetting(k, v)
+                }
+            });
+
         // ── Set up winecfg/regedit buttons with icon + spinner ──
         let winecfg_icon = gtk::Image::from_icon_name("applications-engineering-symbolic");
         let winecfg_spinner = gtk::Spinner::builder()
@@ -309,16 +490,27 @@ impl SimpleComponent for RegistryEditorModel {
             system_registry: None,
             editing: false,
             loading: false,
+            load_error: None,
             winecfg_running: false,
             regedit_running: false,
+            can_undo: false,
+            tabs_valid: true,
+            current_tab: 0,
+            last_save_snapshot: None,
             edit_save_tooltip: crate::t!("registry.edit"),
             cancel_tooltip: crate::t!("registry.cancel"),
             pending_edit: false,
+            pending_settings: Vec::new(),
+            edit_queue: EditQueue::new(),
+            keep_editing: false,
+            loaded_mtime: None,
+            external_change_warned: false,
             parent_window,
             general_ctrl,
             graphics_ctrl,
             fonts_ctrl,
             platform_ctrl,
+            input_ctrl,
             prefix_store,
             process_tracker,
             watch_kill: None,
@@ -328,6 +520,7 @@ impl SimpleComponent for RegistryEditorModel {
             regedit_btn: gtk::Button::new(),
             regedit_icon: regedit_icon.clone(),
             regedit_spinner: regedit_spinner.clone(),
+            diff_window: None,
             tracker: 0,
         };
 
@@ -357,7 +550,23 @@ impl SimpleComponent for RegistryEditorModel {
         self.reset();
         match msg {
             RegistryEditorMsg::ToggleEdit => {
-                if self.editing {
+                if self.editing && !self.tabs_valid {
+                    return;
+                }
+                if self.editing && self.pending_settings.is_empty() {
+                    // Nothing to write; just leave edit mode
+                    self.set_editing(false);
+                    self.set_edit_save_tooltip(crate::t!("registry.edit"));
+                    set_editing_all_tabs(
+                        &self.general_ctrl,
+                        &self.graphics_ctrl,
+                        &self.platform_ctrl,
+                        &self.fonts_ctrl,
+                        &self.input_ctrl,
+                        false,
+                    );
+                } else if self.editing {
+                    self.keep_editing = false;
                     sender.input(RegistryEditorMsg::SaveRegistry);
                 } else if self.registry_editor.is_none() || self.system_registry.is_none() {
                     self.pending_edit = true;
@@ -373,6 +582,8 @@ impl SimpleComponent for RegistryEditorModel {
                         .emit(platform_tab::PlatformTabInput::SetEditing(true));
                     self.fonts_ctrl
                         .emit(fonts_tab::FontsTabInput::SetEditing(true));
+                    self.input_ctrl
+                        .emit(input_tab::InputTabInput::SetEditing(true));
                 }
             }
 
@@ -382,6 +593,7 @@ impl SimpleComponent for RegistryEditorModel {
                 }
                 if !self.prefix_path.as_os_str().is_empty() {
                     self.set_loading(true);
+                    self.loaded_mtime = reg_files_mtime(&self.prefix_path);
                     let prefix_path = self.prefix_path.clone();
                     let prefix_path_str = prefix_path.to_string_lossy().to_string();
                     let store = Arc::clone(&self.prefix_store);
@@ -408,6 +620,7 @@ impl SimpleComponent for RegistryEditorModel {
                 }
                 if !self.prefix_path.as_os_str().is_empty() {
                     self.set_loading(true);
+                    self.loaded_mtime = reg_files_mtime(&self.prefix_path);
                     self.system_registry = None;
                     let prefix_path = self.prefix_path.clone();
                     let prefix_path_str = prefix_path.to_string_lossy().to_string();
@@ -421,7 +634,7 @@ impl SimpleComponent for RegistryEditorModel {
                 }
             }
 
-            RegistryEditorMsg::LoadSettings(general, graphics, platform, fonts) => {
+            RegistryEditorMsg::LoadSettings(general, graphics, platform, fonts, input) => {
                 self.general_ctrl
                     .emit(general_tab::GeneralTabInput::LoadSettings(general));
                 self.graphics_ctrl
@@ -430,12 +643,15 @@ impl SimpleComponent for RegistryEditorModel {
                     .emit(platform_tab::PlatformTabInput::LoadSettings(platform));
                 self.fonts_ctrl
                     .emit(fonts_tab::FontsTabInput::LoadSettings(fonts));
+                self.input_ctrl
+                    .emit(input_tab::InputTabInput::LoadSettings(input));
             }
 
             RegistryEditorMsg::RegistryEditorLoaded(editor, system_registry) => {
                 self.registry_editor = Some(editor);
                 self.system_registry = Some(system_registry);
                 self.loading = false;
+                self.set_load_error(None);
                 if self.pending_edit {
                     self.pending_edit = false;
                     self.set_editing(true);
@@ -448,36 +664,107 @@ impl SimpleComponent for RegistryEditorModel {
                         .emit(platform_tab::PlatformTabInput::SetEditing(true));
                     self.fonts_ctrl
                         .emit(fonts_tab::FontsTabInput::SetEditing(true));
+                    self.input_ctrl
+                        .emit(input_tab::InputTabInput::SetEditing(true));
                 }
             }
 
+            RegistryEditorMsg::RegistryLoadFailed(error) => {
+                log::error!("[regedit] failed to load registry: {}", error);
+                self.set_loading(false);
+                self.pending_edit = false;
+                self.set_load_error(Some(error));
+            }
+
+            RegistryEditorMsg::RetryLoad => {
+                self.set_load_error(None);
+                sender.input(RegistryEditorMsg::LoadRegistry);
+            }
+
             RegistryEditorMsg::ApplySetting(section, setting) => {
-                self.handle_apply_setting(section, setting, &sender);
+                self.queue_setting(section, setting);
+            }
+
+            RegistryEditorMsg::SetTabsValid(valid) => self.set_tabs_valid(valid),
+
+            RegistryEditorMsg::ApplyRegistry => {
+                if !self.editing || !self.tabs_valid || self.pending_settings.is_empty() {
+                    return;
+                }
+                self.keep_editing = true;
+                sender.input(RegistryEditorMsg::SaveRegistry);
             }
 
             RegistryEditorMsg::SaveRegistry => {
+                let changed_on_disk = matches!(
+                    (self.loaded_mtime, reg_files_mtime(&self.prefix_path)),
+                    (Some(loaded), Some(current)) if current > loaded
+                );
+                if changed_on_disk {
+                    self.confirm_overwrite_external_changes(&sender);
+                    return;
+                }
+                let editor_arc = self.registry_editor.clone();
+                let system_arc = self.system_registry.clone();
+                let pp = self.prefix_path.clone();
+                let edits = self.pending_settings.clone();
+                let s = sender.clone();
+                self.edit_queue.push(async move {
+                    if let Some(ec) = &editor_arc {
+                        for (section, setting) in &edits {
+                            write_setting(ec, system_arc.as_ref(), section, setting).await;
+                        }
+                    }
+                    let result =
+                        pending_registry_changes(&pp, editor_arc.as_ref(), system_arc.as_ref())
+                            .await;
+                    match result {
+                        Ok(changes) => s.input(RegistryEditorMsg::PreviewChanges(changes)),
+                        Err(e) => s.input(RegistryEditorMsg::ShowError(e.to_string())),
+                    }
+                });
+            }
+
+            RegistryEditorMsg::PreviewChanges(changes) => {
+                if !self.editing {
+                    return;
+                }
+                if changes.is_empty() {
+                    sender.input(RegistryEditorMsg::SaveRegistryConfirmed);
+                } else {
+                    self.confirm_pending_changes(changes, &sender);
+                }
+            }
+
+            RegistryEditorMsg::SaveRegistryConfirmed => {
                 let editor_arc = self.registry_editor.clone();
                 let system_arc = self.system_registry.clone();
                 let store = Arc::clone(&self.prefix_store);
                 let pp = self.prefix_path.clone();
+                let edits = self.pending_settings.clone();
                 let s = sender.clone();
 
-                self.set_editing(false);
-                self.set_edit_save_tooltip(crate::t!("registry.edit"));
-                set_editing_all_tabs(
-                    &self.general_ctrl,
-                    &self.graphics_ctrl,
-                    &self.platform_ctrl,
-                    &self.fonts_ctrl,
-                    false,
-                );
+                if !self.keep_editing {
+                    self.set_editing(false);
+                    self.set_edit_save_tooltip(crate::t!("registry.edit"));
+                    set_editing_all_tabs(
+                        &self.general_ctrl,
+                        &self.graphics_ctrl,
+                        &self.platform_ctrl,
+                        &self.fonts_ctrl,
+                        &self.input_ctrl,
+                        false,
+                    );
+                }
 
-                tokio::spawn(async move {
+                self.edit_queue.push(async move {
                     let font_substitutes_key =
                         "Software\\Microsoft\\Windows NT\\CurrentVersion\\FontSubstitutes";
 
                     let result = async {
-                        if let Some(ec) = editor_arc {
+                        let snapshot = RegistrySnapshot::capture(&pp)?;
+
+                        if let Some(ec) = &editor_arc {
                             let editor = ec.lock().await;
                             let _ = editor.registry.delete_key(font_substitutes_key).await;
                             editor.save_registry(&pp).await?;
@@ -495,18 +782,30 @@ impl SimpleComponent for RegistryEditorModel {
                             let _ = store.save_registry_hashes(&pp_str, &uh, &sh);
                         }
 
-                        Ok::<(), PrefixError>(())
+                        Ok::<RegistrySnapshot, PrefixError>(snapshot)
                     }
                     .await;
 
                     match result {
-                        Ok(()) => s.input(RegistryEditorMsg::RegistrySaveComplete),
+                        Ok(snapshot) => {
+                            if let Some(ec) = &editor_arc {
+                                let pp_str = pp.to_string_lossy();
+                                for (section, setting) in &edits {
+                                    cache_setting(ec, &store, &pp_str, section, setting).await;
+                                }
+                            }
+                            note_saved_settings(&pp, &edits);
+                            log_saved_settings(&pp, &edits);
+                            s.input(RegistryEditorMsg::RegistrySaveComplete(snapshot));
+                        }
                         Err(e) => s.input(RegistryEditorMsg::RegistrySaveError(e.to_string())),
                     }
                 });
             }
 
             RegistryEditorMsg::CancelEdit => {
+                self.keep_editing = false;
+                self.pending_settings.clear();
                 self.set_editing(false);
                 self.set_edit_save_tooltip(crate::t!("registry.edit"));
                 set_editing_all_tabs(
@@ -514,12 +813,28 @@ impl SimpleComponent for RegistryEditorModel {
                     &self.graphics_ctrl,
                     &self.platform_ctrl,
                     &self.fonts_ctrl,
+                    &self.input_ctrl,
                     false,
                 );
+                // A declined save preview leaves edits in the loaded registries
+                // (and the settings cache), so drop both and re-read the files;
+                // the load sends the on-disk values back to every tab.
+                self.pending_edit = false;
+                self.registry_editor = None;
                 self.system_registry = None;
                 let pp = self.prefix_path.to_string_lossy().to_string();
                 let _ = self.prefix_store.invalidate_registry_cache(&pp);
-                sender.input(RegistryEditorMsg::LoadRegistry);
+                if !self.prefix_path.as_os_str().is_empty() {
+                    // Not LoadRegistry: it bails out while a load is running
+                    self.set_loading(true);
+                    self.loaded_mtime = reg_files_mtime(&self.prefix_path);
+                    spawn_registry_load(
+                        self.prefix_path.clone(),
+                        pp,
+                        Arc::clone(&self.prefix_store),
+                        sender.clone(),
+                    );
+                }
             }
 
             RegistryEditorMsg::RunWinecfg => {
@@ -603,6 +918,7 @@ impl SimpleComponent for RegistryEditorModel {
                 let _ = self.prefix_store.invalidate_registry_cache(&pp);
                 self.registry_editor = None;
                 self.system_registry = None;
+                self.pending_settings.clear();
                 self.set_editing(false);
                 self.set_edit_save_tooltip(crate::t!("registry.edit"));
                 set_editing_all_tabs(
@@ -610,13 +926,33 @@ impl SimpleComponent for RegistryEditorModel {
                     &self.graphics_ctrl,
                     &self.platform_ctrl,
                     &self.fonts_ctrl,
+                    &self.input_ctrl,
                     false,
                 );
                 sender.input(RegistryEditorMsg::LoadRegistry);
             }
 
+            RegistryEditorMsg::RegFilesChanged => {
+                // Our own saves land here too; `RegistrySaveComplete` already
+                // recorded their mtime
+                if reg_files_mtime(&self.prefix_path) == self.loaded_mtime {
+                    return;
+                }
+                // Reloading would drop buffered edits; ask the same way Save
+                // does when the files changed under it
+                if self.editing && !self.pending_settings.is_empty() {
+                    if !self.external_change_warned {
+                        self.external_change_warned = true;
+                        self.confirm_overwrite_external_changes(&sender);
+                    }
+                    return;
+                }
+                sender.input(RegistryEditorMsg::RefreshReg);
+            }
+
             RegistryEditorMsg::ConfigUpdated(config) => {
                 self.set_config(config);
+                self.pending_settings.clear();
                 self.set_editing(false);
                 self.set_edit_save_tooltip(crate::t!("registry.edit"));
                 sender.input(RegistryEditorMsg::LoadRegistry);
@@ -625,8 +961,11 @@ impl SimpleComponent for RegistryEditorModel {
             RegistryEditorMsg::PrefixPathUpdated(path) => {
                 let pp = path.clone();
                 self.set_prefix_path(path);
+                self.last_save_snapshot = None;
+                self.set_can_undo(false);
                 self.registry_editor = None;
                 self.system_registry = None;
+                self.pending_settings.clear();
                 self.watch_kill = None;
 
                 let s = sender.clone();
@@ -655,7 +994,7 @@ impl SimpleComponent for RegistryEditorModel {
                                     .is_ok()
                                 {}
                                 std::thread::sleep(std::time::Duration::from_millis(500));
-                                let _ = s.input(RegistryEditorMsg::RefreshReg);
+                                let _ = s.input(RegistryEditorMsg::RegFilesChanged);
                             }
                             Err(mpsc::RecvTimeoutError::Timeout) => {
                                 if kill_rx.try_recv().is_ok() {
@@ -674,12 +1013,26 @@ impl SimpleComponent for RegistryEditorModel {
                         default_graphics_settings(),
                         PlatformSettings::default(),
                         FontsSettings::default(),
+                        InputSettings::default(),
                     ));
                 }
                 sender.input(RegistryEditorMsg::LoadRegistry);
             }
 
-            RegistryEditorMsg::RegistrySaveComplete => {
+            RegistryEditorMsg::OverwriteExternalChanges => {
+                self.loaded_mtime = None;
+                sender.input(RegistryEditorMsg::SaveRegistry);
+            }
+
+            RegistryEditorMsg::RegistrySaveComplete(snapshot) => {
+                self.pending_settings.clear();
+                self.loaded_mtime = reg_files_mtime(&self.prefix_path);
+                self.last_save_snapshot = Some(snapshot);
+                self.set_can_undo(true);
+                if self.keep_editing {
+                    self.keep_editing = false;
+                    return;
+                }
                 self.set_editing(false);
                 self.set_edit_save_tooltip(crate::t!("registry.edit"));
                 set_editing_all_tabs(
@@ -687,12 +1040,18 @@ impl SimpleComponent for RegistryEditorModel {
                     &self.graphics_ctrl,
                     &self.platform_ctrl,
                     &self.fonts_ctrl,
+                    &self.input_ctrl,
                     false,
                 );
             }
 
             RegistryEditorMsg::RegistrySaveError(error) => {
                 log::error!("[regedit] registry save error: {}", error);
+                if self.keep_editing {
+                    self.keep_editing = false;
+                    sender.input(RegistryEditorMsg::ShowError(error));
+                    return;
+                }
                 self.set_editing(false);
                 self.set_edit_save_tooltip(crate::t!("registry.edit"));
                 set_editing_all_tabs(
@@ -700,426 +1059,1230 @@ impl SimpleComponent for RegistryEditorModel {
                     &self.graphics_ctrl,
                     &self.platform_ctrl,
                     &self.fonts_ctrl,
+                    &self.input_ctrl,
                     false,
                 );
             }
 
-            RegistryEditorMsg::PollProcesses => {
+            RegistryEditorMsg::UndoLastSave => {
+                let Some(snapshot) = self.last_save_snapshot.take() else {
+                    return;
+                };
+                self.set_can_undo(false);
+                let store = Arc::clone(&self.prefix_store);
                 let pp = self.prefix_path.clone();
-                let winecfg_track = pp.join("__wine_winecfg__");
-                let regedit_track = pp.join("__wine_regedit__");
-                let mut tracker = self.process_tracker.lock().unwrap();
-
-                // Clean up dead processes and re-check running state
-                let _changed = tracker.poll_dead();
-                let winecfg_alive = tracker.is_running(&winecfg_track);
-                let regedit_alive = tracker.is_running(&regedit_track);
-                drop(tracker);
-
-                self.set_winecfg_running(winecfg_alive);
-                self.set_regedit_running(regedit_alive);
-
-                if winecfg_alive {
-                    self.winecfg_spinner.start();
-                    self.winecfg_btn.set_child(Some(&self.winecfg_spinner));
-                } else {
-                    self.winecfg_spinner.stop();
-                    self.winecfg_btn.set_child(Some(&self.winecfg_icon));
-                }
+                let s = sender.clone();
+                tokio::spawn(async move {
+                    let result = tokio::task::spawn_blocking(move || {
+                        snapshot.restore()?;
+                        if let Ok((uh, sh)) = hash_registry_files(&pp) {
+                            let _ = store.save_registry_hashes(&pp.to_string_lossy(), &uh, &sh);
+                        }
+                        Ok::<(), PrefixError>(())
+                    })
+                    .await
+                    .map_err(|e| PrefixError::RegistryError(format!("Task join error: {}", e)))
+                    .and_then(|r| r);
 
-                if regedit_alive {
-                    self.regedit_spinner.start();
-                    self.regedit_btn.set_child(Some(&self.regedit_spinner));
-                } else {
-                    self.regedit_spinner.stop();
-                    self.regedit_btn.set_child(Some(&self.regedit_icon));
-                }
+                    match result {
+                        Ok(()) => {
+                            log::info!("[regedit] restored registry from last save snapshot");
+                            s.input(RegistryEditorMsg::RefreshReg);
+                        }
+                        Err(e) => s.input(RegistryEditorMsg::ShowError(e.to_string())),
+                    }
+                });
             }
-        }
-    }
-}
 
-// ── ApplySetting handler ─────────────────────────────────────────────────
+            RegistryEditorMsg::ExportReg => {
+                let s = sender.clone();
+                crate::dialogs::save_file(
+                    &self.parent_window,
+                    &crate::t!("registry.export_title"),
+                    &format!("{}.reg", self.config.name),
+                    &["reg"],
+                    move |path| {
+                        if let Some(path) = path {
+                            s.input(RegistryEditorMsg::ExportRegTo(PathBuf::from(path)));
+                        }
+                    },
+                );
+            }
 
-impl RegistryEditorModel {
-    fn handle_apply_setting(
-        &mut self,
-        section: String,
-        setting: String,
-        _sender: &ComponentSender<Self>,
-    ) {
-        if !self.editing {
-            return;
-        }
-        if let Some(editor_arc) = &self.registry_editor {
-            let ec = editor_arc.clone();
-            let system_ec = self.system_registry.clone();
-            let pp = self.prefix_path.to_string_lossy().to_string();
-            let store = Arc::clone(&self.prefix_store);
-            let section_c = section.clone();
-            let setting_c = setting.clone();
-
-            tokio::spawn(async move {
-                match section_c.as_str() {
-                    // ── General: Windows Version ──
-                    "Software\\Wine" => {
-                        let version = setting_c.strip_prefix("Version=").unwrap_or(&setting_c);
-                        let mut editor = ec.lock().await;
-                        let version = if version.is_empty() {
-                            None
-                        } else {
-                            Some(version.to_string())
+            RegistryEditorMsg::ExportRegTo(dest) => {
+                let editor_arc = self.registry_editor.clone();
+                let system_arc = self.system_registry.clone();
+                let pp = self.prefix_path.clone();
+                let s = sender.clone();
+                tokio::spawn(async move {
+                    let result = async {
+                        // Prefer the loaded registries so pending edits are included
+                        let text = match (editor_arc, system_arc) {
+                            (Some(ec), Some(system_arc)) => {
+                                let editor = ec.lock().await;
+                                let system_registry = system_arc.lock().await;
+                                WineRegistry::export_regs(
+                                    &[&editor.registry, &*system_registry],
+                                    &[],
+                                )
+                                .await?
+                            }
+                            _ => {
+                                let user =
+                                    WineRegistry::load_from_file(&pp.join("user.reg")).await?;
+                                let system =
+                                    WineRegistry::load_from_file(&pp.join("system.reg")).await?;
+                                WineRegistry::export_regs(&[&user, &system], &[]).await?
+                            }
                         };
-                        if let Some(ref v) = version {
-                            let _ = editor.set_windows_version(v).await;
-                        } else {
-                            let _ = editor
-                                .registry
-                                .delete_value("Software\\Wine", "Version")
-                                .await;
-                        }
-                        let _ = store.save_setting(
-                            &pp,
-                            "Software\\Wine",
-                            "Version",
-                            version.as_deref(),
-                        );
+                        std::fs::write(&dest, text)?;
+                        Ok::<(), PrefixError>(())
                     }
+                    .await;
 
-                    // ── General: Audio Driver ──
-                    "Software\\Wine\\Drivers\\Audio" => {
-                        let mut editor = ec.lock().await;
-                        let _ = editor.set_audio_driver(&setting_c).await;
-                        let _ = store.save_setting(
-                            &pp,
-                            "Software\\Wine\\Drivers\\Audio",
-                            "",
-                            Some(&setting_c),
-                        );
+                    match result {
+                        Ok(()) => log::info!("[regedit] exported registry to {}", dest.display()),
+                        Err(e) => s.input(RegistryEditorMsg::ShowError(e.to_string())),
                     }
+                });
+            }
 
-                    // ── General: DPI ──
-                    "Control Panel\\Desktop" => {
-                        if let Some(log_pixels) = setting_c.strip_prefix("LogPixels=") {
-                            if let Ok(v) = log_pixels.parse::<u32>() {
-                                let mut editor = ec.lock().await;
-                                let dpi = DpiSettings {
-                                    log_pixels: Some(v),
-                                };
-                                let _ = editor.set_dpi_settings(&dpi).await;
-                                let _ = store.save_setting(
-                                    &pp,
-                                    "Control Panel\\Desktop",
-                                    "LogPixels",
-                                    Some(log_pixels),
-                                );
-                            }
+            RegistryEditorMsg::ImportReg => {
+                let s = sender.clone();
+                crate::dialogs::pick_file(
+                    &self.parent_window,
+                    &crate::t!("registry.import_title"),
+                    &["reg"],
+                    move |path| {
+                        if let Some(path) = path {
+                            s.input(RegistryEditorMsg::ImportRegFrom(PathBuf::from(path)));
                         }
-                    }
+                    },
+                );
+            }
 
-                    // ── General: Virtual Desktop enabled ──
-                    "Software\\Wine\\Explorer" => {
-                        let mut editor = ec.lock().await;
-                        let current = editor.get_virtual_desktop().await.ok().flatten().unwrap_or(
-                            VirtualDesktopSettings {
-                                enabled: false,
-                                width: 1024,
-                                height: 768,
-                            },
-                        );
-                        let enabled = !setting_c.is_empty();
-                        let updated = VirtualDesktopSettings {
-                            enabled,
-                            width: current.width,
-                            height: current.height,
+            RegistryEditorMsg::ImportRegFrom(source) => {
+                let editor_arc = self.registry_editor.clone();
+                let store = Arc::clone(&self.prefix_store);
+                let pp = self.prefix_path.clone();
+                let s = sender.clone();
+                self.edit_queue.push(async move {
+                    let result = async {
+                        let editor_arc = match editor_arc {
+                            Some(ec) => ec,
+                            None => Arc::new(Mutex::new(RegistryEditor::with_prefix(&pp).await?)),
                         };
-                        let _ = editor.set_virtual_desktop(&updated).await;
-                        let _ = store.save_setting(
-                            &pp,
-                            "Software\\Wine\\Explorer",
-                            "Desktop",
-                            Some(if enabled { "Default" } else { "" }),
-                        );
-                        if enabled {
-                            let _ = store.save_setting(
-                                &pp,
-                                "Software\\Wine\\Explorer\\Desktops",
-                                "Default",
-                                Some(&format!("{}x{}", current.width, current.height)),
-                            );
-                        }
-                    }
-
-                    // ── General: Virtual Desktop size ──
-                    "Software\\Wine\\Explorer\\Desktops" => {
-                        if let Some(size) = setting_c.strip_prefix("Default=") {
-                            if let Some((w_str, h_str)) = size.split_once('x') {
-                                if let (Ok(w), Ok(h)) = (w_str.parse::<u32>(), h_str.parse::<u32>())
-                                {
-                                    let mut editor = ec.lock().await;
-                                    let current = editor
-                                        .get_virtual_desktop()
-                                        .await
-                                        .ok()
-                                        .flatten()
-                                        .unwrap_or(VirtualDesktopSettings {
-                                            enabled: false,
-                                            width: 1024,
-                                            height: 768,
-                                        });
-                                    let updated = VirtualDesktopSettings {
-                                        enabled: current.enabled,
-                                        width: w,
-                                        height: h,
-                                    };
-                                    let _ = editor.set_virtual_desktop(&updated).await;
-                                    let _ = store.save_setting(
-                                        &pp,
-                                        "Software\\Wine\\Explorer\\Desktops",
-                                        "Default",
-                                        Some(size),
-                                    );
-                                }
+                        let mut editor = editor_arc.lock().await;
+                        let report = editor.import_reg_file(&source).await?;
+                        if report.imported_keys > 0 {
+                            editor.save_registry(&pp).await?;
+                            let pp_str = pp.to_string_lossy();
+                            if let Ok((uh, sh)) = hash_registry_files(&pp) {
+                                let _ = store.save_registry_hashes(&pp_str, &uh, &sh);
                             }
                         }
+                        Ok::<RegImportReport, PrefixError>(report)
                     }
+                    .await;
 
-                    // ── Graphics: D3D settings ──
-                    "Software\\Wine\\Direct3D" => {
-                        let mut editor = ec.lock().await;
-                        if let Some(renderer) = setting_c.strip_prefix("renderer=") {
-                            if renderer.is_empty() {
-                                let _ = editor
-                                    .registry
-                                    .delete_value("Software\\Wine\\Direct3D", "renderer")
-                                    .await;
-                            } else {
-                                let _ = editor.set_d3d_renderer(renderer).await;
-                            }
-                            let _ = store.save_setting(
-                                &pp,
-                                "Software\\Wine\\Direct3D",
-                                "renderer",
-                                if renderer.is_empty() {
-                                    None
-                                } else {
-                                    Some(renderer)
-                                },
-                            );
-                        } else if let Some(csmt) = setting_c.strip_prefix("csmt=") {
-                            let v = csmt != "0";
-                            let _ = editor.set_d3d_csmt(v).await;
-                            let _ = store.save_setting(
-                                &pp,
-                                "Software\\Wine\\Direct3D",
-                                "csmt",
-                                Some(csmt),
-                            );
-                        } else if let Some(mode) = setting_c.strip_prefix("OffscreenRenderingMode=")
-                        {
-                            if mode.is_empty() {
-                                let _ = editor
-                                    .registry
-                                    .delete_value(
-                                        "Software\\Wine\\Direct3D",
-                                        "OffscreenRenderingMode",
-                                    )
-                                    .await;
-                            } else {
-                                let _ = editor.set_offscreen_rendering_mode(mode).await;
-                            }
-                            let _ = store.save_setting(
+                    match result {
+                        Ok(report) => {
+                            prefix::prefix_log::record(
                                 &pp,
-                                "Software\\Wine\\Direct3D",
-                                "OffscreenRenderingMode",
-                                if mode.is_empty() { None } else { Some(mode) },
+                                "registry",
+                                &format!(
+                                    "imported {} key(s) from {}",
+                                    report.imported_keys,
+                                    source.display()
+                                ),
                             );
-                        } else if let Some(size) = setting_c.strip_prefix("VideoMemorySize=") {
-                            if let Ok(v) = size.parse::<u32>() {
-                                let _ = editor.set_video_memory_size(v).await;
-                                let _ = store.save_setting(
-                                    &pp,
-                                    "Software\\Wine\\Direct3D",
-                                    "VideoMemorySize",
-                                    Some(size),
-                                );
-                            }
+                            s.input(RegistryEditorMsg::ImportRegComplete(report));
                         }
+                        Err(e) => s.input(RegistryEditorMsg::ShowError(e.to_string())),
                     }
+                });
+            }
 
-                    // ── Platform: Mac Driver ──
-                    "Software\\Wine\\Mac Driver" => {
-                        let mut editor = ec.lock().await;
-                        if let Some((key, val)) = setting_c.split_once('=') {
-                            if let Some(current) =
-                                editor.get_mac_driver_settings().await.ok().flatten()
-                            {
-                                let mut updated = current;
-                                let v = val == "Y";
-                                match key {
-                                    "AllowVerticalSync" => updated.allow_vertical_sync = Some(v),
-                                    "CaptureDisplaysForFullscreen" => {
-                                        updated.capture_displays_for_fullscreen = Some(v)
-                                    }
-                                    "UsePreciseScrolling" => {
-                                        updated.use_precise_scrolling = Some(v)
-                                    }
-                                    "RetinaMode" => updated.retina_mode = Some(v),
-                                    "LeftOptionIsAlt" => updated.left_option_is_alt = Some(v),
-                                    "RightOptionIsAlt" => updated.right_option_is_alt = Some(v),
-                                    "LeftCommandIsCtrl" => updated.left_command_is_ctrl = Some(v),
-                                    "RightCommandIsCtrl" => updated.right_command_is_ctrl = Some(v),
-                                    _ => {}
-                                }
-                                let _ = editor.set_mac_driver_settings(&updated).await;
-                            } else {
-                                let mut settings = MacDriverSettings::new();
-                                let v = val == "Y";
-                                match key {
-                                    "AllowVerticalSync" => settings.allow_vertical_sync = Some(v),
-                                    "CaptureDisplaysForFullscreen" => {
-                                        settings.capture_displays_for_fullscreen = Some(v)
-                                    }
-                                    "UsePreciseScrolling" => {
-                                        settings.use_precise_scrolling = Some(v)
-                                    }
-                                    "RetinaMode" => settings.retina_mode = Some(v),
-                                    "LeftOptionIsAlt" => settings.left_option_is_alt = Some(v),
-                                    "RightOptionIsAlt" => settings.right_option_is_alt = Some(v),
-                                    "LeftCommandIsCtrl" => settings.left_command_is_ctrl = Some(v),
-                                    "RightCommandIsCtrl" => {
-                                        settings.right_command_is_ctrl = Some(v)
-                                    }
-                                    _ => {}
-                                }
-                                let _ = editor.set_mac_driver_settings(&settings).await;
-                            }
-                            let _ = store.save_setting(
-                                &pp,
-                                "Software\\Wine\\Mac Driver",
-                                key,
-                                Some(val),
-                            );
+            RegistryEditorMsg::ImportRegComplete(report) => {
+                let mut body = crate::tf!(
+                    "registry.import_summary",
+                    "keys" => &report.imported_keys.to_string(),
+                    "values" => &report.imported_values.to_string()
+                );
+                if !report.rejected.is_empty() {
+                    body.push_str("\n\n");
+                    body.push_str(&crate::tf!(
+                        "registry.import_rejected",
+                        "count" => &report.rejected.len().to_string()
+                    ));
+                    for r in &report.rejected {
+                        match &r.value_name {
+                            Some(v) => body.push_str(&format!("\n• {} [{}]: {}", r.key_path, v, r.error_message)),
+                            None => body.push_str(&format!("\n• {}: {}", r.key_path, r.error_message)),
                         }
                     }
+                }
+                let alert = adw::AlertDialog::new(Some(&crate::t!("registry.import_title")), Some(&body));
+                alert.add_response("ok", &crate::t!("dialogs.ok"));
+                alert.set_default_response(Some("ok"));
+                alert.set_close_response("ok");
+                alert.choose(
+                    Some(&self.parent_window),
+                    None::<&gtk::gio::Cancellable>,
+                    |_| {},
+                );
+                if report.imported_keys > 0 {
+                    sender.input(RegistryEditorMsg::RefreshReg);
+                }
+            }
 
-                    // ── Platform: X11 Driver ──
-                    "Software\\Wine\\X11 Driver" => {
-                        let mut editor = ec.lock().await;
-                        if let Some((key, val)) = setting_c.split_once('=') {
-                            if let Some(current) =
-                                editor.get_x11_driver_settings().await.ok().flatten()
-                            {
-                                let mut updated = current;
-                                let v = val == "Y";
-                                match key {
-                                    "Decorated" => updated.decorated = Some(v),
-                                    "ClientSideGraphics" => updated.client_side_graphics = Some(v),
-                                    "ClientSideWithRender" => {
-                                        updated.client_side_with_render = Some(v)
-                                    }
-                                    "ClientSideAntiAliasWithRender" => {
-                                        updated.client_side_antialias_with_render = Some(v)
-                                    }
-                                    "ClientSideAntiAliasWithCore" => {
-                                        updated.client_side_antialias_with_core = Some(v)
-                                    }
-                                    "GrabFullscreen" => updated.grab_fullscreen = Some(v),
-                                    "GrabPointer" => updated.grab_pointer = Some(v),
-                                    "Managed" => updated.managed = Some(v),
-                                    "UseXRandR" => updated.use_xrandr = Some(v),
-                                    "UseXVidMode" => updated.use_xvid_mode = Some(v),
-                                    _ => {}
-                                }
-                                let _ = editor.set_x11_driver_settings(&updated).await;
-                            } else {
-                                let mut settings = X11DriverSettings::new();
-                                let v = val == "Y";
-                                match key {
-                                    "Decorated" => settings.decorated = Some(v),
-                                    "ClientSideGraphics" => settings.client_side_graphics = Some(v),
-                                    "ClientSideWithRender" => {
-                                        settings.client_side_with_render = Some(v)
-                                    }
-                                    "ClientSideAntiAliasWithRender" => {
-                                        settings.client_side_antialias_with_render = Some(v)
-                                    }
-                                    "ClientSideAntiAliasWithCore" => {
-                                        settings.client_side_antialias_with_core = Some(v)
-                                    }
-                                    "GrabFullscreen" => settings.grab_fullscreen = Some(v),
-                                    "GrabPointer" => settings.grab_pointer = Some(v),
-                                    "Managed" => settings.managed = Some(v),
-                                    "UseXRandR" => settings.use_xrandr = Some(v),
-                                    "UseXVidMode" => settings.use_xvid_mode = Some(v),
-                                    _ => {}
-                                }
-                                let _ = editor.set_x11_driver_settings(&settings).await;
-                            }
-                            let _ = store.save_setting(
+            RegistryEditorMsg::CopyFromPrefix => {
+                request_prefix_list(&sender, RegistryEditorMsg::PromptCopySource)
+            }
+            RegistryEditorMsg::PromptCopySource(prefixes) => {
+                self.prompt_copy_source(prefixes, &sender)
+            }
+            RegistryEditorMsg::CompareWithPrefix => {
+                request_prefix_list(&sender, RegistryEditorMsg::OpenDiffWindow)
+            }
+            RegistryEditorMsg::OpenDiffWindow(prefixes) => self.open_diff_window(prefixes, &sender),
+
+            RegistryEditorMsg::TabSwitched(page) => self.set_current_tab(page),
+
+            RegistryEditorMsg::ResetTab => {
+                let page = self.current_tab;
+                if SKIP_RESET_CONFIRMATION.load(Ordering::Relaxed) {
+                    sender.input(RegistryEditorMsg::ResetTabConfirmed(page));
+                    return;
+                }
+                let editor_arc = self.registry_editor.clone();
+                let s = sender.clone();
+                self.edit_queue.push(async move {
+                    let entries = match (&editor_arc, reset_targets(page)) {
+                        (Some(ec), Some(targets)) => {
+                            reset_entries(&*ec.lock().await, &targets).await
+                        }
+                        _ => Vec::new(),
+                    };
+                    s.input(RegistryEditorMsg::ConfirmReset(page, entries));
+                });
+            }
+
+            RegistryEditorMsg::ConfirmReset(page, entries) => {
+                self.confirm_reset(page, entries, &sender)
+            }
+
+            RegistryEditorMsg::ResetTabConfirmed(page) => {
+                let Some(targets) = reset_targets(page) else {
+                    return;
+                };
+                let editor_arc = self.registry_editor.clone();
+                let store = Arc::clone(&self.prefix_store);
+                let pp = self.prefix_path.clone();
+                let s = sender.clone();
+                self.edit_queue.push(async move {
+                    let result = async {
+                        let snapshot = RegistrySnapshot::capture(&pp)?;
+                        let editor_arc = match editor_arc {
+                            Some(ec) => ec,
+                            None => Arc::new(Mutex::new(RegistryEditor::with_prefix(&pp).await?)),
+                        };
+                        let editor = editor_arc.lock().await;
+                        for (key_path, value_name) in targets.values {
+                            editor.registry.delete_value(key_path, value_name).await?;
+                        }
+                        for key_path in targets.keys {
+                            editor.registry.delete_key(key_path).await?;
+                        }
+                        editor.save_registry(&pp).await?;
+                        let pp_str = pp.to_string_lossy();
+                        if let Ok((uh, sh)) = hash_registry_files(&pp) {
+                            let _ = store.save_registry_hashes(&pp_str, &uh, &sh);
+                        }
+                        Ok::<RegistrySnapshot, PrefixError>(snapshot)
+                    }
+                    .await;
+
+                    match result {
+                        Ok(snapshot) => {
+                            log::info!("[regedit] reset tab {} to Wine defaults", page);
+                            prefix::prefix_log::record(
                                 &pp,
-                                "Software\\Wine\\X11 Driver",
-                                key,
-                                Some(val),
+                                "registry",
+                                &format!("saved: reset tab {} to Wine defaults", page),
                             );
+                            s.input(RegistryEditorMsg::RegistrySaveComplete(snapshot));
+                            s.input(RegistryEditorMsg::RefreshReg);
                         }
+                        Err(e) => s.input(RegistryEditorMsg::ShowError(e.to_string())),
                     }
+                });
+            }
 
-                    // ── Fonts: FontSubstitutes (HKLM / system.reg) ──
-                    "Software\\Microsoft\\Windows NT\\CurrentVersion\\FontSubstitutes" => {
-                        if let Some((key, val)) = setting_c.split_once('=') {
-                            let key = key.trim();
-                            let val = val.trim();
-                            if !key.is_empty() {
-                                if let Some(system_ec) = system_ec {
-                                    let system_registry = system_ec.lock().await;
-                                    if val.is_empty() {
-                                        let _ = system_registry
-                                            .delete_value(
-                                                "Software\\Microsoft\\Windows NT\\CurrentVersion\\FontSubstitutes",
-                                                key,
-                                            )
-                                            .await;
-                                    } else {
-                                        let _ = system_registry
-                                            .set_value(
-                                                "Software\\Microsoft\\Windows NT\\CurrentVersion\\FontSubstitutes",
-                                                key,
-                                                Value::Sz(val.to_string()),
-                                            )
-                                            .await;
-                                    }
-                                }
-                                let _ = store.save_setting(
-                                    &pp,
+            RegistryEditorMsg::CopyFromPrefixRun(source, groups) => {
+                let editor_arc = self.registry_editor.clone();
+                let store = Arc::clone(&self.prefix_store);
+                let pp = self.prefix_path.clone();
+                let s = sender.clone();
+                self.edit_queue.push(async move {
+                    let result = async {
+                        let snapshot = RegistrySnapshot::capture(&pp)?;
+                        let source_editor = RegistryEditor::with_prefix(&source).await?;
+                        let editor_arc = match editor_arc {
+                            Some(ec) => ec,
+                            None => Arc::new(Mutex::new(RegistryEditor::with_prefix(&pp).await?)),
+                        };
+                        let mut editor = editor_arc.lock().await;
+                        copy_settings(&source_editor, &mut *editor, &groups).await?;
+                        editor.save_registry(&pp).await?;
+                        let pp_str = pp.to_string_lossy();
+                        if let Ok((uh, sh)) = hash_registry_files(&pp) {
+                            let _ = store.save_registry_hashes(&pp_str, &uh, &sh);
+                        }
+                        Ok::<RegistrySnapshot, PrefixError>(snapshot)
+                    }
+                    .await;
+
+                    match result {
+                        Ok(snapshot) => {
+                            log::info!(
+                                "[regedit] copied {} setting group(s) from '{}'",
+                                groups.len(),
+                                source.display()
+                            );
+                            prefix::prefix_log::record(
+                                &pp,
+                                "registry",
+                                &format!(
+                                    "saved: copied {} setting group(s) from {}",
+                                    groups.len(),
+                                    source.display()
+                                ),
+                            );
+                            // Undoable like a regular save
+                            s.input(RegistryEditorMsg::RegistrySaveComplete(snapshot));
+                            s.input(RegistryEditorMsg::RefreshReg);
+                        }
+                        Err(e) => s.input(RegistryEditorMsg::ShowError(e.to_string())),
+                    }
+                });
+            }
+
+            RegistryEditorMsg::ShowError(error) => {
+                log::error!("[regedit] {}", error);
+                let alert = adw::AlertDialog::new(Some(&crate::t!("dialogs.error")), Some(&error));
+                alert.add_response("ok", &crate::t!("dialogs.ok"));
+                alert.set_default_response(Some("ok"));
+                alert.set_close_response("ok");
+                alert.choose(
+                    Some(&self.parent_window),
+                    None::<&gtk::gio::Cancellable>,
+                    |_| {},
+                );
+            }
+
+            RegistryEditorMsg::PollProcesses => {
+                let pp = self.prefix_path.clone();
+                let winecfg_track = pp.join("__wine_winecfg__");
+                let regedit_track = pp.join("__wine_regedit__");
+                let mut tracker = self.process_tracker.lock().unwrap();
+
+                // Clean up dead processes and re-check running state
+                let _changed = tracker.poll_dead();
+                let winecfg_alive = tracker.is_running(&winecfg_track);
+                let regedit_alive = tracker.is_running(&regedit_track);
+                drop(tracker);
+
+                self.set_winecfg_running(winecfg_alive);
+                self.set_regedit_running(regedit_alive);
+
+                if winecfg_alive {
+                    self.winecfg_spinner.start();
+                    self.winecfg_btn.set_child(Some(&self.winecfg_spinner));
+                } else {
+                    self.winecfg_spinner.stop();
+                    self.winecfg_btn.set_child(Some(&self.winecfg_icon));
+                }
+
+                if regedit_alive {
+                    self.regedit_spinner.start();
+                    self.regedit_btn.set_child(Some(&self.regedit_spinner));
+                } else {
+                    self.regedit_spinner.stop();
+                    self.regedit_btn.set_child(Some(&self.regedit_icon));
+                }
+            }
+        }
+    }
+}
+
+// ── Pending edits ────────────────────────────────────────────────────────
+
+impl RegistryEditorModel {
+    /// Buffer a tab edit until Save. A later edit of the same value replaces
+    /// the earlier one.
+    fn queue_setting(&mut self, section: String, setting: String) {
+        if !self.editing {
+            return;
+        }
+        if self.pending_settings.is_empty() {
+            self.external_change_warned = false;
+        }
+        let value_name = |setting: &str| setting.rsplit_once('=').map(|(k, _)| k.to_string());
+        let name = value_name(&setting);
+        self.pending_settings
+            .retain(|(s, existing)| *s != section || value_name(existing) != name);
+        self.pending_settings.push((section, setting));
+    }
+}
+
+/// Write one buffered `(section, setting)` edit into the in-memory registries;
+/// nothing reaches the `.reg` files until they are saved.
+async fn write_setting(
+    ec: &Arc<Mutex<RegistryEditor>>,
+    system_ec: Option<&Arc<Mutex<WineRegistry>>>,
+    section: &str,
+    setting: &str,
+) {
+    match section {
+        // ── General: Windows Version ──
+        "Software\\Wine" => {
+            let version = setting.strip_prefix("Version=").unwrap_or(setting);
+            let mut editor = ec.lock().await;
+            let version = if version.is_empty() {
+                None
+            } else {
+                Some(version.to_string())
+            };
+            if let Some(ref v) = version {
+                let _ = editor.set_windows_version(v).await;
+                if let Some(system_ec) = system_ec {
+                    let system_registry = system_ec.lock().await;
+                    if let Err(e) = RegistryEditor::set_nt_version_info(&system_registry, v).await {
+                        log::warn!("[registry] failed to write NT version info: {}", e);
+                    }
+                }
+            } else {
+                let _ = editor
+                    .registry
+                    .delete_value("Software\\Wine", "Version")
+                    .await;
+            }
+        }
+
+        // ── General: Audio Driver ──
+        "Software\\Wine\\Drivers\\Audio" => {
+            let mut editor = ec.lock().await;
+            let driver = (setting != general_tab::AUDIO_DEFAULT).then_some(setting);
+            if let Some(driver) = driver {
+                let _ = editor.set_audio_driver(driver).await;
+            } else {
+                let _ = editor
+                    .registry
+                    .delete_value("Software\\Wine\\Drivers\\Audio", "")
+                    .await;
+            }
+        }
+
+        // ── General: DPI ──
+        "Control Panel\\Desktop" => {
+            if let Some(log_pixels) = setting.strip_prefix("LogPixels=") {
+                if let Ok(v) = log_pixels.parse::<u32>() {
+                    let mut editor = ec.lock().await;
+                    let dpi = DpiSettings {
+                        log_pixels: Some(v),
+                    };
+                    let _ = editor.set_dpi_settings(&dpi).await;
+                }
+            }
+        }
+
+        // ── General: Virtual Desktop enabled ──
+        "Software\\Wine\\Explorer" => {
+            let mut editor = ec.lock().await;
+            let current = editor.get_virtual_desktop().await.ok().flatten().unwrap_or(
+                VirtualDesktopSettings {
+                    enabled: false,
+                    width: 1024,
+                    height: 768,
+                },
+            );
+            let enabled = !setting.is_empty();
+            let updated = VirtualDesktopSettings {
+                enabled,
+                width: current.width,
+                height: current.height,
+            };
+            let _ = editor.set_virtual_desktop(&updated).await;
+        }
+
+        // ── General: Virtual Desktop size ──
+        "Software\\Wine\\Explorer\\Desktops" => {
+            if let Some(size) = setting.strip_prefix("Default=") {
+                if let Some((w_str, h_str)) = size.split_once('x') {
+                    if let (Ok(w), Ok(h)) = (w_str.parse::<u32>(), h_str.parse::<u32>()) {
+                        let mut editor = ec.lock().await;
+                        let current = editor.get_virtual_desktop().await.ok().flatten().unwrap_or(
+                            VirtualDesktopSettings {
+                                enabled: false,
+                                width: 1024,
+                                height: 768,
+                            },
+                        );
+                        let updated = VirtualDesktopSettings {
+                            enabled: current.enabled,
+                            width: w,
+                            height: h,
+                        };
+                        let _ = editor.set_virtual_desktop(&updated).await;
+                    }
+                }
+            }
+        }
+
+        // ── Graphics: D3D settings ──
+        "Software\\Wine\\Direct3D" => {
+            let mut editor = ec.lock().await;
+            if let Some(renderer) = setting.strip_prefix("renderer=") {
+                if renderer.is_empty() {
+                    let _ = editor
+                        .registry
+                        .delete_value("Software\\Wine\\Direct3D", "renderer")
+                        .await;
+                } else {
+                    let _ = editor.set_d3d_renderer(renderer).await;
+                }
+            } else if let Some(csmt) = setting.strip_prefix("csmt=") {
+                let v = csmt != "0";
+                let _ = editor.set_d3d_csmt(v).await;
+            } else if let Some(mode) = setting.strip_prefix("OffscreenRenderingMode=") {
+                if mode.is_empty() {
+                    let _ = editor
+                        .registry
+                        .delete_value("Software\\Wine\\Direct3D", "OffscreenRenderingMode")
+                        .await;
+                } else {
+                    let _ = editor.set_offscreen_rendering_mode(mode).await;
+                }
+            } else if let Some(size) = setting.strip_prefix("VideoMemorySize=") {
+                if let Ok(v) = size.parse::<u32>() {
+                    let _ = editor.set_video_memory_size(v).await;
+                }
+            }
+        }
+
+        // ── Input: DirectInput ──
+        "Software\\Wine\\DirectInput" => {
+            let mut editor = ec.lock().await;
+            if let Some((key, val)) = setting.split_once('=') {
+                let mut settings = DirectInputSettings::default();
+                match key {
+                    "MouseWarpOverride" => {
+                        settings.mouse_warp_override = MouseWarpOverride::from_string(val);
+                    }
+                    "DefaultDeadZone" => {
+                        settings.default_dead_zone = val.parse().ok();
+                    }
+                    _ => return,
+                }
+                if val.is_empty() {
+                    let _ = editor
+                        .registry
+                        .delete_value("Software\\Wine\\DirectInput", key)
+                        .await;
+                } else {
+                    let _ = editor.set_direct_input_settings(&settings).await;
+                }
+            }
+        }
+
+        // ── Input: per-device joystick state ──
+        "Software\\Wine\\DirectInput\\Joysticks" => {
+            if let Some((name, state)) = setting.rsplit_once('=') {
+                let mut editor = ec.lock().await;
+                match JoystickState::from_string(state) {
+                    Some(state) => {
+                        let _ = editor.set_joystick_state(name, state).await;
+                    }
+                    None => {
+                        let _ = editor.remove_joystick_override(name).await;
+                    }
+                }
+            }
+        }
+
+        // ── Platform: Mac Driver ──
+        "Software\\Wine\\Mac Driver" => {
+            let mut editor = ec.lock().await;
+            if let Some((key, val)) = setting.split_once('=') {
+                if let Some(current) = editor.get_mac_driver_settings().await.ok().flatten() {
+                    let mut updated = current;
+                    let v = val == "Y";
+                    match key {
+                        "AllowVerticalSync" => updated.allow_vertical_sync = Some(v),
+                        "CaptureDisplaysForFullscreen" => {
+                            updated.capture_displays_for_fullscreen = Some(v)
+                        }
+                        "UsePreciseScrolling" => updated.use_precise_scrolling = Some(v),
+                        "RetinaMode" => updated.retina_mode = Some(v),
+                        "LeftOptionIsAlt" => updated.left_option_is_alt = Some(v),
+                        "RightOptionIsAlt" => updated.right_option_is_alt = Some(v),
+                        "LeftCommandIsCtrl" => updated.left_command_is_ctrl = Some(v),
+                        "RightCommandIsCtrl" => updated.right_command_is_ctrl = Some(v),
+                        _ => {}
+                    }
+                    let _ = editor.set_mac_driver_settings(&updated).await;
+                } else {
+                    let mut settings = MacDriverSettings::new();
+                    let v = val == "Y";
+                    match key {
+                        "AllowVerticalSync" => settings.allow_vertical_sync = Some(v),
+                        "CaptureDisplaysForFullscreen" => {
+                            settings.capture_displays_for_fullscreen = Some(v)
+                        }
+                        "UsePreciseScrolling" => settings.use_precise_scrolling = Some(v),
+                        "RetinaMode" => settings.retina_mode = Some(v),
+                        "LeftOptionIsAlt" => settings.left_option_is_alt = Some(v),
+                        "RightOptionIsAlt" => settings.right_option_is_alt = Some(v),
+                        "LeftCommandIsCtrl" => settings.left_command_is_ctrl = Some(v),
+                        "RightCommandIsCtrl" => settings.right_command_is_ctrl = Some(v),
+                        _ => {}
+                    }
+                    let _ = editor.set_mac_driver_settings(&settings).await;
+                }
+            }
+        }
+
+        // ── Platform: X11 Driver ──
+        "Software\\Wine\\X11 Driver" => {
+            let mut editor = ec.lock().await;
+            if let Some((key, val)) = setting.split_once('=') {
+                if let Some(current) = editor.get_x11_driver_settings().await.ok().flatten() {
+                    let mut updated = current;
+                    let v = val == "Y";
+                    match key {
+                        "Decorated" => updated.decorated = Some(v),
+                        "ClientSideGraphics" => updated.client_side_graphics = Some(v),
+                        "ClientSideWithRender" => updated.client_side_with_render = Some(v),
+                        "ClientSideAntiAliasWithRender" => {
+                            updated.client_side_antialias_with_render = Some(v)
+                        }
+                        "ClientSideAntiAliasWithCore" => {
+                            updated.client_side_antialias_with_core = Some(v)
+                        }
+                        "GrabFullscreen" => updated.grab_fullscreen = Some(v),
+                        "GrabPointer" => updated.grab_pointer = Some(v),
+                        "Managed" => updated.managed = Some(v),
+                        "UseXRandR" => updated.use_xrandr = Some(v),
+                        "UseXVidMode" => updated.use_xvid_mode = Some(v),
+                        "UsePrimarySelection" => updated.use_primary_selection = Some(v),
+                        _ => {}
+                    }
+                    let _ = editor.set_x11_driver_settings(&updated).await;
+                } else {
+                    let mut settings = X11DriverSettings::new();
+                    let v = val == "Y";
+                    match key {
+                        "Decorated" => settings.decorated = Some(v),
+                        "ClientSideGraphics" => settings.client_side_graphics = Some(v),
+                        "ClientSideWithRender" => settings.client_side_with_render = Some(v),
+                        "ClientSideAntiAliasWithRender" => {
+                            settings.client_side_antialias_with_render = Some(v)
+                        }
+                        "ClientSideAntiAliasWithCore" => {
+                            settings.client_side_antialias_with_core = Some(v)
+                        }
+                        "GrabFullscreen" => settings.grab_fullscreen = Some(v),
+                        "GrabPointer" => settings.grab_pointer = Some(v),
+                        "Managed" => settings.managed = Some(v),
+                        "UseXRandR" => settings.use_xrandr = Some(v),
+                        "UseXVidMode" => settings.use_xvid_mode = Some(v),
+                        "UsePrimarySelection" => settings.use_primary_selection = Some(v),
+                        _ => {}
+                    }
+                    let _ = editor.set_x11_driver_settings(&settings).await;
+                }
+            }
+        }
+
+        // ── Fonts: FontSubstitutes (HKLM / system.reg) ──
+        "Software\\Microsoft\\Windows NT\\CurrentVersion\\FontSubstitutes" => {
+            if let Some((key, val)) = setting.split_once('=') {
+                let key = key.trim();
+                let val = val.trim();
+                if !key.is_empty() {
+                    if let Some(system_ec) = system_ec {
+                        let system_registry = system_ec.lock().await;
+                        if val.is_empty() {
+                            let _ = system_registry
+                                .delete_value(
                                     "Software\\Microsoft\\Windows NT\\CurrentVersion\\FontSubstitutes",
                                     key,
-                                    if val.is_empty() { None } else { Some(val) },
-                                );
-                            }
+                                )
+                                .await;
+                        } else {
+                            let _ = system_registry
+                                .set_value(
+                                    "Software\\Microsoft\\Windows NT\\CurrentVersion\\FontSubstitutes",
+                                    key,
+                                    Value::Sz(val.to_string()),
+                                )
+                                .await;
                         }
                     }
+                }
+            }
+        }
+
+        _ => {}
+    }
+}
+
+/// Record one saved `(section, setting)` edit in the settings cache, the way
+/// a registry load caches it. Only called once the edit is on disk, so a
+/// declined or failed save never shows up as the current value.
+async fn cache_setting(
+    ec: &Arc<Mutex<RegistryEditor>>,
+    store: &prefix::PrefixStore,
+    pp: &str,
+    section: &str,
+    setting: &str,
+) {
+    let set = |v: &str| (!v.is_empty()).then(|| v.to_string());
+    let mut entries: Vec<(&str, &str, Option<String>)> = Vec::new();
+    match section {
+        "Software\\Wine" => {
+            let version = setting.strip_prefix("Version=").unwrap_or(setting);
+            entries.push((section, "Version", set(version)));
+        }
+        "Software\\Wine\\Drivers\\Audio" => {
+            let driver = (setting != general_tab::AUDIO_DEFAULT).then(|| setting.to_string());
+            entries.push((section, "", driver));
+        }
+        "Control Panel\\Desktop" => {
+            if let Some(log_pixels) = setting.strip_prefix("LogPixels=")
+                && log_pixels.parse::<u32>().is_ok()
+            {
+                entries.push((section, "LogPixels", set(log_pixels)));
+            }
+        }
+        "Software\\Wine\\Explorer" => {
+            let enabled = !setting.is_empty();
+            let desktop = if enabled { "Default" } else { "" };
+            entries.push((section, "Desktop", Some(desktop.to_string())));
+            if enabled && let Some(vd) = ec.lock().await.get_virtual_desktop().await.ok().flatten()
+            {
+                let size = format!("{}x{}", vd.width, vd.height);
+                entries.push(("Software\\Wine\\Explorer\\Desktops", "Default", Some(size)));
+            }
+        }
+        "Software\\Wine\\Explorer\\Desktops" => {
+            if let Some(size) = setting.strip_prefix("Default=")
+                && DesktopSize::from_string(size).is_some()
+            {
+                entries.push((section, "Default", set(size)));
+            }
+        }
+        "Software\\Wine\\Direct3D" => match setting.split_once('=') {
+            Some((key @ ("renderer" | "OffscreenRenderingMode"), val)) => {
+                entries.push((section, key, set(val)));
+            }
+            Some((key @ "csmt", val)) => entries.push((section, key, Some(val.to_string()))),
+            Some((key @ "VideoMemorySize", val)) if val.parse::<u32>().is_ok() => {
+                entries.push((section, key, set(val)));
+            }
+            _ => {}
+        },
+        "Software\\Wine\\DirectInput" => {
+            if let Some((key @ ("MouseWarpOverride" | "DefaultDeadZone"), val)) =
+                setting.split_once('=')
+            {
+                entries.push((section, key, set(val)));
+            }
+        }
+        "Software\\Wine\\DirectInput\\Joysticks" => {
+            if let Some((name, state)) = setting.rsplit_once('=') {
+                entries.push((section, name, set(state)));
+            }
+        }
+        "Software\\Wine\\Mac Driver" | "Software\\Wine\\X11 Driver" => {
+            if let Some((key, val)) = setting.split_once('=') {
+                entries.push((section, key, Some(val.to_string())));
+            }
+        }
+        "Software\\Microsoft\\Windows NT\\CurrentVersion\\FontSubstitutes" => {
+            if let Some((key, val)) = setting.split_once('=')
+                && !key.trim().is_empty()
+            {
+                entries.push((section, key.trim(), set(val.trim())));
+            }
+        }
+        _ => {}
+    }
+    for (section, key, value) in entries {
+        let _ = store.save_setting(pp, section, key, value.as_deref());
+    }
+}
+
+/// Note Windows version changes in the prefix history once they are saved.
+fn note_saved_settings(prefix_path: &Path, settings: &[(String, String)]) {
+    for (section, setting) in settings {
+        if section != "Software\\Wine" {
+            continue;
+        }
+        let note = match setting.strip_prefix("Version=").unwrap_or(setting) {
+            "" => "Windows version reset to default".to_string(),
+            v => format!("Windows version set to {}", v),
+        };
+        let _ = AppService::global()
+            .prefix_manager()
+            .add_prefix_note(prefix_path, &note, true);
+    }
+}
+
+/// Write a registry save and the settings it changed to the prefix log.
+fn log_saved_settings(prefix_path: &Path, settings: &[(String, String)]) {
+    let mut lines = vec![format!("saved {} setting(s)", settings.len())];
+    lines.extend(
+        settings
+            .iter()
+            .map(|(section, setting)| format!("  [{}] {}", section, setting)),
+    );
+    prefix::prefix_log::record(prefix_path, "registry", &lines.join("\n"));
+}
+
+// ── Compare with another prefix ──────────────────────────────────────────
+
+impl RegistryEditorModel {
+    fn open_diff_window(
+        &mut self,
+        prefixes: Vec<(String, PathBuf)>,
+        sender: &ComponentSender<Self>,
+    ) {
+        if prefixes.len() < 2 {
+            sender.input(RegistryEditorMsg::ShowError(crate::t!(
+                "registry.diff.no_other"
+            )));
+            return;
+        }
+        let current = prefixes
+            .iter()
+            .position(|(_, path)| *path == self.prefix_path)
+            .unwrap_or(0);
+        self.diff_window = Some(
+            diff_view::RegistryDiffModel::builder()
+                .launch((self.parent_window.clone(), prefixes, current))
+                .detach(),
+        );
+    }
+}
+
+/// Scan for prefixes off the main thread, since the scan loads every
+/// prefix's config, and send their `(name, path)` pairs as `msg`. A failed
+/// scan sends an empty list.
+fn request_prefix_list(
+    sender: &ComponentSender<RegistryEditorModel>,
+    msg: fn(Vec<(String, PathBuf)>) -> RegistryEditorMsg,
+) {
+    let pm = AppService::global().prefix_manager().clone();
+    let s = sender.clone();
+    gtk::glib::spawn_future_local(async move {
+        let scan = gtk::gio::spawn_blocking(move || {
+            pm.scan_prefixes()
+                .map(|prefixes| prefixes.into_iter().map(|p| (p.name, p.path)).collect())
+                .unwrap_or_default()
+        });
+        match scan.await {
+            Ok(prefixes) => s.input(msg(prefixes)),
+            Err(_) => log::warn!("[regedit] prefix scan task panicked"),
+        }
+    });
+}
+
+// ── External changes ─────────────────────────────────────────────────────
+
+impl RegistryEditorModel {
+    /// The `.reg` files changed since they were loaded (winecfg, regedit or a
+    /// text editor). Offer to reload, dropping these edits, or to overwrite.
+    fn confirm_overwrite_external_changes(&self, sender: &ComponentSender<Self>) {
+        let alert = adw::AlertDialog::new(
+            Some(&crate::t!("registry.external_change.title")),
+            Some(&crate::t!("registry.external_change.body")),
+        );
+        alert.add_response("cancel", &crate::t!("dialogs.cancel"));
+        alert.add_response("reload", &crate::t!("registry.external_change.reload"));
+        alert.add_response(
+            "overwrite",
+            &crate::t!("registry.external_change.overwrite"),
+        );
+        alert.set_response_appearance("overwrite", adw::ResponseAppearance::Destructive);
+        alert.set_default_response(Some("reload"));
+        alert.set_close_response("cancel");
+        let s = sender.clone();
+        alert.choose(
+            Some(&self.parent_window),
+            None::<&gtk::gio::Cancellable>,
+            move |response| match response.as_str() {
+                "reload" => s.input(RegistryEditorMsg::RefreshReg),
+                "overwrite" => s.input(RegistryEditorMsg::OverwriteExternalChanges),
+                _ => {}
+            },
+        );
+    }
 
-                    _ => {}
+    /// List the values a save would write and save only once confirmed.
+    fn confirm_pending_changes(
+        &self,
+        changes: Vec<(String, String, Value)>,
+        sender: &ComponentSender<Self>,
+    ) {
+        let list = gtk::ListBox::builder()
+            .selection_mode(gtk::SelectionMode::None)
+            .css_classes(["boxed-list"])
+            .build();
+        for (key, name, value) in &changes {
+            let data = match value {
+                Value::Delete => crate::t!("registry.diff.removed"),
+                other => display_value(other),
+            };
+            let row = adw::ActionRow::builder()
+                .title(name)
+                .subtitle(key)
+                .use_markup(false)
+                .build();
+            row.add_suffix(
+                &gtk::Label::builder()
+                    .label(data)
+                    .ellipsize(gtk::pango::EllipsizeMode::End)
+                    .max_width_chars(24)
+                    .selectable(true)
+                    .css_classes(["dim-label"])
+                    .build(),
+            );
+            list.append(&row);
+        }
+        let scrolled = gtk::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .propagate_natural_height(true)
+            .max_content_height(360)
+            .child(&list)
+            .build();
+
+        let alert = adw::AlertDialog::new(
+            Some(&crate::t!("registry.preview.title")),
+            Some(&crate::tf!("registry.preview.body", "count" => &changes.len().to_string())),
+        );
+        alert.set_extra_child(Some(&scrolled));
+        alert.add_response("cancel", &crate::t!("dialogs.cancel"));
+        alert.add_response("save", &crate::t!("registry.preview.save"));
+        alert.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+        alert.set_default_response(Some("save"));
+        alert.set_close_response("cancel");
+        let s = sender.clone();
+        alert.choose(
+            Some(&self.parent_window),
+            None::<&gtk::gio::Cancellable>,
+            move |response| {
+                if response == "save" {
+                    s.input(RegistryEditorMsg::SaveRegistryConfirmed);
+                } else {
+                    // The edits are already in the loaded registries
+                    s.input(RegistryEditorMsg::CancelEdit);
                 }
-            });
+            },
+        );
+    }
+}
+
+impl RegistryEditorModel {
+    /// List what resetting `page` removes and reset only once confirmed.
+    /// "Don't ask again" lasts until Tequila quits.
+    fn confirm_reset(
+        &self,
+        page: u32,
+        entries: Vec<(String, String, String)>,
+        sender: &ComponentSender<Self>,
+    ) {
+        let content = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .spacing(12)
+            .build();
+        if !entries.is_empty() {
+            let list = gtk::ListBox::builder()
+                .selection_mode(gtk::SelectionMode::None)
+                .css_classes(["boxed-list"])
+                .build();
+            for (title, subtitle, detail) in &entries {
+                let row = adw::ActionRow::builder()
+                    .title(title)
+                    .subtitle(subtitle)
+                    .use_markup(false)
+                    .build();
+                row.add_suffix(
+                    &gtk::Label::builder()
+                        .label(detail)
+                        .ellipsize(gtk::pango::EllipsizeMode::End)
+                        .max_width_chars(24)
+                        .css_classes(["dim-label"])
+                        .build(),
+                );
+                list.append(&row);
+            }
+            content.append(
+                &gtk::ScrolledWindow::builder()
+                    .hscrollbar_policy(gtk::PolicyType::Never)
+                    .propagate_natural_height(true)
+                    .max_content_height(360)
+                    .child(&list)
+                    .build(),
+            );
         }
+        let dont_ask = gtk::CheckButton::with_label(&crate::t!("registry.reset.dont_ask"));
+        content.append(&dont_ask);
+
+        let alert = adw::AlertDialog::new(
+            Some(&crate::t!("registry.reset.title")),
+            Some(&crate::t!("registry.reset.desc")),
+        );
+        alert.set_extra_child(Some(&content));
+        alert.add_response("cancel", &crate::t!("dialogs.cancel"));
+        alert.add_response("reset", &crate::t!("registry.reset.confirm"));
+        alert.set_response_appearance("reset", adw::ResponseAppearance::Destructive);
+        alert.set_default_response(Some("cancel"));
+        alert.set_close_response("cancel");
+        let s = sender.clone();
+        alert.choose(
+            Some(&self.parent_window),
+            None::<&gtk::gio::Cancellable>,
+            move |response| {
+                if response == "reset" {
+                    if dont_ask.is_active() {
+                        SKIP_RESET_CONFIRMATION.store(true, Ordering::Relaxed);
+                    }
+                    s.input(RegistryEditorMsg::ResetTabConfirmed(page));
+                }
+            },
+        );
+    }
+}
+
+/// What resetting `targets` would remove from `editor`, as rows for
+/// [`RegistryEditorModel::confirm_reset`]: each value with its data, then
+/// each key with how many values it holds. Anything already absent is left
+/// out.
+async fn reset_entries(
+    editor: &RegistryEditor,
+    targets: &ResetTargets,
+) -> Vec<(String, String, String)> {
+    let hive = editor.registry.hive_root();
+    let mut entries = Vec::new();
+    for &(key_path, value_name) in targets.values {
+        if let Ok(Some(value)) = editor.registry.get_value(key_path, value_name).await {
+            entries.push((
+                value_name.to_string(),
+                format!("{}\\{}", hive, key_path),
+                display_value(&value),
+            ));
+        }
+    }
+    for &key_path in targets.keys {
+        if !editor.registry.key_exists(key_path).await.unwrap_or(false) {
+            continue;
+        }
+        let count = editor
+            .registry
+            .get_key_values(key_path)
+            .await
+            .map(|values| values.len())
+            .unwrap_or(0);
+        entries.push((
+            key_path.rsplit('\\').next().unwrap_or(key_path).to_string(),
+            format!("{}\\{}", hive, key_path),
+            crate::tf!("registry.reset.value_count", "count" => &count.to_string()),
+        ));
+    }
+    entries
+}
+
+/// Everything a save would write: the user registry's edits since load and
+/// how the in-memory `system.reg` differs from the file on disk.
+async fn pending_registry_changes(
+    prefix_path: &Path,
+    editor: Option<&Arc<Mutex<RegistryEditor>>>,
+    system: Option<&Arc<Mutex<WineRegistry>>>,
+) -> Result<Vec<(String, String, Value)>, PrefixError> {
+    let mut changes = Vec::new();
+    if let Some(editor) = editor {
+        let editor = editor.lock().await;
+        let hive = editor.registry.hive_root();
+        for (key, name, value) in editor.pending_changes().await? {
+            changes.push((format!("{}\\{}", hive, key), name, value));
+        }
+    }
+    if let Some(system) = system {
+        let on_disk = WineRegistry::load_from_file(&prefix_path.join("system.reg")).await?;
+        let hive = on_disk.hive_root();
+        for (key, name, value) in system.lock().await.changes_since(&on_disk).await? {
+            changes.push((format!("{}\\{}", hive, key), name, value));
+        }
+    }
+    Ok(changes)
+}
+
+/// Newest modification time of the prefix's `user.reg` and `system.reg`.
+fn reg_files_mtime(prefix_path: &Path) -> Option<SystemTime> {
+    ["user.reg", "system.reg"]
+        .iter()
+        .filter_map(|file| prefix_path.join(file).metadata().ok()?.modified().ok())
+        .max()
+}
+
+// ── Copy from another prefix ─────────────────────────────────────────────
+
+impl RegistryEditorModel {
+    /// Ask which prefix to copy from and which setting groups to take.
+    fn prompt_copy_source(&self, prefixes: Vec<(String, PathBuf)>, sender: &ComponentSender<Self>) {
+        let sources: Vec<(String, PathBuf)> = prefixes
+            .into_iter()
+            .filter(|(_, path)| *path != self.prefix_path)
+            .collect();
+        if sources.is_empty() {
+            sender.input(RegistryEditorMsg::ShowError(crate::t!(
+                "registry.copy.no_sources"
+            )));
+            return;
+        }
+
+        let names: Vec<&str> = sources.iter().map(|(name, _)| name.as_str()).collect();
+        let source_dropdown = gtk::DropDown::from_strings(&names);
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 6);
+        content.append(&source_dropdown);
+        let checks: Vec<(SettingGroup, gtk::CheckButton)> = SettingGroup::ALL
+            .iter()
+            .map(|&group| {
+                let check = gtk::CheckButton::builder()
+                    .label(setting_group_label(group))
+                    .active(true)
+                    .build();
+                content.append(&check);
+                (group, check)
+            })
+            .collect();
+
+        let alert = adw::AlertDialog::new(
+            Some(&crate::t!("registry.copy.title")),
+            Some(&crate::t!("registry.copy.desc")),
+        );
+        alert.set_extra_child(Some(&content));
+        alert.add_response("cancel", &crate::t!("dialogs.cancel"));
+        alert.add_response("copy", &crate::t!("registry.copy.confirm"));
+        alert.set_response_appearance("copy", adw::ResponseAppearance::Suggested);
+        alert.set_default_response(Some("copy"));
+        alert.set_close_response("cancel");
+        let sender = sender.clone();
+        alert.choose(
+            Some(&self.parent_window),
+            None::<&gtk::gio::Cancellable>,
+            move |response| {
+                if response != "copy" {
+                    return;
+                }
+                let Some((_, source)) = sources.get(source_dropdown.selected() as usize) else {
+                    return;
+                };
+                let groups: Vec<SettingGroup> = checks
+                    .iter()
+                    .filter(|(_, check)| check.is_active())
+                    .map(|(group, _)| *group)
+                    .collect();
+                if !groups.is_empty() {
+                    sender.input(RegistryEditorMsg::CopyFromPrefixRun(source.clone(), groups));
+                }
+            },
+        );
+    }
+}
+
+fn setting_group_label(group: SettingGroup) -> String {
+    match group {
+        SettingGroup::WindowsVersion => crate::t!("registry.copy.group.windows_version"),
+        SettingGroup::Direct3D => crate::t!("registry.copy.group.direct3d"),
+        SettingGroup::Audio => crate::t!("registry.copy.group.audio"),
+        SettingGroup::Drivers => crate::t!("registry.copy.group.drivers"),
+        SettingGroup::Dpi => crate::t!("registry.copy.group.dpi"),
+        SettingGroup::VirtualDesktop => crate::t!("registry.copy.group.virtual_desktop"),
     }
 }
 
 // ── Helpers ──────────────────────────────────────────────────────────────
 
+/// Set by "Don't ask again" in the reset confirmation; resets then go
+/// ahead without asking for the rest of the session.
+static SKIP_RESET_CONFIRMATION: AtomicBool = AtomicBool::new(false);
+
+/// Registry values and keys owned by a notebook page, removed by "Reset" so
+/// Wine falls back to its built-in defaults.
+struct ResetTargets {
+    values: &'static [(&'static str, &'static str)],
+    keys: &'static [&'static str],
+}
+
+/// `None` for pages that can't be reset safely: font substitutes live in
+/// `system.reg` alongside the ones `wine.inf` installs.
+fn reset_targets(page: u32) -> Option<ResetTargets> {
+    match page {
+        // General
+        0 => Some(ResetTargets {
+            values: &[
+                ("Software\\Wine", "Version"),
+                ("Control Panel\\Desktop", "LogPixels"),
+                ("Software\\Wine\\Explorer", "Desktop"),
+            ],
+            keys: &[
+                "Software\\Wine\\Drivers\\Audio",
+                "Software\\Wine\\Explorer\\Desktops",
+            ],
+        }),
+        // Graphics
+        1 => Some(ResetTargets {
+            values: &[
+                ("Software\\Wine\\Direct3D", "renderer"),
+                ("Software\\Wine\\Direct3D", "csmt"),
+                ("Software\\Wine\\Direct3D", "OffscreenRenderingMode"),
+                ("Software\\Wine\\Direct3D", "VideoMemorySize"),
+            ],
+            keys: &[],
+        }),
+        // Platform
+        3 => Some(ResetTargets {
+            values: &[],
+            keys: &["Software\\Wine\\X11 Driver", "Software\\Wine\\Mac Driver"],
+        }),
+        // Input
+        4 => Some(ResetTargets {
+            values: &[
+                ("Software\\Wine\\DirectInput", "MouseWarpOverride"),
+                ("Software\\Wine\\DirectInput", "DefaultDeadZone"),
+            ],
+            keys: &["Software\\Wine\\DirectInput\\Joysticks"],
+        }),
+        _ => None,
+    }
+}
+
 fn set_editing_all_tabs(
     general: &Controller<GeneralTabModel>,
     graphics: &Controller<GraphicsTabModel>,
     platform: &Controller<PlatformTabModel>,
     fonts: &Controller<FontsTabModel>,
+    input: &Controller<InputTabModel>,
     editing: bool,
 ) {
     general.emit(general_tab::GeneralTabInput::SetEditing(editing));
     graphics.emit(graphics_tab::GraphicsTabInput::SetEditing(editing));
     platform.emit(platform_tab::PlatformTabInput::SetEditing(editing));
     fonts.emit(fonts_tab::FontsTabInput::SetEditing(editing));
+    input.emit(input_tab::InputTabInput::SetEditing(editing));
 }
 
 fn default_general_settings() -> GeneralSettings {
@@ -1187,12 +2350,13 @@ fn load_settings_from_cache(
     load_dword: &dyn Fn(&str, &str) -> Option<u32>,
     sender: &ComponentSender<RegistryEditorModel>,
 ) {
+    // The cache stores the virtual desktop normalised to the "Default" slot
     let load_vd = || -> Option<VirtualDesktopSettings> {
-        let e = load("Software\\Wine\\Explorer", "Desktop")?;
-        let sz = load("Software\\Wine\\Explorer\\Desktops", "Default")?;
-        let size = DesktopSize::from_string(&sz).unwrap_or_else(|| DesktopSize::new(1024, 768));
+        let size = load("Software\\Wine\\Explorer\\Desktops", "Default")
+            .and_then(|sz| DesktopSize::from_string(&sz))?;
+        let enabled = load("Software\\Wine\\Explorer", "Desktop").is_some_and(|e| !e.is_empty());
         Some(VirtualDesktopSettings {
-            enabled: !e.is_empty(),
+            enabled,
             width: size.width,
             height: size.height,
         })
@@ -1226,30 +2390,30 @@ fn load_settings_from_cache(
                             "Software\\Wine\\Mac Driver",
                             "AllowVerticalSync",
                         )
-                        .map(|v| matches!(v.as_str(), "Y" | "y" | "T" | "t" | "1")),
+                        .map(|v| is_option_true(&v)),
                         capture_displays: load(
                             "Software\\Wine\\Mac Driver",
                             "CaptureDisplaysForFullscreen",
                         )
-                        .map(|v| matches!(v.as_str(), "Y" | "y" | "T" | "t" | "1")),
+                        .map(|v| is_option_true(&v)),
                         precise_scrolling: load(
                             "Software\\Wine\\Mac Driver",
                             "UsePreciseScrolling",
                         )
-                        .map(|v| matches!(v.as_str(), "Y" | "y" | "T" | "t" | "1")),
+                        .map(|v| is_option_true(&v)),
                         retina_mode: load("Software\\Wine\\Mac Driver", "RetinaMode")
-                            .map(|v| matches!(v.as_str(), "Y" | "y" | "T" | "t" | "1")),
+                            .map(|v| is_option_true(&v)),
                         left_option_alt: load("Software\\Wine\\Mac Driver", "LeftOptionIsAlt")
-                            .map(|v| matches!(v.as_str(), "Y" | "y" | "T" | "t" | "1")),
+                            .map(|v| is_option_true(&v)),
                         right_option_alt: load("Software\\Wine\\Mac Driver", "RightOptionIsAlt")
-                            .map(|v| matches!(v.as_str(), "Y" | "y" | "T" | "t" | "1")),
+                            .map(|v| is_option_true(&v)),
                         left_command_ctrl: load("Software\\Wine\\Mac Driver", "LeftCommandIsCtrl")
-                            .map(|v| matches!(v.as_str(), "Y" | "y" | "T" | "t" | "1")),
+                            .map(|v| is_option_true(&v)),
                         right_command_ctrl: load(
                             "Software\\Wine\\Mac Driver",
                             "RightCommandIsCtrl",
                         )
-                        .map(|v| matches!(v.as_str(), "Y" | "y" | "T" | "t" | "1")),
+                        .map(|v| is_option_true(&v)),
                     })
                 } else {
                     None
@@ -1262,27 +2426,33 @@ fn load_settings_from_cache(
         },
         x11: if x11_present {
             Some(X11Settings {
-                decorated: load("Software\\Wine\\X11 Driver", "Decorated").map(|v| v != "N"),
+                decorated: load("Software\\Wine\\X11 Driver", "Decorated")
+                    .map(|v| is_option_true(&v)),
                 client_side_graphics: load("Software\\Wine\\X11 Driver", "ClientSideGraphics")
-                    .map(|v| v != "N"),
+                    .map(|v| is_option_true(&v)),
                 client_side_with_render: load("Software\\Wine\\X11 Driver", "ClientSideWithRender")
-                    .map(|v| v != "N"),
+                    .map(|v| is_option_true(&v)),
                 client_side_antialias_with_render: load(
                     "Software\\Wine\\X11 Driver",
                     "ClientSideAntiAliasWithRender",
                 )
-                .map(|v| v != "N"),
+                .map(|v| is_option_true(&v)),
                 client_side_antialias_with_core: load(
                     "Software\\Wine\\X11 Driver",
                     "ClientSideAntiAliasWithCore",
                 )
-                .map(|v| v != "N"),
+                .map(|v| is_option_true(&v)),
                 grab_fullscreen: load("Software\\Wine\\X11 Driver", "GrabFullscreen")
-                    .map(|v| v == "Y"),
-                grab_pointer: load("Software\\Wine\\X11 Driver", "GrabPointer").map(|v| v != "N"),
-                managed: load("Software\\Wine\\X11 Driver", "Managed").map(|v| v != "N"),
-                use_xrandr: load("Software\\Wine\\X11 Driver", "UseXRandR").map(|v| v != "N"),
-                use_xvid_mode: load("Software\\Wine\\X11 Driver", "UseXVidMode").map(|v| v == "Y"),
+                    .map(|v| is_option_true(&v)),
+                grab_pointer: load("Software\\Wine\\X11 Driver", "GrabPointer")
+                    .map(|v| is_option_true(&v)),
+                managed: load("Software\\Wine\\X11 Driver", "Managed").map(|v| is_option_true(&v)),
+                use_xrandr: load("Software\\Wine\\X11 Driver", "UseXRandR")
+                    .map(|v| is_option_true(&v)),
+                use_xvid_mode: load("Software\\Wine\\X11 Driver", "UseXVidMode")
+                    .map(|v| is_option_true(&v)),
+                use_primary_selection: load("Software\\Wine\\X11 Driver", "UsePrimarySelection")
+                    .map(|v| is_option_true(&v)),
             })
         } else {
             None
@@ -1322,7 +2492,26 @@ fn load_settings_from_cache(
             .collect(),
     };
 
-    sender.input(RegistryEditorMsg::LoadSettings(general, graphics, platform, fonts));
+    let input = InputSettings {
+        mouse_warp_override: load("Software\\Wine\\DirectInput", "MouseWarpOverride"),
+        default_dead_zone: load_dword("Software\\Wine\\DirectInput", "DefaultDeadZone"),
+        joysticks: store
+            .get_settings_section(prefix_path, "Software\\Wine\\DirectInput\\Joysticks")
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(name, state)| {
+                let state = JoystickState::from_string(&state?)?;
+                Some(input_tab::JoystickEntry {
+                    name,
+                    enabled: state == JoystickState::Enabled,
+                })
+            })
+            .collect(),
+    };
+
+    sender.input(RegistryEditorMsg::LoadSettings(
+        general, graphics, platform, fonts, input,
+    ));
 }
 
 /// Background registry load (cold path): reads .reg files, caches, sends to tabs.
@@ -1346,6 +2535,8 @@ fn spawn_registry_load(
             let virtual_desktop = editor.get_virtual_desktop().await?;
             let dpi_settings = editor.get_dpi_settings().await?;
             let x11_driver_settings = editor.get_x11_driver_settings().await?;
+            let direct_input_settings = editor.get_direct_input_settings().await?;
+            let joystick_overrides = editor.get_joystick_overrides().await?;
             let font_substitutes = system_registry
                 .get_key_values("Software\\Microsoft\\Windows NT\\CurrentVersion\\FontSubstitutes")
                 .await?;
@@ -1402,6 +2593,7 @@ fn spawn_registry_load(
                 managed: x.managed,
                 use_xrandr: x.use_xrandr,
                 use_xvid_mode: x.use_xvid_mode,
+                use_primary_selection: x.use_primary_selection,
             });
 
             let platform = PlatformSettings { mac, x11 };
@@ -1438,7 +2630,30 @@ fn spawn_registry_load(
                     .collect(),
             };
 
-            Ok::<_, PrefixError>((editor, system_registry, general, graphics, platform, fonts))
+            let input = InputSettings {
+                mouse_warp_override: direct_input_settings
+                    .as_ref()
+                    .and_then(|d| d.mouse_warp_override.as_ref())
+                    .map(|m| m.to_string().to_string()),
+                default_dead_zone: direct_input_settings.and_then(|d| d.default_dead_zone),
+                joysticks: joystick_overrides
+                    .into_iter()
+                    .map(|j| input_tab::JoystickEntry {
+                        name: j.name,
+                        enabled: j.state == JoystickState::Enabled,
+                    })
+                    .collect(),
+            };
+
+            Ok::<_, PrefixError>((
+                editor,
+                system_registry,
+                general,
+                graphics,
+                platform,
+                fonts,
+                input,
+            ))
         }
         .await;
         let _ = tx.send(result);
@@ -1447,7 +2662,7 @@ fn spawn_registry_load(
     let pp2 = prefix_path_str;
     tokio::spawn(async move {
         match rx.await {
-            Ok(Ok((editor, system_registry, general, graphics, platform, fonts))) => {
+            Ok(Ok((editor, system_registry, general, graphics, platform, fonts, input))) => {
                 // Check whether the cached data is still fresh by comparing hashes
                 let hashes_match = hash_registry_files(std::path::Path::new(&pp2))
                     .ok()
@@ -1499,6 +2714,22 @@ fn spawn_registry_load(
                         "LogPixels",
                         general.log_pixels.map(|v| v.to_string()).as_deref()
                     );
+                    save!(
+                        "Software\\Wine\\Explorer",
+                        "Desktop",
+                        Some(if general.virtual_desktop_enabled { "Default" } else { "" })
+                    );
+                    save!(
+                        "Software\\Wine\\Explorer\\Desktops",
+                        "Default",
+                        Some(
+                            format!(
+                                "{}x{}",
+                                general.virtual_desktop_width, general.virtual_desktop_height
+                            )
+                            .as_str()
+                        )
+                    );
                     if let Some(x11) = &platform.x11 {
                         save!(
                             "Software\\Wine\\X11 Driver",
@@ -1556,6 +2787,11 @@ fn spawn_registry_load(
                             "UseXVidMode",
                             x11.use_xvid_mode.map(|v| if v { "Y" } else { "N" })
                         );
+                        save!(
+                            "Software\\Wine\\X11 Driver",
+                            "UsePrimarySelection",
+                            x11.use_primary_selection.map(|v| if v { "Y" } else { "N" })
+                        );
                     }
                     #[cfg(target_os = "macos")]
                     if let Some(mac) = &platform.mac {
@@ -1617,23 +2853,48 @@ fn spawn_registry_load(
                             Some(substitution.target.as_str())
                         );
                     }
+                    save!(
+                        "Software\\Wine\\DirectInput",
+                        "MouseWarpOverride",
+                        input.mouse_warp_override.as_deref()
+                    );
+                    save!(
+                        "Software\\Wine\\DirectInput",
+                        "DefaultDeadZone",
+                        input.default_dead_zone.map(|v| v.to_string()).as_deref()
+                    );
+                    for joystick in &input.joysticks {
+                        save!(
+                            "Software\\Wine\\DirectInput\\Joysticks",
+                            joystick.name.as_str(),
+                            Some(if joystick.enabled {
+                                JoystickState::Enabled.to_string()
+                            } else {
+                                JoystickState::Disabled.to_string()
+                            })
+                        );
+                    }
 
                     // Save file hashes so subsequent loads can skip re-parsing
                     if let Ok((uh, sh)) = hash_registry_files(std::path::Path::new(&pp)) {
                         let _ = store.save_registry_hashes(pp, &uh, &sh);
                     }
                 }
-                sender.input(RegistryEditorMsg::LoadSettings(general, graphics, platform, fonts));
+                sender.input(RegistryEditorMsg::LoadSettings(
+                    general, graphics, platform, fonts, input,
+                ));
                 sender.input(RegistryEditorMsg::RegistryEditorLoaded(
                     Arc::new(Mutex::new(editor)),
                     Arc::new(Mutex::new(system_registry)),
                 ));
             }
             Ok(Err(e)) => {
-                log::error!("[regedit] failed to load registry: {}", e);
+                sender.input(RegistryEditorMsg::RegistryLoadFailed(e.to_string()));
             }
             Err(_) => {
-                log::error!("[regedit] failed to receive registry load result");
+                sender.input(RegistryEditorMsg::RegistryLoadFailed(
+                    "registry load task ended without a result".to_string(),
+                ));
             }
         }
     });
@@ -26,6 +26,7 @@ pub struct X11Settings {
     pub managed: Option<bool>,
     pub use_xrandr: Option<bool>,
     pub use_xvid_mode: Option<bool>,
+    pub use_primary_selection: Option<bool>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -427,6 +428,24 @@ impl SimpleComponent for PlatformTabModel {
                             },
                         },
                     },
+
+                    adw::ActionRow {
+                        set_title: &crate::t!("registry.platform.x11.use_primary_selection"),
+                        set_subtitle: &crate::t!("registry.platform.x11.use_primary_selection_sub"),
+
+                        add_suffix = &gtk::Switch {
+                            set_valign: gtk::Align::Center,
+                            #[track = "model.changed(PlatformTabModel::x11())"]
+                            set_active: model.x11.use_primary_selection.unwrap_or(false),
+                            #[track = "model.changed(PlatformTabModel::editing())"]
+                            set_sensitive: model.editing,
+                            connect_active_notify[sender] => move |sw| {
+                                sender.input(PlatformTabInput::UpdateField(
+                                    "x11_use_primary_selection".into(), sw.is_active().to_string(),
+                                ));
+                            },
+                        },
+                    },
                 },
             }
         }
@@ -517,6 +536,11 @@ impl SimpleComponent for PlatformTabModel {
                     "x11_managed" => ("x11_managed", "X11 Driver", "Managed"),
                     "x11_use_xrandr" => ("x11_use_xrandr", "X11 Driver", "UseXRandR"),
                     "x11_use_xvid_mode" => ("x11_use_xvid_mode", "X11 Driver", "UseXVidMode"),
+                    "x11_use_primary_selection" => (
+                        "x11_use_primary_selection",
+                        "X11 Driver",
+                        "UsePrimarySelection",
+                    ),
                     _ => return,
                 };
 
@@ -611,6 +635,11 @@ impl SimpleComponent for PlatformTabModel {
                         x.use_xvid_mode = Some(v);
                         self.set_x11(x);
                     }
+                    "x11_use_primary_selection" => {
+                        let mut x = self.x11.clone();
+                        x.use_primary_selection = Some(v);
+                        self.set_x11(x);
+                    }
                     _ => unreachable!(),
                 }
 
@@ -19,6 +19,7 @@ pub struct SettingsWindow {
     // Page subtitle data
     runtime_subtitle: String,
     graphics_subtitle: String,
+    icon_cache_subtitle: String,
 
     // NavigationView kept in model for push/pop actions in update()
     #[tracker::do_not_track]
@@ -47,8 +48,27 @@ pub enum SettingsMsg {
     // GitHub API key entry
     GithubKeyChanged(Option<String>),
 
-    // Language
+    // General
     LanguageChanged(u32),
+    DefaultArchitectureChanged(u32),
+
+    // Appearance
+    ThemeChanged(u32),
+
+    // Terminal emulator command
+    TerminalChanged(Option<String>),
+
+    // Application scanning
+    ScanScriptsChanged(bool),
+    ScanExtensionsChanged(Vec<String>),
+    ManualAppScanChanged(bool),
+    PrefixLoggingChanged(bool),
+
+    // Performance
+    RegistryReadsChanged(usize),
+
+    // Maintenance
+    ClearIconCache,
 
     // Window
     Close,
@@ -104,6 +124,15 @@ fn graphics_subtitle() -> String {
     }
 }
 
+fn icon_cache_subtitle() -> String {
+    let (count, bytes) = AppService::global().icon_cache_usage();
+    crate::tf!(
+        "settings.clear_icon_cache_sub",
+        "count" => &count.to_string(),
+        "size" => &gtk::glib::format_size(bytes).to_string()
+    )
+}
+
 // ── Component ────────────────────────────────────────────────────────────
 
 #[relm4::component(pub, async)]
@@ -166,6 +195,127 @@ impl AsyncComponent for SettingsWindow {
                 },
             },
 
+            adw::PreferencesGroup {
+                set_title: &crate::t!("settings.general"),
+
+                adw::ActionRow {
+                    set_title: &crate::t!("settings.language"),
+                    set_subtitle: &crate::t!("settings.language_switch"),
+                    set_activatable_widget: Some(&language_box),
+
+                    add_suffix: &language_box,
+                },
+
+                #[name = "language_box"]
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Horizontal,
+                    set_spacing: 3,
+
+                    #[name = "language_combo"]
+                    gtk::DropDown {
+                        set_valign: gtk::Align::Center,
+                    },
+                },
+
+                #[name = "default_arch_row"]
+                adw::ComboRow {
+                    set_title: &crate::t!("settings.default_arch"),
+                    set_subtitle: &crate::t!("settings.default_arch_sub"),
+                    set_model: Some(&gtk::StringList::new(&["win32", "win64"])),
+                },
+            },
+
+            adw::PreferencesGroup {
+                set_title: &crate::t!("settings.appearance"),
+
+                adw::ActionRow {
+                    set_title: &crate::t!("settings.theme"),
+                    set_subtitle: &crate::t!("settings.theme_sub"),
+                    set_activatable_widget: Some(&theme_combo),
+                    add_suffix: &theme_combo,
+                },
+
+                #[name = "theme_combo"]
+                gtk::DropDown {
+                    set_valign: gtk::Align::Center,
+                },
+            },
+
+                adw::PreferencesGroup {
+                    set_title: &crate::t!("settings.terminal"),
+                    set_description: Some(&crate::t!("settings.terminal_desc")),
+
+                    #[name = "terminal_entry"]
+                    adw::EntryRow {
+                        set_title: &crate::t!("settings.terminal_command"),
+                        set_show_apply_button: true,
+                        connect_apply[sender] => move |entry| {
+                            let text = entry.text().trim().to_string();
+                            let value = if text.is_empty() { None } else { Some(text) };
+                            sender.input(SettingsMsg::TerminalChanged(value));
+                        },
+                    },
+                },
+
+                adw::PreferencesGroup {
+                    set_title: &crate::t!("settings.scanning"),
+
+                    #[name = "scan_scripts_row"]
+                    adw::SwitchRow {
+                        set_title: &crate::t!("settings.scan_scripts"),
+                        set_subtitle: &crate::t!("settings.scan_scripts_sub"),
+                    },
+
+                    #[name = "scan_extensions_entry"]
+                    adw::EntryRow {
+                        set_title: &crate::t!("settings.scan_extensions"),
+                        set_show_apply_button: true,
+                        connect_apply[sender] => move |entry| {
+                            let extensions = entry
+                                .text()
+                                .split(|c: char| c == ',' || c.is_whitespace())
+                                .map(|e| e.trim_start_matches('.').to_lowercase())
+                                .filter(|e| !e.is_empty())
+                                .collect();
+                            sender.input(SettingsMsg::ScanExtensionsChanged(extensions));
+                        },
+                    },
+
+                    #[name = "manual_scan_row"]
+                    adw::SwitchRow {
+                        set_title: &crate::t!("settings.manual_app_scan"),
+                        set_subtitle: &crate::t!("settings.manual_app_scan_sub"),
+                    },
+                },
+
+                adw::PreferencesGroup {
+                    set_title: &crate::t!("settings.performance"),
+
+                    #[name = "registry_reads_row"]
+                    adw::SpinRow {
+                        set_title: &crate::t!("settings.registry_reads"),
+                        set_subtitle: &crate::t!("settings.registry_reads_sub"),
+                        set_adjustment: Some(&gtk::Adjustment::new(
+                            prefix::registry::DEFAULT_MAX_CONCURRENT_READS as f64,
+                            1.0,
+                            64.0,
+                            1.0,
+                            4.0,
+                            0.0,
+                        )),
+                    },
+                },
+
+                adw::PreferencesGroup {
+                    set_title: &crate::t!("settings.diagnostics"),
+
+                    #[name = "prefix_logging_row"]
+                    adw::SwitchRow {
+                        set_title: &crate::t!("settings.prefix_logging"),
+                        set_subtitle: &crate::t!("settings.prefix_logging_sub"),
+                    },
+                },
+
             adw::PreferencesGroup {
                 set_title: &crate::t!("settings.github"),
                 set_description: Some(&crate::t!("settings.github_desc")),
@@ -202,11 +352,7 @@ impl AsyncComponent for SettingsWindow {
                         set_valign: gtk::Align::Center,
                         connect_clicked[github_key_entry] => move |_| {
                             github_key_entry.set_text("");
-                            let mut settings =
-                                prefix::Settings::load()
-                                    .unwrap_or_else(|| RuntimeManager::new().into());
-                            settings.github_api_key = None;
-                            if let Err(e) = settings.save() {
+                            if let Err(e) = prefix::Settings::update(|s| s.github_api_key = None) {
                                 log::error!("[settings] failed to save github_api_key: {}", e);
                             }
                         },
@@ -214,38 +360,26 @@ impl AsyncComponent for SettingsWindow {
                 },
             },
 
-            adw::PreferencesGroup {
-                    set_title: &crate::t!("settings.language"),
-                    set_description: Some(&crate::t!("settings.language_desc")),
-
-                    adw::ActionRow {
-                        set_title: &crate::t!("settings.language"),
-                        set_subtitle: &crate::t!("settings.language_switch"),
-                        set_activatable_widget: Some(&language_box),
-
-                        add_suffix: &language_box,
-                    },
-
-                    #[name = "language_box"]
-                    gtk::Box {
-                        set_orientation: gtk::Orientation::Horizontal,
-                        set_spacing: 3,
-
-                        #[name = "language_combo"]
-                        gtk::DropDown {
-                            set_valign: gtk::Align::Center,
-                        },
-                    },
-                },
-
                 adw::PreferencesGroup {
                     set_title: &crate::t!("settings.directories"),
                         set_description: Some(&crate::t!("settings.directories_desc")),
 
                 adw::ActionRow {
                     set_title: &crate::t!("settings.open_prefixes"),
-                    set_subtitle: &crate::t!("settings.open_prefixes_sub"),
+                    // The path is ellipsized when long; the tooltip has it in full
+                    set_subtitle: &prefixes_dir.display().to_string(),
+                    set_subtitle_lines: 1,
+                    set_tooltip_text: Some(&prefixes_dir.display().to_string()),
                     set_activatable: true,
+                    add_suffix = &gtk::Button {
+                        set_icon_name: "edit-copy-symbolic",
+                        set_tooltip_text: Some(&crate::t!("settings.copy_path")),
+                        set_valign: gtk::Align::Center,
+                        add_css_class: "flat",
+                        connect_clicked[prefixes_dir] => move |btn| {
+                            btn.clipboard().set_text(&prefixes_dir.display().to_string());
+                        },
+                    },
                     connect_activated[prefixes_dir] => move |_| {
                         let path = prefixes_dir.to_string_lossy().to_string();
                         std::thread::spawn(move || {
@@ -256,6 +390,16 @@ impl AsyncComponent for SettingsWindow {
                         });
                     },
                 },
+                adw::ActionRow {
+                    set_title: &crate::t!("settings.clear_icon_cache"),
+                    #[watch]
+                    set_subtitle: &model.icon_cache_subtitle,
+                    add_suffix = &gtk::Button {
+                        set_label: &crate::t!("settings.clear_icon_cache_button"),
+                        set_valign: gtk::Align::Center,
+                        connect_clicked => SettingsMsg::ClearIconCache,
+                    },
+                },
                 adw::ActionRow {
                     set_title: &crate::t!("settings.open_data"),
                     set_subtitle: &crate::t!("settings.open_data_sub"),
@@ -331,6 +475,7 @@ impl AsyncComponent for SettingsWindow {
         let mut model = SettingsWindow {
             runtime_subtitle,
             graphics_subtitle: graphics_subtitle_str,
+            icon_cache_subtitle: icon_cache_subtitle(),
             nav: placeholder_nav,
             runtime_ctrl,
             graphics_ctrl,
@@ -366,20 +511,81 @@ impl AsyncComponent for SettingsWindow {
             });
         }
 
+        let settings = prefix::Settings::current();
+
         // Load initial GitHub API key from settings
-        if let Some(key) = prefix::Settings::load().and_then(|s| s.github_api_key) {
-            widgets.github_key_entry.set_text(&key);
+        if let Some(key) = &settings.github_api_key {
+            widgets.github_key_entry.set_text(key);
         }
 
+        if let Some(terminal) = &settings.terminal {
+            widgets.terminal_entry.set_text(terminal);
+        }
+
+        // Connect after loading so the initial value isn't saved back
+        widgets.scan_scripts_row.set_active(settings.scan_scripts);
+        let scan_sender = sender.clone();
+        widgets.scan_scripts_row.connect_active_notify(move |row| {
+            scan_sender.input(SettingsMsg::ScanScriptsChanged(row.is_active()));
+        });
+        widgets
+            .scan_extensions_entry
+            .set_text(&settings.extra_scan_extensions.join(", "));
+        widgets.manual_scan_row.set_active(settings.manual_app_scan);
+        let scan_sender = sender.clone();
+        widgets.manual_scan_row.connect_active_notify(move |row| {
+            scan_sender.input(SettingsMsg::ManualAppScanChanged(row.is_active()));
+        });
+        if let Some(max) = settings.max_registry_reads {
+            widgets.registry_reads_row.set_value(max as f64);
+        }
+        let reads_sender = sender.clone();
+        widgets.registry_reads_row.connect_value_notify(move |row| {
+            reads_sender.input(SettingsMsg::RegistryReadsChanged(row.value() as usize));
+        });
+        widgets
+            .prefix_logging_row
+            .set_active(settings.prefix_logging);
+        let logging_sender = sender.clone();
+        widgets
+            .prefix_logging_row
+            .connect_active_notify(move |row| {
+                logging_sender.input(SettingsMsg::PrefixLoggingChanged(row.is_active()));
+            });
+
+        widgets
+            .default_arch_row
+            .set_selected(u32::from(settings.default_architecture != "win32"));
+        let arch_sender = sender.clone();
+        widgets
+            .default_arch_row
+            .connect_selected_notify(move |row| {
+                arch_sender.input(SettingsMsg::DefaultArchitectureChanged(row.selected()));
+            });
+
+        // ── Theme combo setup ──
+        let theme_items = gtk::StringList::new(&[
+            &crate::t!("settings.theme_auto"),
+            &crate::t!("settings.theme_light"),
+            &crate::t!("settings.theme_dark"),
+        ]);
+        widgets.theme_combo.set_model(Some(&theme_items));
+        let theme_idx: u32 = match settings.theme.as_str() {
+            "light" => 1,
+            "dark" => 2,
+            _ => 0,
+        };
+        widgets.theme_combo.set_selected(theme_idx);
+        let theme_sender = sender.clone();
+        widgets.theme_combo.connect_selected_notify(move |combo| {
+            theme_sender.input(SettingsMsg::ThemeChanged(combo.selected()));
+        });
+
         // ── Language combo setup ──
         let language_items = gtk::StringList::new(&["Follow System", "中文（简体）", "English"]);
         widgets.language_combo.set_model(Some(&language_items));
 
-        // Load current language preference
-        let current_lang = prefix::Settings::load()
-            .map(|s| s.language)
-            .unwrap_or_else(|| "system".to_string());
-        let lang_idx: u32 = match current_lang.as_str() {
+        let lang_idx: u32 = match settings.language.as_str() {
             "zh-CN" => 1,
             "en" => 2,
             _ => 0,
@@ -433,10 +639,7 @@ impl AsyncComponent for SettingsWindow {
 
             // ── GitHub API key ──
             SettingsMsg::GithubKeyChanged(value) => {
-                let mut settings =
-                    prefix::Settings::load().unwrap_or_else(|| RuntimeManager::new().into());
-                settings.github_api_key = value;
-                if let Err(e) = settings.save() {
+                if let Err(e) = prefix::Settings::update(|s| s.github_api_key = value) {
                     log::error!("[settings] failed to save github_api_key: {}", e);
                 }
                 // The API key change will be picked up on the next
@@ -449,10 +652,7 @@ impl AsyncComponent for SettingsWindow {
                     2 => "en",
                     _ => "system",
                 };
-                let mut settings =
-                    prefix::Settings::load().unwrap_or_else(|| RuntimeManager::new().into());
-                settings.language = lang_str.to_string();
-                if let Err(e) = settings.save() {
+                if let Err(e) = prefix::Settings::update(|s| s.language = lang_str.to_string()) {
                     log::error!("[settings] failed to save language: {}", e);
                 } else {
                     log::info!("[i18n] language preference saved: {}", lang_str);
@@ -471,6 +671,81 @@ impl AsyncComponent for SettingsWindow {
                     );
                 }
             }
+            SettingsMsg::DefaultArchitectureChanged(idx) => {
+                let arch = if idx == 0 { "win32" } else { "win64" };
+                if let Err(e) = prefix::Settings::update(|s| s.default_architecture = arch.into()) {
+                    log::error!("[settings] failed to save default_architecture: {}", e);
+                }
+            }
+            // ── Terminal ──
+            SettingsMsg::TerminalChanged(value) => {
+                if let Err(e) = prefix::Settings::update(|s| s.terminal = value) {
+                    log::error!("[settings] failed to save terminal: {}", e);
+                }
+            }
+            // ── Application scanning ──
+            SettingsMsg::ScanScriptsChanged(enabled) => {
+                AppService::global()
+                    .prefix_manager()
+                    .scanner()
+                    .set_include_scripts(enabled);
+                if let Err(e) = prefix::Settings::update(|s| s.scan_scripts = enabled) {
+                    log::error!("[settings] failed to save scan_scripts: {}", e);
+                }
+            }
+            SettingsMsg::ScanExtensionsChanged(extensions) => {
+                AppService::global()
+                    .prefix_manager()
+                    .scanner()
+                    .set_executable_extensions(
+                        prefix::DEFAULT_EXECUTABLE_EXTENSIONS
+                            .iter()
+                            .copied()
+                            .chain(extensions.iter().map(String::as_str)),
+                    );
+                if let Err(e) = prefix::Settings::update(|s| s.extra_scan_extensions = extensions) {
+                    log::error!("[settings] failed to save extra_scan_extensions: {}", e);
+                }
+            }
+            SettingsMsg::ManualAppScanChanged(enabled) => {
+                if let Err(e) = prefix::Settings::update(|s| s.manual_app_scan = enabled) {
+                    log::error!("[settings] failed to save manual_app_scan: {}", e);
+                }
+            }
+            // ── Performance ──
+            SettingsMsg::RegistryReadsChanged(max) => {
+                prefix::registry::set_max_concurrent_reads(max);
+                if let Err(e) = prefix::Settings::update(|s| s.max_registry_reads = Some(max)) {
+                    log::error!("[settings] failed to save max_registry_reads: {}", e);
+                }
+            }
+            // ── Diagnostics ──
+            SettingsMsg::PrefixLoggingChanged(enabled) => {
+                prefix::prefix_log::set_enabled(enabled);
+                if let Err(e) = prefix::Settings::update(|s| s.prefix_logging = enabled) {
+                    log::error!("[settings] failed to save prefix_logging: {}", e);
+                }
+            }
+            // ── Maintenance ──
+            SettingsMsg::ClearIconCache => {
+                let svc = AppService::global();
+                if let Err(e) = svc.clear_icon_cache() {
+                    log::error!("[settings] failed to clear icon cache: {}", e);
+                }
+                self.set_icon_cache_subtitle(icon_cache_subtitle());
+            }
+            // ── Appearance ──
+            SettingsMsg::ThemeChanged(idx) => {
+                let theme = match idx {
+                    1 => "light",
+                    2 => "dark",
+                    _ => "auto",
+                };
+                crate::app::resources::apply_theme(theme);
+                if let Err(e) = prefix::Settings::update(|s| s.theme = theme.to_string()) {
+                    log::error!("[settings] failed to save theme: {}", e);
+                }
+            }
             // ── Window ──
             SettingsMsg::Close => {
                 root.set_visible(false);